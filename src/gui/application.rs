@@ -0,0 +1,58 @@
+use crate::gui::{Win, APPLICATION_ID};
+use crate::model::ProviderHandle;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gio::prelude::*;
+use gio::ApplicationFlags;
+use glib::{OptionArg, OptionFlags};
+use gtk::prelude::*;
+use relm::Component;
+
+/// The options `main` handles before running the application, with their descriptions. They are registered, so the
+/// application does not reject them as unknown.
+const MAIN_OPTIONS: &[(&str, &str)] = &[("mock", "Show a fixed program without network access")];
+
+/// Run the application with the given provider. Launching it a second time presents the window of the running
+/// instance instead of creating a new one.
+pub fn run(provider: ProviderHandle) {
+    let application = new_application(ApplicationFlags::HANDLES_OPEN);
+
+    let win: Rc<RefCell<Option<Component<Win>>>> = Rc::new(RefCell::new(None));
+
+    application.connect_activate(move |app| {
+        let mut win = win.borrow_mut();
+        match &*win {
+            Some(component) => component.widget().present(),
+            None => {
+                let component = relm::init::<Win>((app.clone(), provider.clone()))
+                    .expect("Could not spawn window");
+                app.add_window(component.widget());
+                *win = Some(component);
+            }
+        }
+    });
+
+    // There are no files to open, launching with arguments only shows the window.
+    application.connect_open(|app, _files, _hint| app.activate());
+
+    application.run(&std::env::args().collect::<Vec<_>>());
+}
+
+/// Create the application, accepting the options of `main`.
+pub(super) fn new_application(flags: ApplicationFlags) -> gtk::Application {
+    let application =
+        gtk::Application::new(Some(APPLICATION_ID), flags).expect("Could not create application");
+    for (name, description) in MAIN_OPTIONS {
+        application.add_main_option(
+            name,
+            glib::Char(0),
+            OptionFlags::NONE,
+            OptionArg::None,
+            description,
+            None,
+        );
+    }
+    application
+}
@@ -0,0 +1,98 @@
+use std::f64::consts::PI;
+
+use cairo::{Context, FontSlant, FontWeight, Format, ImageSurface};
+use gdk_pixbuf::Pixbuf;
+
+/// The width and height of the avatars in pixels, the size of the channel icons.
+const AVATAR_SIZE: i32 = 44;
+
+/// The radius of the rounded corners in pixels.
+const CORNER_RADIUS: f64 = 8.0;
+
+/// The background colors of the avatars, dark enough for white initials.
+const COLORS: &[(f64, f64, f64)] = &[
+    (0.11, 0.44, 0.85),
+    (0.15, 0.64, 0.41),
+    (0.90, 0.38, 0.00),
+    (0.75, 0.11, 0.16),
+    (0.57, 0.25, 0.67),
+    (0.53, 0.36, 0.22),
+    (0.13, 0.55, 0.55),
+];
+
+/// Draw a colored rounded rectangle with the initials of the channel, shown if it has no icon. The same name always
+/// gets the same color.
+pub fn avatar_pixbuf(name: &str) -> Option<Pixbuf> {
+    let surface = ImageSurface::create(Format::ARgb32, AVATAR_SIZE, AVATAR_SIZE).ok()?;
+    let size = AVATAR_SIZE as f64;
+
+    {
+        let context = Context::new(&surface);
+
+        // The rounded rectangle.
+        context.new_sub_path();
+        context.arc(
+            size - CORNER_RADIUS,
+            CORNER_RADIUS,
+            CORNER_RADIUS,
+            -PI / 2.0,
+            0.0,
+        );
+        context.arc(
+            size - CORNER_RADIUS,
+            size - CORNER_RADIUS,
+            CORNER_RADIUS,
+            0.0,
+            PI / 2.0,
+        );
+        context.arc(
+            CORNER_RADIUS,
+            size - CORNER_RADIUS,
+            CORNER_RADIUS,
+            PI / 2.0,
+            PI,
+        );
+        context.arc(CORNER_RADIUS, CORNER_RADIUS, CORNER_RADIUS, PI, 1.5 * PI);
+        context.close_path();
+        let (red, green, blue) = color(name);
+        context.set_source_rgb(red, green, blue);
+        context.fill();
+
+        // The initials in the center.
+        let initials = initials(name);
+        context.set_source_rgb(1.0, 1.0, 1.0);
+        context.select_font_face("Sans", FontSlant::Normal, FontWeight::Bold);
+        context.set_font_size(18.0);
+        let extents = context.text_extents(&initials);
+        context.move_to(
+            (size - extents.width) / 2.0 - extents.x_bearing,
+            (size - extents.height) / 2.0 - extents.y_bearing,
+        );
+        context.show_text(&initials);
+    }
+
+    surface.flush();
+    gdk::pixbuf_get_from_surface(&surface, 0, 0, AVATAR_SIZE, AVATAR_SIZE)
+}
+
+/// The initials of the channel, the first letters of the first two words like `DE` for `Das Erste`, or the first two
+/// letters of a single word like `ZD` for `ZDF`.
+fn initials(name: &str) -> String {
+    let words: Vec<&str> = name.split_whitespace().collect();
+    let initials: String = match words.as_slice() {
+        [] => String::new(),
+        [word] => word.chars().take(2).collect(),
+        [first, second, ..] => first
+            .chars()
+            .take(1)
+            .chain(second.chars().take(1))
+            .collect(),
+    };
+    initials.to_uppercase()
+}
+
+/// The background color of the avatar of the channel, derived from its name.
+fn color(name: &str) -> (f64, f64, f64) {
+    let sum: usize = name.bytes().map(|b| b as usize).sum();
+    COLORS[sum % COLORS.len()]
+}
@@ -0,0 +1,111 @@
+use crate::gui::{avatar_pixbuf, icon_pixbuf};
+use crate::model::{Channel, Movie, Program};
+use crate::Error;
+
+use std::fs::File;
+use std::path::Path;
+
+use cairo::{Context, FontSlant, FontWeight, Format, ImageSurface};
+use gdk::prelude::*;
+
+/// The maximum number of broadcasts shown on a card.
+const MAX_ENTRIES: usize = 5;
+
+/// The width of the card and the heights of its header and of each broadcast in pixels.
+const WIDTH: i32 = 720;
+const HEADER_HEIGHT: i32 = 96;
+const ROW_HEIGHT: i32 = 72;
+const MARGIN: f64 = 24.0;
+
+/// The size of the channel icons in pixels.
+const ICON_SIZE: f64 = 44.0;
+
+/// Titles longer than this number of characters are shortened.
+const MAX_TITLE_CHARS: usize = 34;
+
+/// Render a card of the picks, e.g. to send "what are we watching" to a family chat, and write it as PNG to the given
+/// path. At most `MAX_ENTRIES` broadcasts are shown, ordered by their start.
+pub fn write_card_to_path<P: AsRef<Path>>(picks: &Program, path: P) -> Result<(), Error> {
+    let mut entries: Vec<&(Channel, Movie)> = picks.iter().collect();
+    entries.sort_by_key(|(_c, m)| (m.get_start().is_none(), m.get_start()));
+    entries.truncate(MAX_ENTRIES);
+
+    let height = HEADER_HEIGHT + ROW_HEIGHT * entries.len().max(1) as i32;
+    let surface =
+        ImageSurface::create(Format::ARgb32, WIDTH, height).map_err(|_| Error::RenderingImage)?;
+    let context = Context::new(&surface);
+
+    context.set_source_rgb(0.14, 0.14, 0.16);
+    context.paint();
+
+    context.set_source_rgb(1.0, 1.0, 1.0);
+    context.select_font_face("Sans", FontSlant::Normal, FontWeight::Bold);
+    context.set_font_size(32.0);
+    context.move_to(MARGIN, 58.0);
+    context.show_text("Tonight on TV");
+
+    if entries.is_empty() {
+        context.select_font_face("Sans", FontSlant::Normal, FontWeight::Normal);
+        context.set_font_size(22.0);
+        context.move_to(MARGIN, HEADER_HEIGHT as f64 + 42.0);
+        context.show_text("Nothing picked for tonight.");
+    }
+
+    for (i, (channel, movie)) in entries.into_iter().enumerate() {
+        draw_entry(
+            &context,
+            (HEADER_HEIGHT + ROW_HEIGHT * i as i32) as f64,
+            channel,
+            movie,
+        );
+    }
+
+    let mut file = File::create(path)?;
+    surface
+        .write_to_png(&mut file)
+        .map_err(|_| Error::RenderingImage)
+}
+
+/// Draw a broadcast in the row starting at the given height. The channel is shown by its icon or a avatar with its
+/// initials, and its name below the title.
+fn draw_entry(context: &Context, top: f64, channel: &Channel, movie: &Movie) {
+    if let Some(icon) = icon_pixbuf(channel).or_else(|| avatar_pixbuf(&channel.get_name())) {
+        let icon_top = top + (ROW_HEIGHT as f64 - ICON_SIZE) / 2.0;
+        context.set_source_pixbuf(&icon, MARGIN, icon_top);
+        context.rectangle(MARGIN, icon_top, ICON_SIZE, ICON_SIZE);
+        context.fill();
+    }
+
+    let text_left = MARGIN + ICON_SIZE + 16.0;
+
+    context.set_source_rgb(0.6, 0.8, 1.0);
+    context.select_font_face("Sans", FontSlant::Normal, FontWeight::Bold);
+    context.set_font_size(22.0);
+    context.move_to(text_left, top + 32.0);
+    context.show_text(
+        &movie
+            .get_start()
+            .map(|s| s.format("%H:%M").to_string())
+            .unwrap_or_default(),
+    );
+
+    context.set_source_rgb(1.0, 1.0, 1.0);
+    context.select_font_face("Sans", FontSlant::Normal, FontWeight::Normal);
+    context.move_to(text_left + 80.0, top + 32.0);
+    context.show_text(&shorten(&movie.get_title()));
+
+    context.set_source_rgb(0.7, 0.7, 0.7);
+    context.set_font_size(16.0);
+    context.move_to(text_left + 80.0, top + 56.0);
+    context.show_text(&channel.get_name());
+}
+
+/// Shorten the title to at most `MAX_TITLE_CHARS` characters.
+fn shorten(title: &str) -> String {
+    if title.chars().count() <= MAX_TITLE_CHARS {
+        title.to_string()
+    } else {
+        let shortened: String = title.chars().take(MAX_TITLE_CHARS - 1).collect();
+        format!("{}…", shortened.trim_end())
+    }
+}
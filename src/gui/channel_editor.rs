@@ -0,0 +1,224 @@
+use crate::model::ChannelOrder;
+
+use gtk::prelude::*;
+use gtk::{
+    Adjustment, Box, Button, CheckButton, IconSize, Image, Orientation, ScrolledWindow,
+    ToggleButton, Window, WindowType,
+};
+use libhandy::{HeaderBar, HeaderBarExt};
+use relm::{connect, Relm, Update, Widget};
+use relm_derive::Msg;
+
+/// Messages for the channel editor.
+#[derive(Msg)]
+pub enum ChannelEditorMsg {
+    /// Move the channel at the index one up.
+    MoveUp(usize),
+    /// Move the channel at the index one down.
+    MoveDown(usize),
+    /// Save the order, the favorites and the checked channels.
+    Save,
+    /// The order or the shown channels were changed. Should be handled by the parent. If all channels are shown, the
+    /// list of shown channels is empty, so new channels are shown too.
+    Changed((ChannelOrder, Vec<String>)),
+}
+
+/// The model for the channel editor containing the channels in their order.
+pub struct ChannelEditorModel {
+    order: ChannelOrder,
+    channels: Vec<String>,
+    /// The channels shown at all, empty if all are shown.
+    subscribed: Vec<String>,
+    relm: Relm<ChannelEditor>,
+}
+
+/// The channel editor is a window to choose the shown channels, to mark favorite channels and to reorder the channels.
+pub struct ChannelEditor {
+    model: ChannelEditorModel,
+    window: Window,
+    box_channels: Box,
+    checks_subscribed: Vec<CheckButton>,
+    toggles_favorite: Vec<ToggleButton>,
+}
+
+impl Update for ChannelEditor {
+    type Model = ChannelEditorModel;
+    type ModelParam = (ChannelOrder, Vec<String>, Vec<String>);
+    type Msg = ChannelEditorMsg;
+
+    fn model(relm: &Relm<Self>, (order, channels, subscribed): Self::ModelParam) -> Self::Model {
+        ChannelEditorModel {
+            channels: order.arrange(&channels),
+            order,
+            subscribed,
+            relm: relm.clone(),
+        }
+    }
+
+    fn update(&mut self, event: ChannelEditorMsg) {
+        match event {
+            ChannelEditorMsg::MoveUp(index) => {
+                if index > 0 {
+                    self.swap(index - 1, index);
+                }
+            }
+            ChannelEditorMsg::MoveDown(index) => {
+                if index + 1 < self.model.channels.len() {
+                    self.swap(index, index + 1);
+                }
+            }
+            ChannelEditorMsg::Save => {
+                self.read_checks();
+                self.model.order.set_channels(self.model.channels.clone());
+
+                let subscribed = if self.model.subscribed.len() == self.model.channels.len() {
+                    vec![]
+                } else {
+                    self.model.subscribed.clone()
+                };
+
+                self.model.relm.stream().emit(ChannelEditorMsg::Changed((
+                    self.model.order.clone(),
+                    subscribed,
+                )));
+                self.window.close();
+            }
+            ChannelEditorMsg::Changed(_) => {}
+        }
+    }
+}
+
+impl Widget for ChannelEditor {
+    type Root = Window;
+
+    fn root(&self) -> Self::Root {
+        self.window.clone()
+    }
+
+    fn view(relm: &Relm<Self>, model: Self::Model) -> Self {
+        let window = Window::new(WindowType::Toplevel);
+        window.set_default_size(360, 480);
+        window.set_modal(true);
+
+        let header_bar = HeaderBar::new();
+        header_bar.set_title(Some("Channels"));
+        header_bar.set_show_close_button(true);
+
+        let button_save = Button::with_label("Save");
+        button_save
+            .get_style_context()
+            .add_class("suggested-action");
+        connect!(
+            relm,
+            button_save,
+            connect_clicked(_),
+            ChannelEditorMsg::Save
+        );
+        header_bar.pack_end(&button_save);
+
+        window.set_titlebar(Some(&header_bar));
+
+        let scrolled_window = ScrolledWindow::new::<Adjustment, Adjustment>(None, None);
+        scrolled_window.set_vexpand(true);
+
+        let box_channels = Box::new(Orientation::Vertical, 0);
+        scrolled_window.add(&box_channels);
+
+        window.add(&scrolled_window);
+
+        let mut channel_editor = ChannelEditor {
+            model,
+            window,
+            box_channels,
+            checks_subscribed: vec![],
+            toggles_favorite: vec![],
+        };
+        channel_editor.reset_channels();
+        channel_editor.window.show_all();
+
+        channel_editor
+    }
+}
+
+impl ChannelEditor {
+    /// Remember which channels are checked as shown and as favorites.
+    fn read_checks(&mut self) {
+        for (toggle, channel) in self.toggles_favorite.iter().zip(&self.model.channels) {
+            self.model.order.set_favorite(channel, toggle.get_active());
+        }
+
+        self.model.subscribed = self
+            .checks_subscribed
+            .iter()
+            .zip(&self.model.channels)
+            .filter(|(check, _channel)| check.get_active())
+            .map(|(_check, channel)| channel.clone())
+            .collect();
+    }
+
+    /// Weather the channel is shown at all.
+    fn is_subscribed(&self, channel: &str) -> bool {
+        self.model.subscribed.is_empty() || self.model.subscribed.iter().any(|c| c == channel)
+    }
+
+    /// Swap the channels at the indices and show the new order.
+    fn swap(&mut self, a: usize, b: usize) {
+        self.read_checks();
+        self.model.channels.swap(a, b);
+        self.reset_channels();
+    }
+
+    /// Show a row for each channel with a check for favorites and buttons to move it.
+    fn reset_channels(&mut self) {
+        let box_channels = self.box_channels.clone();
+        box_channels.foreach(|c| box_channels.remove(c));
+        self.checks_subscribed.clear();
+        self.toggles_favorite.clear();
+
+        for (index, channel) in self.model.channels.iter().enumerate() {
+            let row = Box::new(Orientation::Horizontal, 5);
+
+            let check = CheckButton::with_label(channel);
+            check.set_tooltip_text(Some("Show the channel"));
+            check.set_active(self.is_subscribed(channel));
+            check.set_hexpand(true);
+
+            let toggle_favorite = ToggleButton::new();
+            toggle_favorite.set_image(Some(&Image::from_icon_name(
+                Some("starred-symbolic"),
+                IconSize::Button,
+            )));
+            toggle_favorite.set_tooltip_text(Some("Favorite, shown at the top"));
+            toggle_favorite.set_active(self.model.order.is_favorite(channel));
+
+            let button_up = Button::from_icon_name(Some("go-up-symbolic"), IconSize::Button);
+            button_up.set_sensitive(index > 0);
+            connect!(
+                self.model.relm,
+                button_up,
+                connect_clicked(_),
+                ChannelEditorMsg::MoveUp(index)
+            );
+
+            let button_down = Button::from_icon_name(Some("go-down-symbolic"), IconSize::Button);
+            button_down.set_sensitive(index + 1 < self.model.channels.len());
+            connect!(
+                self.model.relm,
+                button_down,
+                connect_clicked(_),
+                ChannelEditorMsg::MoveDown(index)
+            );
+
+            row.add(&check);
+            row.add(&toggle_favorite);
+            row.add(&button_up);
+            row.add(&button_down);
+            self.box_channels.add(&row);
+
+            self.checks_subscribed.push(check);
+            self.toggles_favorite.push(toggle_favorite);
+        }
+
+        self.box_channels.show_all();
+    }
+}
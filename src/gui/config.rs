@@ -0,0 +1,144 @@
+use crate::gui::APPLICATION_ID;
+use crate::model::{Settings, TimeSlot};
+
+use std::convert::TryFrom;
+
+use gio::prelude::*;
+use gio::SettingsSchemaSource;
+
+/// The window width used if the schema is not installed.
+const DEFAULT_WINDOW_WIDTH: i32 = 360;
+/// The window height used if the schema is not installed.
+const DEFAULT_WINDOW_HEIGHT: i32 = 720;
+
+/// The configuration of the application stored in GSettings: the window size, the provider, the last time slot and the
+/// last session. It is also read by the command line, so both use the same provider and time slot.
+///
+/// If the schema is not installed, e.g. when running from the build directory, the defaults are used and nothing is stored.
+#[derive(Clone)]
+pub struct Config {
+    settings: Option<gio::Settings>,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        // `gio::Settings::new` aborts if the schema is not installed.
+        let installed = SettingsSchemaSource::get_default()
+            .and_then(|source| source.lookup(APPLICATION_ID, true))
+            .is_some();
+
+        Config {
+            settings: if installed {
+                Some(gio::Settings::new(APPLICATION_ID))
+            } else {
+                None
+            },
+        }
+    }
+
+    /// The size of the window when it was closed the last time.
+    pub fn get_window_size(&self) -> (i32, i32) {
+        match &self.settings {
+            Some(settings) => (
+                settings.get_int("window-width"),
+                settings.get_int("window-height"),
+            ),
+            None => (DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT),
+        }
+    }
+
+    pub fn set_window_size(&self, (width, height): (i32, i32)) {
+        if let Some(settings) = &self.settings {
+            let _ = settings.set_int("window-width", width);
+            let _ = settings.set_int("window-height", height);
+        }
+    }
+
+    /// Apply the provider and time slot stored in the configuration to the settings. Those still read from the settings
+    /// file of earlier versions are moved to the configuration if it does not have them yet.
+    pub fn apply_to(&self, settings: &mut Settings) {
+        if let Some(config) = &self.settings {
+            if config.get_user_value("provider").is_none() {
+                self.set_provider(&settings.get_provider());
+            }
+            if config.get_user_value("time-slot").is_none() {
+                self.set_time_slot(settings.get_time_slot());
+            }
+
+            if let Some(provider) = config.get_string("provider") {
+                settings.set_provider(&provider);
+            }
+            if let Some(time_slot) = config.get_string("time-slot") {
+                if let Ok(time_slot) = TimeSlot::try_from(&time_slot[..]) {
+                    settings.set_time_slot(time_slot);
+                }
+            }
+        }
+    }
+
+    /// Store the provider the program is loaded from.
+    pub fn set_provider(&self, provider: &str) {
+        if let Some(config) = &self.settings {
+            let _ = config.set_string("provider", provider);
+        }
+    }
+
+    /// Store the time slot chosen the last time.
+    pub fn set_time_slot(&self, time_slot: TimeSlot) {
+        if let Some(config) = &self.settings {
+            let _ = config.set_string("time-slot", &time_slot.to_string());
+        }
+    }
+
+    /// Reset the stored configuration to its defaults.
+    pub fn reset(&self) {
+        if let Some(config) = &self.settings {
+            for key in &[
+                "window-width",
+                "window-height",
+                "provider",
+                "time-slot",
+                "scroll-position",
+                "search",
+                "view-group",
+            ] {
+                config.reset(key);
+            }
+        }
+    }
+
+    /// The scroll position of the list, the search and the shown channel group when the application was closed the
+    /// last time. The search and the group are empty if not used.
+    pub fn get_session(&self) -> (f64, String, Option<String>) {
+        match &self.settings {
+            Some(config) => (
+                config.get_double("scroll-position"),
+                config
+                    .get_string("search")
+                    .map(|s| s.to_string())
+                    .unwrap_or_default(),
+                config
+                    .get_string("view-group")
+                    .map(|g| g.to_string())
+                    .filter(|g| !g.is_empty()),
+            ),
+            None => (0.0, String::new(), None),
+        }
+    }
+
+    /// Store the scroll position of the list, the search and the shown channel group to restore them on the next start.
+    pub fn set_session(&self, scroll_position: f64, search: &str, view_group: Option<&str>) {
+        if let Some(config) = &self.settings {
+            let _ = config.set_double("scroll-position", scroll_position);
+            let _ = config.set_string("search", search);
+            let _ = config.set_string("view-group", view_group.unwrap_or(""));
+        }
+    }
+
+    /// Write the changed configuration to the disk, e.g. before quitting.
+    pub fn sync(&self) {
+        if self.settings.is_some() {
+            gio::Settings::sync();
+        }
+    }
+}
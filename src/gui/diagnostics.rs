@@ -0,0 +1,178 @@
+use crate::model::cache::{self, CacheUsage};
+use crate::model::metrics::{self, ReloadTimings};
+
+use std::time::Duration;
+
+use gtk::prelude::*;
+use gtk::{Align, Button, Grid, Label, Window, WindowType};
+use libhandy::{HeaderBar, HeaderBarExt};
+use relm::{connect, Relm, Update, Widget};
+use relm_derive::Msg;
+
+/// Messages for the diagnostics.
+#[derive(Msg)]
+pub enum DiagnosticsMsg {
+    /// Show the current numbers.
+    Refresh,
+}
+
+/// The diagnostics are a window showing how much memory and storage the caches use, e.g. to choose their limits on
+/// phones with little memory, and how long the steps of the last reload took, e.g. to tell where the time goes on slow
+/// phones.
+pub struct Diagnostics {
+    window: Window,
+    label_disk: Label,
+    label_icons: Label,
+    label_descriptions: Label,
+    label_first_paint: Label,
+    label_network: Label,
+    label_parse: Label,
+    label_decode_icons: Label,
+    label_build_list: Label,
+}
+
+impl Update for Diagnostics {
+    type Model = ();
+    type ModelParam = ();
+    type Msg = DiagnosticsMsg;
+
+    fn model(_: &Relm<Self>, _: Self::ModelParam) -> Self::Model {}
+
+    fn update(&mut self, event: DiagnosticsMsg) {
+        match event {
+            DiagnosticsMsg::Refresh => {
+                let usage = CacheUsage::current();
+                self.label_disk
+                    .set_text(&usage_text(usage.get_disk(), cache::get_disk_limit()));
+                self.label_icons.set_text(&usage_text(
+                    usage.get_icons() as u64,
+                    cache::get_icon_limit() as u64,
+                ));
+                self.label_descriptions.set_text(&usage_text(
+                    usage.get_descriptions() as u64,
+                    cache::get_description_limit() as u64,
+                ));
+
+                self.label_first_paint
+                    .set_text(&duration_text(metrics::first_paint()));
+
+                let timings = ReloadTimings::last();
+                self.label_network
+                    .set_text(&duration_text(timings.get_network()));
+                self.label_parse
+                    .set_text(&duration_text(timings.get_parse()));
+                self.label_decode_icons
+                    .set_text(&duration_text(timings.get_decode_icons()));
+                self.label_build_list
+                    .set_text(&duration_text(timings.get_build_list()));
+            }
+        }
+    }
+}
+
+impl Widget for Diagnostics {
+    type Root = Window;
+
+    fn root(&self) -> Self::Root {
+        self.window.clone()
+    }
+
+    fn view(relm: &Relm<Self>, _model: Self::Model) -> Self {
+        let window = Window::new(WindowType::Toplevel);
+        window.set_default_size(360, -1);
+
+        let header_bar = HeaderBar::new();
+        header_bar.set_title(Some("Diagnostics"));
+        header_bar.set_show_close_button(true);
+
+        let button_refresh =
+            Button::from_icon_name(Some("view-refresh-symbolic"), gtk::IconSize::SmallToolbar);
+        button_refresh.set_tooltip_text(Some("Refresh"));
+        connect!(
+            relm,
+            button_refresh,
+            connect_clicked(_),
+            DiagnosticsMsg::Refresh
+        );
+        header_bar.pack_start(&button_refresh);
+
+        window.set_titlebar(Some(&header_bar));
+
+        let grid = Grid::new();
+        grid.set_row_spacing(5);
+        grid.set_column_spacing(10);
+        grid.set_margin_start(10);
+        grid.set_margin_end(10);
+        grid.set_margin_top(10);
+        grid.set_margin_bottom(10);
+
+        let mut row = 0;
+        let mut add_row = |name: &str| -> Label {
+            let label_name = Label::new(Some(name));
+            label_name.set_xalign(0.0);
+            label_name.set_hexpand(true);
+
+            let label_value = Label::new(None);
+            label_value.set_halign(Align::End);
+            label_value.set_selectable(true);
+
+            grid.attach(&label_name, 0, row, 1, 1);
+            grid.attach(&label_value, 1, row, 1, 1);
+            row += 1;
+            label_value
+        };
+
+        let label_disk = add_row("Downloaded icons");
+        let label_icons = add_row("Decoded channel icons");
+        let label_descriptions = add_row("Descriptions");
+        let label_first_paint = add_row("Startup: first paint");
+        let label_network = add_row("Last reload: network");
+        let label_parse = add_row("Last reload: parsing");
+        let label_decode_icons = add_row("Last reload: decoding icons");
+        let label_build_list = add_row("Last reload: building the list");
+
+        window.add(&grid);
+        window.show_all();
+
+        relm.stream().emit(DiagnosticsMsg::Refresh);
+
+        Diagnostics {
+            window,
+            label_disk,
+            label_icons,
+            label_descriptions,
+            label_first_paint,
+            label_network,
+            label_parse,
+            label_decode_icons,
+            label_build_list,
+        }
+    }
+}
+
+/// The used size of a cache of the limit, e.g. "1.5 MiB of 8.0 MiB".
+fn usage_text(size: u64, limit: u64) -> String {
+    format!("{} of {}", format_size(size), format_size(limit))
+}
+
+/// The duration in milliseconds, e.g. "250 ms".
+fn duration_text(duration: Duration) -> String {
+    format!("{} ms", duration.as_millis())
+}
+
+/// Format the number of bytes in the largest fitting unit.
+fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
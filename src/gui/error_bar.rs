@@ -0,0 +1,141 @@
+use crate::Error;
+
+use gtk::prelude::*;
+use gtk::{Button, InfoBar, Label, MessageType, ResponseType};
+use relm::{connect, Relm, Update, Widget};
+use relm_derive::Msg;
+
+/// The response of the retry button.
+const RESPONSE_RETRY: ResponseType = ResponseType::Other(0);
+
+/// The response of the reset button.
+const RESPONSE_RESET: ResponseType = ResponseType::Other(1);
+
+/// The response of the report button.
+const RESPONSE_REPORT: ResponseType = ResponseType::Other(2);
+
+/// Messages for the error bar.
+#[derive(Msg)]
+pub enum ErrorBarMsg {
+    /// Show the given error.
+    Show(Error),
+    /// Show the given error with the option to report it.
+    ShowReportable(Error),
+    /// Show a warning that the file could not be read and is ignored, with the option to reset it.
+    ShowReset(String),
+    /// Hide the error bar.
+    Hide,
+    /// The retry button was clicked. Should be handled by the parent.
+    Retry,
+    /// The reset button of a warning was clicked. Should be handled by the parent.
+    Reset,
+    /// The report button was clicked. Should be handled by the parent.
+    Report,
+}
+
+/// The error bar is a widget showing a error message with a button to retry the failed action, or a warning about a
+/// file that can not be read with a button to reset it. Errors the user opted in to report also have a button to
+/// report them.
+pub struct ErrorBar {
+    info_bar: InfoBar,
+    label: Label,
+    button_retry: Button,
+    button_reset: Button,
+    button_report: Button,
+}
+
+impl Update for ErrorBar {
+    type Model = ();
+    type ModelParam = ();
+    type Msg = ErrorBarMsg;
+
+    fn model(_: &Relm<Self>, _: Self::ModelParam) -> Self::Model {}
+
+    fn update(&mut self, event: ErrorBarMsg) {
+        match event {
+            ErrorBarMsg::Show(error) => {
+                self.label.set_text(&error.to_string());
+                self.info_bar.set_message_type(MessageType::Error);
+                self.button_retry.set_visible(true);
+                self.button_reset.set_visible(false);
+                self.button_report.set_visible(false);
+                self.info_bar.show_all();
+            }
+            ErrorBarMsg::ShowReportable(error) => {
+                self.label.set_text(&error.to_string());
+                self.info_bar.set_message_type(MessageType::Error);
+                self.button_retry.set_visible(true);
+                self.button_reset.set_visible(false);
+                self.button_report.set_visible(true);
+                self.info_bar.show_all();
+            }
+            ErrorBarMsg::ShowReset(warning) => {
+                self.label.set_text(&warning);
+                self.info_bar.set_message_type(MessageType::Warning);
+                self.button_retry.set_visible(false);
+                self.button_reset.set_visible(true);
+                self.button_report.set_visible(false);
+                self.info_bar.show_all();
+            }
+            ErrorBarMsg::Hide | ErrorBarMsg::Retry | ErrorBarMsg::Reset | ErrorBarMsg::Report => {
+                self.info_bar.hide()
+            }
+        }
+    }
+}
+
+impl Widget for ErrorBar {
+    type Root = InfoBar;
+
+    fn root(&self) -> Self::Root {
+        self.info_bar.clone()
+    }
+
+    fn view(relm: &Relm<Self>, _model: Self::Model) -> Self {
+        let info_bar = InfoBar::new();
+        info_bar.set_message_type(MessageType::Error);
+        info_bar.set_show_close_button(true);
+        info_bar.set_no_show_all(true);
+
+        let label = Label::new(None);
+        label.set_line_wrap(true);
+        info_bar.get_content_area().add(&label);
+
+        // Only the button of the shown message is visible.
+        let button_retry = info_bar
+            .add_button("Retry", RESPONSE_RETRY)
+            .expect("The info bar should have an action area");
+        button_retry.set_no_show_all(true);
+        let button_reset = info_bar
+            .add_button("Reset", RESPONSE_RESET)
+            .expect("The info bar should have an action area");
+        button_reset.set_no_show_all(true);
+        let button_report = info_bar
+            .add_button("Report", RESPONSE_REPORT)
+            .expect("The info bar should have an action area");
+        button_report.set_no_show_all(true);
+
+        connect!(
+            relm,
+            info_bar,
+            connect_response(_, response),
+            if response == RESPONSE_RETRY {
+                ErrorBarMsg::Retry
+            } else if response == RESPONSE_RESET {
+                ErrorBarMsg::Reset
+            } else if response == RESPONSE_REPORT {
+                ErrorBarMsg::Report
+            } else {
+                ErrorBarMsg::Hide
+            }
+        );
+
+        ErrorBar {
+            info_bar,
+            label,
+            button_retry,
+            button_reset,
+            button_report,
+        }
+    }
+}
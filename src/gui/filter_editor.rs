@@ -0,0 +1,229 @@
+use crate::model::{FilterType, ProgramFilter};
+
+use gtk::prelude::*;
+use gtk::{
+    Adjustment, Box, Button, Entry, IconSize, Inhibit, Label, Orientation, ScrolledWindow, Window,
+    WindowType,
+};
+use libhandy::{HeaderBar, HeaderBarExt};
+use relm::{connect, Relm, Update, Widget};
+use relm_derive::Msg;
+
+/// Messages for the filter editor.
+#[derive(Msg)]
+pub enum FilterEditorMsg {
+    /// The value of the filter at the index was edited.
+    Edit(usize),
+    /// Remove the filter at the index.
+    Remove(usize),
+    /// Save the edited filters.
+    Save,
+    /// The window is closed.
+    Close,
+    /// The filters were edited but not saved yet, to preview their effect. Should be handled by the parent.
+    Preview(ProgramFilter),
+    /// The filters were saved. Should be handled by the parent.
+    Changed(ProgramFilter),
+}
+
+/// The model for the filter editor containing the filters before editing them.
+pub struct FilterEditorModel {
+    original: ProgramFilter,
+    filters: Vec<FilterType>,
+    removed: Vec<bool>,
+    saved: bool,
+    relm: Relm<FilterEditor>,
+}
+
+/// The filter editor is a window to edit the values of the filters and to remove filters. Each edit is previewed in
+/// the list, closing the window without saving restores the filters.
+pub struct FilterEditor {
+    model: FilterEditorModel,
+    window: Window,
+    button_save: Button,
+    rows: Vec<Box>,
+    entries: Vec<Entry>,
+}
+
+impl Update for FilterEditor {
+    type Model = FilterEditorModel;
+    type ModelParam = ProgramFilter;
+    type Msg = FilterEditorMsg;
+
+    fn model(relm: &Relm<Self>, original: ProgramFilter) -> Self::Model {
+        let filters = original.get_filters();
+        FilterEditorModel {
+            removed: vec![false; filters.len()],
+            filters,
+            original,
+            saved: false,
+            relm: relm.clone(),
+        }
+    }
+
+    fn update(&mut self, event: FilterEditorMsg) {
+        match event {
+            FilterEditorMsg::Edit(index) => {
+                let valid = self.is_valid(index);
+                let style = self.entries[index].get_style_context();
+                if valid {
+                    style.remove_class("error");
+                    self.entries[index].set_tooltip_text(None);
+                } else {
+                    style.add_class("error");
+                    self.entries[index].set_tooltip_text(Some("The value can not be empty"));
+                }
+
+                self.button_save
+                    .set_sensitive((0..self.entries.len()).all(|i| self.is_valid(i)));
+                self.preview();
+            }
+            FilterEditorMsg::Remove(index) => {
+                self.model.removed[index] = true;
+                self.rows[index].hide();
+
+                self.button_save
+                    .set_sensitive((0..self.entries.len()).all(|i| self.is_valid(i)));
+                self.preview();
+            }
+            FilterEditorMsg::Save => {
+                self.model.saved = true;
+                self.model
+                    .relm
+                    .stream()
+                    .emit(FilterEditorMsg::Changed(self.edited()));
+                self.window.close();
+            }
+            FilterEditorMsg::Close => {
+                if !self.model.saved {
+                    self.model
+                        .relm
+                        .stream()
+                        .emit(FilterEditorMsg::Preview(self.model.original.clone()));
+                }
+            }
+            FilterEditorMsg::Preview(_) | FilterEditorMsg::Changed(_) => {}
+        }
+    }
+}
+
+impl Widget for FilterEditor {
+    type Root = Window;
+
+    fn root(&self) -> Self::Root {
+        self.window.clone()
+    }
+
+    fn view(relm: &Relm<Self>, model: Self::Model) -> Self {
+        let window = Window::new(WindowType::Toplevel);
+        window.set_default_size(360, 480);
+        window.set_modal(true);
+        connect!(
+            relm,
+            window,
+            connect_delete_event(_, _),
+            return (FilterEditorMsg::Close, Inhibit(false))
+        );
+
+        let header_bar = HeaderBar::new();
+        header_bar.set_title(Some("Filters"));
+        header_bar.set_show_close_button(true);
+
+        let button_save = Button::with_label("Save");
+        button_save
+            .get_style_context()
+            .add_class("suggested-action");
+        connect!(relm, button_save, connect_clicked(_), FilterEditorMsg::Save);
+        header_bar.pack_end(&button_save);
+
+        window.set_titlebar(Some(&header_bar));
+
+        let scrolled_window = ScrolledWindow::new::<Adjustment, Adjustment>(None, None);
+        scrolled_window.set_vexpand(true);
+
+        let box_filters = Box::new(Orientation::Vertical, 5);
+        let mut rows = vec![];
+        let mut entries = vec![];
+        for (index, filter) in model.filters.iter().enumerate() {
+            let row = Box::new(Orientation::Horizontal, 5);
+
+            let label = Label::new(Some(filter.get_label()));
+            label.set_width_chars(12);
+            label.set_xalign(0.0);
+            label.get_style_context().add_class("dim-label");
+
+            // Channel groups and hidden broadcasts can only be removed.
+            let entry = Entry::new();
+            entry.set_text(&filter.get_value());
+            entry.set_sensitive(filter.is_editable());
+            entry.set_hexpand(true);
+            connect!(
+                relm,
+                entry,
+                connect_changed(_),
+                FilterEditorMsg::Edit(index)
+            );
+
+            let button_remove =
+                Button::from_icon_name(Some("list-remove-symbolic"), IconSize::Button);
+            button_remove.set_tooltip_text(Some("Remove the filter"));
+            connect!(
+                relm,
+                button_remove,
+                connect_clicked(_),
+                FilterEditorMsg::Remove(index)
+            );
+
+            row.add(&label);
+            row.add(&entry);
+            row.add(&button_remove);
+            box_filters.add(&row);
+
+            rows.push(row);
+            entries.push(entry);
+        }
+        scrolled_window.add(&box_filters);
+
+        window.add(&scrolled_window);
+        window.show_all();
+
+        FilterEditor {
+            model,
+            window,
+            button_save,
+            rows,
+            entries,
+        }
+    }
+}
+
+impl FilterEditor {
+    /// Weather the filter at the index was removed or has a valid value.
+    fn is_valid(&self, index: usize) -> bool {
+        let filter = &self.model.filters[index];
+        self.model.removed[index]
+            || !filter.is_editable()
+            || filter.with_value(&self.entries[index].get_text()).is_ok()
+    }
+
+    /// The filters with the edits applied. Filters with a invalid value are kept unchanged.
+    fn edited(&self) -> ProgramFilter {
+        let mut filter = self.model.original.clone();
+        for (index, old) in self.model.filters.iter().enumerate() {
+            if self.model.removed[index] {
+                filter.remove(old);
+            } else if let Ok(new) = old.with_value(&self.entries[index].get_text()) {
+                filter.replace(old, new);
+            }
+        }
+        filter
+    }
+
+    /// Notify the parent about the edited filters, to show their effect in the list.
+    fn preview(&self) {
+        self.model
+            .relm
+            .stream()
+            .emit(FilterEditorMsg::Preview(self.edited()));
+    }
+}
@@ -0,0 +1,223 @@
+use crate::model::{CustomGroup, CustomGroups};
+
+use gtk::prelude::*;
+use gtk::{
+    Adjustment, Box, Button, CheckButton, ComboBoxText, Orientation, ScrolledWindow, Window,
+    WindowType,
+};
+use libhandy::{HeaderBar, HeaderBarExt};
+use relm::{connect, Relm, Update, Widget};
+use relm_derive::Msg;
+
+/// Messages for the group editor.
+#[derive(Msg)]
+pub enum GroupEditorMsg {
+    /// A existing group was selected or a new name was entered.
+    SelectGroup,
+    /// Save the selected group with the checked channels.
+    Save,
+    /// Delete the selected group.
+    Delete,
+    /// Block all channels of the selected group.
+    Block,
+    /// The groups were changed. Should be handled by the parent.
+    Changed(CustomGroups),
+    /// All channels of the group should be blocked. Should be handled by the parent.
+    BlockGroup(CustomGroup),
+}
+
+/// The model for the group editor containing the groups.
+pub struct GroupEditorModel {
+    groups: CustomGroups,
+    channels: Vec<String>,
+    relm: Relm<GroupEditor>,
+}
+
+/// The group editor is a window to create, change and delete the channel groups of the user.
+pub struct GroupEditor {
+    model: GroupEditorModel,
+    window: Window,
+    combo_name: ComboBoxText,
+    checks_channel: Vec<CheckButton>,
+}
+
+impl Update for GroupEditor {
+    type Model = GroupEditorModel;
+    type ModelParam = (CustomGroups, Vec<String>);
+    type Msg = GroupEditorMsg;
+
+    fn model(relm: &Relm<Self>, (groups, channels): Self::ModelParam) -> Self::Model {
+        GroupEditorModel {
+            groups,
+            channels,
+            relm: relm.clone(),
+        }
+    }
+
+    fn update(&mut self, event: GroupEditorMsg) {
+        match event {
+            GroupEditorMsg::SelectGroup => {
+                // Only existing groups change the checked channels, allowing to name a new group after checking them.
+                if let Some(group) = self.model.groups.get(&self.selected_name()) {
+                    let channels = group.get_channels();
+                    for (check, channel) in self.checks_channel.iter().zip(&self.model.channels) {
+                        check.set_active(channels.contains(channel));
+                    }
+                }
+            }
+            GroupEditorMsg::Save => {
+                let name = self.selected_name();
+                if name.is_empty() {
+                    return;
+                }
+
+                let mut group = CustomGroup::new(&name);
+                for (check, channel) in self.checks_channel.iter().zip(&self.model.channels) {
+                    if check.get_active() {
+                        group.add_channel(channel);
+                    }
+                }
+
+                self.model.groups.set(group);
+                self.groups_changed();
+            }
+            GroupEditorMsg::Delete => {
+                self.model.groups.remove(&self.selected_name());
+                self.groups_changed();
+                self.combo_name.set_active(None);
+            }
+            GroupEditorMsg::Block => {
+                if let Some(group) = self.model.groups.get(&self.selected_name()) {
+                    self.model
+                        .relm
+                        .stream()
+                        .emit(GroupEditorMsg::BlockGroup(group.clone()));
+                }
+            }
+            GroupEditorMsg::Changed(_) | GroupEditorMsg::BlockGroup(_) => {}
+        }
+    }
+}
+
+impl Widget for GroupEditor {
+    type Root = Window;
+
+    fn root(&self) -> Self::Root {
+        self.window.clone()
+    }
+
+    fn view(relm: &Relm<Self>, model: Self::Model) -> Self {
+        let window = Window::new(WindowType::Toplevel);
+        window.set_default_size(360, 480);
+        window.set_modal(true);
+
+        let header_bar = HeaderBar::new();
+        header_bar.set_title(Some("Channel groups"));
+        header_bar.set_show_close_button(true);
+
+        let button_save = Button::with_label("Save");
+        button_save
+            .get_style_context()
+            .add_class("suggested-action");
+        connect!(relm, button_save, connect_clicked(_), GroupEditorMsg::Save);
+        header_bar.pack_end(&button_save);
+
+        window.set_titlebar(Some(&header_bar));
+
+        let root = Box::new(Orientation::Vertical, 5);
+
+        let combo_name = ComboBoxText::with_entry();
+        for group in model.groups.iter() {
+            combo_name.append_text(&group.get_name());
+        }
+        connect!(
+            relm,
+            combo_name,
+            connect_changed(_),
+            GroupEditorMsg::SelectGroup
+        );
+        root.add(&combo_name);
+
+        let scrolled_window = ScrolledWindow::new::<Adjustment, Adjustment>(None, None);
+        scrolled_window.set_vexpand(true);
+
+        let box_channels = Box::new(Orientation::Vertical, 0);
+        let checks_channel: Vec<CheckButton> = model
+            .channels
+            .iter()
+            .map(|c| CheckButton::with_label(c))
+            .collect();
+        for check in &checks_channel {
+            box_channels.add(check);
+        }
+        scrolled_window.add(&box_channels);
+        root.add(&scrolled_window);
+
+        let box_actions = Box::new(Orientation::Horizontal, 5);
+        box_actions.set_homogeneous(true);
+
+        let button_block = Button::with_label("Block channels");
+        connect!(
+            relm,
+            button_block,
+            connect_clicked(_),
+            GroupEditorMsg::Block
+        );
+
+        let button_delete = Button::with_label("Delete group");
+        button_delete
+            .get_style_context()
+            .add_class("destructive-action");
+        connect!(
+            relm,
+            button_delete,
+            connect_clicked(_),
+            GroupEditorMsg::Delete
+        );
+
+        box_actions.add(&button_block);
+        box_actions.add(&button_delete);
+        root.add(&box_actions);
+
+        window.add(&root);
+        window.show_all();
+
+        GroupEditor {
+            model,
+            window,
+            combo_name,
+            checks_channel,
+        }
+    }
+}
+
+impl GroupEditor {
+    /// The name of the group that is selected or entered.
+    fn selected_name(&self) -> String {
+        self.combo_name
+            .get_active_text()
+            .map(|t| t.trim().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Update the list of groups and notify the parent.
+    fn groups_changed(&self) {
+        let name = self.selected_name();
+
+        self.combo_name.remove_all();
+        for group in self.model.groups.iter() {
+            self.combo_name.append_text(&group.get_name());
+        }
+
+        if let Some(entry) = self.combo_name.get_child() {
+            if let Ok(entry) = entry.downcast::<gtk::Entry>() {
+                entry.set_text(&name);
+            }
+        }
+
+        self.model
+            .relm
+            .stream()
+            .emit(GroupEditorMsg::Changed(self.model.groups.clone()));
+    }
+}
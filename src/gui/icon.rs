@@ -0,0 +1,19 @@
+use crate::model::Channel;
+
+use gdk_pixbuf::{Colorspace, Pixbuf};
+use glib::Bytes;
+
+/// Get the icon of the `Channel` as a `gdk_pixbuf::Pixbuf`.
+pub fn icon_pixbuf(channel: &Channel) -> Option<Pixbuf> {
+    channel.get_icon().map(|icon| {
+        Pixbuf::from_bytes(
+            &Bytes::from(icon.as_raw()),
+            Colorspace::Rgb,
+            true,
+            8,
+            icon.width() as i32,
+            icon.height() as i32,
+            4 * icon.width() as i32,
+        )
+    })
+}
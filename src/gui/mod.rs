@@ -1,13 +1,52 @@
+mod application;
+mod avatar;
+mod card;
+mod channel_editor;
+mod config;
+mod diagnostics;
+mod error_bar;
+mod filter_editor;
+mod group_editor;
+mod icon;
 mod movie_list;
 mod movie_list_item;
 mod movie_page;
+mod placeholder;
+mod preferences;
+mod remote_dialog;
+mod shortcuts;
 mod sliding_stack;
+mod tasks;
+#[cfg(all(test, feature = "mock"))]
+mod test;
+mod theme;
+mod toast;
+mod unlock_dialog;
 mod win;
 
+pub use application::run;
+pub use config::Config;
 pub use win::Win;
 
+/// The application id, used to register the application on the session bus.
+const APPLICATION_ID: &str = "de.schmidhuberj.tvtoday";
+
+use avatar::avatar_pixbuf;
+use card::write_card_to_path;
+use channel_editor::{ChannelEditor, ChannelEditorMsg};
+use diagnostics::Diagnostics;
+use error_bar::{ErrorBar, ErrorBarMsg};
+use filter_editor::{FilterEditor, FilterEditorMsg};
+use group_editor::{GroupEditor, GroupEditorMsg};
+use icon::icon_pixbuf;
 use movie_list::{MovieList, MovieListMsg};
-use movie_list_item::MovieListItem;
+use movie_list_item::{MovieListItem, MovieListItemData, MovieListItemMsg};
 use movie_page::{MoviePage, MoviePageMsg};
+use placeholder::{Placeholder, PlaceholderMsg};
+use preferences::{ClearData, Preferences, PreferencesMsg};
+use remote_dialog::show_remote_dialog;
 use sliding_stack::{SlidingStack, SlidingStackMsg};
+use theme::Theme;
+use toast::{Toast, ToastMsg};
+use unlock_dialog::run_unlock_dialog;
 use win::WinMsg;
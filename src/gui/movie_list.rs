@@ -1,111 +1,540 @@
-use crate::gui::{MovieListItem, SlidingStack, SlidingStackMsg, WinMsg};
-use crate::model::{FilterType, Program, ProgramFilter, Provider};
+use crate::gui::{
+    avatar_pixbuf, icon_pixbuf, show_remote_dialog, tasks, write_card_to_path, ChannelEditor,
+    ChannelEditorMsg, ClearData, Config, Diagnostics, ErrorBar, ErrorBarMsg, FilterEditor,
+    FilterEditorMsg, GroupEditor, GroupEditorMsg, MovieListItem, MovieListItemData,
+    MovieListItemMsg, Placeholder, PlaceholderMsg, Preferences, PreferencesMsg, SlidingStack,
+    SlidingStackMsg, Theme, Toast, ToastMsg, WinMsg,
+};
+use crate::model::metrics::{self, ReloadStep};
+use crate::model::providers::Saved;
+use crate::model::remote::{RemoteCommand, RemoteServer, RemoteView};
+use crate::model::{
+    cache, encryption, paths, search, send_health_report, speak, summarize, tonights_picks,
+    write_widget_to_path, Archive, Channel, ChannelAttribute, ChannelGroup, ChannelOrder,
+    CustomGroup, CustomGroups, FilterMode, FilterType, Movie, MovieAttribute, ParseFailure,
+    PersonalRatings, Preset, Presets, Program, ProgramDay, ProgramFilter, Provider, ProviderHandle,
+    Settings, SortOrder, StartedBroadcasts, SyncLog, TimeSlot,
+};
 use crate::Error;
 
-use std::fs::File;
+use std::cell::Cell;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs;
 use std::path::PathBuf;
-use std::thread;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Local, NaiveDateTime};
+use glib::Continue;
 use gtk::prelude::*;
 use gtk::{
-    Adjustment, Box, Button, ListBox, ListBoxRow, Orientation, ScrolledWindow, SelectionMode,
-    Spinner, Viewport,
+    ActionBar, Adjustment, Box, Button, CheckButton, ComboBoxText, Entry, FileChooserAction,
+    FileChooserNative, Image, Inhibit, Label, ListBox, ListBoxRow, Orientation, Overlay,
+    RadioButton, ResponseType, ScrolledWindow, SearchBar, SearchEntry, SelectionMode, Spinner,
+    ToggleButton, Viewport,
 };
 use libhandy::{HeaderBar, HeaderBarExt};
 use relm::{connect, Component, ContainerWidget, Relm, StreamHandle, Update, Widget};
 use relm_derive::Msg;
-use tokio::runtime::Runtime;
 
 #[derive(Msg)]
-pub enum MovieListMsg<T: 'static + Provider> {
+pub enum MovieListMsg {
     SwitchStack,
+    /// The window was drawn the first time. The program is only loaded afterwards, so the window and the cached
+    /// program are shown without waiting for the network.
+    Painted,
     Reload,
-    ReloadFinished((T, Result<Program, Error>)),
+    /// Store the state of the list to restore it on the next start. Sends `WinMsg::ShutdownReady` when done.
+    Shutdown,
+    /// Apply the filter to the loaded program again after it changed, reloading it only if it is stale.
+    Refilter,
+    /// A part of the program being loaded, shown before the whole program is loaded.
+    ProgramPart(Program),
+    ReloadFinished(Result<Program, Error>),
+    IconsLoaded(Result<Program, Error>),
     RowActivated(ListBoxRow),
+    /// The selected row changed, e.g. with a single click if movies are opened with a double click.
+    RowSelected(Option<ListBoxRow>),
+    /// Open the movie this many rows away from the opened one.
+    OpenRelative(i32),
+    /// Open the details of the movie, e.g. from the menu of its row.
+    OpenMovie(Movie),
+    /// Block the title of the movie.
+    BlockTitle(Movie),
+    /// Block the channel.
+    BlockChannel(Channel),
+    /// Open the website of the movie in the browser.
+    OpenInBrowser(Movie),
+    /// Show or hide the checks to select rows for blocking them together.
+    SetSelectionMode(bool),
+    /// The check of the row of the movie on the channel was toggled in the selection mode.
+    Check(((Channel, Movie), bool)),
+    /// Block the titles of the checked rows.
+    BlockCheckedTitles,
+    /// Block the channels of the checked rows.
+    BlockCheckedChannels,
     AddFilter(FilterType),
+    /// Add a filter until the given time.
+    AddFilterUntil((FilterType, NaiveDateTime)),
+    /// Restore the filters as they were before the last ones were added, from the toast.
+    UndoFilters,
+    TogglePin(Movie),
+    Sort(SortOrder),
+    Group(bool),
+    Voting(bool),
+    Vote(Movie),
+    SetActive(bool),
+    /// Reload the program in the background if enabled and it is stale.
+    AutoRefresh,
+    /// Dim or hide the movies that started since the list was built.
+    CheckStarted,
+    /// The system clock jumped, e.g. as the timezone changed. The started movies are updated immediately.
+    ClockChanged,
+    OpenGroupEditor,
+    SetGroups(CustomGroups),
+    OpenChannelEditor,
+    SetChannelOrder((ChannelOrder, Vec<String>)),
+    OpenFilterEditor,
+    /// Show the effect of filters edited but not saved yet.
+    PreviewFilters(ProgramFilter),
+    SetFilters(ProgramFilter),
+    BlockGroup(CustomGroup),
+    SetView(Option<String>),
+    SetFilterMode(FilterMode),
+    ShowGroup((ChannelGroup, bool)),
+    SavePreset,
+    ApplyPreset(String),
+    SetMaxAgeRating(Option<u32>),
+    HideReruns(bool),
+    /// Show a movie broadcasted on multiple channels at the same time only once.
+    Dedup(bool),
+    SetCompact(bool),
+    SetDark(bool),
+    SetReducedMotion(bool),
+    SetTwoLineTitles(bool),
+    ReadPicks,
+    OpenPreferences,
+    OpenDiagnostics,
+    SetSettings(Settings),
+    SetEncryption(Option<String>),
+    ClearData(ClearData),
+    FilterFileUnreadable(Error),
+    ResetFilterFile,
+    /// Send the report about the part of the website the provider failed to read, from the error bar.
+    ReportParseFailure,
+    ReportSent(Result<(), Error>),
+    SetTimeSlot(TimeSlot),
+    SetDay(ProgramDay),
+    Search(bool),
+    SetSearch(String),
+    MoveSelection(i32),
+    OpenSelected,
+    ShowShortcuts,
+    SetFolded(bool),
+    ExportJson,
+    ExportCard,
+    /// Ask where to save the loaded program to open it again later.
+    SaveProgram,
+    /// Ask for a saved program and show it instead of the current one.
+    OpenSavedProgram,
+    /// Show the current program again instead of the saved one.
+    CloseSavedProgram,
+    /// Ask for the ratings exported from IMDb and import them.
+    ImportRatings,
+    /// Start the remote control if it is not running yet and show its QR code.
+    OpenRemote,
+    /// A command sent from the remote control.
+    Remote(RemoteCommand),
+    ShowAll,
+    InsertRows(u32),
 }
 
-pub struct MovieListModel<T: 'static + Provider> {
+/// The id of the view showing all channels.
+const VIEW_ALL: &str = "all";
+
+/// The age ratings (FSK) selectable as maximum age rating.
+const AGE_RATINGS: [u32; 4] = [0, 6, 12, 16];
+
+/// The number of rows created at once, the remaining rows are created when idle.
+const ROWS_PER_CHUNK: usize = 40;
+
+/// How long to wait for further changes of the filter before applying it in milliseconds.
+const REFILTER_DELAY: u32 = 300;
+
+/// How often to check whether the program should be refreshed in the background in milliseconds.
+const AUTO_REFRESH_CHECK_INTERVAL: u32 = 60 * 1000;
+
+/// How often to check whether more movies started in milliseconds.
+const STARTED_CHECK_INTERVAL: u32 = 60 * 1000;
+
+/// The number of days a movie broadcasted again is considered a rerun.
+const RERUN_DAYS: u32 = 14;
+
+pub struct MovieListModel {
     program: Program,
-    provider: T,
+    /// The program as loaded, before the filter was applied.
+    loaded: Program,
+    provider: ProviderHandle,
+    /// The provider of the saved program shown instead of the current one, if any.
+    saved: Option<Saved>,
+    /// Weather the program is being loaded.
+    reloading: bool,
+    /// Weather the program should be loaded again once the current loading finished, e.g. as the time slot changed.
+    reload_pending: bool,
+    /// Weather the filter changed and is applied to the program shortly.
+    refilter_pending: bool,
+    /// The scroll position of the last session, restored once the program is shown.
+    restore_scroll: Option<f64>,
+
+    /// The error the program failed to load with the last time, explained by the placeholder of the list.
+    load_error: Option<Error>,
+    /// The part of the website the provider failed to read the last time, only kept if reports are enabled.
+    parse_failure: Option<ParseFailure>,
+    /// When the program was loaded successfully the last time.
+    last_reload: Option<Instant>,
+    /// When the list was created, to measure how long it takes until the window is drawn.
+    created: Instant,
+    /// Weather the window is active. No refreshes are needed while inactive.
+    active: bool,
+
+    /// The order the program is sorted by.
+    sort_order: SortOrder,
+    /// The program in the order it is shown in the list.
+    shown: Program,
+    /// The movie of the selected row, selected again once the list is rebuilt.
+    selected: Option<Movie>,
+    /// Whether rows can be checked to block them together.
+    selection_mode: bool,
+    /// The rows checked in the selection mode.
+    checked: Vec<(Channel, Movie)>,
+    /// The filters added the last time, with their expiry before if they were added already, to undo adding them.
+    undo_filters: Vec<(FilterType, Option<Option<NaiveDateTime>>)>,
+    /// The movie opened last on the movie page.
+    opened: Option<Movie>,
+    /// The movies pinned to the top of the list in this session.
+    pinned: Vec<Movie>,
+    /// Whether the household voting mode is enabled, sorting the pinned movies by their votes.
+    voting: bool,
+    /// The votes for the pinned movies in this session.
+    votes: HashMap<Movie, u32>,
+    /// The program shown by the remote control.
+    remote_view: RemoteView,
+    /// The URL of the remote control once it was started.
+    remote_url: Option<String>,
+    /// Whether consecutive movies of the same channel are grouped under a header.
+    grouped: bool,
+    /// Whether movies already broadcasted in the last `RERUN_DAYS` are hidden.
+    hide_reruns: bool,
+    /// Whether a movie broadcasted on multiple channels at the same time is shown only once.
+    dedup: bool,
+    /// The shown program before the movies were deduplicated, used to list all channels of a movie.
+    duplicates: Program,
+    /// The movies added to the program by the last reload, marked as new.
+    added: Vec<Movie>,
+    /// The movies of the program that were dimmed or hidden as started when the list was built.
+    started: Vec<Movie>,
+    /// The time slot of the shown program.
+    time_slot: TimeSlot,
+    /// The day of the shown program.
+    day: ProgramDay,
+    /// The text the titles of the shown movies must contain, independent of their spelling.
+    search: String,
 
     filter: ProgramFilter,
-    filter_path: PathBuf,
+    /// The synced change log the filters are stored in.
+    filter_log: SyncLog,
+
+    custom_groups: CustomGroups,
+    custom_groups_path: PathBuf,
+    /// The name of the custom group whose channels are exclusively shown.
+    view_group: Option<String>,
+
+    /// The order of the channels and the favorite channels.
+    channel_order: ChannelOrder,
+    channel_order_path: PathBuf,
+
+    presets: Presets,
+    presets_path: PathBuf,
+
+    settings: Settings,
+    settings_path: PathBuf,
+    /// The file with the password of the provider, kept with the user data so it is encrypted with it.
+    credentials_path: PathBuf,
+    config: Config,
+
+    /// The broadcasts of the last `RERUN_DAYS`, used to detect reruns.
+    archive: Archive,
+    archive_path: PathBuf,
+
+    /// The ratings the user imported from IMDb, movies rated below the minimum of the settings are hidden.
+    personal_ratings: PersonalRatings,
+    personal_ratings_path: PathBuf,
 
     movies: Vec<Component<MovieListItem>>,
+    /// Counts how often the rows were reset, rows scheduled to be created for a earlier reset are dropped.
+    rows_generation: u32,
+    /// Whether parts of the program being loaded were shown already.
+    receiving_parts: bool,
 
-    stream_win: StreamHandle<WinMsg<T>>,
-    relm: Relm<MovieList<T>>,
+    stream_win: StreamHandle<WinMsg>,
+    relm: Relm<MovieList>,
 }
 
-impl<T: 'static + Provider> MovieListModel<T> {
-    fn write_filters(&self) -> Result<(), Error> {
-        self.filter.write_to_path(self.filter_path.clone())
+impl MovieListModel {
+    fn write_filters(&mut self) -> Result<(), Error> {
+        self.filter_log.write(&self.filter.clone().into())
+    }
+
+    fn write_archive(&self) -> Result<(), Error> {
+        self.archive.write_to_path(self.archive_path.clone())
+    }
+
+    fn write_personal_ratings(&self) -> Result<(), Error> {
+        self.personal_ratings
+            .write_to_path(self.personal_ratings_path.clone())
+    }
+
+    fn write_settings(&self) -> Result<(), Error> {
+        self.settings.write_to_path(self.settings_path.clone())?;
+        self.settings
+            .write_credentials_to_path(self.credentials_path.clone())
+    }
+
+    fn write_presets(&self) -> Result<(), Error> {
+        self.presets.write_to_path(self.presets_path.clone())
+    }
+
+    fn write_custom_groups(&self) -> Result<(), Error> {
+        self.custom_groups
+            .write_to_path(self.custom_groups_path.clone())
+    }
+
+    fn write_channel_order(&self) -> Result<(), Error> {
+        self.channel_order
+            .write_to_path(self.channel_order_path.clone())
+    }
+
+    /// Remove all user data, the settings and the cache.
+    fn remove_all(&self) -> Result<(), Error> {
+        for path in paths::user_data_files()? {
+            fs::remove_file(path)?;
+        }
+        encryption::forget();
+
+        if self.settings_path.exists() {
+            fs::remove_file(&self.settings_path)?;
+        }
+        self.config.reset();
+
+        paths::clear_user_cache_dir()?;
+
+        Ok(())
     }
 }
 
-pub struct MovieList<T: 'static + Provider> {
-    model: MovieListModel<T>,
+pub struct MovieList {
+    model: MovieListModel,
     widgets: MovieListWidgets,
     components: MovieListComponents,
 }
 
 pub struct MovieListComponents {
     stack: Component<SlidingStack<Box, ScrolledWindow>>,
+    error_bar: Component<ErrorBar>,
+    placeholder: Component<Placeholder>,
+    group_editor: Option<Component<GroupEditor>>,
+    channel_editor: Option<Component<ChannelEditor>>,
+    filter_editor: Option<Component<FilterEditor>>,
+    preferences: Option<Component<Preferences>>,
+    diagnostics: Option<Component<Diagnostics>>,
+    theme: Theme,
+    toast: Component<Toast>,
 }
 
 struct MovieListWidgets {
     root: Box,
+    /// The header bar, its subtitle tells when the program was updated.
+    header_bar: HeaderBar,
+    /// Shown while a saved program is shown, to show the current program again.
+    button_close_saved: Button,
     listbox: ListBox,
     loading_spinner: Spinner,
+    scrolled_window: ScrolledWindow,
+    button_search: ToggleButton,
+    search_bar: SearchBar,
+    entry_search: SearchEntry,
+    combo_view: ComboBoxText,
+    checks_group: Vec<(ChannelGroup, CheckButton)>,
+    radios_sort: Vec<(SortOrder, RadioButton)>,
+    check_grouped: CheckButton,
+    check_reruns: CheckButton,
+    combo_time_slot: ComboBoxText,
+    combo_preset: ComboBoxText,
+    entry_preset: Entry,
+    /// The bar with the primary actions at the bottom, only created once the window is folded.
+    bottom_bar: Option<BottomBar>,
+    button_select: ToggleButton,
+    /// The bar with the actions for the checked rows, shown in the selection mode.
+    selection_bar: ActionBar,
+    label_checked: Label,
+    button_block_titles: Button,
+    button_block_channels: Button,
 }
 
-impl<T: 'static + Provider> Update for MovieList<T> {
-    type Model = MovieListModel<T>;
-    type ModelParam = (StreamHandle<WinMsg<T>>, T);
-    type Msg = MovieListMsg<T>;
+/// The primary actions at the bottom of the window, in thumb reach on phones.
+struct BottomBar {
+    action_bar: ActionBar,
+    button_search: ToggleButton,
+    combo_preset: ComboBoxText,
+}
 
-    fn model(relm: &Relm<Self>, (stream_win, provider): Self::ModelParam) -> MovieListModel<T> {
-        let mut user_data_dir =
-            glib::get_user_data_dir().expect("Could not get user data directory");
-        user_data_dir.push("tvtoday");
+impl Update for MovieList {
+    type Model = MovieListModel;
+    type ModelParam = (StreamHandle<WinMsg>, ProviderHandle, Settings, Config);
+    type Msg = MovieListMsg;
 
-        if !user_data_dir.exists() {
-            std::fs::create_dir_all(user_data_dir.clone())
-                .expect("Could not create the user data directory");
-        }
+    fn model(
+        relm: &Relm<Self>,
+        (stream_win, provider, settings, config): Self::ModelParam,
+    ) -> MovieListModel {
+        let mut filter_log = ProgramFilter::open_log(paths::user_data_dir(), &paths::device_id());
+        let filter_opt = ProgramFilter::read_from_log_or_migrate(
+            &mut filter_log,
+            paths::user_data_file("filters.json"),
+            paths::user_data_file("filters.csv"),
+        );
+
+        let custom_groups_path = paths::user_data_file("groups.csv");
+        let custom_groups_opt = CustomGroups::read_from_path(custom_groups_path.clone());
 
-        let mut filter_path = user_data_dir.clone();
-        filter_path.push("filters.csv");
+        let channel_order_path = paths::user_data_file("channels.csv");
+        let channel_order_opt = ChannelOrder::read_from_path(channel_order_path.clone());
 
-        if !filter_path.exists() {
-            let _ = File::create(filter_path.clone());
+        let presets_path = paths::user_data_file("presets.csv");
+        let presets_opt = Presets::read_from_path(presets_path.clone());
+
+        let settings_path = paths::user_config_file("settings.csv");
+        let credentials_path = paths::user_data_file("credentials.csv");
+        stream_win.emit(WinMsg::SetOmdbApiKey(settings.get_omdb_api_key()));
+        stream_win.emit(WinMsg::SetTranslationUrl(settings.get_translation_url()));
+
+        let archive_path = paths::user_data_file("archive.csv");
+        let archive_opt = Archive::read_from_path(archive_path.clone());
+
+        let personal_ratings_path = paths::user_data_file("ratings.csv");
+        let personal_ratings =
+            PersonalRatings::read_from_path(&personal_ratings_path).unwrap_or_default();
+        stream_win.emit(WinMsg::SetPersonalRatings(personal_ratings.clone()));
+
+        // The filters are not applied until the file is fixed or reset, instead of failing to start.
+        if let Err(error) = &filter_opt {
+            relm.stream()
+                .emit(MovieListMsg::FilterFileUnreadable(error.clone()));
         }
+        let filter = filter_opt.unwrap_or_default();
+        stream_win.emit(WinMsg::SetFilterMode(filter.get_mode()));
 
-        let filter_opt = ProgramFilter::read_from_path(filter_path.clone());
+        let (scroll_position, search, view_group) = config.get_session();
 
-        relm.stream().emit(MovieListMsg::Reload);
+        // The program cached by the last start today is shown until the current one is loaded.
+        let cached = read_cached_program(settings.get_time_slot()).unwrap_or_default();
+
+        relm::interval(relm.stream(), AUTO_REFRESH_CHECK_INTERVAL, || {
+            MovieListMsg::AutoRefresh
+        });
+        relm::interval(relm.stream(), STARTED_CHECK_INTERVAL, || {
+            MovieListMsg::CheckStarted
+        });
         MovieListModel {
-            program: Program::new(),
+            program: filter.filter(&cached),
+            loaded: Program::new(),
+            reloading: false,
+            reload_pending: false,
+            refilter_pending: false,
+            restore_scroll: Some(scroll_position).filter(|p| *p > 0.0),
             provider,
+            saved: None,
+
+            load_error: None,
+            parse_failure: None,
+            last_reload: None,
+            created: Instant::now(),
+            active: true,
+
+            sort_order: SortOrder::Channel,
+            shown: Program::new(),
+            selected: None,
+            selection_mode: false,
+            checked: vec![],
+            undo_filters: vec![],
+            opened: None,
+            pinned: vec![],
+            voting: false,
+            votes: HashMap::new(),
+            remote_view: RemoteView::new(),
+            remote_url: None,
+            grouped: false,
+            hide_reruns: false,
+            dedup: false,
+            duplicates: Program::new(),
+            added: vec![],
+            started: vec![],
+            time_slot: settings.get_time_slot(),
+            day: ProgramDay::Today,
+            search: search.trim().to_string(),
+
+            filter,
+            filter_log,
 
-            filter: filter_opt.unwrap_or(ProgramFilter::new()),
-            filter_path,
+            custom_groups: custom_groups_opt.unwrap_or_default(),
+            custom_groups_path,
+            view_group,
+
+            channel_order: channel_order_opt.unwrap_or_default(),
+            channel_order_path,
+
+            presets: presets_opt.unwrap_or_default(),
+            presets_path,
+
+            settings,
+            settings_path,
+            credentials_path,
+            config,
+
+            archive: archive_opt.unwrap_or_default(),
+            archive_path,
+            personal_ratings,
+            personal_ratings_path,
 
             movies: vec![],
+            rows_generation: 0,
+            receiving_parts: false,
 
             stream_win,
             relm: relm.clone(),
         }
     }
 
-    fn update(&mut self, event: MovieListMsg<T>) {
+    fn update(&mut self, event: MovieListMsg) {
         match event {
             MovieListMsg::SwitchStack => {
                 self.components.stack.emit(SlidingStackMsg::Switch);
             }
+            MovieListMsg::Painted => {
+                metrics::record_first_paint(self.model.created.elapsed());
+                self.model.relm.stream().emit(MovieListMsg::Reload);
+            }
             MovieListMsg::Reload => {
+                // Reloads requested while loading are done once afterwards.
+                if self.model.reloading {
+                    self.model.reload_pending = true;
+                    return;
+                }
+                self.model.reloading = true;
                 self.widgets.loading_spinner.set_visible(true);
+                metrics::start_reload();
 
                 let stream = self.model.relm.stream().clone();
                 self.components.stack.emit(SlidingStackMsg::ShowSecondPage);
@@ -114,155 +543,2408 @@ impl<T: 'static + Provider> Update for MovieList<T> {
                     stream.emit(MovieListMsg::ReloadFinished(result))
                 });
 
-                let mut provider = self.model.provider.clone();
+                let stream = self.model.relm.stream().clone();
+                let (_channel_parts, sender_parts) =
+                    relm::Channel::new(move |part| stream.emit(MovieListMsg::ProgramPart(part)));
+                // The callback of the provider may be called from any thread.
+                let sender_parts = Mutex::new(sender_parts);
+
+                self.model.receiving_parts = false;
+                // The program is loaded by a copy of the provider, which knows more about it afterwards.
+                let handle = self.model.provider.clone();
+                let mut provider = handle.get();
+                provider.set_time_slot(self.model.time_slot);
+                provider.set_day(self.model.day);
+                let mut saved = self.model.saved.as_ref().map(|s| s.clone());
 
-                thread::spawn(move || {
-                    let rt = Runtime::new().expect("Could not create runtime");
-                    let program = rt.block_on(provider.get_program());
-                    sender.send((provider, program)).unwrap()
+                tasks::spawn(move || async move {
+                    let on_part = |part: Program| {
+                        let _ = sender_parts.lock().unwrap().send(part);
+                    };
+                    let program = match saved.as_mut() {
+                        Some(saved) => saved.get_program_progressively(&on_part).await,
+                        None => provider.get_program_progressively(&on_part).await,
+                    };
+                    handle.set(provider);
+                    let _ = sender.send(program);
                 });
             }
-            MovieListMsg::ReloadFinished((provider, program_res)) => {
+            MovieListMsg::ProgramPart(part) => {
+                // The rows of the last program are replaced once the first part arrived.
+                if !self.model.receiving_parts {
+                    self.model.receiving_parts = true;
+                    let listbox = &self.widgets.listbox;
+                    listbox.foreach(|c| listbox.remove(c));
+                    self.model.movies.clear();
+                    self.model.program = Program::new();
+                    self.model.shown = Program::new();
+                    self.model.rows_generation += 1;
+                }
+
+                let part = self.model.filter.filter(&part);
+                for (channel, movie) in self.visible(part).iter() {
+                    if !self.model.shown.iter().any(|(_c, m)| m == movie) {
+                        self.model.program.add(channel.clone(), movie.clone());
+                        self.model.shown.add(channel.clone(), movie.clone());
+                    }
+                }
+                self.insert_rows();
+            }
+            MovieListMsg::Shutdown => {
+                let scroll_position = self
+                    .widgets
+                    .scrolled_window
+                    .get_vadjustment()
+                    .map(|a| a.get_value())
+                    .unwrap_or(0.0);
+                let search = if self.widgets.search_bar.get_search_mode() {
+                    self.widgets.entry_search.get_text().to_string()
+                } else {
+                    String::new()
+                };
+                self.model.config.set_session(
+                    scroll_position,
+                    &search,
+                    self.model.view_group.as_deref(),
+                );
+                self.model.config.sync();
+
+                self.model.stream_win.emit(WinMsg::ShutdownReady);
+            }
+            MovieListMsg::Refilter => {
+                self.model.refilter_pending = false;
+
+                if self.is_stale() {
+                    self.model.relm.stream().emit(MovieListMsg::Reload);
+                } else {
+                    self.model.program = self.model.filter.filter(&self.model.loaded);
+                    self.reset_movies();
+                }
+            }
+            MovieListMsg::ReloadFinished(program_res) => {
                 self.widgets.loading_spinner.set_visible(false);
+                self.model.receiving_parts = false;
+                self.model.reloading = false;
+
+                let loaded = program_res.is_ok();
+                match program_res {
+                    Ok(program) => {
+                        // Hidden broadcasts that ended are not needed any more.
+                        if self.model.filter.remove_expired(Local::now().naive_local()) {
+                            let _ = self.model.write_filters();
+                        }
+                        // Everything would be new on the first load or when switching to or from a saved program.
+                        self.model.added = if self.model.loaded.iter().next().is_none()
+                            || self.model.saved.is_some()
+                        {
+                            vec![]
+                        } else {
+                            program
+                                .diff(&self.model.loaded)
+                                .iter()
+                                .map(|(_c, m)| m.clone())
+                                .collect()
+                        };
+                        self.model.loaded = program.clone();
+                        self.model.program = self.model.filter.filter(&program);
+                        self.model.last_reload = Some(Instant::now());
 
+                        // A saved program is neither broadcasted today nor does it change reminders.
+                        if self.model.saved.is_some() {
+                            self.widgets.header_bar.set_subtitle(Some("Saved program"));
+                        } else {
+                            let today = Local::now().date_naive();
+                            self.model.archive.add_program(&program, today);
+                            self.model
+                                .archive
+                                .prune(today - chrono::Duration::days(RERUN_DAYS as i64));
+                            let _ = self.model.write_archive();
+
+                            if self.model.day == ProgramDay::Today {
+                                let _ = program
+                                    .write_to_path(cached_program_path(self.model.time_slot));
+                            }
+
+                            self.model.stream_win.emit(WinMsg::ProgramLoaded(program));
+                            self.widgets.header_bar.set_subtitle(Some(&format!(
+                                "Updated at {}",
+                                Local::now().format("%H:%M")
+                            )));
+                        }
+                        self.model.load_error = None;
+                        self.model.parse_failure = None;
+                        self.components.error_bar.emit(ErrorBarMsg::Hide);
+                    }
+                    Err(error) => {
+                        // Reload once the provider is available again.
+                        if let Error::ProviderUnavailable(remaining) = error {
+                            relm::timeout(
+                                self.model.relm.stream(),
+                                remaining.as_millis() as u32,
+                                || MovieListMsg::Reload,
+                            );
+                        }
+
+                        // The last program stays shown, e.g. the cached one when offline. Parts of the failed
+                        // reload are replaced by it.
+                        self.model.program = self.model.filter.filter(&self.model.loaded);
+                        self.model.load_error = Some(error.clone());
+
+                        // Reports are only offered if the user set where to send them.
+                        self.model.parse_failure = self
+                            .model
+                            .provider
+                            .lock()
+                            .get_parse_failure()
+                            .filter(|_| self.model.settings.get_health_report_url().is_some());
+                        if self.model.parse_failure.is_some() {
+                            self.components
+                                .error_bar
+                                .emit(ErrorBarMsg::ShowReportable(error));
+                        } else {
+                            self.components.error_bar.emit(ErrorBarMsg::Show(error));
+                        }
+                    }
+                }
+                self.reset_movies();
+                cache::set_icon_size(self.model.program.icon_size());
+                if loaded && self.model.saved.is_none() {
+                    self.write_widget();
+                }
+
+                if self.model.reload_pending {
+                    self.model.reload_pending = false;
+                    self.model.relm.stream().emit(MovieListMsg::Reload);
+                } else if loaded && self.model.saved.is_none() {
+                    // The icons are loaded after the program is shown.
+                    self.load_icons();
+                }
+            }
+            MovieListMsg::IconsLoaded(program_res) => {
                 if let Ok(program) = program_res {
-                    self.model.program = self.model.filter.filter(&program);
+                    let program = program.limit_icons(cache::get_icon_limit());
+                    let channels: HashMap<String, Channel> = program
+                        .iter()
+                        .map(|(c, _m)| (c.get_name(), c.clone()))
+                        .collect();
+                    let with_icon = |program: &Program| -> Program {
+                        program
+                            .iter()
+                            .map(|(c, m)| {
+                                (channels.get(&c.get_name()).unwrap_or(c).clone(), m.clone())
+                            })
+                            .collect()
+                    };
+
+                    self.model.loaded = with_icon(&self.model.loaded);
+                    self.model.program = with_icon(&self.model.program);
+                    self.model.shown = with_icon(&self.model.shown);
+                    cache::set_icon_size(self.model.program.icon_size());
+
+                    // Patch the icons into the existing rows.
+                    for (component, (channel, _movie)) in
+                        self.model.movies.iter().zip(self.model.shown.iter())
+                    {
+                        component.emit(MovieListItemMsg::SetChannel(channel.clone()));
+                    }
+                }
+            }
+            MovieListMsg::AddFilter(filter) => self.add_filters(vec![(filter, None)]),
+            MovieListMsg::AddFilterUntil((filter, expiry)) => {
+                self.add_filters(vec![(filter, Some(expiry))])
+            }
+            MovieListMsg::UndoFilters => {
+                let undo: Vec<_> = self.model.undo_filters.drain(..).collect();
+                for (filter, previous) in undo {
+                    match previous {
+                        None => self.model.filter.remove(&filter),
+                        Some(None) => self.model.filter.add(filter),
+                        Some(Some(expiry)) => self.model.filter.add_until(filter, expiry),
+                    }
+                }
+                self.schedule_refilter();
+
+                let _ = self.model.write_filters();
+            }
+            MovieListMsg::TogglePin(movie) => {
+                if let Some(index) = self.model.pinned.iter().position(|m| m == &movie) {
+                    self.model.pinned.remove(index);
+                    self.model.votes.remove(&movie);
+                } else {
+                    self.model.pinned.push(movie);
+                }
 
+                self.reset_movies();
+                self.write_widget();
+            }
+            MovieListMsg::Sort(order) => {
+                self.model.sort_order = order;
+                self.reset_movies();
+            }
+            MovieListMsg::Voting(voting) => {
+                self.model.voting = voting;
+                self.reset_movies();
+            }
+            MovieListMsg::Vote(movie) => {
+                *self.model.votes.entry(movie).or_insert(0) += 1;
+                self.reset_movies();
+            }
+            MovieListMsg::SetActive(active) => {
+                if active && !self.model.active && self.is_stale() {
+                    self.model.relm.stream().emit(MovieListMsg::Reload);
+                }
+                self.model.active = active;
+            }
+            MovieListMsg::AutoRefresh => {
+                if self.model.settings.get_auto_refresh()
+                    && !self.model.reloading
+                    && self.is_stale()
+                {
+                    // The reloaded program is shown where the user left off.
+                    self.model.restore_scroll = self
+                        .widgets
+                        .scrolled_window
+                        .get_vadjustment()
+                        .map(|a| a.get_value());
+                    self.model.relm.stream().emit(MovieListMsg::Reload);
+                }
+            }
+            MovieListMsg::CheckStarted => {
+                if self.started_movies() != self.model.started {
                     self.reset_movies();
+                }
+            }
+            MovieListMsg::ClockChanged => {
+                // The movies running now are different at the new time.
+                if self.model.time_slot == TimeSlot::Now && self.model.saved.is_none() {
+                    self.model.relm.stream().emit(MovieListMsg::Reload);
                 } else {
-                    self.model.program = Program::new();
                     self.reset_movies();
                 }
-                self.model.provider = provider.clone();
-                self.model.stream_win.emit(WinMsg::UpdateProvider(provider));
             }
-            MovieListMsg::AddFilter(filter) => {
-                self.model.filter.add(filter);
-                self.model.relm.stream().emit(MovieListMsg::Reload);
+            MovieListMsg::OpenGroupEditor => {
+                // The channels of the program and the ones already in a group can be added to a group.
+                let mut channels: Vec<String> = vec![];
+                let group_channels = self
+                    .model
+                    .custom_groups
+                    .iter()
+                    .flat_map(|g| g.get_channels());
+                for channel in self
+                    .model
+                    .program
+                    .iter()
+                    .map(|(c, _m)| c.get_name())
+                    .chain(group_channels)
+                {
+                    if !channels.contains(&channel) {
+                        channels.push(channel);
+                    }
+                }
 
-                let _ = self.model.write_filters();
+                let group_editor = relm::create_component::<GroupEditor>((
+                    self.model.custom_groups.clone(),
+                    channels,
+                ));
+                connect!(
+                    group_editor@GroupEditorMsg::Changed(ref groups),
+                    self.model.relm,
+                    MovieListMsg::SetGroups(groups.clone())
+                );
+                connect!(
+                    group_editor@GroupEditorMsg::BlockGroup(ref group),
+                    self.model.relm,
+                    MovieListMsg::BlockGroup(group.clone())
+                );
+
+                if let Some(window) = self.widgets.root.get_toplevel() {
+                    if let Ok(window) = window.downcast::<gtk::Window>() {
+                        group_editor.widget().set_transient_for(Some(&window));
+                    }
+                }
+
+                self.components.group_editor = Some(group_editor);
             }
-            MovieListMsg::RowActivated(row) => {
-                let index = self
-                    .widgets
-                    .listbox
-                    .get_children()
+            MovieListMsg::OpenChannelEditor => {
+                let channels: Vec<String> = self
+                    .model
+                    .program
                     .iter()
-                    .position(|x| x.clone() == row)
-                    .unwrap();
+                    .map(|(c, _m)| c.get_name())
+                    .collect();
 
-                let movie = &self.model.program[index];
-                self.model
-                    .stream_win
-                    .emit(WinMsg::SelectedMovie(movie.clone()));
+                let channel_editor = relm::create_component::<ChannelEditor>((
+                    self.model.channel_order.clone(),
+                    channels,
+                    self.model.settings.get_subscribed_channels(),
+                ));
+                connect!(
+                    channel_editor@ChannelEditorMsg::Changed(ref change),
+                    self.model.relm,
+                    MovieListMsg::SetChannelOrder(change.clone())
+                );
+
+                if let Some(window) = self.widgets.root.get_toplevel() {
+                    if let Ok(window) = window.downcast::<gtk::Window>() {
+                        channel_editor.widget().set_transient_for(Some(&window));
+                    }
+                }
+
+                self.components.channel_editor = Some(channel_editor);
             }
-        }
-    }
-}
+            MovieListMsg::OpenFilterEditor => {
+                let filter_editor =
+                    relm::create_component::<FilterEditor>(self.model.filter.clone());
+                connect!(
+                    filter_editor@FilterEditorMsg::Preview(ref filter),
+                    self.model.relm,
+                    MovieListMsg::PreviewFilters(filter.clone())
+                );
+                connect!(
+                    filter_editor@FilterEditorMsg::Changed(ref filter),
+                    self.model.relm,
+                    MovieListMsg::SetFilters(filter.clone())
+                );
 
-impl<T: 'static + Provider> Widget for MovieList<T> {
-    type Root = Box;
+                if let Some(window) = self.widgets.root.get_toplevel() {
+                    if let Ok(window) = window.downcast::<gtk::Window>() {
+                        filter_editor.widget().set_transient_for(Some(&window));
+                    }
+                }
 
-    fn root(&self) -> Self::Root {
-        self.widgets.root.clone()
-    }
+                self.components.filter_editor = Some(filter_editor);
+            }
+            MovieListMsg::PreviewFilters(filter) => {
+                self.model.filter = filter;
+                self.schedule_refilter();
+            }
+            MovieListMsg::SetFilters(filter) => {
+                self.model.filter = filter;
+                self.schedule_refilter();
+                self.reset_group_checks();
 
-    fn view(relm: &Relm<Self>, model: Self::Model) -> Self {
-        let root = Box::new(Orientation::Vertical, 0);
-        root.set_hexpand(true);
+                let _ = self.model.write_filters();
+            }
+            MovieListMsg::SetChannelOrder((order, subscribed_channels)) => {
+                self.model.channel_order = order;
+                let _ = self.model.write_channel_order();
+                self.model
+                    .settings
+                    .set_subscribed_channels(subscribed_channels);
+                let _ = self.model.write_settings();
 
-        let header_bar = HeaderBar::new();
-        header_bar.set_title(Some("Movies"));
+                self.reset_movies();
+            }
+            MovieListMsg::SetGroups(groups) => {
+                self.model.custom_groups = groups;
+                let _ = self.model.write_custom_groups();
 
-        let loading_spinner = Spinner::new();
-        loading_spinner.set_visible(false);
-        loading_spinner.start();
+                self.reset_view_groups();
+            }
+            MovieListMsg::BlockGroup(group) => {
+                for filter in group.to_filters() {
+                    self.model.filter.add(filter);
+                }
+                self.schedule_refilter();
 
-        header_bar.pack_start(&loading_spinner);
+                let _ = self.model.write_filters();
+            }
+            MovieListMsg::SetView(view_group) => {
+                self.model.view_group = view_group;
+                self.reset_movies();
+            }
+            MovieListMsg::SetFilterMode(mode) => {
+                self.model.filter.set_mode(mode);
+                self.schedule_refilter();
 
-        let button_switch_stack = Button::new();
-        button_switch_stack.set_image(Some(&gtk::Image::from_icon_name(
-            Some("open-menu-symbolic"),
-            gtk::IconSize::Menu,
-        )));
-        connect!(
-            relm,
-            button_switch_stack,
-            connect_clicked(_),
-            MovieListMsg::SwitchStack
-        );
+                let _ = self.model.write_filters();
 
-        header_bar.pack_end(&button_switch_stack);
+                self.reset_group_checks();
+                self.model.stream_win.emit(WinMsg::SetFilterMode(mode));
+            }
+            MovieListMsg::SetMaxAgeRating(max_age_rating) => {
+                if self.model.filter.get_max_age_rating() != max_age_rating {
+                    self.model.filter.set_max_age_rating(max_age_rating);
+                    self.schedule_refilter();
 
-        root.add(&header_bar);
+                    let _ = self.model.write_filters();
+                }
+            }
+            MovieListMsg::HideReruns(hide_reruns) => {
+                self.model.hide_reruns = hide_reruns;
+                self.reset_movies();
+            }
+            MovieListMsg::Dedup(dedup) => {
+                self.model.dedup = dedup;
+                self.reset_movies();
+            }
+            MovieListMsg::ReadPicks => {
+                let picks = tonights_picks(&self.model.shown, &self.model.pinned);
+                if let Err(error) = speak(&summarize(&picks)) {
+                    self.components.error_bar.emit(ErrorBarMsg::Show(error));
+                }
+            }
+            MovieListMsg::OpenPreferences => {
+                let preferences =
+                    relm::create_component::<Preferences>(self.model.settings.clone());
+                connect!(
+                    preferences@PreferencesMsg::Changed(ref settings),
+                    self.model.relm,
+                    MovieListMsg::SetSettings((**settings).clone())
+                );
+                connect!(
+                    preferences@PreferencesMsg::Cleared(data),
+                    self.model.relm,
+                    MovieListMsg::ClearData(data)
+                );
+                connect!(
+                    preferences@PreferencesMsg::SetEncryption(ref passphrase),
+                    self.model.relm,
+                    MovieListMsg::SetEncryption(passphrase.clone())
+                );
 
-        let scrolled_window = ScrolledWindow::new::<Adjustment, Adjustment>(None, None);
-        scrolled_window.set_hexpand(true);
-        scrolled_window.set_vexpand(true);
+                if let Some(window) = self.widgets.root.get_toplevel() {
+                    if let Ok(window) = window.downcast::<gtk::Window>() {
+                        preferences.widget().set_transient_for(Some(&window));
+                    }
+                }
 
-        let viewport = Viewport::new::<Adjustment, Adjustment>(None, None);
+                self.components.preferences = Some(preferences);
+            }
+            MovieListMsg::OpenDiagnostics => {
+                let diagnostics = relm::create_component::<Diagnostics>(());
 
-        scrolled_window.add(&viewport);
+                if let Some(window) = self.widgets.root.get_toplevel() {
+                    if let Ok(window) = window.downcast::<gtk::Window>() {
+                        diagnostics.widget().set_transient_for(Some(&window));
+                    }
+                }
 
-        let listbox = ListBox::new();
-        listbox.set_selection_mode(SelectionMode::None);
+                self.components.diagnostics = Some(diagnostics);
+            }
+            MovieListMsg::SetSettings(settings) => {
+                let time_slot_changed =
+                    settings.get_time_slot() != self.model.settings.get_time_slot();
+                let min_personal_rating_changed = settings.get_min_personal_rating()
+                    != self.model.settings.get_min_personal_rating();
+                let started_changed = settings.get_started_broadcasts()
+                    != self.model.settings.get_started_broadcasts()
+                    || settings.get_started_minutes() != self.model.settings.get_started_minutes();
 
-        viewport.add(&listbox);
+                self.model.settings = settings;
+                let _ = self.model.write_settings();
+                cache::set_limits(&self.model.settings);
+                self.model
+                    .config
+                    .set_provider(&self.model.settings.get_provider());
+                self.model.stream_win.emit(WinMsg::SetTranslationUrl(
+                    self.model.settings.get_translation_url(),
+                ));
+                self.model.stream_win.emit(WinMsg::SetOmdbApiKey(
+                    self.model.settings.get_omdb_api_key(),
+                ));
+                self.model.stream_win.emit(WinMsg::SetInhibitSuspend(
+                    self.model.settings.get_inhibit_suspend(),
+                ));
 
-        let menu_box = gtk::Box::new(Orientation::Vertical, 0);
+                self.update_motion();
+                self.update_activation();
+                if min_personal_rating_changed || started_changed {
+                    self.reset_movies();
+                }
 
-        let button_reload = Button::new();
-        button_reload.set_label("Reload");
-        connect!(
-            relm,
-            button_reload,
-            connect_clicked(_),
-            MovieListMsg::Reload
-        );
+                // Changing the widget will update the model.
+                if time_slot_changed {
+                    self.widgets
+                        .combo_time_slot
+                        .set_active_id(Some(&self.model.settings.get_time_slot().to_string()));
+                }
+            }
+            MovieListMsg::SetEncryption(passphrase) => {
+                let result = match passphrase {
+                    Some(passphrase) => encryption::enable(&passphrase),
+                    None => encryption::disable(),
+                };
+                if let Err(error) = result {
+                    self.components.error_bar.emit(ErrorBarMsg::Show(error));
+                }
+            }
+            MovieListMsg::FilterFileUnreadable(error) => {
+                self.components
+                    .error_bar
+                    .emit(ErrorBarMsg::ShowReset(format!(
+                        "{} The filters are not applied until the file is fixed or reset.",
+                        error
+                    )));
+            }
+            MovieListMsg::ResetFilterFile => {
+                let _ = fs::remove_file(paths::user_data_file("filters.json"));
+                let _ = fs::remove_file(paths::user_data_file("filters.csv"));
+                self.model
+                    .relm
+                    .stream()
+                    .emit(MovieListMsg::ClearData(ClearData::Filters));
+            }
+            MovieListMsg::ReportParseFailure => {
+                let url = self.model.settings.get_health_report_url();
+                if let (Some(url), Some(failure)) = (url, self.model.parse_failure.take()) {
+                    let stream = self.model.relm.stream().clone();
+                    let (_channel, sender) = relm::Channel::new(move |result| {
+                        stream.emit(MovieListMsg::ReportSent(result))
+                    });
 
-        menu_box.add(&button_reload);
+                    tasks::spawn(move || async move {
+                        let result =
+                            send_health_report(&url, &failure, env!("CARGO_PKG_VERSION")).await;
+                        let _ = sender.send(result);
+                    });
+                }
+            }
+            MovieListMsg::ReportSent(result) => {
+                if let Err(error) = result {
+                    self.components.error_bar.emit(ErrorBarMsg::Show(error));
+                }
+            }
+            MovieListMsg::ClearData(data) => {
+                let result = match data {
+                    ClearData::History => {
+                        self.model.archive = Archive::new();
+                        self.reset_movies();
+                        self.model.write_archive()
+                    }
+                    ClearData::Cache => paths::clear_user_cache_dir().map_err(Error::from),
+                    ClearData::Filters => {
+                        self.model.filter = ProgramFilter::new();
+                        self.schedule_refilter();
 
-        let stack = relm::create_component::<SlidingStack<Box, ScrolledWindow>>((
-            menu_box,
-            scrolled_window.clone(),
-        ));
-        stack.emit(SlidingStackMsg::ShowSecondPage);
+                        self.reset_group_checks();
+                        self.model
+                            .stream_win
+                            .emit(WinMsg::SetFilterMode(self.model.filter.get_mode()));
+                        self.model.write_filters()
+                    }
+                    // The settings are shown by many widgets, they are applied by starting again.
+                    ClearData::Settings => {
+                        self.model.settings = Settings::new();
+                        self.model.config.reset();
+                        self.model.write_settings()
+                    }
+                    ClearData::Everything => self.model.remove_all(),
+                };
 
-        root.add(stack.widget());
+                match result {
+                    Err(error) => self.components.error_bar.emit(ErrorBarMsg::Show(error)),
+                    Ok(()) if data == ClearData::Settings || data == ClearData::Everything => {
+                        self.model.stream_win.emit(WinMsg::Quit)
+                    }
+                    Ok(()) => {}
+                }
+            }
+            MovieListMsg::SetTimeSlot(time_slot) => {
+                if time_slot == self.model.time_slot {
+                    return;
+                }
 
-        connect!(
-            relm,
-            listbox,
-            connect_row_activated(_, row),
-            MovieListMsg::RowActivated(row.clone())
-        );
+                self.model.time_slot = time_slot;
+                self.model.settings.set_time_slot(time_slot);
+                self.model.config.set_time_slot(time_slot);
+                self.model.relm.stream().emit(MovieListMsg::Reload);
+            }
+            MovieListMsg::Search(active) => {
+                self.widgets.search_bar.set_search_mode(active);
+                self.widgets.button_search.set_active(active);
+                if let Some(bottom_bar) = &self.widgets.bottom_bar {
+                    bottom_bar.button_search.set_active(active);
+                }
+                self.update_activation();
 
-        root.show_all();
+                if active {
+                    self.widgets.entry_search.grab_focus();
+                } else {
+                    self.widgets.entry_search.set_text("");
+                }
+            }
+            MovieListMsg::SetSearch(text) => {
+                self.model.search = text.trim().to_string();
+                self.reset_movies();
 
-        let widgets = MovieListWidgets {
-            root,
-            listbox,
-            loading_spinner,
-        };
-        let components = MovieListComponents { stack };
-        Self {
-            model,
-            widgets,
-            components,
-        }
-    }
-}
+                if !self.model.search.is_empty() {
+                    self.model
+                        .relm
+                        .stream()
+                        .emit(MovieListMsg::MoveSelection(0));
+                }
+            }
+            MovieListMsg::MoveSelection(offset) => {
+                let listbox = &self.widgets.listbox;
+                let rows = listbox.get_children().len() as i32;
+                if rows == 0 {
+                    return;
+                }
 
-impl<T: Provider> MovieList<T> {
-    fn reset_movies(&mut self) {
-        let listbox = &mut self.widgets.listbox;
+                let index = listbox
+                    .get_selected_row()
+                    .map(|r| r.get_index() + offset)
+                    .unwrap_or(0)
+                    .max(0)
+                    .min(rows - 1);
 
-        let listbox_clone = listbox.clone();
-        listbox.foreach(|c| listbox_clone.remove(c));
+                if let Some(row) = listbox.get_row_at_index(index) {
+                    listbox.select_row(Some(&row));
 
-        for data in self.model.program.iter() {
-            let component = listbox.add_widget::<MovieListItem>(data.clone());
-            self.model.movies.push(component);
-        }
-    }
+                    // Keep the selected row visible.
+                    let allocation = row.get_allocation();
+                    if let Some(adjustment) = self.widgets.scrolled_window.get_vadjustment() {
+                        adjustment.clamp_page(
+                            allocation.y as f64,
+                            (allocation.y + allocation.height) as f64,
+                        );
+                    }
+                }
+            }
+            MovieListMsg::OpenSelected => {
+                if let Some(row) = self.widgets.listbox.get_selected_row() {
+                    self.model
+                        .relm
+                        .stream()
+                        .emit(MovieListMsg::RowActivated(row));
+                }
+            }
+            MovieListMsg::SetFolded(folded) => {
+                if folded && self.widgets.bottom_bar.is_none() {
+                    let bottom_bar = self.create_bottom_bar();
+                    self.widgets.root.add(&bottom_bar.action_bar);
+                    self.widgets.bottom_bar = Some(bottom_bar);
+                    self.reset_presets();
+                }
+
+                // The actions are either in the header bar or at the bottom.
+                self.widgets.button_search.set_visible(!folded);
+                if let Some(bottom_bar) = &self.widgets.bottom_bar {
+                    bottom_bar.action_bar.set_visible(folded);
+                }
+            }
+            MovieListMsg::ExportJson => {
+                if let Some(path) = self.choose_export_path("Export the program", "program.json") {
+                    if let Err(error) = self.model.shown.write_json_to_path(path) {
+                        self.components.error_bar.emit(ErrorBarMsg::Show(error));
+                    }
+                }
+            }
+            MovieListMsg::ExportCard => {
+                if let Some(path) = self.choose_export_path("Share tonight's picks", "tonight.png")
+                {
+                    let picks = tonights_picks(&self.model.shown, &self.model.pinned);
+                    if let Err(error) = write_card_to_path(&picks, path) {
+                        self.components.error_bar.emit(ErrorBarMsg::Show(error));
+                    }
+                }
+            }
+            MovieListMsg::SaveProgram => {
+                if let Some(path) = self.choose_export_path("Save the program", "program.tvtoday") {
+                    if let Err(error) = self.model.loaded.write_to_path(path) {
+                        self.components.error_bar.emit(ErrorBarMsg::Show(error));
+                    }
+                }
+            }
+            MovieListMsg::OpenSavedProgram => {
+                let path = match self.choose_import_path("Open a saved program") {
+                    Some(path) => path,
+                    None => return,
+                };
+
+                match Program::read_from_path(path) {
+                    Ok(program) => {
+                        self.model.saved = Some(Saved::with_program(program));
+                        self.widgets.button_close_saved.show();
+                        self.model.relm.stream().emit(MovieListMsg::Reload);
+                    }
+                    Err(error) => self.components.error_bar.emit(ErrorBarMsg::Show(error)),
+                }
+            }
+            MovieListMsg::CloseSavedProgram => {
+                self.model.saved = None;
+                self.model.loaded = Program::new();
+                self.widgets.button_close_saved.hide();
+                self.model.relm.stream().emit(MovieListMsg::Reload);
+            }
+            MovieListMsg::OpenRemote => {
+                if self.model.remote_url.is_none() {
+                    let server = RemoteServer::bind(self.model.remote_view.clone())
+                        .and_then(|server| server.get_url().map(|url| (server, url)));
+                    let (server, url) = match server {
+                        Ok(server) => server,
+                        Err(error) => {
+                            self.components.error_bar.emit(ErrorBarMsg::Show(error));
+                            return;
+                        }
+                    };
+
+                    let stream = self.model.relm.stream().clone();
+                    let (_channel, sender) = relm::Channel::new(move |command| {
+                        stream.emit(MovieListMsg::Remote(command))
+                    });
+                    // The commands are sent from the threads of the server.
+                    let sender = Mutex::new(sender);
+                    tasks::spawn(move || async move {
+                        let _ = server
+                            .serve(move |command| {
+                                let _ = sender.lock().unwrap().send(command);
+                            })
+                            .await;
+                    });
+
+                    self.model.remote_url = Some(url);
+                    self.update_remote();
+                }
+
+                if let Some(url) = &self.model.remote_url {
+                    let window = self
+                        .widgets
+                        .root
+                        .get_toplevel()
+                        .and_then(|w| w.downcast::<gtk::Window>().ok());
+                    if let Err(error) = show_remote_dialog(window.as_ref(), url) {
+                        self.components.error_bar.emit(ErrorBarMsg::Show(error));
+                    }
+                }
+            }
+            MovieListMsg::Remote(command) => match command {
+                RemoteCommand::Open(movie) => self
+                    .model
+                    .relm
+                    .stream()
+                    .emit(MovieListMsg::OpenMovie(movie)),
+                RemoteCommand::TogglePin(movie) => self
+                    .model
+                    .relm
+                    .stream()
+                    .emit(MovieListMsg::TogglePin(movie)),
+            },
+            MovieListMsg::ImportRatings => {
+                let path = match self.choose_import_path("Import your ratings exported from IMDb") {
+                    Some(path) => path,
+                    None => return,
+                };
+
+                match PersonalRatings::read_from_imdb_path(path) {
+                    Ok(ratings) => {
+                        self.model.personal_ratings = ratings;
+                        if let Err(error) = self.model.write_personal_ratings() {
+                            self.components.error_bar.emit(ErrorBarMsg::Show(error));
+                        }
+                        self.model.stream_win.emit(WinMsg::SetPersonalRatings(
+                            self.model.personal_ratings.clone(),
+                        ));
+                        self.reset_movies();
+                    }
+                    Err(error) => self.components.error_bar.emit(ErrorBarMsg::Show(error)),
+                }
+            }
+            MovieListMsg::ShowAll => {
+                // Changing the widgets will update the model.
+                self.widgets.entry_search.set_text("");
+                self.widgets.combo_view.set_active_id(Some(VIEW_ALL));
+                self.widgets.check_reruns.set_active(false);
+            }
+            MovieListMsg::InsertRows(generation) => {
+                if generation == self.model.rows_generation {
+                    self.insert_rows();
+                }
+            }
+            MovieListMsg::ShowShortcuts => self.model.stream_win.emit(WinMsg::ShowShortcuts),
+            MovieListMsg::SetDay(day) => {
+                if day == self.model.day {
+                    return;
+                }
+
+                self.model.day = day;
+                self.model.relm.stream().emit(MovieListMsg::Reload);
+            }
+            MovieListMsg::SetTwoLineTitles(two_line_titles) => {
+                self.model.settings.set_two_line_titles(two_line_titles);
+                let _ = self.model.write_settings();
+
+                self.reset_movies();
+            }
+            MovieListMsg::SetReducedMotion(reduced_motion) => {
+                self.model.settings.set_reduced_motion(reduced_motion);
+                let _ = self.model.write_settings();
+
+                self.components.theme.set_reduced_motion(reduced_motion);
+                self.update_motion();
+            }
+            MovieListMsg::SetDark(dark) => {
+                self.model.settings.set_dark(dark);
+                let _ = self.model.write_settings();
+
+                self.components.theme.set_dark(dark);
+            }
+            MovieListMsg::SetCompact(compact) => {
+                self.model.settings.set_compact(compact);
+                let _ = self.model.write_settings();
+
+                self.components.theme.set_compact(compact);
+                self.update_motion();
+                self.reset_movies();
+            }
+            MovieListMsg::ShowGroup((group, shown)) => {
+                let filter = FilterType::Channel(ChannelAttribute::Group(group));
+                let present = self.model.filter.contains(&filter);
+                let should_be_present =
+                    shown == (self.model.filter.get_mode() == FilterMode::Allow);
+
+                if present != should_be_present {
+                    if should_be_present {
+                        self.model.filter.add(filter);
+                    } else {
+                        self.model.filter.remove(&filter);
+                    }
+                    self.schedule_refilter();
+
+                    let _ = self.model.write_filters();
+                }
+            }
+            MovieListMsg::SavePreset => {
+                let name = self.widgets.entry_preset.get_text().trim().to_string();
+                if name.is_empty() {
+                    return;
+                }
+
+                self.model.presets.set(Preset::new(
+                    &name,
+                    self.model.sort_order,
+                    self.model.grouped,
+                    self.model.view_group.clone(),
+                    Some(self.model.time_slot),
+                ));
+                let _ = self.model.write_presets();
+
+                self.widgets.entry_preset.set_text("");
+                self.reset_presets();
+            }
+            MovieListMsg::ApplyPreset(name) => {
+                // Changing the widgets will update the model.
+                if let Some(preset) = self.model.presets.get(&name) {
+                    for (order, radio) in &self.widgets.radios_sort {
+                        if *order == preset.get_sort_order() {
+                            radio.set_active(true);
+                        }
+                    }
+                    self.widgets.check_grouped.set_active(preset.get_grouped());
+                    if let Some(time_slot) = preset.get_time_slot() {
+                        self.widgets
+                            .combo_time_slot
+                            .set_active_id(Some(&time_slot.to_string()));
+                    }
+
+                    let view_id = preset.get_view_group().unwrap_or(VIEW_ALL.to_string());
+                    if !self.widgets.combo_view.set_active_id(Some(&view_id)) {
+                        self.widgets.combo_view.set_active_id(Some(VIEW_ALL));
+                    }
+                }
+            }
+            MovieListMsg::Group(grouped) => {
+                self.model.grouped = grouped;
+                self.reset_movies();
+            }
+            MovieListMsg::RowActivated(row) => {
+                let index = row.get_index() as usize;
+                if !self.model.selection_mode {
+                    self.open(index);
+                } else if let Some(component) = self.model.movies.get(index) {
+                    component.emit(MovieListItemMsg::ToggleChecked);
+                }
+            }
+            MovieListMsg::OpenRelative(offset) => {
+                let opened = &self.model.opened;
+                let index = self
+                    .model
+                    .shown
+                    .iter()
+                    .position(|(_c, m)| Some(m) == opened.as_ref());
+
+                if let Some(index) = index {
+                    let next = index as i32 + offset;
+                    if next >= 0 && (next as usize) < self.model.shown.len() {
+                        self.open(next as usize);
+                    }
+                }
+            }
+            MovieListMsg::OpenMovie(movie) => {
+                if let Some(index) = self.model.shown.iter().position(|(_c, m)| m == &movie) {
+                    self.open(index);
+                }
+            }
+            MovieListMsg::SetSelectionMode(selection_mode) => {
+                if selection_mode == self.model.selection_mode {
+                    return;
+                }
+
+                self.model.selection_mode = selection_mode;
+                self.model.checked.clear();
+                for component in &self.model.movies {
+                    component.emit(MovieListItemMsg::SetSelectionMode(selection_mode));
+                }
+
+                self.widgets.button_select.set_active(selection_mode);
+                self.widgets.selection_bar.set_visible(selection_mode);
+                self.update_checked();
+            }
+            MovieListMsg::Check((data, checked)) => {
+                self.model.checked.retain(|d| d != &data);
+                if checked {
+                    self.model.checked.push(data);
+                }
+                self.update_checked();
+            }
+            MovieListMsg::BlockCheckedTitles => {
+                let mut filters: Vec<FilterType> = vec![];
+                for (_channel, movie) in &self.model.checked {
+                    let filter = FilterType::Movie(MovieAttribute::Title(movie.get_title()));
+                    if !filters.contains(&filter) {
+                        filters.push(filter);
+                    }
+                }
+
+                self.block_filters(filters);
+                self.model
+                    .relm
+                    .stream()
+                    .emit(MovieListMsg::SetSelectionMode(false));
+            }
+            MovieListMsg::BlockCheckedChannels => {
+                let mut filters: Vec<FilterType> = vec![];
+                for (channel, _movie) in &self.model.checked {
+                    let filter = FilterType::Channel(ChannelAttribute::Name(channel.get_name()));
+                    if !filters.contains(&filter) {
+                        filters.push(filter);
+                    }
+                }
+
+                self.block_filters(filters);
+                self.model
+                    .relm
+                    .stream()
+                    .emit(MovieListMsg::SetSelectionMode(false));
+            }
+            MovieListMsg::BlockTitle(movie) => self.block_filters(vec![FilterType::Movie(
+                MovieAttribute::Title(movie.get_title()),
+            )]),
+            MovieListMsg::BlockChannel(channel) => self.block_filters(vec![FilterType::Channel(
+                ChannelAttribute::Name(channel.get_name()),
+            )]),
+            MovieListMsg::OpenInBrowser(movie) => {
+                if let Some(url) = self.get_url(&movie) {
+                    let _ =
+                        gio::AppInfo::launch_default_for_uri::<gio::AppLaunchContext>(&url, None);
+                }
+            }
+            MovieListMsg::RowSelected(row) => {
+                self.model.selected = row
+                    .and_then(|r| self.model.shown.iter().nth(r.get_index() as usize))
+                    .map(|(_c, m)| m.clone());
+            }
+        }
+    }
+}
+
+impl Widget for MovieList {
+    type Root = Box;
+
+    fn root(&self) -> Self::Root {
+        self.widgets.root.clone()
+    }
+
+    fn view(relm: &Relm<Self>, model: Self::Model) -> Self {
+        let root = Box::new(Orientation::Vertical, 0);
+        root.set_hexpand(true);
+
+        let stream = relm.stream().clone();
+        let painted = Cell::new(false);
+        root.connect_draw(move |_, _| {
+            if !painted.replace(true) {
+                stream.emit(MovieListMsg::Painted);
+            }
+            Inhibit(false)
+        });
+
+        let header_bar = HeaderBar::new();
+        header_bar.set_title(Some("Movies"));
+
+        let loading_spinner = Spinner::new();
+        loading_spinner.set_visible(false);
+
+        header_bar.pack_start(&loading_spinner);
+
+        let button_close_saved = Button::new();
+        button_close_saved.set_image(Some(&gtk::Image::from_icon_name(
+            Some("window-close-symbolic"),
+            gtk::IconSize::Menu,
+        )));
+        button_close_saved.set_tooltip_text(Some("Show the current program again"));
+        button_close_saved.set_no_show_all(true);
+        connect!(
+            relm,
+            button_close_saved,
+            connect_clicked(_),
+            MovieListMsg::CloseSavedProgram
+        );
+
+        header_bar.pack_start(&button_close_saved);
+
+        let button_switch_stack = Button::new();
+        button_switch_stack.set_image(Some(&gtk::Image::from_icon_name(
+            Some("open-menu-symbolic"),
+            gtk::IconSize::Menu,
+        )));
+        connect!(
+            relm,
+            button_switch_stack,
+            connect_clicked(_),
+            MovieListMsg::SwitchStack
+        );
+
+        header_bar.pack_end(&button_switch_stack);
+
+        let button_dark = ToggleButton::new();
+        button_dark.set_image(Some(&gtk::Image::from_icon_name(
+            Some("weather-clear-night-symbolic"),
+            gtk::IconSize::Menu,
+        )));
+        button_dark.set_tooltip_text(Some("Dark mode"));
+        button_dark.set_active(model.settings.get_dark());
+        connect!(
+            relm,
+            button_dark,
+            connect_toggled(b),
+            MovieListMsg::SetDark(b.get_active())
+        );
+
+        header_bar.pack_end(&button_dark);
+
+        let button_search = ToggleButton::new();
+        button_search.set_image(Some(&gtk::Image::from_icon_name(
+            Some("system-search-symbolic"),
+            gtk::IconSize::Menu,
+        )));
+        button_search.set_tooltip_text(Some("Search"));
+        connect!(
+            relm,
+            button_search,
+            connect_toggled(b),
+            MovieListMsg::Search(b.get_active())
+        );
+
+        header_bar.pack_end(&button_search);
+
+        let button_select = ToggleButton::new();
+        button_select.set_image(Some(&gtk::Image::from_icon_name(
+            Some("object-select-symbolic"),
+            gtk::IconSize::Menu,
+        )));
+        button_select.set_tooltip_text(Some("Select movies to block them together"));
+        connect!(
+            relm,
+            button_select,
+            connect_toggled(b),
+            MovieListMsg::SetSelectionMode(b.get_active())
+        );
+
+        header_bar.pack_end(&button_select);
+
+        let combo_view = ComboBoxText::new();
+        combo_view.set_tooltip_text(Some("Show the channels of a group"));
+        connect!(
+            relm,
+            combo_view,
+            connect_changed(c),
+            MovieListMsg::SetView(
+                c.get_active_id()
+                    .filter(|id| id.as_str() != VIEW_ALL)
+                    .map(|id| id.to_string())
+            )
+        );
+
+        header_bar.pack_end(&combo_view);
+
+        root.add(&header_bar);
+
+        let entry_search = SearchEntry::new();
+        entry_search.set_placeholder_text(Some("Search titles"));
+        connect!(
+            relm,
+            entry_search,
+            connect_search_changed(e),
+            MovieListMsg::SetSearch(e.get_text().to_string())
+        );
+
+        connect!(
+            relm,
+            entry_search,
+            connect_key_press_event(_, key),
+            return search_key_press(key)
+        );
+        connect!(
+            relm,
+            entry_search,
+            connect_activate(_),
+            MovieListMsg::OpenSelected
+        );
+
+        let search_bar = SearchBar::new();
+        search_bar.add(&entry_search);
+        search_bar.connect_entry(&entry_search);
+
+        root.add(&search_bar);
+
+        let error_bar = relm::create_component::<ErrorBar>(());
+        connect!(error_bar@ErrorBarMsg::Retry, relm, MovieListMsg::Reload);
+        connect!(error_bar@ErrorBarMsg::Reset, relm, MovieListMsg::ResetFilterFile);
+        connect!(error_bar@ErrorBarMsg::Report, relm, MovieListMsg::ReportParseFailure);
+
+        root.add(error_bar.widget());
+
+        let scrolled_window = ScrolledWindow::new::<Adjustment, Adjustment>(None, None);
+        scrolled_window.set_hexpand(true);
+        scrolled_window.set_vexpand(true);
+
+        let viewport = Viewport::new::<Adjustment, Adjustment>(None, None);
+
+        scrolled_window.add(&viewport);
+
+        let listbox = ListBox::new();
+        listbox.set_selection_mode(SelectionMode::None);
+
+        viewport.add(&listbox);
+
+        let placeholder = relm::create_component::<Placeholder>(());
+        connect!(placeholder@PlaceholderMsg::Retry, relm, MovieListMsg::Reload);
+        connect!(placeholder@PlaceholderMsg::ShowAll, relm, MovieListMsg::ShowAll);
+        connect!(placeholder@PlaceholderMsg::AdjustFilters, relm, MovieListMsg::SwitchStack);
+        listbox.set_placeholder(Some(placeholder.widget()));
+
+        let menu_box = gtk::Box::new(Orientation::Vertical, 0);
+
+        let button_reload = Button::new();
+        button_reload.set_label("Reload");
+        connect!(
+            relm,
+            button_reload,
+            connect_clicked(_),
+            MovieListMsg::Reload
+        );
+
+        menu_box.add(&button_reload);
+
+        let button_read_picks = Button::new();
+        button_read_picks.set_label("Read out tonight's picks");
+        button_read_picks.set_tooltip_text(Some(
+            "Read out the pinned movies or the movies at prime time",
+        ));
+        connect!(
+            relm,
+            button_read_picks,
+            connect_clicked(_),
+            MovieListMsg::ReadPicks
+        );
+
+        menu_box.add(&button_read_picks);
+
+        let button_export = Button::with_label("Export as JSON");
+        button_export.set_tooltip_text(Some("Save the shown program for other tools"));
+        connect!(
+            relm,
+            button_export,
+            connect_clicked(_),
+            MovieListMsg::ExportJson
+        );
+
+        menu_box.add(&button_export);
+
+        let button_export_card = Button::with_label("Share tonight's picks as image");
+        button_export_card.set_tooltip_text(Some(
+            "Save a card of the pinned movies or the movies at prime time, e.g. to send it to a chat",
+        ));
+        connect!(
+            relm,
+            button_export_card,
+            connect_clicked(_),
+            MovieListMsg::ExportCard
+        );
+
+        menu_box.add(&button_export_card);
+
+        let button_import_ratings = Button::with_label("Import IMDb ratings");
+        button_import_ratings.set_tooltip_text(Some(
+            "Import the CSV exported on the \"Your Ratings\" page of IMDb to show your ratings and hide movies you rated low",
+        ));
+        connect!(
+            relm,
+            button_import_ratings,
+            connect_clicked(_),
+            MovieListMsg::ImportRatings
+        );
+
+        menu_box.add(&button_import_ratings);
+
+        let button_remote = Button::with_label("Remote control");
+        button_remote.set_tooltip_text(Some(
+            "Show a QR code to see the program on a phone and open or pin movies from it",
+        ));
+        connect!(
+            relm,
+            button_remote,
+            connect_clicked(_),
+            MovieListMsg::OpenRemote
+        );
+
+        menu_box.add(&button_remote);
+
+        let button_save_program = Button::with_label("Save program…");
+        button_save_program.set_tooltip_text(Some(
+            "Save the loaded program to review it later, also without a network connection",
+        ));
+        connect!(
+            relm,
+            button_save_program,
+            connect_clicked(_),
+            MovieListMsg::SaveProgram
+        );
+
+        menu_box.add(&button_save_program);
+
+        let button_open_saved = Button::with_label("Open saved program…");
+        connect!(
+            relm,
+            button_open_saved,
+            connect_clicked(_),
+            MovieListMsg::OpenSavedProgram
+        );
+
+        menu_box.add(&button_open_saved);
+
+        menu_box.add(&section_header("Program"));
+
+        let combo_day = ComboBoxText::new();
+        for day in ProgramDay::ALL.iter() {
+            combo_day.append(Some(&day.to_string()), day.label());
+        }
+        combo_day.set_active_id(Some(&model.day.to_string()));
+        connect!(
+            relm,
+            combo_day,
+            connect_changed(c),
+            c.get_active_id()
+                .and_then(|id| ProgramDay::try_from(id.as_str()).ok())
+                .map(MovieListMsg::SetDay)
+        );
+
+        menu_box.add(&combo_day);
+
+        let combo_time_slot = ComboBoxText::new();
+        for time_slot in TimeSlot::ALL.iter() {
+            combo_time_slot.append(Some(&time_slot.to_string()), time_slot.label());
+        }
+        combo_time_slot.set_active_id(Some(&model.time_slot.to_string()));
+        connect!(
+            relm,
+            combo_time_slot,
+            connect_changed(c),
+            c.get_active_id()
+                .and_then(|id| TimeSlot::try_from(id.as_str()).ok())
+                .map(MovieListMsg::SetTimeSlot)
+        );
+
+        menu_box.add(&combo_time_slot);
+
+        menu_box.add(&section_header("Sort by"));
+
+        let sort_orders = [
+            (SortOrder::Channel, "Channel"),
+            (SortOrder::Start, "Start time"),
+            (SortOrder::Title, "Title"),
+            (SortOrder::Year, "Year"),
+            (SortOrder::Genre, "Genre"),
+        ];
+
+        let mut radios_sort: Vec<(SortOrder, RadioButton)> = vec![];
+        for (order, label) in sort_orders.iter().cloned() {
+            let radio = match radios_sort.first() {
+                Some((_o, group)) => RadioButton::with_label_from_widget(group, label),
+                None => RadioButton::with_label(label),
+            };
+            radio.set_active(order == model.sort_order);
+            connect!(
+                relm,
+                radio,
+                connect_toggled(r),
+                if r.get_active() {
+                    Some(MovieListMsg::Sort(order))
+                } else {
+                    None
+                }
+            );
+
+            menu_box.add(&radio);
+            radios_sort.push((order, radio));
+        }
+
+        let check_grouped = CheckButton::with_label("Group by channel");
+        check_grouped.set_active(model.grouped);
+        connect!(
+            relm,
+            check_grouped,
+            connect_toggled(c),
+            MovieListMsg::Group(c.get_active())
+        );
+
+        menu_box.add(&check_grouped);
+
+        let check_reruns = CheckButton::with_label("Hide recent reruns");
+        check_reruns.set_tooltip_text(Some(&format!(
+            "Hide movies already broadcasted in the last {} days",
+            RERUN_DAYS
+        )));
+        connect!(
+            relm,
+            check_reruns,
+            connect_toggled(c),
+            MovieListMsg::HideReruns(c.get_active())
+        );
+
+        menu_box.add(&check_reruns);
+
+        let check_dedup = CheckButton::with_label("Merge repeated broadcasts");
+        check_dedup.set_tooltip_text(Some(
+            "Show a movie broadcasted on multiple channels at the same time, e.g. on the regional channels, only once",
+        ));
+        connect!(
+            relm,
+            check_dedup,
+            connect_toggled(c),
+            MovieListMsg::Dedup(c.get_active())
+        );
+
+        menu_box.add(&check_dedup);
+
+        menu_box.add(&section_header("Presets"));
+
+        let combo_preset = ComboBoxText::new();
+        connect!(
+            relm,
+            combo_preset,
+            connect_changed(c),
+            c.get_active_text()
+                .map(|name| MovieListMsg::ApplyPreset(name.to_string()))
+        );
+
+        let box_save_preset = Box::new(Orientation::Horizontal, 0);
+        let entry_preset = Entry::new();
+        entry_preset.set_placeholder_text(Some("Preset name"));
+        entry_preset.set_hexpand(true);
+        connect!(
+            relm,
+            entry_preset,
+            connect_activate(_),
+            MovieListMsg::SavePreset
+        );
+
+        let button_save_preset = Button::with_label("Save");
+        connect!(
+            relm,
+            button_save_preset,
+            connect_clicked(_),
+            MovieListMsg::SavePreset
+        );
+
+        box_save_preset.add(&entry_preset);
+        box_save_preset.add(&button_save_preset);
+
+        menu_box.add(&combo_preset);
+        menu_box.add(&box_save_preset);
+
+        let check_voting = CheckButton::with_label("Household voting");
+        check_voting.set_tooltip_text(Some("Vote for the pinned movies"));
+        check_voting.set_active(model.voting);
+        connect!(
+            relm,
+            check_voting,
+            connect_toggled(c),
+            MovieListMsg::Voting(c.get_active())
+        );
+
+        menu_box.add(&check_voting);
+
+        menu_box.add(&section_header("Maximum age rating"));
+
+        let combo_age_rating = ComboBoxText::new();
+        combo_age_rating.append(Some(VIEW_ALL), "All");
+        for age_rating in AGE_RATINGS.iter() {
+            combo_age_rating.append(
+                Some(&age_rating.to_string()),
+                &format!("FSK {}", age_rating),
+            );
+        }
+        combo_age_rating.set_active_id(Some(
+            &model
+                .filter
+                .get_max_age_rating()
+                .map(|r| r.to_string())
+                .unwrap_or(VIEW_ALL.to_string()),
+        ));
+        connect!(
+            relm,
+            combo_age_rating,
+            connect_changed(c),
+            c.get_active_id()
+                .map(|id| MovieListMsg::SetMaxAgeRating(id.parse().ok()))
+        );
+
+        menu_box.add(&combo_age_rating);
+
+        menu_box.add(&section_header("Channel groups"));
+
+        let button_group_editor = Button::with_label("Edit channel groups");
+        connect!(
+            relm,
+            button_group_editor,
+            connect_clicked(_),
+            MovieListMsg::OpenGroupEditor
+        );
+
+        menu_box.add(&button_group_editor);
+
+        let button_channel_editor = Button::with_label("Favorite and reorder channels");
+        connect!(
+            relm,
+            button_channel_editor,
+            connect_clicked(_),
+            MovieListMsg::OpenChannelEditor
+        );
+
+        menu_box.add(&button_channel_editor);
+
+        let button_filter_editor = Button::with_label("Edit filters");
+        connect!(
+            relm,
+            button_filter_editor,
+            connect_clicked(_),
+            MovieListMsg::OpenFilterEditor
+        );
+
+        menu_box.add(&button_filter_editor);
+
+        let check_allow = CheckButton::with_label("Only show matching filters");
+        check_allow.set_tooltip_text(Some("Use the filters as a allow list"));
+        check_allow.set_active(model.filter.get_mode() == FilterMode::Allow);
+        connect!(
+            relm,
+            check_allow,
+            connect_toggled(c),
+            MovieListMsg::SetFilterMode(if c.get_active() {
+                FilterMode::Allow
+            } else {
+                FilterMode::Block
+            })
+        );
+
+        menu_box.add(&check_allow);
+
+        let mut checks_group = vec![];
+        for group in ChannelGroup::ALL.iter().cloned() {
+            let check = CheckButton::with_label(group.get_label());
+            connect!(
+                relm,
+                check,
+                connect_toggled(c),
+                MovieListMsg::ShowGroup((group, c.get_active()))
+            );
+
+            menu_box.add(&check);
+            checks_group.push((group, check));
+        }
+
+        menu_box.add(&section_header("Settings"));
+
+        let check_compact = CheckButton::with_label("Compact text-only mode");
+        check_compact.set_tooltip_text(Some(
+            "No icons and animations, high-contrast text and larger buttons for e-ink displays",
+        ));
+        check_compact.set_active(model.settings.get_compact());
+        connect!(
+            relm,
+            check_compact,
+            connect_toggled(c),
+            MovieListMsg::SetCompact(c.get_active())
+        );
+
+        menu_box.add(&check_compact);
+
+        let check_reduced_motion = CheckButton::with_label("Reduce motion");
+        check_reduced_motion.set_tooltip_text(Some(
+            "No page transitions and spinner animations, also helps on weak hardware",
+        ));
+        check_reduced_motion.set_active(model.settings.get_reduced_motion());
+        connect!(
+            relm,
+            check_reduced_motion,
+            connect_toggled(c),
+            MovieListMsg::SetReducedMotion(c.get_active())
+        );
+
+        menu_box.add(&check_reduced_motion);
+
+        let check_two_line_titles = CheckButton::with_label("Show long titles on two lines");
+        check_two_line_titles.set_active(model.settings.get_two_line_titles());
+        connect!(
+            relm,
+            check_two_line_titles,
+            connect_toggled(c),
+            MovieListMsg::SetTwoLineTitles(c.get_active())
+        );
+
+        menu_box.add(&check_two_line_titles);
+
+        let button_preferences = Button::with_label("Preferences");
+        connect!(
+            relm,
+            button_preferences,
+            connect_clicked(_),
+            MovieListMsg::OpenPreferences
+        );
+
+        menu_box.add(&button_preferences);
+
+        let button_shortcuts = Button::with_label("Keyboard shortcuts");
+        connect!(
+            relm,
+            button_shortcuts,
+            connect_clicked(_),
+            MovieListMsg::ShowShortcuts
+        );
+
+        menu_box.add(&button_shortcuts);
+
+        let button_diagnostics = Button::with_label("Diagnostics");
+        connect!(
+            relm,
+            button_diagnostics,
+            connect_clicked(_),
+            MovieListMsg::OpenDiagnostics
+        );
+
+        menu_box.add(&button_diagnostics);
+
+        let mut theme = Theme::new(&root);
+        theme.set_compact(model.settings.get_compact());
+        theme.set_reduced_motion(model.settings.get_reduced_motion());
+        theme.set_dark(model.settings.get_dark());
+
+        let stack = relm::create_component::<SlidingStack<Box, ScrolledWindow>>((
+            menu_box,
+            scrolled_window.clone(),
+        ));
+        stack.emit(SlidingStackMsg::ShowSecondPage);
+
+        let toast = relm::create_component::<Toast>(());
+        connect!(toast@ToastMsg::Undo, relm, MovieListMsg::UndoFilters);
+
+        // The toast is shown above the bottom of the list.
+        let overlay = Overlay::new();
+        overlay.add(stack.widget());
+        overlay.add_overlay(toast.widget());
+        root.add(&overlay);
+
+        let selection_bar = ActionBar::new();
+        selection_bar.set_no_show_all(true);
+
+        let label_checked = Label::new(None);
+        label_checked.show();
+        selection_bar.pack_start(&label_checked);
+
+        let button_block_channels = Button::with_label("Block channels");
+        button_block_channels
+            .get_style_context()
+            .add_class("destructive-action");
+        button_block_channels.show();
+        connect!(
+            relm,
+            button_block_channels,
+            connect_clicked(_),
+            MovieListMsg::BlockCheckedChannels
+        );
+        selection_bar.pack_end(&button_block_channels);
+
+        let button_block_titles = Button::with_label("Block titles");
+        button_block_titles
+            .get_style_context()
+            .add_class("destructive-action");
+        button_block_titles.show();
+        connect!(
+            relm,
+            button_block_titles,
+            connect_clicked(_),
+            MovieListMsg::BlockCheckedTitles
+        );
+        selection_bar.pack_end(&button_block_titles);
+
+        root.add(&selection_bar);
+
+        connect!(
+            relm,
+            listbox,
+            connect_row_activated(_, row),
+            MovieListMsg::RowActivated(row.clone())
+        );
+        connect!(
+            relm,
+            listbox,
+            connect_row_selected(_, row),
+            MovieListMsg::RowSelected(row.cloned())
+        );
+
+        root.show_all();
+
+        let widgets = MovieListWidgets {
+            root,
+            header_bar,
+            button_close_saved,
+            listbox,
+            loading_spinner,
+            scrolled_window,
+            button_search,
+            search_bar,
+            entry_search,
+            combo_view,
+            checks_group,
+            radios_sort,
+            check_grouped,
+            check_reruns,
+            combo_time_slot,
+            combo_preset,
+            entry_preset,
+            bottom_bar: None,
+            button_select,
+            selection_bar,
+            label_checked,
+            button_block_titles,
+            button_block_channels,
+        };
+        let components = MovieListComponents {
+            stack,
+            error_bar,
+            placeholder,
+            group_editor: None,
+            channel_editor: None,
+            filter_editor: None,
+            preferences: None,
+            diagnostics: None,
+            theme,
+            toast,
+        };
+        let mut movie_list = Self {
+            model,
+            widgets,
+            components,
+        };
+        if movie_list.model.program.iter().next().is_some() {
+            movie_list.reset_movies();
+            movie_list
+                .widgets
+                .header_bar
+                .set_subtitle(Some("Cached program"));
+        }
+        movie_list.reset_view_groups();
+        // The search of the last session is restored.
+        if !movie_list.model.search.is_empty() {
+            let search = movie_list.model.search.clone();
+            movie_list.widgets.entry_search.set_text(&search);
+            relm.stream().emit(MovieListMsg::Search(true));
+        }
+        movie_list.reset_group_checks();
+        movie_list.reset_presets();
+        movie_list.update_motion();
+        movie_list.update_activation();
+        movie_list
+    }
+}
+
+impl MovieList {
+    /// Ask the user where to save an export, proposing the given file name.
+    fn choose_export_path(&self, title: &str, name: &str) -> Option<PathBuf> {
+        let window = self
+            .widgets
+            .root
+            .get_toplevel()
+            .and_then(|w| w.downcast::<gtk::Window>().ok());
+        let dialog = FileChooserNative::new(
+            Some(title),
+            window.as_ref(),
+            FileChooserAction::Save,
+            Some("Export"),
+            None,
+        );
+        dialog.set_current_name(name);
+        dialog.set_do_overwrite_confirmation(true);
+
+        if dialog.run() == ResponseType::Accept {
+            dialog.get_filename()
+        } else {
+            None
+        }
+    }
+
+    /// Ask the user for a file to import.
+    fn choose_import_path(&self, title: &str) -> Option<PathBuf> {
+        let window = self
+            .widgets
+            .root
+            .get_toplevel()
+            .and_then(|w| w.downcast::<gtk::Window>().ok());
+        let dialog = FileChooserNative::new(
+            Some(title),
+            window.as_ref(),
+            FileChooserAction::Open,
+            Some("Import"),
+            None,
+        );
+
+        if dialog.run() == ResponseType::Accept {
+            dialog.get_filename()
+        } else {
+            None
+        }
+    }
+
+    /// Open the movie at the index of the shown program on the movie page. The row is selected, if rows are
+    /// selectable, so going back shows where the movie is in the list.
+    fn open(&mut self, index: usize) {
+        let (channel, movie) = self.model.shown[index].clone();
+        self.model.opened = Some(movie.clone());
+
+        if let Some(row) = self.widgets.listbox.get_row_at_index(index as i32) {
+            self.widgets.listbox.select_row(Some(&row));
+        }
+
+        self.model
+            .stream_win
+            .emit(WinMsg::SelectedMovie((channel, movie)));
+        self.model.stream_win.emit(WinMsg::SetNeighbours((
+            index > 0,
+            index + 1 < self.model.shown.len(),
+        )));
+    }
+
+    /// Apply how rows are opened. With a double click, a single click selects a row, so rows are always selectable.
+    /// Otherwise they are only selectable while searching, to open the first match with Enter.
+    fn update_activation(&self) {
+        let double_click = self.model.settings.get_double_click();
+        let searching = self.widgets.search_bar.get_search_mode();
+
+        let listbox = &self.widgets.listbox;
+        listbox.set_activate_on_single_click(!double_click);
+        listbox.set_selection_mode(if double_click || searching {
+            SelectionMode::Single
+        } else {
+            SelectionMode::None
+        });
+    }
+
+    /// Apply the transition duration to the pages and the spinner, or disable them if animations are off.
+    fn update_motion(&self) {
+        let animations = self.components.theme.get_animations();
+        let duration = self.transition_duration();
+
+        self.components
+            .stack
+            .emit(SlidingStackMsg::SetTransitionDuration(duration));
+        self.model
+            .stream_win
+            .emit(WinMsg::SetTransitionDuration(duration));
+
+        if animations {
+            self.widgets.loading_spinner.start();
+        } else {
+            self.widgets.loading_spinner.stop();
+        }
+    }
+
+    /// Get the URL of a website about the movie from the provider of the shown program.
+    fn get_url(&self, movie: &Movie) -> Option<String> {
+        match &self.model.saved {
+            Some(saved) => saved.get_url(movie),
+            None => self.model.provider.lock().get_url(movie),
+        }
+    }
+
+    /// Get the duration of the transitions in milliseconds, zero if animations are disabled.
+    fn transition_duration(&self) -> u32 {
+        if self.components.theme.get_animations() {
+            self.model.settings.get_transition_duration()
+        } else {
+            0
+        }
+    }
+
+    /// Fill the view selection with the custom groups, keeping the current selection if possible.
+    fn reset_view_groups(&self) {
+        let combo_view = &self.widgets.combo_view;
+        let active = self.model.view_group.clone();
+
+        combo_view.remove_all();
+        combo_view.append(Some(VIEW_ALL), "All channels");
+        for group in self.model.custom_groups.iter() {
+            combo_view.append(Some(&group.get_name()), &group.get_name());
+        }
+
+        let active_id = active.unwrap_or(VIEW_ALL.to_string());
+        if !combo_view.set_active_id(Some(&active_id)) {
+            combo_view.set_active_id(Some(VIEW_ALL));
+        }
+    }
+
+    /// Fill the preset selection with the presets.
+    fn reset_presets(&self) {
+        let combo_preset = &self.widgets.combo_preset;
+
+        combo_preset.remove_all();
+        for preset in self.model.presets.iter() {
+            combo_preset.append_text(&preset.get_name());
+        }
+
+        if let Some(bottom_bar) = &self.widgets.bottom_bar {
+            bottom_bar.combo_preset.remove_all();
+            for preset in self.model.presets.iter() {
+                bottom_bar.combo_preset.append_text(&preset.get_name());
+            }
+        }
+    }
+
+    /// Create the bar with the reload and search actions and the presets.
+    fn create_bottom_bar(&self) -> BottomBar {
+        let action_bar = ActionBar::new();
+
+        let button_reload = Button::new();
+        button_reload.set_image(Some(&gtk::Image::from_icon_name(
+            Some("view-refresh-symbolic"),
+            gtk::IconSize::Button,
+        )));
+        button_reload.set_tooltip_text(Some("Reload"));
+        connect!(
+            self.model.relm,
+            button_reload,
+            connect_clicked(_),
+            MovieListMsg::Reload
+        );
+
+        let button_search = ToggleButton::new();
+        button_search.set_image(Some(&gtk::Image::from_icon_name(
+            Some("system-search-symbolic"),
+            gtk::IconSize::Button,
+        )));
+        button_search.set_tooltip_text(Some("Search"));
+        button_search.set_active(self.widgets.search_bar.get_search_mode());
+        connect!(
+            self.model.relm,
+            button_search,
+            connect_toggled(b),
+            MovieListMsg::Search(b.get_active())
+        );
+
+        let combo_preset = ComboBoxText::new();
+        combo_preset.set_tooltip_text(Some("Apply a preset"));
+        connect!(
+            self.model.relm,
+            combo_preset,
+            connect_changed(c),
+            c.get_active_text()
+                .map(|name| MovieListMsg::ApplyPreset(name.to_string()))
+        );
+
+        action_bar.pack_start(&button_reload);
+        action_bar.pack_start(&button_search);
+        action_bar.pack_end(&combo_preset);
+        action_bar.show_all();
+
+        BottomBar {
+            action_bar,
+            button_search,
+            combo_preset,
+        }
+    }
+
+    /// Check the channel groups that are shown with the current filters.
+    fn reset_group_checks(&self) {
+        let allow = self.model.filter.get_mode() == FilterMode::Allow;
+        for (group, check) in &self.widgets.checks_group {
+            let filter = FilterType::Channel(ChannelAttribute::Group(*group));
+            check.set_active(self.model.filter.contains(&filter) == allow);
+        }
+    }
+
+    /// Write tonight's picks of the shown program to the file for desktop widgets, if one is set.
+    fn write_widget(&self) {
+        if let Some(path) = self.model.settings.get_widget_path() {
+            let picks = tonights_picks(&self.model.shown, &self.model.pinned);
+            if let Err(error) = write_widget_to_path(&picks, Local::now().naive_local(), path) {
+                self.components.error_bar.emit(ErrorBarMsg::Show(error));
+            }
+        }
+    }
+
+    /// Load the icons of the channels of the program without blocking the UI.
+    fn load_icons(&self) {
+        let stream = self.model.relm.stream().clone();
+
+        let (_channel, sender) =
+            relm::Channel::new(move |result| stream.emit(MovieListMsg::IconsLoaded(result)));
+
+        let handle = self.model.provider.clone();
+        let mut provider = handle.get();
+        let program = self.model.program.clone();
+
+        tasks::spawn(move || async move {
+            let program = provider.get_icons(&program).await;
+            handle.set(provider);
+            let _ = sender.send(program);
+        });
+    }
+
+    /// Get whether the loaded program is stale, as it was loaded longer than the refresh interval ago or not at all.
+    fn is_stale(&self) -> bool {
+        // A saved program does not change.
+        if self.model.saved.is_some() {
+            return self.model.last_reload.is_none();
+        }
+
+        let stale_after =
+            Duration::from_secs(self.model.settings.get_refresh_interval() as u64 * 60);
+        self.model
+            .last_reload
+            .map(|t| t.elapsed() > stale_after)
+            .unwrap_or(true)
+    }
+
+    /// Apply the changed filter to the loaded program shortly, so changes in quick succession are applied at once.
+    fn schedule_refilter(&mut self) {
+        if !self.model.refilter_pending {
+            self.model.refilter_pending = true;
+            relm::timeout(self.model.relm.stream(), REFILTER_DELAY, || {
+                MovieListMsg::Refilter
+            });
+        }
+    }
+
+    /// Get the movies of the program that started more than the started minutes ago, if they are dimmed or hidden.
+    fn started_movies(&self) -> Vec<Movie> {
+        if self.model.settings.get_started_broadcasts() == StartedBroadcasts::Show {
+            return vec![];
+        }
+
+        let now = Local::now().naive_local();
+        let minutes = self.model.settings.get_started_minutes();
+        self.model
+            .program
+            .iter()
+            .filter(|(_c, m)| m.started_before(now, minutes))
+            .map(|(_c, m)| m.clone())
+            .collect()
+    }
+
+    /// Get the broadcasts of the program to show, leaving out unsubscribed channels, channels not in the shown group,
+    /// titles not matching the search, hidden reruns and hidden started movies.
+    fn visible(&self, mut program: Program) -> Program {
+        let subscribed = self.model.settings.get_subscribed_channels();
+        if !subscribed.is_empty() {
+            program = program
+                .iter()
+                .filter(|(c, _m)| subscribed.contains(&c.get_name()))
+                .cloned()
+                .collect();
+        }
+
+        if let Some(group) = self
+            .model
+            .view_group
+            .as_ref()
+            .and_then(|name| self.model.custom_groups.get(name))
+        {
+            program = program
+                .iter()
+                .filter(|(c, _m)| group.contains(c))
+                .cloned()
+                .collect();
+        }
+
+        if !self.model.search.is_empty() {
+            let search = search::fold(&self.model.search);
+            program = program
+                .iter()
+                .filter(|(_c, m)| search::matches_search(&m.get_title(), &search))
+                .cloned()
+                .collect();
+        }
+
+        let min_rating = self.model.settings.get_min_personal_rating();
+        let ratings = &self.model.personal_ratings;
+        if min_rating > 0 && !ratings.is_empty() {
+            program = program
+                .iter()
+                .filter(|(_c, m)| ratings.get(m).map(|r| r >= min_rating).unwrap_or(true))
+                .cloned()
+                .collect();
+        }
+
+        let today = Local::now().date_naive();
+        let archive = &self.model.archive;
+        if self.model.hide_reruns {
+            program = program
+                .iter()
+                .filter(|(_c, m)| !archive.is_rerun(m, today, RERUN_DAYS))
+                .cloned()
+                .collect();
+        }
+
+        if self.model.settings.get_started_broadcasts() == StartedBroadcasts::Hide {
+            let started = &self.model.started;
+            program = program
+                .iter()
+                .filter(|(_c, m)| !started.contains(m))
+                .cloned()
+                .collect();
+        }
+
+        program
+    }
+
+    fn reset_movies(&mut self) {
+        let listbox = &mut self.widgets.listbox;
+
+        let listbox_clone = listbox.clone();
+        listbox.foreach(|c| listbox_clone.remove(c));
+        self.model.movies.clear();
+        metrics::reset(ReloadStep::BuildList);
+        self.model.started = self.started_movies();
+
+        let program = self.model.channel_order.apply(
+            &self.model.program.sorted(self.model.sort_order),
+            self.model.sort_order,
+        );
+
+        let mut program = self.visible(program);
+        if self.model.dedup {
+            self.model.duplicates = program.clone();
+            program = program.dedup_movies();
+        } else {
+            self.model.duplicates = Program::new();
+        }
+
+        // Pinned movies are shown first, in the order they were pinned or by their votes.
+        let pinned = &self.model.pinned;
+        let votes = &self.model.votes;
+        let mut pinned_rows: Vec<_> = program
+            .iter()
+            .filter(|(_c, m)| pinned.contains(m))
+            .cloned()
+            .collect();
+        pinned_rows.sort_by_key(|(_c, m)| pinned.iter().position(|p| p == m));
+        if self.model.voting {
+            pinned_rows.sort_by_key(|(_c, m)| Reverse(votes.get(m).cloned().unwrap_or(0)));
+        }
+
+        self.model.shown = pinned_rows
+            .into_iter()
+            .chain(
+                program
+                    .iter()
+                    .filter(|(_c, m)| !pinned.contains(m))
+                    .cloned(),
+            )
+            .collect();
+        self.update_remote();
+
+        // Rows created for a earlier program are not inserted any more.
+        self.model.rows_generation += 1;
+        self.insert_rows();
+
+        self.components
+            .placeholder
+            .emit(PlaceholderMsg::Set(self.model.load_error.clone()));
+    }
+
+    /// Show the program as listed on the remote control, if it was started.
+    fn update_remote(&self) {
+        if self.model.remote_url.is_some() {
+            self.model
+                .remote_view
+                .set(self.model.shown.clone(), self.model.pinned.clone());
+        }
+    }
+
+    /// Hide the movies matching the filters and show a toast to undo it. In the allow mode the filters are removed from
+    /// the allowed ones instead, as adding them would show the movies. Filters that are not allowed can not be blocked
+    /// then.
+    fn block_filters(&mut self, filters: Vec<FilterType>) {
+        if self.model.filter.get_mode() == FilterMode::Block {
+            self.add_filters(filters.into_iter().map(|f| (f, None)).collect());
+            return;
+        }
+
+        let filter = &mut self.model.filter;
+        let undo = &mut self.model.undo_filters;
+        undo.clear();
+
+        for removed in filters {
+            if filter.contains(&removed) {
+                let expiry = filter.get_expiry(&removed);
+                filter.remove(&removed);
+                undo.push((removed, Some(expiry)));
+            }
+        }
+        self.schedule_refilter();
+
+        let _ = self.model.write_filters();
+
+        self.show_undo_toast("No longer allowing");
+    }
+
+    /// Add the filters, forever or until their expiry, and show a toast to undo adding them.
+    fn add_filters(&mut self, filters: Vec<(FilterType, Option<NaiveDateTime>)>) {
+        let filter = &mut self.model.filter;
+        let undo = &mut self.model.undo_filters;
+        undo.clear();
+
+        for (added, expiry) in filters {
+            let previous = if filter.contains(&added) {
+                Some(filter.get_expiry(&added))
+            } else {
+                None
+            };
+            // Filters already added forever do not change.
+            if previous == Some(None) && expiry.is_none() {
+                continue;
+            }
+
+            match expiry {
+                Some(expiry) => filter.add_until(added.clone(), expiry),
+                None => filter.add(added.clone()),
+            }
+            undo.push((added, previous));
+        }
+        self.schedule_refilter();
+
+        let _ = self.model.write_filters();
+
+        let verb = match (
+            self.model.undo_filters.first(),
+            self.model.filter.get_mode(),
+        ) {
+            (Some((FilterType::Broadcast(_), _)), _) => "Hidden",
+            (_, FilterMode::Block) => "Blocked",
+            (_, FilterMode::Allow) => "Allowed",
+        };
+        self.show_undo_toast(verb);
+    }
+
+    /// Show a toast offering to undo the last change of the filters, if there was one.
+    fn show_undo_toast(&self, verb: &str) {
+        if let Some((first, _previous)) = self.model.undo_filters.first() {
+            let text = match self.model.undo_filters.len() {
+                1 => format!("{} '{}'", verb, first.get_value()),
+                count => format!("{} '{}' and {} more", verb, first.get_value(), count - 1),
+            };
+            self.components.toast.emit(ToastMsg::Show(text));
+        }
+    }
+
+    /// Show how many rows are checked, the actions need at least one.
+    fn update_checked(&self) {
+        let count = self.model.checked.len();
+        self.widgets
+            .label_checked
+            .set_text(&format!("{} selected", count));
+        self.widgets.button_block_titles.set_sensitive(count > 0);
+        self.widgets.button_block_channels.set_sensitive(count > 0);
+    }
+
+    /// Create the next `ROWS_PER_CHUNK` rows of the shown program. The remaining rows are created once the main loop is
+    /// idle, so the window is drawn and stays responsive while hundreds of rows are created.
+    fn insert_rows(&mut self) {
+        let start = Instant::now();
+        let listbox = &self.widgets.listbox;
+        let today = Local::now().date_naive();
+        let archive = &self.model.archive;
+        let pinned = &self.model.pinned;
+        let votes = &self.model.votes;
+        let grouped = self.model.grouped;
+        let voting = self.model.voting;
+        let shown = &self.model.shown;
+        let selected = &self.model.selected;
+        let started = &self.model.started;
+        let added = &self.model.added;
+        let duplicates = &self.model.duplicates;
+        let transition_duration = self.transition_duration();
+        let dim_started = self.model.settings.get_started_broadcasts() == StartedBroadcasts::Dim;
+
+        let pinned_count = shown
+            .iter()
+            .take_while(|(_c, m)| pinned.contains(m))
+            .count();
+        let first = self.model.movies.len();
+        let last = shown.len().min(first + ROWS_PER_CHUNK);
+
+        for (index, data) in shown.iter().enumerate().take(last).skip(first) {
+            let item = MovieListItemData {
+                data: data.clone(),
+                pinned: index < pinned_count,
+                show_channel: !grouped,
+                votes: if voting && index < pinned_count {
+                    Some(votes.get(&data.1).cloned().unwrap_or(0))
+                } else {
+                    None
+                },
+                rerun: archive.is_rerun(&data.1, today, RERUN_DAYS),
+                new: added.contains(&data.1),
+                also_on: duplicates
+                    .channels_of(&data.1)
+                    .iter()
+                    .map(|c| c.get_name())
+                    .filter(|name| name != &data.0.get_name())
+                    .collect(),
+                dimmed: dim_started && started.contains(&data.1),
+                compact: self.model.settings.get_compact(),
+                two_line_titles: self.model.settings.get_two_line_titles(),
+                transition_duration,
+                has_url: self.get_url(&data.1).is_some(),
+                selection_mode: self.model.selection_mode,
+                checked: self.model.checked.contains(data),
+            };
+            let component = listbox.add_widget::<MovieListItem>(item);
+
+            let stream = self.model.relm.stream().clone();
+            let (channel, movie) = data.clone();
+            component.stream().observe(move |msg| match msg {
+                MovieListItemMsg::TogglePin => stream.emit(MovieListMsg::TogglePin(movie.clone())),
+                MovieListItemMsg::Vote => stream.emit(MovieListMsg::Vote(movie.clone())),
+                MovieListItemMsg::Block => stream.emit(MovieListMsg::BlockTitle(movie.clone())),
+                MovieListItemMsg::Open => stream.emit(MovieListMsg::OpenMovie(movie.clone())),
+                MovieListItemMsg::BlockChannel => {
+                    stream.emit(MovieListMsg::BlockChannel(channel.clone()))
+                }
+                MovieListItemMsg::OpenInBrowser => {
+                    stream.emit(MovieListMsg::OpenInBrowser(movie.clone()))
+                }
+                MovieListItemMsg::Checked(checked) => stream.emit(MovieListMsg::Check((
+                    (channel.clone(), movie.clone()),
+                    *checked,
+                ))),
+                MovieListItemMsg::SetChannel(_)
+                | MovieListItemMsg::Swiped
+                | MovieListItemMsg::ShowMenu
+                | MovieListItemMsg::SetSelectionMode(_)
+                | MovieListItemMsg::ToggleChecked => {}
+            });
+
+            // The header of the row, starting a new section or channel group.
+            let header = Box::new(Orientation::Vertical, 0);
+            let mut has_header = false;
+
+            if pinned_count > 0 && (index == 0 || index == pinned_count) {
+                header.add(&section_header(if index == 0 {
+                    "Pinned"
+                } else {
+                    "Tonight"
+                }));
+                has_header = true;
+            }
+
+            let new_group = index == 0
+                || index == pinned_count
+                || shown[index - 1].0.get_name() != data.0.get_name();
+            if grouped && new_group {
+                header.add(&channel_header(&data.0));
+                has_header = true;
+            }
+
+            if has_header {
+                header.show_all();
+                component.widget().set_header(Some(&header));
+            }
+
+            if selected.as_ref() == Some(&data.1) {
+                listbox.select_row(Some(component.widget()));
+            }
+
+            self.model.movies.push(component);
+        }
+        metrics::record(ReloadStep::BuildList, start.elapsed());
+
+        if last < shown.len() {
+            let stream = self.model.relm.stream().clone();
+            let generation = self.model.rows_generation;
+            glib::idle_add_local(move || {
+                stream.emit(MovieListMsg::InsertRows(generation));
+                Continue(false)
+            });
+        } else if !self.model.reloading && self.model.last_reload.is_some() {
+            // The scroll position of the last session is restored once the whole current program is shown and laid
+            // out, not already for the cached program.
+            if let (Some(position), Some(adjustment)) = (
+                self.model.restore_scroll.take(),
+                self.widgets.scrolled_window.get_vadjustment(),
+            ) {
+                glib::idle_add_local(move || {
+                    adjustment.set_value(position);
+                    Continue(false)
+                });
+            }
+        }
+    }
+}
+
+/// A label used as a header for a section of the list.
+fn section_header(title: &str) -> Label {
+    let label = Label::new(Some(title));
+    label.set_xalign(0.0);
+    label.get_style_context().add_class("dim-label");
+    label.show();
+    label
+}
+
+/// A header for a group of movies of the same channel, showing the channel icon or a avatar with its initials.
+fn channel_header(channel: &Channel) -> Box {
+    let header = Box::new(Orientation::Horizontal, 10);
+
+    if let Some(pixbuf) = icon_pixbuf(channel).or_else(|| avatar_pixbuf(&channel.get_name())) {
+        header.add(&Image::from_pixbuf(Some(&pixbuf)));
+    }
+    header.add(&Label::new(Some(&channel.get_name())));
+
+    header
+}
+
+/// Move through the matches of the search with the arrow keys. Other keys are handled by the search entry.
+fn search_key_press(key: &gdk::EventKey) -> (Option<MovieListMsg>, Inhibit) {
+    match key.get_keyval() {
+        gdk::keys::constants::Up => (Some(MovieListMsg::MoveSelection(-1)), Inhibit(true)),
+        gdk::keys::constants::Down => (Some(MovieListMsg::MoveSelection(1)), Inhibit(true)),
+        _ => (None, Inhibit(false)),
+    }
+}
+
+/// The file the program of the time slot loaded the last time today is cached in.
+fn cached_program_path(time_slot: TimeSlot) -> PathBuf {
+    paths::user_cache_file(&format!("program-{}.json", time_slot))
+}
+
+/// Read the program of the time slot cached today, if any. Programs cached on a earlier day are outdated.
+fn read_cached_program(time_slot: TimeSlot) -> Option<Program> {
+    let path = cached_program_path(time_slot);
+    let modified: DateTime<Local> = fs::metadata(&path).and_then(|m| m.modified()).ok()?.into();
+    if modified.date_naive() != Local::now().date_naive() {
+        return None;
+    }
+
+    Program::read_from_path(path).ok()
 }
@@ -1,24 +1,162 @@
+use crate::gui::{avatar_pixbuf, icon_pixbuf};
 use crate::model::{Channel, Movie};
 
 use gtk::prelude::*;
-use pango::{AttrList, Attribute};
+use gtk::{
+    Button, EventSequenceState, GestureLongPress, GestureMultiPress, GestureSwipe, Orientation,
+    Popover, ReliefStyle,
+};
+use pango::{AttrList, Attribute, EllipsizeMode, WrapMode};
 use relm::{Relm, Widget};
 use relm_derive::{widget, Msg};
 
 #[derive(Msg)]
-pub enum MovieListItemMsg {}
+pub enum MovieListItemMsg {
+    /// Toggle if the movie is pinned to the top of the list. Handled by the `MovieList`.
+    TogglePin,
+    /// Vote for the movie in the household voting mode. Handled by the `MovieList`.
+    Vote,
+    /// Set the channel, e.g. when its icon was loaded.
+    SetChannel(Channel),
+    /// The row was swiped away or long-pressed, hide it before blocking the title.
+    Swiped,
+    /// Block the title of the movie once the row is hidden. Handled by the `MovieList`.
+    Block,
+    /// Show the menu of the row, opened with a right click or a long press.
+    ShowMenu,
+    /// Open the details of the movie. Handled by the `MovieList`.
+    Open,
+    /// Block the channel of the movie. Handled by the `MovieList`.
+    BlockChannel,
+    /// Open the website of the movie in the browser. Handled by the `MovieList`.
+    OpenInBrowser,
+    /// Show or hide the check to select the row for blocking it together with others.
+    SetSelectionMode(bool),
+    /// Toggle the check of the row, e.g. when it is activated in the selection mode.
+    ToggleChecked,
+    /// The check of the row was toggled. Handled by the `MovieList`.
+    Checked(bool),
+}
+
+/// The horizontal speed in pixels per second a row must be swiped to the left with to block its title.
+const SWIPE_VELOCITY: f64 = 500.0;
+
+/// A action of the menu of a row as its label, the message it sends and weather it is sensitive.
+type MenuAction = (&'static str, fn() -> MovieListItemMsg, bool);
+
+/// The data needed to create a `MovieListItem`.
+pub struct MovieListItemData {
+    /// The channel and movie shown.
+    pub data: (Channel, Movie),
+    /// Whether the movie is pinned to the top of the list.
+    pub pinned: bool,
+    /// Whether the channel is shown. Not the case if the list is grouped by channel.
+    pub show_channel: bool,
+    /// The votes of the movie if it can be voted for.
+    pub votes: Option<u32>,
+    /// Whether the movie was already broadcasted recently.
+    pub rerun: bool,
+    /// Whether the movie was not in the program before it was reloaded the last time.
+    pub new: bool,
+    /// The names of the other channels broadcasting the movie at the same time, if repeated broadcasts are merged.
+    pub also_on: Vec<String>,
+    /// Whether the movie is dimmed, e.g. as it already started.
+    pub dimmed: bool,
+    /// Whether the compact text-only mode is used.
+    pub compact: bool,
+    /// Whether long titles wrap to a second line before being ellipsized.
+    pub two_line_titles: bool,
+    /// The duration of hiding the row when it is swiped away in milliseconds.
+    pub transition_duration: u32,
+    /// Whether the provider knows a website about the movie, which can be opened from the menu.
+    pub has_url: bool,
+    /// Whether the rows can be selected to block them together.
+    pub selection_mode: bool,
+    /// Whether the row is selected in the selection mode.
+    pub checked: bool,
+}
 
 pub struct MovieListItemModel {
     data: (Channel, Movie),
+    pinned: bool,
+    show_channel: bool,
+    votes: Option<u32>,
+    rerun: bool,
+    new: bool,
+    also_on: Vec<String>,
+    dimmed: bool,
+    compact: bool,
+    two_line_titles: bool,
+    transition_duration: u32,
+    has_url: bool,
+    selection_mode: bool,
+    checked: bool,
+    /// The gestures to block the title and to open the menu, kept as long as the row exists.
+    gestures: Option<(GestureSwipe, GestureLongPress, GestureMultiPress)>,
+    /// The menu of the row, created when it is shown the first time.
+    menu: Option<Popover>,
+    relm: Relm<MovieListItem>,
 }
 
 #[widget]
 impl Widget for MovieListItem {
-    fn model(_relm: &Relm<Self>, data: (Channel, Movie)) -> MovieListItemModel {
-        MovieListItemModel { data }
+    fn model(relm: &Relm<Self>, item: MovieListItemData) -> MovieListItemModel {
+        MovieListItemModel {
+            data: item.data,
+            pinned: item.pinned,
+            show_channel: item.show_channel,
+            votes: item.votes,
+            rerun: item.rerun,
+            new: item.new,
+            also_on: item.also_on,
+            dimmed: item.dimmed,
+            compact: item.compact,
+            two_line_titles: item.two_line_titles,
+            transition_duration: item.transition_duration,
+            has_url: item.has_url,
+            selection_mode: item.selection_mode,
+            checked: item.checked,
+            gestures: None,
+            menu: None,
+            relm: relm.clone(),
+        }
     }
 
-    fn update(&mut self, _event: MovieListItemMsg) {}
+    fn update(&mut self, event: MovieListItemMsg) {
+        match event {
+            MovieListItemMsg::SetChannel(channel) => {
+                self.model.data.0 = channel;
+                self.show_channel();
+            }
+            MovieListItemMsg::Swiped => {
+                self.widgets.revealer.set_reveal_child(false);
+                relm::timeout(
+                    self.model.relm.stream(),
+                    self.model.transition_duration,
+                    || MovieListItemMsg::Block,
+                );
+            }
+            MovieListItemMsg::ShowMenu => self.show_menu(),
+            MovieListItemMsg::SetSelectionMode(selection_mode) => {
+                self.model.selection_mode = selection_mode;
+                self.widgets.check_selected.set_visible(selection_mode);
+                if !selection_mode {
+                    self.widgets.check_selected.set_active(false);
+                }
+            }
+            MovieListItemMsg::ToggleChecked => {
+                let check = &self.widgets.check_selected;
+                check.set_active(!check.get_active());
+            }
+            MovieListItemMsg::TogglePin
+            | MovieListItemMsg::Vote
+            | MovieListItemMsg::Block
+            | MovieListItemMsg::Open
+            | MovieListItemMsg::BlockChannel
+            | MovieListItemMsg::OpenInBrowser
+            | MovieListItemMsg::Checked(_) => {}
+        }
+    }
 
     fn init_view(&mut self) {
         let attr_list = AttrList::new();
@@ -27,37 +165,259 @@ impl Widget for MovieListItem {
         self.widgets.label_channel.set_attributes(Some(&attr_list));
         self.widgets.label_movie.set_attributes(Some(&attr_list));
 
-        let pixbuf_opt = self.model.data.0.get_icon_as_pixbuf();
+        // Long titles are ellipsized instead of stretching the row, the full title is shown as tooltip.
+        let label_movie = &self.widgets.label_movie;
+        label_movie.set_ellipsize(EllipsizeMode::End);
+        label_movie.set_max_width_chars(1);
+        label_movie.set_tooltip_text(Some(&self.model.data.1.get_title()));
+        if self.model.two_line_titles {
+            label_movie.set_line_wrap(true);
+            label_movie.set_line_wrap_mode(WrapMode::WordChar);
+            label_movie.set_lines(2);
+        }
 
-        if let Some(pixbuf) = pixbuf_opt {
-            self.widgets.icon_channel.set_from_pixbuf(Some(&pixbuf));
+        let details = details(&self.model.data.1);
+        if details.is_empty() {
+            self.widgets.label_details.set_visible(false);
+        } else {
+            self.widgets.label_details.set_text(&details);
+            self.widgets.label_details.set_ellipsize(EllipsizeMode::End);
+            self.widgets.label_details.set_max_width_chars(1);
+            self.widgets
+                .label_details
+                .get_style_context()
+                .add_class("dim-label");
+        }
 
-            self.widgets.label_channel.set_visible(false);
-            self.widgets.icon_channel.set_visible(true);
+        self.show_channel();
+        self.add_gestures();
+        self.widgets
+            .revealer
+            .set_transition_duration(self.model.transition_duration);
+
+        if self.model.compact {
+            self.widgets.button_pin.set_label("Pin");
         } else {
-            self.widgets.icon_channel.set_visible(false);
-            self.widgets.label_channel.set_visible(true);
+            self.widgets
+                .button_pin
+                .set_image(Some(&gtk::Image::from_icon_name(
+                    Some("view-pin-symbolic"),
+                    gtk::IconSize::Menu,
+                )));
+        }
+        self.widgets.button_pin.set_active(self.model.pinned);
+
+        self.widgets
+            .check_selected
+            .set_visible(self.model.selection_mode);
+        self.widgets.check_selected.set_active(self.model.checked);
+
+        self.widgets.label_rerun.set_visible(self.model.rerun);
+        self.widgets.label_new.set_visible(self.model.new);
+
+        if self.model.also_on.is_empty() {
+            self.widgets.label_also_on.set_visible(false);
+        } else {
+            self.widgets
+                .label_also_on
+                .set_text(&format!("also on {}", self.model.also_on.join(", ")));
+        }
+
+        if self.model.dimmed {
+            self.widgets.box_content.set_opacity(0.5);
+        }
+
+        if let Some(votes) = self.model.votes {
+            self.widgets.button_vote.set_label(&format!("+{}", votes));
+            self.widgets.button_vote.set_visible(true);
+        } else {
+            self.widgets.button_vote.set_visible(false);
         }
     }
 
     view! {
         gtk::ListBoxRow {
-            #[name="box_content"]
-            gtk::Box {
-                spacing: 10,
-                #[name="icon_channel"]
-                gtk::Image {
-                },
-                #[name="label_channel"]
-                gtk::Label {
-                    label: &self.model.data.0.get_name(),
+            #[name="revealer"]
+            gtk::Revealer {
+                transition_type: gtk::RevealerTransitionType::SlideLeft,
+                reveal_child: true,
+                #[name="box_content"]
+                gtk::Box {
+                    spacing: 10,
+                    #[name="check_selected"]
+                    gtk::CheckButton {
+                        tooltip_text: Some("Select to block it together with others"),
+                        toggled(check) => MovieListItemMsg::Checked(check.get_active()),
+                    },
+                    #[name="icon_channel"]
+                    gtk::Image {
+                        tooltip_text: Some(&self.model.data.0.get_name()),
+                    },
+                    #[name="label_channel"]
+                    gtk::Label {
+                        label: &self.model.data.0.get_name(),
 
-                },
-                #[name="label_movie"]
-                gtk::Label {
-                    label: &self.model.data.1.get_title()
+                    },
+                    gtk::Box {
+                        orientation: gtk::Orientation::Vertical,
+                        hexpand: true,
+                        valign: gtk::Align::Center,
+                        #[name="label_movie"]
+                        gtk::Label {
+                            label: &self.model.data.1.get_title(),
+                            xalign: 0.0,
+                        },
+                        #[name="label_details"]
+                        gtk::Label {
+                            xalign: 0.0,
+                        },
+                    },
+                    #[name="label_also_on"]
+                    gtk::Label {
+                        sensitive: false,
+                    },
+                    #[name="label_new"]
+                    gtk::Label {
+                        label: "new",
+                        tooltip_text: Some("Added since the program was reloaded the last time"),
+                    },
+                    #[name="label_rerun"]
+                    gtk::Label {
+                        label: "rerun",
+                        tooltip_text: Some("Already broadcasted recently"),
+                        sensitive: false,
+                    },
+                    #[name="button_vote"]
+                    gtk::Button {
+                        relief: gtk::ReliefStyle::None,
+                        tooltip_text: Some("Vote for this movie"),
+                        clicked => MovieListItemMsg::Vote,
+                    },
+                    #[name="button_pin"]
+                    gtk::ToggleButton {
+                        relief: gtk::ReliefStyle::None,
+                        tooltip_text: Some("Pin to the top"),
+                        toggled => MovieListItemMsg::TogglePin,
+                    },
                 },
             },
         }
     }
 }
+
+impl MovieListItem {
+    /// Block the title when the row is swiped to the left on a touchscreen, show the menu on a right click or a long
+    /// press.
+    fn add_gestures(&mut self) {
+        let row = self.root();
+
+        let swipe = GestureSwipe::new(&row);
+        swipe.set_touch_only(true);
+        let stream = self.model.relm.stream().clone();
+        swipe.connect_swipe(move |_gesture, velocity_x, velocity_y| {
+            if velocity_x < -SWIPE_VELOCITY && velocity_x.abs() > velocity_y.abs() {
+                stream.emit(MovieListItemMsg::Swiped);
+            }
+        });
+
+        let long_press = GestureLongPress::new(&row);
+        long_press.set_touch_only(true);
+        let stream = self.model.relm.stream().clone();
+        long_press.connect_pressed(move |gesture, _x, _y| {
+            // The row is not activated when the touch ends.
+            gesture.set_state(EventSequenceState::Claimed);
+            stream.emit(MovieListItemMsg::ShowMenu);
+        });
+
+        let right_click = GestureMultiPress::new(&row);
+        right_click.set_button(gdk::BUTTON_SECONDARY);
+        let stream = self.model.relm.stream().clone();
+        right_click.connect_pressed(move |gesture, _n_press, _x, _y| {
+            gesture.set_state(EventSequenceState::Claimed);
+            stream.emit(MovieListItemMsg::ShowMenu);
+        });
+
+        self.model.gestures = Some((swipe, long_press, right_click));
+    }
+
+    /// Show the menu with the actions of the row.
+    fn show_menu(&mut self) {
+        if self.model.menu.is_none() {
+            let menu_box = gtk::Box::new(Orientation::Vertical, 0);
+            // Blocking the title hides the row like swiping it away.
+            let actions: [MenuAction; 4] = [
+                ("Open details", || MovieListItemMsg::Open, true),
+                ("Block title", || MovieListItemMsg::Swiped, true),
+                ("Block channel", || MovieListItemMsg::BlockChannel, true),
+                (
+                    "Open in browser",
+                    || MovieListItemMsg::OpenInBrowser,
+                    self.model.has_url,
+                ),
+            ];
+            let popover = Popover::new(Some(&self.root()));
+
+            for (label, msg, sensitive) in actions.iter() {
+                let button = Button::with_label(label);
+                button.set_relief(ReliefStyle::None);
+                button.set_sensitive(*sensitive);
+                let stream = self.model.relm.stream().clone();
+                let msg = *msg;
+                let popover_clone = popover.clone();
+                button.connect_clicked(move |_| {
+                    popover_clone.popdown();
+                    stream.emit(msg());
+                });
+                menu_box.add(&button);
+            }
+
+            menu_box.show_all();
+            popover.add(&menu_box);
+            self.model.menu = Some(popover);
+        }
+
+        if let Some(menu) = &self.model.menu {
+            menu.popup();
+        }
+    }
+
+    /// Show the channel icon if available, otherwise a avatar with the initials of the channel. The compact mode shows
+    /// the channel name.
+    fn show_channel(&self) {
+        // No icons are shown in the compact mode.
+        let channel = &self.model.data.0;
+        let pixbuf_opt = if self.model.compact {
+            None
+        } else {
+            icon_pixbuf(channel).or_else(|| avatar_pixbuf(&channel.get_name()))
+        };
+
+        if !self.model.show_channel {
+            self.widgets.icon_channel.set_visible(false);
+            self.widgets.label_channel.set_visible(false);
+        } else if let Some(pixbuf) = pixbuf_opt {
+            self.widgets.icon_channel.set_from_pixbuf(Some(&pixbuf));
+
+            self.widgets.label_channel.set_visible(false);
+            self.widgets.icon_channel.set_visible(true);
+        } else {
+            self.widgets.icon_channel.set_visible(false);
+            self.widgets.label_channel.set_visible(true);
+        }
+    }
+}
+
+/// The basic information about the movie shown below the title, like `20:15 · 2001 · Krimi`.
+fn details(movie: &Movie) -> String {
+    let mut details: Vec<String> = vec![];
+    if let Some(start) = movie.get_start() {
+        details.push(start.format("%H:%M").to_string());
+    }
+    if let Some(year) = movie.get_year() {
+        details.push(year.to_string());
+    }
+    if let Some(genre) = movie.get_genre() {
+        details.push(genre);
+    }
+    details.join(" · ")
+}
@@ -1,43 +1,94 @@
-use crate::gui::{SlidingStack, SlidingStackMsg, WinMsg};
-use crate::model::{Channel, ChannelAttribute, FilterType, Movie, MovieAttribute, Provider};
-
-use std::thread;
-
+use crate::gui::{tasks, SlidingStack, SlidingStackMsg, WinMsg};
+use crate::model::cache::{self, SizedCache};
+use crate::model::{
+    get_poster, get_ratings, translate, Channel, ChannelAttribute, FilterMode, FilterType,
+    HiddenBroadcast, Movie, MovieAttribute, PersonalRatings, ProviderHandle, Ratings, Reminder,
+};
+use crate::Error;
+
+use chrono::{Duration, Local};
+use gdk_pixbuf::prelude::*;
+use gdk_pixbuf::{InterpType, PixbufLoader};
 use gtk::prelude::*;
-use gtk::{Adjustment, Box, Button, Label, Orientation, ScrolledWindow};
+use gtk::{
+    Adjustment, Box, Button, CheckButton, ComboBoxText, Image, Label, Orientation, ScrolledWindow,
+    SpinButton,
+};
 use libhandy::{HeaderBar, HeaderBarExt};
 use relm::{connect, Component, Relm, StreamHandle, Update, Widget};
 use relm_derive::Msg;
-use tokio::runtime::Runtime;
 
 pub enum FilterList {
     ChannelName,
     MovieTitle,
     MovieGenre,
     MovieDivision,
+    MovieDirector,
+    MovieActor,
+    /// Only this broadcast, hidden until it ended independent of the filter mode.
+    Broadcast,
 }
 
 #[derive(Msg)]
-pub enum MoviePageMsg<T: 'static + Provider> {
+pub enum MoviePageMsg {
     Filter(FilterList),
     SwitchStack,
-    SetProvider(T),
     Set((Channel, Movie)),
-    SetMovie(Movie),
+    /// The movie of the program with more information, or the error loading it.
+    SetMovie((Movie, Result<Movie, Error>)),
+    SetReminded(bool),
+    ToggleReminder,
+    SetFilterMode(FilterMode),
+    SetPoster((String, Vec<u8>)),
+    SetOmdbApiKey(Option<String>),
+    SetRatings((Movie, Ratings)),
+    SetTranslationUrl(Option<String>),
+    SetPersonalRatings(PersonalRatings),
+    Translate,
+    SetTranslation((Movie, Result<String, Error>)),
+    OpenInBrowser,
+    SetTransitionDuration(u32),
+    /// Open the movie of the list this many rows away.
+    OpenRelative(i32),
+    /// Whether the movie has a previous and a next movie in the list.
+    SetNeighbours((bool, bool)),
 }
 
-pub struct MoviePageModel<T: 'static + Provider> {
-    channel: Channel,
-    movie: Movie,
+/// The width the poster is scaled to.
+const POSTER_WIDTH: i32 = 300;
+
+/// The number of days a filter is added for if it should expire.
+const TEMPORARY_FILTER_DAYS: i64 = 7;
 
-    provider: T,
+/// The default number of minutes to be reminded before a movie starts.
+const DEFAULT_REMINDER_MINUTES: f64 = 10.0;
 
-    relm: Relm<MoviePage<T>>,
-    win_stream: StreamHandle<WinMsg<T>>,
+pub struct MoviePageModel {
+    channel: Channel,
+    movie: Movie,
+    /// Weather a reminder is set for the movie.
+    reminded: bool,
+    /// The URL of the poster that is shown or currently loading.
+    poster_url: Option<String>,
+    /// The URL of the website about the movie.
+    url: Option<String>,
+    /// The API key of OMDb. External ratings are only looked up if it is set.
+    omdb_api_key: Option<String>,
+    /// The URL of the LibreTranslate server. Descriptions can only be translated if it is set.
+    translation_url: Option<String>,
+    /// The ratings the user imported from IMDb.
+    personal_ratings: PersonalRatings,
+    /// The movies with more information shown before, by the movie of the program.
+    informations: SizedCache<Movie, Movie>,
+
+    provider: ProviderHandle,
+
+    relm: Relm<MoviePage>,
+    win_stream: StreamHandle<WinMsg>,
 }
 
-pub struct MoviePage<T: 'static + Provider> {
-    model: MoviePageModel<T>,
+pub struct MoviePage {
+    model: MoviePageModel,
     widgets: MoviePageWidgets,
     components: MoviePageComponents,
 }
@@ -45,35 +96,66 @@ pub struct MoviePage<T: 'static + Provider> {
 pub struct MoviePageWidgets {
     root: Box,
     header_bar: HeaderBar,
+    button_previous: Button,
+    button_next: Button,
+    button_open_in_browser: Button,
+    image_poster: Image,
     label_channel_name: Label,
     label_movie_genre: Label,
     label_movie_division: Label,
     label_movie_year: Label,
+    label_movie_start: Label,
+    label_movie_age_rating: Label,
+    label_movie_director: Label,
+    label_movie_cast: Label,
+    label_movie_ratings: Label,
+    label_personal_rating: Label,
     label_movie_description: Label,
+    button_translate: Button,
+    label_translation: Label,
+    box_reminder: Box,
+    spin_reminder_minutes: SpinButton,
+    button_reminder: Button,
+    button_broadcast: Button,
+    check_temporary_filter: CheckButton,
+    button_channel_name: Button,
+    button_movie_title: Button,
+    button_movie_genre: Button,
+    button_movie_division: Button,
+    button_movie_director: Button,
+    combo_movie_actor: ComboBoxText,
+    button_movie_actor: Button,
 }
 
 pub struct MoviePageComponents {
     stack: Component<SlidingStack<Box, ScrolledWindow>>,
 }
 
-impl<T: 'static + Provider> Update for MoviePage<T> {
-    type Model = MoviePageModel<T>;
-    type ModelParam = StreamHandle<WinMsg<T>>;
-    type Msg = MoviePageMsg<T>;
+impl Update for MoviePage {
+    type Model = MoviePageModel;
+    type ModelParam = (StreamHandle<WinMsg>, ProviderHandle);
+    type Msg = MoviePageMsg;
 
-    fn model(relm: &Relm<MoviePage<T>>, win_stream: Self::ModelParam) -> Self::Model {
+    fn model(relm: &Relm<MoviePage>, (win_stream, provider): Self::ModelParam) -> Self::Model {
         MoviePageModel {
             channel: Channel::new(""),
             movie: Movie::new(""),
+            reminded: false,
+            poster_url: None,
+            url: None,
+            omdb_api_key: None,
+            translation_url: None,
+            personal_ratings: PersonalRatings::new(),
+            informations: SizedCache::new(cache::get_description_limit()),
 
-            provider: T::new(),
+            provider,
 
             relm: relm.clone(),
             win_stream,
         }
     }
 
-    fn update(&mut self, event: MoviePageMsg<T>) {
+    fn update(&mut self, event: MoviePageMsg) {
         match event {
             MoviePageMsg::Filter(item) => {
                 self.components.stack.emit(SlidingStackMsg::ShowSecondPage);
@@ -104,9 +186,44 @@ impl<T: 'static + Provider> Update for MoviePage<T> {
                             return;
                         }
                     }
+                    FilterList::MovieDirector => {
+                        let director_opt = self.model.movie.get_director();
+                        if let Some(director) = director_opt {
+                            filter = FilterType::Movie(MovieAttribute::Director(director))
+                        } else {
+                            return;
+                        }
+                    }
+                    FilterList::MovieActor => {
+                        let actor_opt = self.widgets.combo_movie_actor.get_active_text();
+                        if let Some(actor) = actor_opt {
+                            filter = FilterType::Movie(MovieAttribute::Actor(actor.to_string()))
+                        } else {
+                            return;
+                        }
+                    }
+                    FilterList::Broadcast => {
+                        let broadcast_opt =
+                            HiddenBroadcast::of(&self.model.channel, &self.model.movie);
+                        if let Some(broadcast) = broadcast_opt {
+                            filter = FilterType::Broadcast(broadcast)
+                        } else {
+                            return;
+                        }
+                    }
                 }
 
-                self.model.win_stream.emit(WinMsg::AddFilter(filter));
+                // Hidden broadcasts expire on their own.
+                let temporary = self.widgets.check_temporary_filter.get_active()
+                    && !matches!(filter, FilterType::Broadcast(_));
+                if temporary {
+                    let expiry = Local::now().naive_local() + Duration::days(TEMPORARY_FILTER_DAYS);
+                    self.model
+                        .win_stream
+                        .emit(WinMsg::AddFilterUntil((filter, expiry)));
+                } else {
+                    self.model.win_stream.emit(WinMsg::AddFilter(filter));
+                }
             }
             MoviePageMsg::SwitchStack => {
                 self.components.stack.emit(SlidingStackMsg::Switch);
@@ -114,34 +231,207 @@ impl<T: 'static + Provider> Update for MoviePage<T> {
             MoviePageMsg::Set((channel, movie)) => {
                 self.model.channel = channel;
                 self.model.movie = movie.clone();
+                // The URL must be looked up with the movie of the program, not the one with more information.
+                self.model.url = self.model.provider.lock().get_url(&movie);
+                self.widgets.label_translation.set_visible(false);
+
+                // Get more information, unless it was loaded before.
+                if let Some(information_movie) = self.model.informations.get(&movie) {
+                    self.model.movie = information_movie;
+                } else {
+                    let stream = self.model.relm.stream().clone();
+
+                    let (_channel, sender) = relm::Channel::new(move |movies| {
+                        stream.emit(MoviePageMsg::SetMovie(movies))
+                    });
+
+                    let provider = self.model.provider.get();
+
+                    tasks::spawn(move || async move {
+                        let information_movie = provider.get_more_information(&movie).await;
+                        let _ = sender.send((movie, information_movie));
+                    });
+                }
+                self.load_poster();
+                self.load_ratings();
+                self.show_all();
+            }
+            MoviePageMsg::SetMovie((movie, information_movie)) => {
+                // If no more information could be loaded, it is tried again next time.
+                let information_movie = match information_movie {
+                    Ok(information_movie) => information_movie,
+                    Err(_) => return,
+                };
+
+                let informations = &mut self.model.informations;
+                informations.set_limit(cache::get_description_limit());
+                informations.insert(
+                    movie,
+                    information_movie.clone(),
+                    information_size(&information_movie),
+                );
+                cache::set_description_size(informations.get_size());
+
+                self.model.movie = information_movie;
+                self.load_poster();
+                self.show_all();
+            }
+            MoviePageMsg::OpenInBrowser => {
+                if let Some(url) = &self.model.url {
+                    let _ =
+                        gio::AppInfo::launch_default_for_uri::<gio::AppLaunchContext>(url, None);
+                }
+            }
+            MoviePageMsg::SetTransitionDuration(duration) => self
+                .components
+                .stack
+                .emit(SlidingStackMsg::SetTransitionDuration(duration)),
+            MoviePageMsg::OpenRelative(offset) => {
+                self.model.win_stream.emit(WinMsg::OpenRelative(offset))
+            }
+            MoviePageMsg::SetNeighbours((previous, next)) => {
+                self.widgets.button_previous.set_sensitive(previous);
+                self.widgets.button_next.set_sensitive(next);
+            }
+            MoviePageMsg::SetOmdbApiKey(api_key) => {
+                self.model.omdb_api_key = api_key;
+            }
+            MoviePageMsg::SetTranslationUrl(url) => {
+                self.model.translation_url = url;
+                self.show_all();
+            }
+            MoviePageMsg::SetPersonalRatings(ratings) => {
+                self.model.personal_ratings = ratings;
+                self.show_all();
+            }
+            MoviePageMsg::Translate => {
+                if let (Some(url), Some(description)) = (
+                    self.model.translation_url.clone(),
+                    self.model.movie.get_description(),
+                ) {
+                    self.widgets.button_translate.set_sensitive(false);
+
+                    let stream = self.model.relm.stream().clone();
 
-                // Get more information.
-                let stream = self.model.relm.stream().clone();
+                    let (_channel, sender) = relm::Channel::new(move |translation| {
+                        stream.emit(MoviePageMsg::SetTranslation(translation))
+                    });
 
-                let (_channel, sender) =
-                    relm::Channel::new(move |movie| stream.emit(MoviePageMsg::SetMovie(movie)));
+                    let movie = self.model.movie.clone();
 
-                let provider = self.model.provider.clone();
+                    tasks::spawn(move || async move {
+                        let translation = translate(&url, &description).await;
+                        let _ = sender.send((movie, translation));
+                    });
+                }
+            }
+            MoviePageMsg::SetTranslation((movie, translation)) => {
+                // The translation of a previously shown movie may arrive late.
+                if movie.get_title() != self.model.movie.get_title() {
+                    return;
+                }
 
-                thread::spawn(move || {
-                    let rt = Runtime::new().expect("Could not create runtime");
-                    let information_movie = rt.block_on(provider.get_more_information(&movie));
-                    sender.send(information_movie).unwrap()
+                self.widgets.button_translate.set_sensitive(true);
+                self.widgets.label_translation.set_text(&match translation {
+                    Ok(text) => text,
+                    Err(error) => format!("Could not translate the description. {}", error),
                 });
-                self.show_all();
+                self.widgets.label_translation.set_visible(true);
+            }
+            MoviePageMsg::SetRatings((movie, ratings)) => {
+                // The ratings of a previously shown movie may arrive late.
+                if movie.get_title() != self.model.movie.get_title() {
+                    return;
+                }
+
+                let scores: Vec<String> = vec![
+                    ratings.get_imdb().map(|s| format!("IMDb {}", s)),
+                    ratings
+                        .get_rotten_tomatoes()
+                        .map(|s| format!("Rotten Tomatoes {}", s)),
+                    ratings
+                        .get_metacritic()
+                        .map(|s| format!("Metacritic {}", s)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+
+                self.widgets
+                    .label_movie_ratings
+                    .set_text(&scores.join(" · "));
+            }
+            MoviePageMsg::SetPoster((url, bytes)) => {
+                // The poster of a previously shown movie may arrive late.
+                if self.model.poster_url.as_ref() != Some(&url) {
+                    return;
+                }
+
+                let loader = PixbufLoader::new();
+                let pixbuf = loader
+                    .write(&bytes)
+                    .and_then(|_| loader.close())
+                    .ok()
+                    .and_then(|_| loader.get_pixbuf())
+                    .and_then(|p| {
+                        let height = p.get_height() * POSTER_WIDTH / p.get_width().max(1);
+                        p.scale_simple(POSTER_WIDTH, height, InterpType::Bilinear)
+                    });
+
+                if let Some(pixbuf) = pixbuf {
+                    self.widgets.image_poster.set_from_pixbuf(Some(&pixbuf));
+                    self.widgets.image_poster.set_visible(true);
+                }
+            }
+            MoviePageMsg::SetFilterMode(mode) => {
+                let verb = match mode {
+                    FilterMode::Block => "Block",
+                    FilterMode::Allow => "Allow",
+                };
+
+                self.widgets
+                    .button_channel_name
+                    .set_label(&format!("{} channel name", verb));
+                self.widgets
+                    .button_movie_title
+                    .set_label(&format!("{} movie title", verb));
+                self.widgets
+                    .button_movie_genre
+                    .set_label(&format!("{} movie genre", verb));
+                self.widgets
+                    .button_movie_division
+                    .set_label(&format!("{} movie division", verb));
+                self.widgets
+                    .button_movie_director
+                    .set_label(&format!("{} director", verb));
+                self.widgets
+                    .button_movie_actor
+                    .set_label(&format!("{} actor", verb));
             }
-            MoviePageMsg::SetMovie(movie) => {
-                self.model.movie = movie;
+            MoviePageMsg::SetReminded(reminded) => {
+                self.model.reminded = reminded;
                 self.show_all();
             }
-            MoviePageMsg::SetProvider(provider) => {
-                self.model.provider = provider;
+            MoviePageMsg::ToggleReminder => {
+                if self.model.reminded {
+                    self.model.win_stream.emit(WinMsg::RemoveReminder((
+                        self.model.channel.clone(),
+                        self.model.movie.clone(),
+                    )));
+                } else {
+                    let minutes = self.widgets.spin_reminder_minutes.get_value_as_int() as u32;
+                    if let Some(reminder) =
+                        Reminder::new(&self.model.channel, &self.model.movie, minutes)
+                    {
+                        self.model.win_stream.emit(WinMsg::AddReminder(reminder));
+                    }
+                }
             }
         }
     }
 }
 
-impl<T: 'static + Provider> Widget for MoviePage<T> {
+impl Widget for MoviePage {
     type Root = Box;
 
     fn root(&self) -> Self::Root {
@@ -167,24 +457,112 @@ impl<T: 'static + Provider> Widget for MoviePage<T> {
 
         header_bar.pack_end(&button_switch_stack);
 
+        let button_open_in_browser = Button::new();
+        button_open_in_browser.set_image(Some(&gtk::Image::from_icon_name(
+            Some("web-browser-symbolic"),
+            gtk::IconSize::Menu,
+        )));
+        button_open_in_browser.set_tooltip_text(Some("Open in browser"));
+        connect!(
+            relm,
+            button_open_in_browser,
+            connect_clicked(_),
+            MoviePageMsg::OpenInBrowser
+        );
+
+        header_bar.pack_end(&button_open_in_browser);
+
+        let button_previous = Button::from_icon_name(Some("go-up-symbolic"), gtk::IconSize::Menu);
+        button_previous.set_tooltip_text(Some("Previous movie"));
+        connect!(
+            relm,
+            button_previous,
+            connect_clicked(_),
+            MoviePageMsg::OpenRelative(-1)
+        );
+        let button_next = Button::from_icon_name(Some("go-down-symbolic"), gtk::IconSize::Menu);
+        button_next.set_tooltip_text(Some("Next movie"));
+        connect!(
+            relm,
+            button_next,
+            connect_clicked(_),
+            MoviePageMsg::OpenRelative(1)
+        );
+
+        header_bar.pack_start(&button_previous);
+        header_bar.pack_start(&button_next);
+
         let scrolled_window = ScrolledWindow::new::<Adjustment, Adjustment>(None, None);
         let scrolled_window_box = Box::new(Orientation::Vertical, 0);
 
         scrolled_window.add(&scrolled_window_box);
 
+        let image_poster = Image::new();
+        image_poster.set_no_show_all(true);
+
         let label_channel_name = Label::new(None);
         let label_movie_genre = Label::new(None);
         let label_movie_division = Label::new(None);
         let label_movie_year = Label::new(None);
+        let label_movie_start = Label::new(None);
+        let label_movie_age_rating = Label::new(None);
+        let label_movie_director = Label::new(None);
+        let label_movie_cast = Label::new(None);
+        let label_movie_ratings = Label::new(None);
+        let label_personal_rating = Label::new(None);
         let label_movie_description = Label::new(None);
 
         label_movie_description.set_line_wrap(true);
 
+        let button_translate = Button::with_label("Translate description");
+        button_translate.set_halign(gtk::Align::Center);
+        button_translate.set_no_show_all(true);
+        connect!(
+            relm,
+            button_translate,
+            connect_clicked(_),
+            MoviePageMsg::Translate
+        );
+
+        let label_translation = Label::new(None);
+        label_translation.set_line_wrap(true);
+        label_translation.set_no_show_all(true);
+        label_movie_cast.set_line_wrap(true);
+
+        scrolled_window_box.add(&image_poster);
         scrolled_window_box.add(&label_channel_name);
+        scrolled_window_box.add(&label_movie_start);
         scrolled_window_box.add(&label_movie_genre);
         scrolled_window_box.add(&label_movie_division);
         scrolled_window_box.add(&label_movie_year);
+        scrolled_window_box.add(&label_movie_age_rating);
+        scrolled_window_box.add(&label_movie_director);
+        scrolled_window_box.add(&label_movie_cast);
+        scrolled_window_box.add(&label_movie_ratings);
+        scrolled_window_box.add(&label_personal_rating);
         scrolled_window_box.add(&label_movie_description);
+        scrolled_window_box.add(&button_translate);
+        scrolled_window_box.add(&label_translation);
+
+        let box_reminder = Box::new(Orientation::Horizontal, 5);
+        box_reminder.set_halign(gtk::Align::Center);
+
+        let spin_reminder_minutes = SpinButton::with_range(1.0, 120.0, 5.0);
+        spin_reminder_minutes.set_value(DEFAULT_REMINDER_MINUTES);
+
+        let button_reminder = Button::new();
+        connect!(
+            relm,
+            button_reminder,
+            connect_clicked(_),
+            MoviePageMsg::ToggleReminder
+        );
+
+        box_reminder.add(&spin_reminder_minutes);
+        box_reminder.add(&Label::new(Some("minutes before")));
+        box_reminder.add(&button_reminder);
+
+        scrolled_window_box.add(&box_reminder);
 
         scrolled_window.set_hexpand(true);
         scrolled_window.set_vexpand(true);
@@ -193,6 +571,18 @@ impl<T: 'static + Provider> Widget for MoviePage<T> {
 
         let menu_box = gtk::Box::new(Orientation::Vertical, 0);
 
+        let button_broadcast = Button::new();
+        button_broadcast.set_label("Hide this broadcast");
+        button_broadcast.set_tooltip_text(Some(
+            "Hide only this broadcast until it ended, without blocking the title",
+        ));
+        connect!(
+            relm,
+            button_broadcast,
+            connect_clicked(_),
+            MoviePageMsg::Filter(FilterList::Broadcast)
+        );
+
         let button_channel_name = Button::new();
         button_channel_name.set_label("Block channel name");
         connect!(
@@ -229,11 +619,47 @@ impl<T: 'static + Provider> Widget for MoviePage<T> {
             MoviePageMsg::Filter(FilterList::MovieDivision)
         );
 
+        let check_temporary_filter =
+            CheckButton::with_label(&format!("Only for {} days", TEMPORARY_FILTER_DAYS));
+        check_temporary_filter.set_tooltip_text(Some(
+            "The filters added below are removed again after a week",
+        ));
+
+        menu_box.add(&button_broadcast);
+        menu_box.add(&check_temporary_filter);
         menu_box.add(&button_channel_name);
         menu_box.add(&button_movie_title);
         menu_box.add(&button_movie_genre);
         menu_box.add(&button_movie_division);
 
+        let button_movie_director = Button::new();
+        button_movie_director.set_label("Block director");
+        connect!(
+            relm,
+            button_movie_director,
+            connect_clicked(_),
+            MoviePageMsg::Filter(FilterList::MovieDirector)
+        );
+
+        let box_movie_actor = Box::new(Orientation::Horizontal, 0);
+        let combo_movie_actor = ComboBoxText::new();
+        combo_movie_actor.set_hexpand(true);
+
+        let button_movie_actor = Button::new();
+        button_movie_actor.set_label("Block actor");
+        connect!(
+            relm,
+            button_movie_actor,
+            connect_clicked(_),
+            MoviePageMsg::Filter(FilterList::MovieActor)
+        );
+
+        box_movie_actor.add(&combo_movie_actor);
+        box_movie_actor.add(&button_movie_actor);
+
+        menu_box.add(&button_movie_director);
+        menu_box.add(&box_movie_actor);
+
         let stack = relm::create_component::<SlidingStack<Box, ScrolledWindow>>((
             menu_box,
             scrolled_window.clone(),
@@ -247,11 +673,35 @@ impl<T: 'static + Provider> Widget for MoviePage<T> {
         let widgets = MoviePageWidgets {
             root,
             header_bar,
+            button_previous,
+            button_next,
+            button_open_in_browser,
+            image_poster,
             label_channel_name,
             label_movie_genre,
             label_movie_division,
             label_movie_year,
+            label_movie_start,
+            label_movie_age_rating,
+            label_movie_director,
+            label_movie_cast,
+            label_movie_ratings,
+            label_personal_rating,
             label_movie_description,
+            button_translate,
+            label_translation,
+            box_reminder,
+            spin_reminder_minutes,
+            button_reminder,
+            button_broadcast,
+            check_temporary_filter,
+            button_channel_name,
+            button_movie_title,
+            button_movie_genre,
+            button_movie_division,
+            button_movie_director,
+            combo_movie_actor,
+            button_movie_actor,
         };
 
         let components = MoviePageComponents { stack };
@@ -264,8 +714,55 @@ impl<T: 'static + Provider> Widget for MoviePage<T> {
     }
 }
 
-impl<T: 'static + Provider> MoviePage<T> {
+impl MoviePage {
+    /// Look up the external ratings of the movie without blocking the UI if a API key is set.
+    fn load_ratings(&mut self) {
+        self.widgets.label_movie_ratings.set_text("");
+
+        if let Some(api_key) = self.model.omdb_api_key.clone() {
+            let stream = self.model.relm.stream().clone();
+
+            let (_channel, sender) =
+                relm::Channel::new(move |ratings| stream.emit(MoviePageMsg::SetRatings(ratings)));
+
+            let movie = self.model.movie.clone();
+
+            tasks::spawn(move || async move {
+                if let Ok(ratings) = get_ratings(&api_key, &movie).await {
+                    let _ = sender.send((movie, ratings));
+                }
+            });
+        }
+    }
+
+    /// Load the poster of the movie without blocking the UI. Nothing is done if it is already shown or loading.
+    fn load_poster(&mut self) {
+        let poster_url = self.model.movie.get_poster_url();
+        if poster_url == self.model.poster_url {
+            return;
+        }
+
+        self.model.poster_url = poster_url.clone();
+        self.widgets.image_poster.set_visible(false);
+
+        if let Some(url) = poster_url {
+            let stream = self.model.relm.stream().clone();
+
+            let (_channel, sender) =
+                relm::Channel::new(move |poster| stream.emit(MoviePageMsg::SetPoster(poster)));
+
+            tasks::spawn(move || async move {
+                if let Ok(bytes) = get_poster(&url).await {
+                    let _ = sender.send((url, bytes));
+                }
+            });
+        }
+    }
+
     fn show_all(&self) {
+        self.widgets
+            .button_open_in_browser
+            .set_sensitive(self.model.url.is_some());
         self.widgets
             .header_bar
             .set_title(Some(&self.model.movie.get_title()));
@@ -289,5 +786,91 @@ impl<T: 'static + Provider> MoviePage<T> {
                 .map(|v| v.to_string())
                 .unwrap_or("".to_string()),
         );
+        self.widgets.label_movie_start.set_text(
+            &self
+                .model
+                .movie
+                .get_start()
+                .map(|s| s.format("%H:%M").to_string())
+                .unwrap_or("".to_string()),
+        );
+        self.widgets.label_personal_rating.set_text(
+            &self
+                .model
+                .personal_ratings
+                .get(&self.model.movie)
+                .map(|r| format!("Your IMDb rating {}/10", r))
+                .unwrap_or("".to_string()),
+        );
+        self.widgets.label_movie_age_rating.set_text(
+            &self
+                .model
+                .movie
+                .get_age_rating()
+                .map(|r| format!("FSK {}", r))
+                .unwrap_or("".to_string()),
+        );
+        self.widgets.label_movie_director.set_text(
+            &self
+                .model
+                .movie
+                .get_director()
+                .map(|d| format!("Directed by {}", d))
+                .unwrap_or("".to_string()),
+        );
+
+        let cast = self.model.movie.get_cast();
+        if cast.is_empty() {
+            self.widgets.label_movie_cast.set_text("");
+        } else {
+            self.widgets
+                .label_movie_cast
+                .set_text(&format!("With {}", cast.join(", ")));
+        }
+
+        self.widgets.combo_movie_actor.remove_all();
+        for actor in &cast {
+            self.widgets.combo_movie_actor.append_text(actor);
+        }
+        self.widgets.combo_movie_actor.set_active(Some(0));
+
+        self.widgets
+            .button_movie_director
+            .set_sensitive(self.model.movie.get_director().is_some());
+        self.widgets
+            .button_movie_actor
+            .set_sensitive(!cast.is_empty());
+        self.widgets
+            .button_broadcast
+            .set_sensitive(self.model.movie.get_start().is_some());
+
+        self.widgets.button_translate.set_visible(
+            self.model.translation_url.is_some() && self.model.movie.get_description().is_some(),
+        );
+
+        self.widgets
+            .box_reminder
+            .set_visible(self.model.movie.get_start().is_some());
+        self.widgets
+            .button_reminder
+            .set_label(if self.model.reminded {
+                "Remove reminder"
+            } else {
+                "Remind me"
+            });
     }
 }
+
+/// The approximate size of the information about the movie in memory in bytes, mostly the description and the cast.
+fn information_size(movie: &Movie) -> usize {
+    let texts = [
+        Some(movie.get_title()),
+        movie.get_genre(),
+        movie.get_division(),
+        movie.get_director(),
+        movie.get_poster_url(),
+        movie.get_description(),
+    ];
+    texts.iter().flatten().map(|t| t.len()).sum::<usize>()
+        + movie.get_cast().iter().map(|c| c.len()).sum::<usize>()
+}
@@ -0,0 +1,165 @@
+use crate::Error;
+
+use gtk::prelude::*;
+use gtk::{Align, Box, Button, IconSize, Image, Justification, Label, Orientation};
+use relm::{connect, Relm, Update, Widget};
+use relm_derive::Msg;
+
+/// Messages for the placeholder.
+#[derive(Msg)]
+pub enum PlaceholderMsg {
+    /// Explain the error the program could not be loaded with, or that no broadcast is shown without an error.
+    Set(Option<Error>),
+    /// The retry button was clicked. Should be handled by the parent.
+    Retry,
+    /// The button to show all broadcasts was clicked. Should be handled by the parent.
+    ShowAll,
+    /// The button to adjust the filters was clicked. Should be handled by the parent.
+    AdjustFilters,
+}
+
+/// The placeholder is shown instead of an empty list, explaining why it is empty and offering a way out.
+pub struct Placeholder {
+    root: Box,
+    image: Image,
+    label_title: Label,
+    label_explanation: Label,
+    button_retry: Button,
+    button_show_all: Button,
+    button_filters: Button,
+}
+
+impl Update for Placeholder {
+    type Model = ();
+    type ModelParam = ();
+    type Msg = PlaceholderMsg;
+
+    fn model(_: &Relm<Self>, _: Self::ModelParam) -> Self::Model {}
+
+    fn update(&mut self, event: PlaceholderMsg) {
+        match event {
+            PlaceholderMsg::Set(error) => {
+                let (icon, title, explanation) = match &error {
+                    Some(Error::Networking) => (
+                        "network-offline-symbolic",
+                        "You are offline",
+                        "The program could not be loaded. Check your internet connection and try again."
+                            .to_string(),
+                    ),
+                    Some(Error::ParsingWebsite) => (
+                        "dialog-warning-symbolic",
+                        "The program could not be read",
+                        "The website of the provider may have changed. Try again later or look for an update."
+                            .to_string(),
+                    ),
+                    Some(error) => (
+                        "dialog-error-symbolic",
+                        "The program could not be loaded",
+                        error.to_string(),
+                    ),
+                    None => (
+                        "edit-find-symbolic",
+                        "Nothing found",
+                        "No broadcast matches the filters, the search or the shown group.".to_string(),
+                    ),
+                };
+
+                self.image.set_from_icon_name(Some(icon), IconSize::Dialog);
+                self.label_title
+                    .set_markup(&format!("<big><b>{}</b></big>", title));
+                self.label_explanation.set_text(&explanation);
+                self.button_retry.set_visible(error.is_some());
+                self.button_show_all.set_visible(error.is_none());
+                self.button_filters.set_visible(error.is_none());
+                self.root.show();
+            }
+            PlaceholderMsg::Retry | PlaceholderMsg::ShowAll | PlaceholderMsg::AdjustFilters => {}
+        }
+    }
+}
+
+impl Widget for Placeholder {
+    type Root = Box;
+
+    fn root(&self) -> Self::Root {
+        self.root.clone()
+    }
+
+    fn view(relm: &Relm<Self>, _model: Self::Model) -> Self {
+        let root = Box::new(Orientation::Vertical, 10);
+        root.set_valign(Align::Center);
+        root.set_margin_start(20);
+        root.set_margin_end(20);
+        root.set_margin_top(40);
+        root.set_margin_bottom(40);
+
+        let image = Image::new();
+        image.set_pixel_size(64);
+        image.get_style_context().add_class("dim-label");
+
+        let label_title = Label::new(None);
+        label_title.set_line_wrap(true);
+        label_title.set_justify(Justification::Center);
+
+        let label_explanation = Label::new(None);
+        label_explanation.set_line_wrap(true);
+        label_explanation.set_justify(Justification::Center);
+
+        let button_box = Box::new(Orientation::Horizontal, 5);
+        button_box.set_halign(Align::Center);
+
+        let button_retry = Button::with_label("Retry");
+        button_retry
+            .get_style_context()
+            .add_class("suggested-action");
+        connect!(
+            relm,
+            button_retry,
+            connect_clicked(_),
+            PlaceholderMsg::Retry
+        );
+
+        let button_show_all = Button::with_label("Show everything");
+        button_show_all.set_tooltip_text(Some(
+            "Clear the search, show all channels and recent reruns",
+        ));
+        connect!(
+            relm,
+            button_show_all,
+            connect_clicked(_),
+            PlaceholderMsg::ShowAll
+        );
+
+        let button_filters = Button::with_label("Adjust filters");
+        connect!(
+            relm,
+            button_filters,
+            connect_clicked(_),
+            PlaceholderMsg::AdjustFilters
+        );
+
+        button_box.add(&button_retry);
+        button_box.add(&button_show_all);
+        button_box.add(&button_filters);
+
+        root.add(&image);
+        root.add(&label_title);
+        root.add(&label_explanation);
+        root.add(&button_box);
+
+        // Hidden until it is known why the list is empty, e.g. not while loading the program the first time.
+        root.show_all();
+        root.hide();
+        root.set_no_show_all(true);
+
+        Placeholder {
+            root,
+            image,
+            label_title,
+            label_explanation,
+            button_retry,
+            button_show_all,
+            button_filters,
+        }
+    }
+}
@@ -0,0 +1,562 @@
+use crate::model::{encryption, Settings, StartedBroadcasts, TimeSlot};
+
+use std::convert::TryFrom;
+use std::path::PathBuf;
+
+use gtk::prelude::*;
+use gtk::{
+    Adjustment, Box, Button, ButtonsType, CheckButton, ComboBoxText, DialogFlags, Entry, Label,
+    MessageDialog, MessageType, Orientation, PolicyType, ResponseType, ScrolledWindow, SpinButton,
+    Window, WindowType,
+};
+use libhandy::{HeaderBar, HeaderBarExt};
+use relm::{connect, Relm, Update, Widget};
+use relm_derive::Msg;
+
+/// The providers that can be selected, with their id and label.
+const PROVIDERS: &[(&str, &str)] = &[
+    ("tvspielfilm", "TV Spielfilm"),
+    ("tvmovie", "TV Movie"),
+    ("tvtoday", "TV Today"),
+    ("zattoo", "Zattoo"),
+    ("tvheadend", "tvheadend"),
+    ("mythtv", "MythTV"),
+    ("dvb", "DVB antenna"),
+];
+
+/// The stored data that can be cleared in the preferences.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClearData {
+    /// The broadcasts of the last days, used to mark reruns.
+    History,
+    /// The cached channel icons and posters.
+    Cache,
+    Filters,
+    /// The settings and the provider and time slot stored in GSettings.
+    Settings,
+    /// All of the above and all other user data, e.g. groups, presets and reminders.
+    Everything,
+}
+
+impl ClearData {
+    /// The label of the button clearing the data.
+    fn label(&self) -> &'static str {
+        match self {
+            ClearData::History => "Clear history",
+            ClearData::Cache => "Clear cache",
+            ClearData::Filters => "Reset filters",
+            ClearData::Settings => "Reset all settings",
+            ClearData::Everything => "Factory reset",
+        }
+    }
+
+    /// The explanation asking to confirm clearing the data.
+    fn explanation(&self) -> &'static str {
+        match self {
+            ClearData::History => {
+                "The broadcasts of the last days are forgotten, reruns are not marked until they are broadcasted again."
+            }
+            ClearData::Cache => "The channel icons and posters are downloaded again.",
+            ClearData::Filters => "All filters are removed, also on synced devices.",
+            ClearData::Settings => {
+                "All settings are reset to their defaults, including the OMDb API key and the data directory. The application is closed to apply them."
+            }
+            ClearData::Everything => {
+                "All filters, groups, presets, reminders, the history, the cache and the settings are removed and the encryption is disabled. The application is closed afterwards."
+            }
+        }
+    }
+}
+
+/// Messages for the preferences.
+#[derive(Msg)]
+pub enum PreferencesMsg {
+    /// Save the entered settings.
+    Save,
+    /// The settings were changed. Should be handled by the parent. Boxed as the settings are large.
+    Changed(std::boxed::Box<Settings>),
+    /// Encrypt the user data with the passphrase or decrypt it with `None`. Should be handled by the parent.
+    SetEncryption(Option<String>),
+    /// Ask to confirm clearing the data.
+    Clear(ClearData),
+    /// Clearing the data was confirmed. Should be handled by the parent.
+    Cleared(ClearData),
+}
+
+/// The model for the preferences containing the settings.
+pub struct PreferencesModel {
+    settings: Settings,
+    relm: Relm<Preferences>,
+}
+
+/// The preferences are a window to change the settings of the application.
+pub struct Preferences {
+    model: PreferencesModel,
+    window: Window,
+    combo_provider: ComboBoxText,
+    entry_provider_login: Entry,
+    entry_provider_password: Entry,
+    entry_provider_server: Entry,
+    combo_time_slot: ComboBoxText,
+    spin_refresh_interval: SpinButton,
+    check_auto_refresh: CheckButton,
+    combo_started_broadcasts: ComboBoxText,
+    spin_started_minutes: SpinButton,
+    spin_transition_duration: SpinButton,
+    check_double_click: CheckButton,
+    spin_disk_cache_limit: SpinButton,
+    spin_icon_cache_limit: SpinButton,
+    spin_description_cache_limit: SpinButton,
+    check_inhibit_suspend: CheckButton,
+    spin_min_personal_rating: SpinButton,
+    check_encrypt: CheckButton,
+    entry_passphrase: Entry,
+    entry_data_dir: Entry,
+    entry_translation_url: Entry,
+    entry_omdb_api_key: Entry,
+    entry_widget_path: Entry,
+    entry_health_report_url: Entry,
+}
+
+impl Update for Preferences {
+    type Model = PreferencesModel;
+    type ModelParam = Settings;
+    type Msg = PreferencesMsg;
+
+    fn model(relm: &Relm<Self>, settings: Self::ModelParam) -> Self::Model {
+        PreferencesModel {
+            settings,
+            relm: relm.clone(),
+        }
+    }
+
+    fn update(&mut self, event: PreferencesMsg) {
+        match event {
+            PreferencesMsg::Save => {
+                let settings = &mut self.model.settings;
+
+                if let Some(provider) = self.combo_provider.get_active_id() {
+                    settings.set_provider(&provider);
+                }
+                let provider_login = self.entry_provider_login.get_text().trim().to_string();
+                settings.set_provider_login(Some(provider_login).filter(|l| !l.is_empty()));
+                let provider_password = self.entry_provider_password.get_text().to_string();
+                settings.set_provider_password(Some(provider_password).filter(|p| !p.is_empty()));
+                let provider_server = self.entry_provider_server.get_text().trim().to_string();
+                settings.set_provider_server(Some(provider_server).filter(|s| !s.is_empty()));
+                if let Some(id) = self.combo_time_slot.get_active_id() {
+                    if let Ok(time_slot) = TimeSlot::try_from(&id[..]) {
+                        settings.set_time_slot(time_slot);
+                    }
+                }
+                settings.set_refresh_interval(self.spin_refresh_interval.get_value_as_int() as u32);
+                settings.set_auto_refresh(self.check_auto_refresh.get_active());
+                if let Some(id) = self.combo_started_broadcasts.get_active_id() {
+                    if let Ok(started_broadcasts) = StartedBroadcasts::try_from(&id[..]) {
+                        settings.set_started_broadcasts(started_broadcasts);
+                    }
+                }
+                settings.set_started_minutes(self.spin_started_minutes.get_value_as_int() as u32);
+                settings.set_transition_duration(
+                    self.spin_transition_duration.get_value_as_int() as u32
+                );
+                settings.set_double_click(self.check_double_click.get_active());
+                settings.set_disk_cache_limit(self.spin_disk_cache_limit.get_value_as_int() as u32);
+                settings.set_icon_cache_limit(self.spin_icon_cache_limit.get_value_as_int() as u32);
+                settings.set_description_cache_limit(
+                    self.spin_description_cache_limit.get_value_as_int() as u32,
+                );
+                settings.set_inhibit_suspend(self.check_inhibit_suspend.get_active());
+                settings.set_min_personal_rating(
+                    self.spin_min_personal_rating.get_value_as_int() as u32
+                );
+
+                let translation_url = self.entry_translation_url.get_text().trim().to_string();
+                settings.set_translation_url(Some(translation_url).filter(|u| !u.is_empty()));
+
+                let omdb_api_key = self.entry_omdb_api_key.get_text().trim().to_string();
+                settings.set_omdb_api_key(Some(omdb_api_key).filter(|k| !k.is_empty()));
+
+                let widget_path = self.entry_widget_path.get_text().trim().to_string();
+                settings.set_widget_path(if widget_path.is_empty() {
+                    None
+                } else {
+                    Some(PathBuf::from(widget_path))
+                });
+
+                let health_report_url = self.entry_health_report_url.get_text().trim().to_string();
+                settings.set_health_report_url(Some(health_report_url).filter(|u| !u.is_empty()));
+
+                let data_dir = self.entry_data_dir.get_text().trim().to_string();
+                settings.set_data_dir(if data_dir.is_empty() {
+                    None
+                } else {
+                    Some(PathBuf::from(data_dir))
+                });
+
+                self.model
+                    .relm
+                    .stream()
+                    .emit(PreferencesMsg::Changed(std::boxed::Box::new(
+                        self.model.settings.clone(),
+                    )));
+
+                // A passphrase is needed to encrypt the data, entering one while encrypted changes it.
+                let passphrase = self.entry_passphrase.get_text().to_string();
+                if self.check_encrypt.get_active() && !passphrase.is_empty() {
+                    self.model
+                        .relm
+                        .stream()
+                        .emit(PreferencesMsg::SetEncryption(Some(passphrase)));
+                } else if !self.check_encrypt.get_active() && encryption::is_enabled() {
+                    self.model
+                        .relm
+                        .stream()
+                        .emit(PreferencesMsg::SetEncryption(None));
+                }
+                self.window.close();
+            }
+            PreferencesMsg::Clear(data) => {
+                if self.confirm(data) {
+                    self.model.relm.stream().emit(PreferencesMsg::Cleared(data));
+                }
+            }
+            PreferencesMsg::Changed(_)
+            | PreferencesMsg::SetEncryption(_)
+            | PreferencesMsg::Cleared(_) => {}
+        }
+    }
+}
+
+impl Widget for Preferences {
+    type Root = Window;
+
+    fn root(&self) -> Self::Root {
+        self.window.clone()
+    }
+
+    fn view(relm: &Relm<Self>, model: Self::Model) -> Self {
+        let window = Window::new(WindowType::Toplevel);
+        window.set_default_size(360, 640);
+        window.set_modal(true);
+
+        let header_bar = HeaderBar::new();
+        header_bar.set_title(Some("Preferences"));
+        header_bar.set_show_close_button(true);
+
+        let button_save = Button::with_label("Save");
+        button_save
+            .get_style_context()
+            .add_class("suggested-action");
+        connect!(relm, button_save, connect_clicked(_), PreferencesMsg::Save);
+        header_bar.pack_end(&button_save);
+
+        window.set_titlebar(Some(&header_bar));
+
+        let root = Box::new(Orientation::Vertical, 5);
+        root.set_margin_start(10);
+        root.set_margin_end(10);
+        root.set_margin_top(10);
+        root.set_margin_bottom(10);
+
+        let combo_provider = ComboBoxText::new();
+        for (id, label) in PROVIDERS {
+            combo_provider.append(Some(id), label);
+        }
+        combo_provider.set_active_id(Some(&model.settings.get_provider()));
+
+        let label_provider_hint = Label::new(Some("A new provider is used after a restart."));
+        label_provider_hint.set_sensitive(false);
+        label_provider_hint.set_line_wrap(true);
+
+        let entry_provider_login = Entry::new();
+        entry_provider_login.set_placeholder_text(Some("Login"));
+        entry_provider_login.set_text(&model.settings.get_provider_login().unwrap_or_default());
+
+        let entry_provider_password = Entry::new();
+        entry_provider_password.set_visibility(false);
+        entry_provider_password.set_placeholder_text(Some("Password"));
+        entry_provider_password
+            .set_text(&model.settings.get_provider_password().unwrap_or_default());
+
+        let label_provider_account_hint = Label::new(Some(
+            "Only needed by providers with an account like Zattoo or a protected server. The password is stored in the data directory, only readable by you and encrypted with the data.",
+        ));
+        label_provider_account_hint.set_sensitive(false);
+        label_provider_account_hint.set_line_wrap(true);
+
+        let entry_provider_server = Entry::new();
+        entry_provider_server.set_placeholder_text(Some("Default of the provider"));
+        entry_provider_server.set_text(&model.settings.get_provider_server().unwrap_or_default());
+
+        let label_provider_server_hint = Label::new(Some(
+            "Only needed by providers on a server of your own, like tvheadend on http://localhost:9981 or a MythTV backend on http://localhost:6544 by default.",
+        ));
+        label_provider_server_hint.set_sensitive(false);
+        label_provider_server_hint.set_line_wrap(true);
+
+        let combo_time_slot = ComboBoxText::new();
+        for time_slot in TimeSlot::ALL.iter() {
+            combo_time_slot.append(Some(&time_slot.to_string()), time_slot.label());
+        }
+        combo_time_slot.set_active_id(Some(&model.settings.get_time_slot().to_string()));
+
+        let spin_refresh_interval = SpinButton::with_range(5.0, 240.0, 5.0);
+        spin_refresh_interval.set_value(model.settings.get_refresh_interval() as f64);
+
+        let check_auto_refresh = CheckButton::with_label("Refresh the program in the background");
+        check_auto_refresh.set_active(model.settings.get_auto_refresh());
+
+        let combo_started_broadcasts = ComboBoxText::new();
+        for started_broadcasts in StartedBroadcasts::ALL.iter() {
+            combo_started_broadcasts.append(
+                Some(&started_broadcasts.to_string()),
+                started_broadcasts.label(),
+            );
+        }
+        combo_started_broadcasts
+            .set_active_id(Some(&model.settings.get_started_broadcasts().to_string()));
+
+        let spin_started_minutes = SpinButton::with_range(0.0, 120.0, 5.0);
+        spin_started_minutes.set_value(model.settings.get_started_minutes() as f64);
+
+        let spin_transition_duration = SpinButton::with_range(0.0, 1000.0, 50.0);
+        spin_transition_duration.set_value(model.settings.get_transition_duration() as f64);
+
+        let check_double_click =
+            CheckButton::with_label("Open movies with a double click, a single click selects them");
+        check_double_click.set_active(model.settings.get_double_click());
+
+        let spin_disk_cache_limit = SpinButton::with_range(1.0, 1000.0, 10.0);
+        spin_disk_cache_limit.set_value(model.settings.get_disk_cache_limit() as f64);
+
+        let spin_icon_cache_limit = SpinButton::with_range(1.0, 100.0, 1.0);
+        spin_icon_cache_limit.set_value(model.settings.get_icon_cache_limit() as f64);
+
+        let spin_description_cache_limit = SpinButton::with_range(1.0, 100.0, 1.0);
+        spin_description_cache_limit.set_value(model.settings.get_description_cache_limit() as f64);
+
+        let spin_min_personal_rating = SpinButton::with_range(0.0, 10.0, 1.0);
+        spin_min_personal_rating.set_value(model.settings.get_min_personal_rating() as f64);
+
+        let check_inhibit_suspend =
+            CheckButton::with_label("Keep the device awake shortly before a reminder");
+        check_inhibit_suspend.set_active(model.settings.get_inhibit_suspend());
+
+        let check_encrypt = CheckButton::with_label("Encrypt the data with a passphrase");
+        check_encrypt.set_active(encryption::is_enabled());
+
+        let entry_passphrase = Entry::new();
+        entry_passphrase.set_visibility(false);
+        entry_passphrase.set_placeholder_text(Some(if encryption::is_enabled() {
+            "Unchanged"
+        } else {
+            "Passphrase"
+        }));
+
+        let label_encrypt_hint = Label::new(Some(
+            "The filters, channels and history are encrypted. The passphrase is asked for on startup and can not be recovered.",
+        ));
+        label_encrypt_hint.set_sensitive(false);
+        label_encrypt_hint.set_line_wrap(true);
+
+        let entry_data_dir = Entry::new();
+        entry_data_dir.set_placeholder_text(Some("Default"));
+        entry_data_dir.set_text(
+            &model
+                .settings
+                .get_data_dir()
+                .map(|d| d.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        );
+
+        let entry_translation_url = Entry::new();
+        entry_translation_url.set_placeholder_text(Some("e.g. https://libretranslate.com"));
+        entry_translation_url.set_text(&model.settings.get_translation_url().unwrap_or_default());
+
+        let entry_omdb_api_key = Entry::new();
+        entry_omdb_api_key.set_visibility(false);
+        entry_omdb_api_key.set_placeholder_text(Some("Not looked up"));
+        entry_omdb_api_key.set_text(&model.settings.get_omdb_api_key().unwrap_or_default());
+
+        let label_omdb_api_key_hint = Label::new(Some(
+            "The IMDb rating of the movies is looked up with this key from omdbapi.com. It is stored with the password of the provider.",
+        ));
+        label_omdb_api_key_hint.set_sensitive(false);
+        label_omdb_api_key_hint.set_line_wrap(true);
+
+        let entry_widget_path = Entry::new();
+        entry_widget_path.set_placeholder_text(Some("Not written"));
+        entry_widget_path.set_text(
+            &model
+                .settings
+                .get_widget_path()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        );
+
+        let label_widget_path_hint = Label::new(Some(
+            "Tonight's picks are written to this file on each reload for desktop widgets like Conky or Waybar, as JSON if it ends with .json and as plain text otherwise.",
+        ));
+        label_widget_path_hint.set_sensitive(false);
+        label_widget_path_hint.set_line_wrap(true);
+
+        let entry_health_report_url = Entry::new();
+        entry_health_report_url.set_placeholder_text(Some("Never sent"));
+        entry_health_report_url
+            .set_text(&model.settings.get_health_report_url().unwrap_or_default());
+
+        let label_health_report_url_hint = Label::new(Some(
+            "When the program can not be read, a report with only the provider, the part of its website that failed and the version of the app can be sent to this address.",
+        ));
+        label_health_report_url_hint.set_sensitive(false);
+        label_health_report_url_hint.set_line_wrap(true);
+
+        let label_data_dir_hint = Label::new(Some("A new data directory is used after a restart."));
+        label_data_dir_hint.set_sensitive(false);
+        label_data_dir_hint.set_line_wrap(true);
+
+        root.add(&preference_label("Provider"));
+        root.add(&combo_provider);
+        root.add(&label_provider_hint);
+        root.add(&preference_label("Account at the provider"));
+        root.add(&entry_provider_login);
+        root.add(&entry_provider_password);
+        root.add(&label_provider_account_hint);
+        root.add(&preference_label("Server of the provider"));
+        root.add(&entry_provider_server);
+        root.add(&label_provider_server_hint);
+        root.add(&preference_label("Time slot"));
+        root.add(&combo_time_slot);
+        root.add(&preference_label("Refresh interval in minutes"));
+        root.add(&spin_refresh_interval);
+        root.add(&check_auto_refresh);
+        root.add(&preference_label("Movies that started some time ago"));
+        root.add(&combo_started_broadcasts);
+        root.add(&preference_label("Minutes after the start"));
+        root.add(&spin_started_minutes);
+        root.add(&preference_label(
+            "Animation duration in milliseconds, 0 disables animations",
+        ));
+        root.add(&spin_transition_duration);
+        root.add(&check_double_click);
+        root.add(&check_inhibit_suspend);
+        root.add(&preference_label(
+            "Hide movies I rated below this on IMDb, 0 shows all",
+        ));
+        root.add(&spin_min_personal_rating);
+        root.add(&preference_label("LibreTranslate server for descriptions"));
+        root.add(&entry_translation_url);
+        root.add(&preference_label("OMDb API key for ratings"));
+        root.add(&entry_omdb_api_key);
+        root.add(&label_omdb_api_key_hint);
+        root.add(&preference_label("File for desktop widgets"));
+        root.add(&entry_widget_path);
+        root.add(&label_widget_path_hint);
+        root.add(&preference_label(
+            "Address for reports about unreadable programs",
+        ));
+        root.add(&entry_health_report_url);
+        root.add(&label_health_report_url_hint);
+        root.add(&check_encrypt);
+        root.add(&entry_passphrase);
+        root.add(&label_encrypt_hint);
+        root.add(&preference_label("Data directory"));
+        root.add(&entry_data_dir);
+        root.add(&label_data_dir_hint);
+        root.add(&preference_label("Stored data"));
+        root.add(&preference_label("Downloaded icons in MiB"));
+        root.add(&spin_disk_cache_limit);
+        root.add(&preference_label("Decoded channel icons in memory in MiB"));
+        root.add(&spin_icon_cache_limit);
+        root.add(&preference_label("Descriptions in memory in MiB"));
+        root.add(&spin_description_cache_limit);
+        for &data in [
+            ClearData::History,
+            ClearData::Cache,
+            ClearData::Filters,
+            ClearData::Settings,
+            ClearData::Everything,
+        ]
+        .iter()
+        {
+            let button = Button::with_label(data.label());
+            if data == ClearData::Everything {
+                button.get_style_context().add_class("destructive-action");
+            }
+            connect!(
+                relm,
+                button,
+                connect_clicked(_),
+                PreferencesMsg::Clear(data)
+            );
+            root.add(&button);
+        }
+
+        // The preferences are higher than the screen of a phone.
+        let scrolled_window = ScrolledWindow::new::<Adjustment, Adjustment>(None, None);
+        scrolled_window.set_policy(PolicyType::Never, PolicyType::Automatic);
+        scrolled_window.add(&root);
+
+        window.add(&scrolled_window);
+        window.show_all();
+
+        Preferences {
+            model,
+            window,
+            combo_provider,
+            entry_provider_login,
+            entry_provider_password,
+            entry_provider_server,
+            combo_time_slot,
+            spin_refresh_interval,
+            spin_transition_duration,
+            check_double_click,
+            spin_disk_cache_limit,
+            check_auto_refresh,
+            combo_started_broadcasts,
+            spin_started_minutes,
+            spin_icon_cache_limit,
+            spin_description_cache_limit,
+            check_inhibit_suspend,
+            spin_min_personal_rating,
+            check_encrypt,
+            entry_passphrase,
+            entry_data_dir,
+            entry_translation_url,
+            entry_omdb_api_key,
+            entry_widget_path,
+            entry_health_report_url,
+        }
+    }
+}
+
+impl Preferences {
+    /// Ask the user to confirm clearing the data.
+    fn confirm(&self, data: ClearData) -> bool {
+        let dialog = MessageDialog::new(
+            Some(&self.window),
+            DialogFlags::MODAL,
+            MessageType::Question,
+            ButtonsType::None,
+            &format!("{}?", data.label()),
+        );
+        dialog.set_property_secondary_text(Some(data.explanation()));
+        dialog.add_button("Cancel", ResponseType::Cancel);
+        dialog
+            .add_button(data.label(), ResponseType::Accept)
+            .get_style_context()
+            .add_class("destructive-action");
+
+        let response = dialog.run();
+        dialog.close();
+        response == ResponseType::Accept
+    }
+}
+
+/// The label of a preference.
+fn preference_label(text: &str) -> Label {
+    let label = Label::new(Some(text));
+    label.set_xalign(0.0);
+    label
+}
@@ -0,0 +1,52 @@
+use crate::model::remote::qr_code;
+use crate::Error;
+
+use gdk_pixbuf::{Colorspace, Pixbuf};
+use glib::Bytes;
+use gtk::prelude::*;
+use gtk::{Dialog, DialogFlags, Image, Label, ResponseType, Window};
+
+/// Show the QR code of the URL of the remote control, so a phone in the same network can open it by scanning it.
+pub fn show_remote_dialog(parent: Option<&Window>, url: &str) -> Result<(), Error> {
+    let code = qr_code(url)?;
+    let pixbuf = Pixbuf::from_bytes(
+        &Bytes::from(code.as_raw()),
+        Colorspace::Rgb,
+        true,
+        8,
+        code.width() as i32,
+        code.height() as i32,
+        4 * code.width() as i32,
+    );
+
+    let dialog = Dialog::with_buttons(
+        Some("Remote control"),
+        parent,
+        DialogFlags::DESTROY_WITH_PARENT,
+        &[("Close", ResponseType::Close)],
+    );
+
+    let label = Label::new(Some(
+        "Scan the code with a phone in the same network to see the program on it, open the details of movies and pin them. The remote control works until the application is closed.",
+    ));
+    label.set_line_wrap(true);
+    label.set_max_width_chars(40);
+
+    let label_url = Label::new(Some(url));
+    label_url.set_selectable(true);
+    label_url.get_style_context().add_class("dim-label");
+
+    let content = dialog.get_content_area();
+    content.set_spacing(10);
+    content.set_margin_start(10);
+    content.set_margin_end(10);
+    content.set_margin_top(10);
+    content.add(&label);
+    content.add(&Image::from_pixbuf(Some(&pixbuf)));
+    content.add(&label_url);
+
+    dialog.connect_response(|dialog, _response| dialog.close());
+    dialog.show_all();
+
+    Ok(())
+}
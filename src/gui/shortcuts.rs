@@ -0,0 +1,96 @@
+use gio::prelude::*;
+use gio::{SimpleAction, SimpleActionGroup};
+use gtk::prelude::*;
+use gtk::{
+    AccelFlags, AccelGroup, ShortcutsGroupBuilder, ShortcutsSectionBuilder,
+    ShortcutsShortcutBuilder, ShortcutsWindow, ShortcutsWindowBuilder,
+};
+
+/// The name of the action group of the window.
+pub const ACTION_GROUP: &str = "win";
+
+/// The actions of the window with their accelerators and the description shown in the shortcuts window.
+pub const SHORTCUTS: &[(&str, &[&str], &str)] = &[
+    ("reload", &["F5", "<Primary>r"], "Reload the program"),
+    ("search", &["<Primary>f"], "Search for a movie"),
+    ("back", &["Escape", "<Alt>Left"], "Go back"),
+    (
+        "previous-movie",
+        &["Page_Up"],
+        "Open the previous movie of the list",
+    ),
+    (
+        "next-movie",
+        &["Page_Down"],
+        "Open the next movie of the list",
+    ),
+    (
+        "show-shortcuts",
+        &["<Primary>question"],
+        "Show the shortcuts",
+    ),
+    ("quit", &["<Primary>q"], "Quit"),
+];
+
+/// Create the actions of `SHORTCUTS` and the accelerator group activating them. The actions are returned with their
+/// name and have to be connected by the caller. The keys of disabled actions are passed on to the focused widget.
+pub fn create_actions() -> (
+    SimpleActionGroup,
+    AccelGroup,
+    Vec<(&'static str, SimpleAction)>,
+) {
+    let group = SimpleActionGroup::new();
+    let accel_group = AccelGroup::new();
+    let mut actions = vec![];
+
+    for (name, accels, _description) in SHORTCUTS {
+        let action = SimpleAction::new(name, None);
+        group.add_action(&action);
+
+        for accel in accels.iter() {
+            let (key, mods) = gtk::accelerator_parse(accel);
+            let action = action.clone();
+            accel_group.connect_accel_group(key, mods, AccelFlags::VISIBLE, move |_, _, _, _| {
+                if action.get_enabled() {
+                    action.activate(None);
+                }
+                action.get_enabled()
+            });
+        }
+
+        actions.push((*name, action));
+    }
+
+    (group, accel_group, actions)
+}
+
+/// Create the window describing the `SHORTCUTS`.
+pub fn shortcuts_window() -> ShortcutsWindow {
+    let window = ShortcutsWindowBuilder::new()
+        .modal(true)
+        .section_name("shortcuts")
+        .build();
+
+    let section = ShortcutsSectionBuilder::new()
+        .section_name("shortcuts")
+        .visible(true)
+        .build();
+    let group = ShortcutsGroupBuilder::new()
+        .title("General")
+        .visible(true)
+        .build();
+
+    for (_name, accels, description) in SHORTCUTS {
+        let shortcut = ShortcutsShortcutBuilder::new()
+            .accelerator(&accels.join(" "))
+            .title(description)
+            .visible(true)
+            .build();
+        group.add(&shortcut);
+    }
+
+    section.add(&group);
+    window.add(&section);
+
+    window
+}
@@ -11,6 +11,8 @@ pub enum SlidingStackMsg {
     Switch,
     /// Show the second page.
     ShowSecondPage,
+    /// Set the duration of the animation in milliseconds. Zero disables it.
+    SetTransitionDuration(u32),
 }
 
 /// The model for the slidin stack containing the two widgets.
@@ -37,18 +39,17 @@ impl<T1: IsA<Widget>, T2: IsA<Widget>> Update for SlidingStack<T1, T2> {
     fn update(&mut self, event: SlidingStackMsg) {
         match event {
             SlidingStackMsg::Switch => match self.stack.get_visible_child_name() {
-                Some(name) => {
-                    if name == "widget1" {
-                        self.stack.set_visible_child(&self.model.widget2);
-                    } else {
-                        self.stack.set_visible_child(&self.model.widget1);
-                    }
+                Some(name) if name == "widget1" => {
+                    self.stack.set_visible_child(&self.model.widget2);
                 }
-                None => {
+                _ => {
                     self.stack.set_visible_child(&self.model.widget1);
                 }
             },
             SlidingStackMsg::ShowSecondPage => self.stack.set_visible_child(&self.model.widget2),
+            SlidingStackMsg::SetTransitionDuration(duration) => {
+                self.stack.set_transition_duration(duration)
+            }
         }
     }
 }
@@ -0,0 +1,71 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use tokio::runtime::Runtime;
+
+/// How often running tasks check whether they are cancelled.
+const CANCEL_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Weather the application shuts down. Running tasks are cancelled and new ones are not started.
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+/// The number of running tasks.
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts a task as running as long as it is alive, also if it panics.
+struct RunningGuard;
+
+impl RunningGuard {
+    fn new() -> Self {
+        RUNNING.fetch_add(1, Ordering::SeqCst);
+        RunningGuard
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Run the future created by the task in a new thread without blocking the UI, e.g. to load data from the network.
+/// The future is dropped unfinished once all tasks are cancelled, so it should send its result at its end.
+pub fn spawn<F, Fut>(task: F)
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = ()>,
+{
+    if CANCELLED.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let guard = RunningGuard::new();
+    thread::spawn(move || {
+        let _guard = guard;
+        let rt = Runtime::new().expect("Could not create runtime");
+        rt.block_on(async {
+            tokio::select! {
+                _ = task() => {}
+                _ = cancelled() => {}
+            }
+        });
+    });
+}
+
+/// Cancel all running tasks and do not start new ones, e.g. when quitting.
+pub fn cancel_all() {
+    CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// Get the number of tasks still running.
+pub fn running() -> usize {
+    RUNNING.load(Ordering::SeqCst)
+}
+
+/// Wait until the tasks are cancelled.
+async fn cancelled() {
+    while !CANCELLED.load(Ordering::SeqCst) {
+        tokio::time::sleep(CANCEL_CHECK_INTERVAL).await;
+    }
+}
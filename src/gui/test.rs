@@ -0,0 +1,379 @@
+//! Tests driving the window with the mock provider. They need a display, e.g. `xvfb-run cargo test` or the broadway
+//! backend, and are skipped without one. All tests share one function, as GTK may only be used from one thread.
+
+use crate::gui::application::new_application;
+use crate::gui::{Win, WinMsg, APPLICATION_ID};
+use crate::model::providers::Mock;
+use crate::model::{
+    paths, ChannelAttribute, FilterType, MovieAttribute, ProgramFilter, Provider, ProviderHandle,
+};
+
+use std::cell::Cell;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use cairo::{Context, Format, ImageSurface};
+use gio::prelude::*;
+use gio::ApplicationFlags;
+use gtk::prelude::*;
+use gtk::{
+    Button, CheckButton, Container, Image, Label, ListBox, ListBoxRow, SearchEntry, Spinner,
+    ToggleButton, Widget,
+};
+use libhandy::prelude::*;
+use libhandy::Leaflet;
+
+/// How long to wait for the window to change before failing.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The number of broadcasts of the mock provider.
+const MOCK_BROADCASTS: usize = 7;
+
+/// Use empty user directories and GSettings kept in memory, so the tests neither read nor change the data and the
+/// configuration of the user. Must be called before any directory is looked up and GTK is initialized. Returns the
+/// directory, or `None` without a display.
+fn init() -> Option<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("tvtoday-gui-test-{}", std::process::id()));
+    for (variable, name) in [
+        ("XDG_DATA_HOME", "data"),
+        ("XDG_CONFIG_HOME", "config"),
+        ("XDG_CACHE_HOME", "cache"),
+    ]
+    .iter()
+    {
+        std::env::set_var(variable, dir.join(name));
+    }
+
+    std::env::set_var("GSETTINGS_BACKEND", "memory");
+    if let Some(schema_dir) = compile_schema(&dir) {
+        std::env::set_var("GSETTINGS_SCHEMA_DIR", schema_dir);
+    }
+
+    gtk::init().ok().map(|_| dir)
+}
+
+/// Compile the schema of the repository into the directory, so the tests use it instead of an installed one. Returns
+/// the directory of the compiled schema, or `None` if `glib-compile-schemas` is not available.
+fn compile_schema(dir: &Path) -> Option<PathBuf> {
+    let schema_dir = dir.join("schemas");
+    fs::create_dir_all(&schema_dir).ok()?;
+    let schema = format!("{}.gschema.xml", APPLICATION_ID);
+    fs::copy(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join(&schema),
+        schema_dir.join(&schema),
+    )
+    .ok()?;
+
+    Command::new("glib-compile-schemas")
+        .arg(&schema_dir)
+        .status()
+        .ok()
+        .filter(|status| status.success())
+        .map(|_| schema_dir)
+}
+
+/// Run the main loop until the condition holds. Panics after `TIMEOUT`.
+fn wait_until<F: Fn() -> bool>(what: &str, condition: F) {
+    let start = Instant::now();
+    while !condition() {
+        assert!(
+            start.elapsed() < TIMEOUT,
+            "Timed out waiting until {}",
+            what
+        );
+        if !gtk::main_iteration_do(false) {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+/// Run the main loop until no events are pending, e.g. the remaining rows were created.
+fn settle() {
+    while gtk::events_pending() {
+        gtk::main_iteration();
+    }
+}
+
+/// Find all widgets of the type in the widget and its children, depth first.
+fn find_all<W: IsA<Widget>>(widget: &Widget) -> Vec<W> {
+    let mut found = vec![];
+    if let Ok(w) = widget.clone().downcast::<W>() {
+        found.push(w);
+    }
+    if let Some(container) = widget.downcast_ref::<Container>() {
+        for child in container.get_children() {
+            found.extend(find_all::<W>(&child));
+        }
+    }
+    found
+}
+
+/// Get the texts of all labels in the widget.
+fn texts(widget: &Widget) -> Vec<String> {
+    find_all::<Label>(widget)
+        .iter()
+        .map(|l| l.get_text().to_string())
+        .collect()
+}
+
+/// Find the button with the label in the widget.
+fn button(widget: &Widget, label: &str) -> Button {
+    find_all::<Button>(widget)
+        .into_iter()
+        .find(|b| b.get_label().map(|l| l == label).unwrap_or(false))
+        .unwrap_or_else(|| panic!("No button '{}'", label))
+}
+
+/// Find the row of the list showing the text.
+fn row_with_text(listbox: &ListBox, text: &str) -> Option<ListBoxRow> {
+    find_all::<ListBoxRow>(listbox.upcast_ref())
+        .into_iter()
+        .find(|r| texts(r.upcast_ref()).iter().any(|t| t == text))
+}
+
+/// Whether the filter is stored in the filters of the user, as read on the next start.
+fn is_stored(filter: &FilterType) -> bool {
+    let mut log = ProgramFilter::open_log(paths::user_data_dir(), &paths::device_id());
+    ProgramFilter::read_from_log_or_migrate(
+        &mut log,
+        paths::user_data_file("filters.json"),
+        paths::user_data_file("filters.csv"),
+    )
+    .map(|f| f.contains(filter))
+    .unwrap_or(false)
+}
+
+/// Write a screenshot of the widget to `target/screenshots/{name}.png` if `TVTODAY_SCREENSHOTS` is set, e.g. for the
+/// README or to compare changes of the layout.
+fn screenshot(widget: &Widget, name: &str) {
+    if std::env::var_os("TVTODAY_SCREENSHOTS").is_none() {
+        return;
+    }
+
+    let surface = ImageSurface::create(
+        Format::ARgb32,
+        widget.get_allocated_width(),
+        widget.get_allocated_height(),
+    )
+    .expect("Could not create the surface");
+    widget.draw(&Context::new(&surface));
+
+    let mut dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    dir.push("target");
+    dir.push("screenshots");
+    fs::create_dir_all(&dir).expect("Could not create the directory of the screenshots");
+
+    let mut file =
+        File::create(dir.join(format!("{}.png", name))).expect("Could not create the screenshot");
+    surface
+        .write_to_png(&mut file)
+        .expect("Could not write the screenshot");
+}
+
+#[test]
+fn test_win() {
+    let dir = match init() {
+        Some(dir) => dir,
+        None => {
+            eprintln!("Skipping the tests of the user interface without a display");
+            return;
+        }
+    };
+
+    // The application accepts the options of `main`, so `tvtoday --mock` shows the window.
+    let application = new_application(ApplicationFlags::NON_UNIQUE);
+    let activated = Rc::new(Cell::new(false));
+    {
+        let activated = activated.clone();
+        application.connect_activate(move |_| activated.set(true));
+    }
+    assert_eq!(
+        application.run(&["tvtoday".to_string(), "--mock".to_string()]),
+        0
+    );
+    assert!(activated.get());
+
+    let application = gtk::Application::new(Some(APPLICATION_ID), ApplicationFlags::NON_UNIQUE)
+        .expect("Could not create application");
+    let provider = ProviderHandle::new(Mock::new());
+    let win = relm::init::<Win>((application, provider.clone())).expect("Could not spawn window");
+    let root: Widget = win.widget().clone().upcast();
+
+    let listbox = find_all::<ListBox>(&root).remove(0);
+    let leaflet = find_all::<Leaflet>(&root).remove(0);
+    let pages = leaflet.get_children();
+
+    // The program is loaded and shown.
+    wait_until("the program is shown", || {
+        listbox.get_children().len() == MOCK_BROADCASTS
+    });
+    settle();
+    assert!(texts(listbox.upcast_ref()).contains(&"Der Bergdoktor".to_string()));
+    // The mock channels have no icons, so avatars with their initials are shown.
+    assert!(find_all::<Image>(listbox.upcast_ref()).iter().any(|i| i
+        .get_tooltip_text()
+        .as_deref()
+        == Some("ZDF")
+        && i.get_pixbuf().is_some()));
+    screenshot(&root, "list");
+
+    // Activating a row shows the page of the movie.
+    let row = listbox.get_row_at_index(0).expect("No row to activate");
+    row.activate();
+    wait_until("the movie is shown", || {
+        leaflet.get_visible_child().as_ref() == pages.get(1)
+    });
+    settle();
+    screenshot(&root, "movie");
+
+    win.emit(WinMsg::Back);
+    wait_until("the list is shown again", || {
+        leaflet.get_visible_child().as_ref() == pages.first()
+    });
+
+    // Blocking a channel reloads the program without it.
+    win.emit(WinMsg::AddFilter(FilterType::Channel(
+        ChannelAttribute::Name("ZDF".to_string()),
+    )));
+    wait_until("the channel is filtered out", || {
+        listbox.get_children().len() == MOCK_BROADCASTS - 2
+    });
+    settle();
+    assert!(!texts(listbox.upcast_ref()).contains(&"Der Bergdoktor".to_string()));
+    // A toast offers to undo blocking it.
+    assert!(texts(&root).contains(&"Blocked 'ZDF'".to_string()));
+
+    // Reloading keeps the filter applied.
+    let spinner = find_all::<Spinner>(&root).remove(0);
+    win.emit(WinMsg::Reload);
+    settle();
+    wait_until("the program is reloaded", || {
+        !spinner.get_visible() && listbox.get_children().len() == MOCK_BROADCASTS - 2
+    });
+
+    // Searching shows the matches and selects the first one.
+    let entry_search = find_all::<SearchEntry>(&root).remove(0);
+    entry_search.set_text("tatort");
+    wait_until("the search matches", || listbox.get_children().len() == 1);
+    wait_until("the match is selected", || {
+        listbox.get_selected_row().map(|r| r.get_index()) == Some(0)
+    });
+
+    // Hiding the broadcast of the shown movie removes only it.
+    listbox
+        .get_row_at_index(0)
+        .expect("No row to activate")
+        .activate();
+    wait_until("the match is shown", || {
+        leaflet.get_visible_child().as_ref() == pages.get(1)
+    });
+    button(&root, "Hide this broadcast").clicked();
+    wait_until("the broadcast is hidden", || {
+        listbox.get_children().is_empty()
+    });
+
+    // Going back from the list ends the search.
+    win.emit(WinMsg::Back);
+    win.emit(WinMsg::Back);
+    wait_until("the search ended", || {
+        listbox.get_children().len() == MOCK_BROADCASTS - 3
+    });
+    settle();
+    assert!(!texts(listbox.upcast_ref()).contains(&"Tatort".to_string()));
+    assert!(texts(listbox.upcast_ref()).contains(&"Tagesthemen".to_string()));
+
+    // Blocking the title on the page of the movie stores the filter and removes the movie from the list.
+    let blocked = FilterType::Movie(MovieAttribute::Title("Tagesthemen".to_string()));
+    assert!(!is_stored(&blocked));
+    row_with_text(&listbox, "Tagesthemen")
+        .expect("No row of the movie")
+        .activate();
+    wait_until("the movie to block is shown", || {
+        leaflet.get_visible_child().as_ref() == pages.get(1)
+    });
+    button(&root, "Block movie title").clicked();
+    wait_until("the title is filtered out", || {
+        listbox.get_children().len() == MOCK_BROADCASTS - 4
+    });
+    settle();
+    assert!(row_with_text(&listbox, "Tagesthemen").is_none());
+    assert!(is_stored(&blocked));
+    assert!(texts(&root).contains(&"Blocked 'Tagesthemen'".to_string()));
+
+    // Undoing it in the toast removes the stored filter and shows the movie again.
+    button(&root, "Undo").clicked();
+    wait_until("the title is shown again", || {
+        listbox.get_children().len() == MOCK_BROADCASTS - 3
+    });
+    settle();
+    assert!(row_with_text(&listbox, "Tagesthemen").is_some());
+    assert!(!is_stored(&blocked));
+    // The other filters are kept.
+    assert!(is_stored(&FilterType::Channel(ChannelAttribute::Name(
+        "ZDF".to_string()
+    ))));
+    win.emit(WinMsg::Back);
+
+    // In the allow mode only the allowed channel is shown.
+    let zdf = FilterType::Channel(ChannelAttribute::Name("ZDF".to_string()));
+    find_all::<CheckButton>(&root)
+        .into_iter()
+        .find(|c| {
+            c.get_label()
+                .map(|l| l == "Only show matching filters")
+                .unwrap_or(false)
+        })
+        .expect("No check for the allow mode")
+        .set_active(true);
+    wait_until("only the allowed channel is shown", || {
+        listbox.get_children().len() == 2
+    });
+    settle();
+    assert!(texts(listbox.upcast_ref()).contains(&"Der Bergdoktor".to_string()));
+
+    // Blocking its selected rows stops allowing the channel instead of allowing it again.
+    find_all::<ToggleButton>(&root)
+        .into_iter()
+        .find(|b| b.get_tooltip_text().as_deref() == Some("Select movies to block them together"))
+        .expect("No button to select movies")
+        .set_active(true);
+    settle();
+    for check in find_all::<CheckButton>(listbox.upcast_ref()) {
+        check.set_active(true);
+    }
+    settle();
+    button(&root, "Block channels").clicked();
+    wait_until("the channel is no longer allowed", || {
+        listbox.get_children().is_empty()
+    });
+    settle();
+    assert!(!is_stored(&zdf));
+    assert!(texts(&root).contains(&"No longer allowing 'ZDF'".to_string()));
+
+    // Undoing it allows the channel again.
+    button(&root, "Undo").clicked();
+    wait_until("the channel is allowed again", || {
+        listbox.get_children().len() == 2
+    });
+    assert!(is_stored(&zdf));
+
+    // A failed reload, e.g. when offline, keeps the last program and only shows the error.
+    let offline = Mock::with_network(Duration::from_millis(0), Duration::from_millis(0), 1.0, 1);
+    provider.set(Box::new(offline));
+    win.emit(WinMsg::Reload);
+    settle();
+    wait_until("the reload failed", || !spinner.get_visible());
+    settle();
+    assert_eq!(listbox.get_children().len(), 2);
+    assert!(texts(&root)
+        .iter()
+        .any(|t| t.starts_with("A networking error occured.")));
+
+    let _ = fs::remove_dir_all(dir);
+}
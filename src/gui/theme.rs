@@ -0,0 +1,96 @@
+use glib::object::IsA;
+use gtk::prelude::*;
+use gtk::{CssProvider, StyleContext, Widget};
+
+/// The style of the compact mode: high-contrast text, no transitions and larger hit targets.
+const COMPACT_CSS: &[u8] = b"
+* {
+    color: #000000;
+    background-image: none;
+    text-shadow: none;
+    -gtk-icon-shadow: none;
+    box-shadow: none;
+    transition: none;
+    animation: none;
+}
+
+window, list, row, headerbar, infobar, popover, scrolledwindow, viewport {
+    background-color: #ffffff;
+}
+
+button, row, checkbutton, radiobutton, combobox, entry, spinbutton {
+    min-height: 48px;
+}
+
+button {
+    border: 2px solid #000000;
+}
+";
+
+/// The theme of the application, which can be switched to a compact text-only mode for e-ink and low-power displays.
+pub struct Theme {
+    provider: CssProvider,
+    /// Whether the desktop enables animations, read before the theme changes it.
+    desktop_animations: bool,
+    compact: bool,
+    reduced_motion: bool,
+}
+
+impl Theme {
+    /// Create the theme and register it for the screen of the given widget.
+    pub fn new<W: IsA<Widget>>(widget: &W) -> Self {
+        let provider = CssProvider::new();
+
+        if let Some(screen) = widget.get_screen() {
+            StyleContext::add_provider_for_screen(
+                &screen,
+                &provider,
+                gtk::STYLE_PROVIDER_PRIORITY_USER,
+            );
+        }
+
+        let desktop_animations = gtk::Settings::get_default()
+            .map(|s| s.get_property_gtk_enable_animations())
+            .unwrap_or(true);
+
+        Theme {
+            provider,
+            desktop_animations,
+            compact: false,
+            reduced_motion: false,
+        }
+    }
+
+    /// Switch the compact mode on or off.
+    pub fn set_compact(&mut self, compact: bool) {
+        let css: &[u8] = if compact { COMPACT_CSS } else { b"" };
+        let _ = self.provider.load_from_data(css);
+
+        self.compact = compact;
+        self.update_animations();
+    }
+
+    /// Switch the reduced motion mode on or off, disabling all animations.
+    pub fn set_reduced_motion(&mut self, reduced_motion: bool) {
+        self.reduced_motion = reduced_motion;
+        self.update_animations();
+    }
+
+    /// Whether animations are shown. They are disabled by the desktop, the compact mode and the reduced motion mode.
+    pub fn get_animations(&self) -> bool {
+        self.desktop_animations && !self.compact && !self.reduced_motion
+    }
+
+    fn update_animations(&self) {
+        if let Some(settings) = gtk::Settings::get_default() {
+            settings.set_property_gtk_enable_animations(self.get_animations());
+        }
+    }
+
+    /// Request the dark or the light variant of the GTK theme.
+    pub fn set_dark(&self, dark: bool) {
+        if let Some(settings) = gtk::Settings::get_default() {
+            settings.set_property_gtk_application_prefer_dark_theme(dark);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use gtk::prelude::*;
+use gtk::{
+    Align, Box, Button, IconSize, Label, Orientation, ReliefStyle, Revealer, RevealerTransitionType,
+};
+use relm::{connect, Relm, Update, Widget};
+use relm_derive::Msg;
+
+/// How long a toast is shown in milliseconds.
+const TOAST_DURATION: u32 = 5000;
+
+/// Messages for the toast.
+#[derive(Msg)]
+pub enum ToastMsg {
+    /// Show the given message with a button to undo the action, replacing the shown one.
+    Show(String),
+    /// Hide the toast.
+    Hide,
+    /// The toast with the given number was shown long enough.
+    Expire(u32),
+    /// The undo button was clicked. Should be handled by the parent.
+    Undo,
+}
+
+/// The model for the toast counting the shown messages.
+pub struct ToastModel {
+    /// The number of the shown message, a newer message is shown for the full duration.
+    shown: u32,
+    relm: Relm<Toast>,
+}
+
+/// The toast is a transient notification at the bottom of a overlay, telling about a action with a button to undo it.
+pub struct Toast {
+    model: ToastModel,
+    revealer: Revealer,
+    label: Label,
+}
+
+impl Update for Toast {
+    type Model = ToastModel;
+    type ModelParam = ();
+    type Msg = ToastMsg;
+
+    fn model(relm: &Relm<Self>, _: Self::ModelParam) -> Self::Model {
+        ToastModel {
+            shown: 0,
+            relm: relm.clone(),
+        }
+    }
+
+    fn update(&mut self, event: ToastMsg) {
+        match event {
+            ToastMsg::Show(message) => {
+                self.model.shown += 1;
+                self.label.set_text(&message);
+                self.revealer.set_reveal_child(true);
+
+                let shown = self.model.shown;
+                relm::timeout(self.model.relm.stream(), TOAST_DURATION, move || {
+                    ToastMsg::Expire(shown)
+                });
+            }
+            ToastMsg::Expire(shown) => {
+                if shown == self.model.shown {
+                    self.revealer.set_reveal_child(false);
+                }
+            }
+            ToastMsg::Hide | ToastMsg::Undo => self.revealer.set_reveal_child(false),
+        }
+    }
+}
+
+impl Widget for Toast {
+    type Root = Revealer;
+
+    fn root(&self) -> Self::Root {
+        self.revealer.clone()
+    }
+
+    fn view(relm: &Relm<Self>, model: Self::Model) -> Self {
+        let revealer = Revealer::new();
+        revealer.set_transition_type(RevealerTransitionType::SlideUp);
+        revealer.set_halign(Align::Center);
+        revealer.set_valign(Align::End);
+
+        let content = Box::new(Orientation::Horizontal, 10);
+        content.get_style_context().add_class("app-notification");
+
+        let label = Label::new(None);
+        label.set_line_wrap(true);
+        content.add(&label);
+
+        let button_undo = Button::with_label("Undo");
+        connect!(relm, button_undo, connect_clicked(_), ToastMsg::Undo);
+        content.add(&button_undo);
+
+        let button_close = Button::from_icon_name(Some("window-close-symbolic"), IconSize::Button);
+        button_close.set_relief(ReliefStyle::None);
+        button_close.set_tooltip_text(Some("Close"));
+        connect!(relm, button_close, connect_clicked(_), ToastMsg::Hide);
+        content.add(&button_close);
+
+        revealer.add(&content);
+
+        Toast {
+            model,
+            revealer,
+            label,
+        }
+    }
+}
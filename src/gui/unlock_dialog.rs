@@ -0,0 +1,57 @@
+use crate::model::encryption;
+
+use gtk::prelude::*;
+use gtk::{Dialog, DialogFlags, Entry, Label, ResponseType, Window};
+
+/// Ask for the passphrase until the encrypted user data is unlocked. Returns `false` if the user gave up.
+pub fn run_unlock_dialog() -> bool {
+    let dialog = Dialog::with_buttons(
+        Some("Unlock"),
+        None::<&Window>,
+        DialogFlags::MODAL,
+        &[
+            ("Quit", ResponseType::Cancel),
+            ("Unlock", ResponseType::Accept),
+        ],
+    );
+    dialog.set_default_response(ResponseType::Accept);
+
+    let label = Label::new(Some(
+        "The data is encrypted. Enter the passphrase to unlock it.",
+    ));
+    label.set_line_wrap(true);
+
+    let entry_passphrase = Entry::new();
+    entry_passphrase.set_visibility(false);
+    entry_passphrase.set_activates_default(true);
+
+    let label_error = Label::new(None);
+    label_error.get_style_context().add_class("error");
+
+    let content = dialog.get_content_area();
+    content.set_spacing(10);
+    content.set_margin_start(10);
+    content.set_margin_end(10);
+    content.set_margin_top(10);
+    content.add(&label);
+    content.add(&entry_passphrase);
+    content.add(&label_error);
+    dialog.show_all();
+
+    let mut unlocked = false;
+    while dialog.run() == ResponseType::Accept {
+        match encryption::unlock(&entry_passphrase.get_text()) {
+            Ok(()) => {
+                unlocked = true;
+                break;
+            }
+            Err(error) => {
+                label_error.set_text(&error.to_string());
+                entry_passphrase.set_text("");
+            }
+        }
+    }
+
+    dialog.close();
+    unlocked
+}
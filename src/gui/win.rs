@@ -1,52 +1,197 @@
-use crate::gui::{MovieList, MovieListMsg, MoviePage, MoviePageMsg};
-use crate::model::{Channel, FilterType, Movie, Provider};
+use crate::gui::{
+    run_unlock_dialog, shortcuts, tasks, Config, MovieList, MovieListMsg, MoviePage, MoviePageMsg,
+};
+use crate::model::cache::{self, CacheUsage};
+use crate::model::{
+    encryption, paths, Channel, ClockWatch, FilterMode, FilterType, Movie, PersonalRatings,
+    Program, ProviderHandle, Reminder, ReminderChange, Reminders, Settings,
+};
+use crate::Error;
 
+use std::path::PathBuf;
+use std::time::Instant;
+
+use chrono::{Duration, Local, NaiveDateTime};
+use gio::prelude::*;
+use gio::{BusType, DBusConnection, DBusSignalFlags, Notification, SimpleAction};
+use glib::{Continue, ToVariant};
 use gtk::prelude::*;
-use gtk::{Box, Inhibit};
+use gtk::{Application, ApplicationInhibitFlags, Inhibit};
 use libhandy::prelude::*;
-use libhandy::{Leaflet, Window};
+use libhandy::{Leaflet, NavigationDirection, Window};
 use relm::{connect, Component, Relm, StreamHandle, Update, Widget};
 use relm_derive::Msg;
 
 #[derive(Msg)]
-pub enum WinMsg<T: 'static + Provider> {
+pub enum WinMsg {
     SelectedMovie((Channel, Movie)),
-    UpdateProvider(T),
+    /// Open the movie of the list this many rows away from the opened one.
+    OpenRelative(i32),
+    /// Whether the opened movie has a previous and a next movie in the list.
+    SetNeighbours((bool, bool)),
     AddFilter(FilterType),
+    /// Add a filter until the given time.
+    AddFilterUntil((FilterType, NaiveDateTime)),
+    SetFilterMode(FilterMode),
+    SetOmdbApiKey(Option<String>),
+    SetTranslationUrl(Option<String>),
+    SetPersonalRatings(PersonalRatings),
+    SetTransitionDuration(u32),
+    SetInhibitSuspend(bool),
+    AddReminder(Reminder),
+    RemoveReminder((Channel, Movie)),
+    CheckReminders,
+    ProgramLoaded(Program),
+    SetActive(bool),
+    Resumed,
+    /// Check whether the system clock jumped, e.g. when it was synced or the timezone changed.
+    CheckClock,
+    Reload,
+    Search,
+    Back,
+    ShowShortcuts,
+    UpdateCacheUsage,
     Quit,
+    /// The movie list stored its state, the running tasks can be cancelled.
+    ShutdownReady,
 }
 
-pub struct WinModel<T: 'static + Provider> {
-    provider: T,
+/// How often to check for due reminders in milliseconds.
+const REMINDER_INTERVAL: u32 = 30 * 1000;
+
+/// How often to check whether the system clock jumped in milliseconds.
+const CLOCK_CHECK_INTERVAL: u32 = 5 * 1000;
+
+/// How many minutes before a reminder is due suspend is inhibited, if enabled.
+const INHIBIT_MINUTES: i64 = 5;
+
+/// How often the size of the caches published on the session bus is updated in milliseconds.
+const CACHE_USAGE_INTERVAL: u32 = 60 * 1000;
+
+/// How often to check whether the cancelled tasks finished when quitting in milliseconds.
+const SHUTDOWN_CHECK_INTERVAL: u32 = 50;
+
+/// How long to wait for the cancelled tasks to finish when quitting.
+const SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+pub struct WinModel {
+    provider: ProviderHandle,
+    /// The settings read at startup, handed to the movie list.
+    settings: Settings,
+    config: Config,
+
+    reminders: Reminders,
+    reminders_path: PathBuf,
 
-    stream_win: StreamHandle<WinMsg<T>>,
+    /// Weather suspend should be inhibited shortly before a reminder is due.
+    inhibit_suspend: bool,
+    /// The cookie of the active inhibition of suspend.
+    inhibit_cookie: Option<u32>,
+
+    /// Detects jumps of the system clock, after which the reminders and the started movies are checked again.
+    clock: ClockWatch,
+
+    /// The application of the window, used to send the notifications of the reminders and to quit.
+    application: Application,
+    /// The connection to the system bus, listening for the system to resume from suspend.
+    _system_bus: Option<DBusConnection>,
+    /// The action of the application with the size of the caches as JSON in its state. It is exported on the session
+    /// bus, activating it updates the state.
+    cache_usage_action: SimpleAction,
+    /// Weather the application is shutting down.
+    quitting: bool,
+
+    stream_win: StreamHandle<WinMsg>,
 }
 
-pub struct Win<T: 'static + Provider> {
-    model: WinModel<T>,
+impl WinModel {
+    fn write_reminders(&self) -> Result<(), Error> {
+        self.reminders.write_to_path(self.reminders_path.clone())
+    }
+}
+
+pub struct Win {
+    model: WinModel,
     widgets: WinWidgets,
-    components: WinComponents<T>,
+    components: WinComponents,
 }
 
 struct WinWidgets {
     root: Window,
     leaflet: Leaflet,
-    page_movie: Box,
 }
 
-struct WinComponents<T: 'static + Provider> {
-    page_list: Component<MovieList<T>>,
-    page_movie: Component<MoviePage<T>>,
+struct WinComponents {
+    page_list: Component<MovieList>,
+    page_movie: Component<MoviePage>,
 }
 
-impl<T: 'static + Provider> Update for Win<T> {
-    type Model = WinModel<T>;
-    type ModelParam = ();
-    type Msg = WinMsg<T>;
+impl Update for Win {
+    type Model = WinModel;
+    type ModelParam = (Application, ProviderHandle);
+    type Msg = WinMsg;
+
+    fn model(relm: &Relm<Self>, (application, provider): Self::ModelParam) -> Self::Model {
+        // Earlier versions stored the settings in the data directory.
+        let mut settings = Settings::read_from_path(paths::user_config_file("settings.csv"))
+            .or_else(|_| Settings::read_from_path(paths::user_data_file("settings.csv")))
+            .unwrap_or_default();
+        let config = Config::new();
+        config.apply_to(&mut settings);
+        // The data directory has to be set before any data is read.
+        paths::set_user_data_dir_override(settings.get_data_dir());
+        cache::set_limits(&settings);
+        // Without unlocking, the encrypted data could neither be read nor written.
+        if encryption::is_enabled() && !encryption::is_unlocked() && !run_unlock_dialog() {
+            std::process::exit(0);
+        }
+        read_credentials(&mut settings);
+        if let (Some(login), Some(password)) = (
+            settings.get_provider_login(),
+            settings.get_provider_password(),
+        ) {
+            provider.lock().set_credentials(&login, &password);
+        }
+
+        let reminders_path = paths::user_data_file("reminders.csv");
+        let reminders = Reminders::read_from_path(reminders_path.clone());
+
+        relm::interval(relm.stream(), REMINDER_INTERVAL, || WinMsg::CheckReminders);
+        relm.stream().emit(WinMsg::CheckReminders);
+        relm::interval(relm.stream(), CLOCK_CHECK_INTERVAL, || WinMsg::CheckClock);
+
+        let system_bus = subscribe_resume(relm.stream().clone());
+        quit_on_termination(relm.stream().clone());
+
+        let cache_usage_action =
+            SimpleAction::new_stateful("cache-usage", None, &cache_usage_json().to_variant());
+        connect!(
+            relm,
+            cache_usage_action,
+            connect_activate(_, _),
+            WinMsg::UpdateCacheUsage
+        );
+        application.add_action(&cache_usage_action);
+        relm::interval(relm.stream(), CACHE_USAGE_INTERVAL, || {
+            WinMsg::UpdateCacheUsage
+        });
 
-    fn model(relm: &Relm<Self>, _: Self::ModelParam) -> Self::Model {
         WinModel {
-            provider: T::new(),
+            provider,
+            inhibit_suspend: settings.get_inhibit_suspend(),
+            inhibit_cookie: None,
+            clock: ClockWatch::new(),
+            settings,
+            config,
+
+            reminders: reminders.unwrap_or_default(),
+            reminders_path,
+
+            application,
+            _system_bus: system_bus,
+            cache_usage_action,
+            quitting: false,
+
             stream_win: relm.stream().clone(),
         }
     }
@@ -54,30 +199,168 @@ impl<T: 'static + Provider> Update for Win<T> {
     fn update(&mut self, event: Self::Msg) {
         match event {
             WinMsg::SelectedMovie((channel, movie)) => {
+                let reminded = self.model.reminders.get(&channel, &movie).is_some();
+
                 self.components
                     .page_movie
                     .emit(MoviePageMsg::Set((channel, movie)));
-                self.widgets
-                    .leaflet
-                    .set_visible_child(&self.widgets.page_movie);
-            }
-            WinMsg::UpdateProvider(provider) => {
-                self.model.provider = provider.clone();
-
                 self.components
                     .page_movie
-                    .emit(MoviePageMsg::SetProvider(provider));
+                    .emit(MoviePageMsg::SetReminded(reminded));
+                // Navigating animates like swiping.
+                self.widgets.leaflet.navigate(NavigationDirection::Forward);
             }
+            WinMsg::OpenRelative(offset) => self
+                .components
+                .page_list
+                .emit(MovieListMsg::OpenRelative(offset)),
+            WinMsg::SetNeighbours(neighbours) => self
+                .components
+                .page_movie
+                .emit(MoviePageMsg::SetNeighbours(neighbours)),
             WinMsg::AddFilter(filter) => self
                 .components
                 .page_list
                 .emit(MovieListMsg::AddFilter(filter)),
-            WinMsg::Quit => gtk::main_quit(),
+            WinMsg::AddFilterUntil(filter) => self
+                .components
+                .page_list
+                .emit(MovieListMsg::AddFilterUntil(filter)),
+            WinMsg::SetFilterMode(mode) => self
+                .components
+                .page_movie
+                .emit(MoviePageMsg::SetFilterMode(mode)),
+            WinMsg::SetOmdbApiKey(api_key) => self
+                .components
+                .page_movie
+                .emit(MoviePageMsg::SetOmdbApiKey(api_key)),
+            WinMsg::SetTranslationUrl(url) => self
+                .components
+                .page_movie
+                .emit(MoviePageMsg::SetTranslationUrl(url)),
+            WinMsg::SetPersonalRatings(ratings) => self
+                .components
+                .page_movie
+                .emit(MoviePageMsg::SetPersonalRatings(ratings)),
+            WinMsg::SetInhibitSuspend(inhibit_suspend) => {
+                self.model.inhibit_suspend = inhibit_suspend;
+                self.update_inhibit();
+            }
+            WinMsg::AddReminder(reminder) => {
+                self.model.reminders.add(reminder);
+                let _ = self.model.write_reminders();
+                self.update_inhibit();
+
+                self.components
+                    .page_movie
+                    .emit(MoviePageMsg::SetReminded(true));
+            }
+            WinMsg::RemoveReminder((channel, movie)) => {
+                self.model.reminders.remove(&channel, &movie);
+                let _ = self.model.write_reminders();
+                self.update_inhibit();
+
+                self.components
+                    .page_movie
+                    .emit(MoviePageMsg::SetReminded(false));
+            }
+            WinMsg::CheckReminders => {
+                let due = self.model.reminders.take_due(Local::now().naive_local());
+
+                if !due.is_empty() {
+                    let _ = self.model.write_reminders();
+                }
+
+                for reminder in due {
+                    self.notify(&reminder);
+                }
+
+                self.update_inhibit();
+            }
+            WinMsg::ProgramLoaded(program) => {
+                let changes = self.model.reminders.update_from(&program);
+
+                if !changes.is_empty() {
+                    let _ = self.model.write_reminders();
+                }
+
+                for change in changes {
+                    self.notify_change(&change);
+                }
+            }
+            WinMsg::SetActive(active) => self
+                .components
+                .page_list
+                .emit(MovieListMsg::SetActive(active)),
+            WinMsg::Resumed => {
+                // The program and the reminders are outdated after a suspend.
+                self.components.page_list.emit(MovieListMsg::Reload);
+                self.model.stream_win.emit(WinMsg::CheckReminders);
+            }
+            WinMsg::CheckClock => {
+                if self.model.clock.jumped() {
+                    self.model.stream_win.emit(WinMsg::CheckReminders);
+                    self.components.page_list.emit(MovieListMsg::ClockChanged);
+                }
+            }
+            WinMsg::SetTransitionDuration(duration) => {
+                self.set_transition_duration(duration);
+                self.components
+                    .page_movie
+                    .emit(MoviePageMsg::SetTransitionDuration(duration));
+            }
+            WinMsg::Reload => self.components.page_list.emit(MovieListMsg::Reload),
+            WinMsg::Search => {
+                self.widgets.leaflet.navigate(NavigationDirection::Back);
+                self.components.page_list.emit(MovieListMsg::Search(true));
+            }
+            WinMsg::Back => {
+                // Going back from the list closes the search.
+                if !self.widgets.leaflet.navigate(NavigationDirection::Back) {
+                    self.components.page_list.emit(MovieListMsg::Search(false));
+                }
+            }
+            WinMsg::ShowShortcuts => {
+                let window = shortcuts::shortcuts_window();
+                window.set_transient_for(Some(&self.widgets.root));
+                window.show_all();
+            }
+            WinMsg::UpdateCacheUsage => self
+                .model
+                .cache_usage_action
+                .set_state(&cache_usage_json().to_variant()),
+            WinMsg::Quit => {
+                if self.model.quitting {
+                    return;
+                }
+                self.model.quitting = true;
+
+                self.model
+                    .config
+                    .set_window_size(self.widgets.root.get_size());
+                // The window is hidden at once, while the session is stored and the tasks finish.
+                self.widgets.root.hide();
+                self.components.page_list.emit(MovieListMsg::Shutdown);
+            }
+            WinMsg::ShutdownReady => {
+                tasks::cancel_all();
+
+                let application = self.model.application.clone();
+                let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+                glib::timeout_add_local(SHUTDOWN_CHECK_INTERVAL, move || {
+                    if tasks::running() == 0 || Instant::now() >= deadline {
+                        application.quit();
+                        Continue(false)
+                    } else {
+                        Continue(true)
+                    }
+                });
+            }
         }
     }
 }
 
-impl<T: 'static + Provider> Widget for Win<T> {
+impl Widget for Win {
     type Root = Window;
     fn root(&self) -> Self::Root {
         self.widgets.root.clone()
@@ -85,15 +368,20 @@ impl<T: 'static + Provider> Widget for Win<T> {
 
     fn view(relm: &Relm<Self>, model: Self::Model) -> Self {
         let root = Window::new();
+        let (width, height) = model.config.get_window_size();
+        root.set_default_size(width, height);
 
         let leaflet = Leaflet::new();
         leaflet.set_can_swipe_back(true);
 
-        let page_list = relm::create_component::<MovieList<T>>((
+        let page_list = relm::create_component::<MovieList>((
             model.stream_win.clone(),
             model.provider.clone(),
+            model.settings.clone(),
+            model.config.clone(),
         ));
-        let page_movie = relm::create_component::<MoviePage<T>>(model.stream_win.clone());
+        let page_movie =
+            relm::create_component::<MoviePage>((model.stream_win.clone(), model.provider.clone()));
 
         page_list.widget().set_size_request(360, -1);
         page_movie.widget().set_size_request(360, -1);
@@ -101,25 +389,75 @@ impl<T: 'static + Provider> Widget for Win<T> {
         leaflet.add(page_list.widget());
         leaflet.add(page_movie.widget());
 
+        // On phones the primary actions of the list are moved to the bottom.
+        page_list.emit(MovieListMsg::SetFolded(leaflet.get_folded()));
+        let stream_list = page_list.stream();
+        leaflet.connect_property_folded_notify(move |l| {
+            stream_list.emit(MovieListMsg::SetFolded(l.get_folded()))
+        });
+
         root.add(&leaflet);
 
+        let (action_group, accel_group, actions) = shortcuts::create_actions();
+        for (name, action) in actions {
+            match name {
+                "reload" => {
+                    connect!(relm, action, connect_activate(_, _), WinMsg::Reload);
+                }
+                "search" => {
+                    connect!(relm, action, connect_activate(_, _), WinMsg::Search);
+                }
+                "back" => {
+                    connect!(relm, action, connect_activate(_, _), WinMsg::Back);
+                }
+                "previous-movie" | "next-movie" => {
+                    let offset = if name == "next-movie" { 1 } else { -1 };
+                    connect!(
+                        relm,
+                        action,
+                        connect_activate(_, _),
+                        WinMsg::OpenRelative(offset)
+                    );
+
+                    // Page up and down only open other movies while one is shown, otherwise they scroll the list.
+                    let page_movie = page_movie.widget().clone().upcast::<gtk::Widget>();
+                    action.set_enabled(false);
+                    leaflet.connect_property_visible_child_notify(move |l| {
+                        action.set_enabled(l.get_visible_child().as_ref() == Some(&page_movie))
+                    });
+                }
+                "show-shortcuts" => {
+                    connect!(relm, action, connect_activate(_, _), WinMsg::ShowShortcuts);
+                }
+                "quit" => {
+                    connect!(relm, action, connect_activate(_, _), WinMsg::Quit);
+                }
+                _ => {}
+            }
+        }
+        root.insert_action_group(shortcuts::ACTION_GROUP, Some(&action_group));
+        root.add_accel_group(&accel_group);
+
+        connect!(
+            relm,
+            root,
+            connect_property_is_active_notify(w),
+            WinMsg::SetActive(w.is_active())
+        );
+
         connect!(
             relm,
             root,
             connect_delete_event(_, _),
-            return (WinMsg::Quit, Inhibit(false))
+            return (WinMsg::Quit, Inhibit(true))
         );
 
         root.show_all();
 
-        let widgets = WinWidgets {
-            root,
-            leaflet,
-            page_movie: page_movie.widget().clone(),
-        };
+        let widgets = WinWidgets { root, leaflet };
 
         let components = WinComponents {
-            page_list: page_list,
+            page_list,
             page_movie,
         };
 
@@ -130,3 +468,162 @@ impl<T: 'static + Provider> Widget for Win<T> {
         }
     }
 }
+
+impl Win {
+    /// Set the duration of the transitions between the pages in milliseconds. Zero disables the animations.
+    fn set_transition_duration(&self, duration: u32) {
+        self.widgets.leaflet.set_child_transition_duration(duration);
+        self.widgets.leaflet.set_mode_transition_duration(duration);
+    }
+
+    /// Inhibit suspend while a reminder is due in the next minutes, so the notification is not missed because the device
+    /// slept. Uses the inhibit portal if available. Releases the inhibition once no reminder is due soon.
+    fn update_inhibit(&mut self) {
+        let now = Local::now().naive_local();
+        let due_soon = self.model.inhibit_suspend
+            && self
+                .model
+                .reminders
+                .next_due()
+                .map(|due| due - now <= Duration::minutes(INHIBIT_MINUTES))
+                .unwrap_or(false);
+
+        match (due_soon, self.model.inhibit_cookie) {
+            (true, None) => {
+                let cookie = self.model.application.inhibit(
+                    Some(&self.widgets.root),
+                    ApplicationInhibitFlags::SUSPEND,
+                    Some("A reminder is due soon"),
+                );
+                // Zero means the request was refused.
+                if cookie != 0 {
+                    self.model.inhibit_cookie = Some(cookie);
+                }
+            }
+            (false, Some(cookie)) => {
+                self.model.application.uninhibit(cookie);
+                self.model.inhibit_cookie = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Send a notification for the due reminder.
+    fn notify(&self, reminder: &Reminder) {
+        let notification = Notification::new(&reminder.get_title());
+        notification.set_body(Some(&format!(
+            "Starts at {} on {}",
+            reminder.get_start().format("%H:%M"),
+            reminder.get_channel()
+        )));
+
+        self.model
+            .application
+            .send_notification(None, &notification);
+    }
+
+    /// Send a notification for a change of a reminded broadcast.
+    fn notify_change(&self, change: &ReminderChange) {
+        let (reminder, body) = match change {
+            ReminderChange::Moved(reminder, old_start) => (
+                reminder,
+                format!(
+                    "Moved from {} to {} on {}",
+                    old_start.format("%H:%M"),
+                    reminder.get_start().format("%H:%M"),
+                    reminder.get_channel()
+                ),
+            ),
+            ReminderChange::Cancelled(reminder) => (
+                reminder,
+                format!(
+                    "No longer broadcasted at {} on {}, the reminder was removed",
+                    reminder.get_start().format("%H:%M"),
+                    reminder.get_channel()
+                ),
+            ),
+        };
+
+        let notification = Notification::new(&reminder.get_title());
+        notification.set_body(Some(&body));
+
+        self.model
+            .application
+            .send_notification(None, &notification);
+    }
+}
+
+/// Listen for the system to resume from suspend using logind. Returns the connection to the system bus that must be kept alive.
+fn subscribe_resume(stream: StreamHandle<WinMsg>) -> Option<DBusConnection> {
+    let connection = gio::bus_get_sync::<gio::Cancellable>(BusType::System, None).ok()?;
+
+    connection.signal_subscribe(
+        Some("org.freedesktop.login1"),
+        Some("org.freedesktop.login1.Manager"),
+        Some("PrepareForSleep"),
+        Some("/org/freedesktop/login1"),
+        None,
+        DBusSignalFlags::NONE,
+        move |_, _, _, _, _, parameters| {
+            // The signal is sent with `true` before suspending and with `false` after resuming.
+            if parameters.to_string() == "(false,)" {
+                stream.emit(WinMsg::Resumed);
+            }
+        },
+    );
+
+    Some(connection)
+}
+
+/// The termination signals sent e.g. when the session ends or with Ctrl+C in a terminal.
+#[cfg(unix)]
+const TERMINATION_SIGNALS: [i32; 2] = [2 /* SIGINT */, 15 /* SIGTERM */];
+
+/// Quit like closing the window on a termination signal, so the data is stored before exiting. A second signal exits
+/// immediately, in case the shutdown hangs.
+#[cfg(unix)]
+fn quit_on_termination(stream: StreamHandle<WinMsg>) {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let received = Rc::new(Cell::new(false));
+    for signal in TERMINATION_SIGNALS.iter() {
+        let stream = stream.clone();
+        let received = received.clone();
+        glib::unix_signal_add_local(*signal, move || {
+            if received.replace(true) {
+                std::process::exit(1);
+            }
+            stream.emit(WinMsg::Quit);
+            Continue(true)
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn quit_on_termination(_stream: StreamHandle<WinMsg>) {}
+
+/// The size and limit of each cache in bytes as JSON.
+fn cache_usage_json() -> String {
+    serde_json::Value::from(CacheUsage::current()).to_string()
+}
+
+/// Read the credentials, like the password of the provider, from the user data, which must be unlocked. Credentials still
+/// stored with the other settings by earlier versions are moved there.
+fn read_credentials(settings: &mut Settings) {
+    let credentials_path = paths::user_data_file("credentials.csv");
+    if settings
+        .read_credentials_from_path(&credentials_path)
+        .is_ok()
+        || settings.get_credentials().is_empty()
+    {
+        return;
+    }
+
+    if settings
+        .write_credentials_to_path(&credentials_path)
+        .is_ok()
+    {
+        let _ = settings.write_to_path(paths::user_config_file("settings.csv"));
+    }
+}
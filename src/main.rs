@@ -1,13 +1,294 @@
-mod error;
 mod gui;
-mod model;
 
-pub use crate::error::Error;
-use crate::gui::Win;
+#[cfg(feature = "dvb")]
+use crate::model::providers::Dvb;
+#[cfg(feature = "mock")]
+use crate::model::providers::Mock;
+#[cfg(feature = "mythtv")]
+use crate::model::providers::MythTv;
+#[cfg(feature = "tv-movie")]
+use crate::model::providers::TvMovie;
+#[cfg(feature = "tv-spielfilm")]
 use crate::model::providers::TvSpielfilm;
+#[cfg(feature = "tv-today")]
+use crate::model::providers::TvToday;
+#[cfg(feature = "tvheadend")]
+use crate::model::providers::Tvheadend;
+#[cfg(feature = "zattoo")]
+use crate::model::providers::Zattoo;
+use crate::model::{
+    digest_html, encryption, paths, waybar_json, ChannelOrder, CircuitBreaker, Program,
+    ProgramFilter, Provider, ProviderHandle, Reminders, Retrying, Settings, DIGEST_DAYS,
+};
+use tvtoday_core::model;
+pub use tvtoday_core::Error;
 
-use relm::Widget;
+use chrono::Local;
+
+use serde_json::json;
+use tokio::runtime::Runtime;
+
+#[cfg(not(any(
+    feature = "tv-spielfilm",
+    feature = "tv-movie",
+    feature = "tv-today",
+    feature = "zattoo",
+    feature = "tvheadend",
+    feature = "mythtv",
+    feature = "dvb",
+    feature = "mock"
+)))]
+compile_error!("At least one provider must be enabled, e.g. with the feature `tv-spielfilm`.");
+
+/// The provider of the program.
+#[cfg(feature = "tv-spielfilm")]
+type DefaultProvider = CircuitBreaker<Retrying<TvSpielfilm>>;
+/// The provider of the program. TV Spielfilm was not built.
+#[cfg(all(not(feature = "tv-spielfilm"), feature = "tv-movie"))]
+type DefaultProvider = CircuitBreaker<Retrying<TvMovie>>;
+/// The provider of the program. Neither TV Spielfilm nor TV Movie were built.
+#[cfg(all(
+    not(any(feature = "tv-spielfilm", feature = "tv-movie")),
+    feature = "tv-today"
+))]
+type DefaultProvider = CircuitBreaker<Retrying<TvToday>>;
+/// The provider of the program. None of the websites were built, Zattoo needs a account.
+#[cfg(all(
+    not(any(feature = "tv-spielfilm", feature = "tv-movie", feature = "tv-today")),
+    feature = "zattoo"
+))]
+type DefaultProvider = CircuitBreaker<Retrying<Zattoo>>;
+/// The provider of the program. Neither the websites nor Zattoo were built, tvheadend needs a server.
+#[cfg(all(
+    not(any(
+        feature = "tv-spielfilm",
+        feature = "tv-movie",
+        feature = "tv-today",
+        feature = "zattoo"
+    )),
+    feature = "tvheadend"
+))]
+type DefaultProvider = CircuitBreaker<Retrying<Tvheadend>>;
+/// The provider of the program. Only MythTV, which needs a backend, and maybe the files from the antenna and the mock
+/// provider were built.
+#[cfg(all(
+    not(any(
+        feature = "tv-spielfilm",
+        feature = "tv-movie",
+        feature = "tv-today",
+        feature = "zattoo",
+        feature = "tvheadend"
+    )),
+    feature = "mythtv"
+))]
+type DefaultProvider = CircuitBreaker<Retrying<MythTv>>;
+/// The provider of the program. Only the files from the antenna, which need a scan and a dump, and maybe the mock
+/// provider were built.
+#[cfg(all(
+    not(any(
+        feature = "tv-spielfilm",
+        feature = "tv-movie",
+        feature = "tv-today",
+        feature = "zattoo",
+        feature = "tvheadend",
+        feature = "mythtv"
+    )),
+    feature = "dvb"
+))]
+type DefaultProvider = CircuitBreaker<Retrying<Dvb>>;
+/// The provider of the program. Only the mock provider was built.
+#[cfg(not(any(
+    feature = "tv-spielfilm",
+    feature = "tv-movie",
+    feature = "tv-today",
+    feature = "zattoo",
+    feature = "tvheadend",
+    feature = "mythtv",
+    feature = "dvb"
+)))]
+type DefaultProvider = CircuitBreaker<Retrying<Mock>>;
 
 fn main() {
-    Win::<TvSpielfilm>::run(()).expect("Could not spawn window");
+    let args: Vec<String> = std::env::args().collect();
+
+    // The provider is chosen in the preferences, unknown or not built providers fall back to the default.
+    match &read_settings().get_provider()[..] {
+        #[cfg(feature = "tv-movie")]
+        "tvmovie" => run::<CircuitBreaker<Retrying<TvMovie>>>(&args),
+        #[cfg(feature = "tv-today")]
+        "tvtoday" => run::<CircuitBreaker<Retrying<TvToday>>>(&args),
+        #[cfg(feature = "zattoo")]
+        "zattoo" => run::<CircuitBreaker<Retrying<Zattoo>>>(&args),
+        #[cfg(feature = "tvheadend")]
+        "tvheadend" => run::<CircuitBreaker<Retrying<Tvheadend>>>(&args),
+        #[cfg(feature = "mythtv")]
+        "mythtv" => run::<CircuitBreaker<Retrying<MythTv>>>(&args),
+        #[cfg(feature = "dvb")]
+        "dvb" => run::<CircuitBreaker<Retrying<Dvb>>>(&args),
+        _ => run::<DefaultProvider>(&args),
+    }
+}
+
+/// Run the application with the provider, depending on the arguments.
+fn run<T: 'static + Provider>(args: &[String]) {
+    let digest_path = args
+        .iter()
+        .position(|a| a == "--digest")
+        .and_then(|i| args.get(i + 1));
+
+    if args.iter().any(|a| a == "--json") {
+        print_json::<T>();
+    } else if args.iter().any(|a| a == "--waybar") {
+        print_waybar::<T>();
+    } else if let Some(path) = digest_path {
+        write_digest::<T>(path);
+    } else if args.iter().any(|a| a == "--mock") {
+        run_mock();
+    } else {
+        gui::run(ProviderHandle::new(new_provider::<T>(&read_settings())));
+    }
+}
+
+/// Show a fixed program without network access, e.g. for screenshots. It is retried like the default provider, so the
+/// simulated failures can be tried out.
+#[cfg(feature = "mock")]
+fn run_mock() {
+    gui::run(ProviderHandle::new(CircuitBreaker::<Retrying<Mock>>::new()));
+}
+
+/// The mock provider was not built, e.g. by a packager.
+#[cfg(not(feature = "mock"))]
+fn run_mock() {
+    eprintln!("tvtoday was built without the mock provider.");
+    std::process::exit(1);
+}
+
+/// Print the filtered program as JSON instead of showing the window, e.g. for status bars.
+fn print_json<T: Provider>() {
+    let (mut provider, filter) = provider_and_filter::<T>();
+
+    let rt = Runtime::new().expect("Could not create runtime");
+    match rt.block_on(provider.get_program()) {
+        Ok(program) => println!("{}", json!(filter.filter(&program))),
+        Err(error) => exit_with(error),
+    }
+}
+
+/// Print the next broadcast with a reminder or on a favorite channel as a single JSON object for status bars like Waybar
+/// or polybar, which poll it. Errors are printed as a object of the class `error`, so the status bar can show them.
+fn print_waybar<T: Provider>() {
+    let (mut provider, filter) = provider_and_filter::<T>();
+    let reminders =
+        Reminders::read_from_path(paths::user_data_file("reminders.csv")).unwrap_or_default();
+    let channel_order =
+        ChannelOrder::read_from_path(paths::user_data_file("channels.csv")).unwrap_or_default();
+
+    let rt = Runtime::new().expect("Could not create runtime");
+    let output = match rt.block_on(provider.get_program()) {
+        Ok(program) => waybar_json(
+            &filter.filter(&program),
+            &reminders,
+            &channel_order,
+            Local::now().naive_local(),
+        ),
+        Err(error) => json!({
+            "text": "",
+            "tooltip": error.to_string(),
+            "class": "error",
+        }),
+    };
+    println!("{}", output);
+}
+
+/// Write a HTML digest of the filtered program of the next days to the path instead of showing the window, e.g. to
+/// mail it weekly.
+fn write_digest<T: Provider>(path: &str) {
+    let (mut provider, filter) = provider_and_filter::<T>();
+
+    let rt = Runtime::new().expect("Could not create runtime");
+    let mut matches = Program::new();
+    for days in 0..DIGEST_DAYS {
+        provider.set_days_ahead(days);
+        match rt.block_on(provider.get_program()) {
+            Ok(program) => {
+                for (channel, movie) in filter.filter(&program).iter() {
+                    matches.add(channel.clone(), movie.clone());
+                }
+            }
+            Err(error) => exit_with(error),
+        }
+    }
+
+    let html = digest_html(&matches, Local::now().date_naive(), DIGEST_DAYS);
+    if let Err(error) = std::fs::write(path, html) {
+        eprintln!("Could not write the digest: {}", error);
+        std::process::exit(1);
+    }
+}
+
+/// Read the settings of the user, the defaults if there are none. The provider and the time slot are read from GSettings,
+/// like in the window.
+fn read_settings() -> Settings {
+    let mut settings = Settings::read_from_path(paths::user_config_file("settings.csv"))
+        .or_else(|_| Settings::read_from_path(paths::user_data_file("settings.csv")))
+        .unwrap_or_default();
+    gui::Config::new().apply_to(&mut settings);
+    settings
+}
+
+/// Create the provider with the account of the user at it and its server, if they are configured.
+fn new_provider<T: Provider>(settings: &Settings) -> T {
+    let mut provider = T::new();
+    if let Some(server) = settings.get_provider_server() {
+        provider.set_server(&server);
+    }
+    if let (Some(login), Some(password)) = (
+        settings.get_provider_login(),
+        settings.get_provider_password(),
+    ) {
+        provider.set_credentials(&login, &password);
+    }
+    provider
+}
+
+/// Get the provider and the filter configured by the user.
+fn provider_and_filter<T: Provider>() -> (T, ProgramFilter) {
+    let mut settings = read_settings();
+    paths::set_user_data_dir_override(settings.get_data_dir());
+    if encryption::is_enabled() {
+        unlock_from_stdin();
+    }
+    // Earlier versions stored the credentials with the other settings, which are used if there is no file.
+    let _ = settings.read_credentials_from_path(paths::user_data_file("credentials.csv"));
+
+    let mut filter_log = ProgramFilter::open_log(paths::user_data_dir(), &paths::device_id());
+    let filter = ProgramFilter::read_from_log_or_migrate(
+        &mut filter_log,
+        paths::user_data_file("filters.json"),
+        paths::user_data_file("filters.csv"),
+    )
+    .unwrap_or_default();
+
+    let mut provider = new_provider::<T>(&settings);
+    provider.set_time_slot(settings.get_time_slot());
+
+    (provider, filter)
+}
+
+/// Unlock the encrypted user data with the passphrase read from the standard input, exiting if it is wrong.
+fn unlock_from_stdin() {
+    eprint!("Passphrase: ");
+    let mut passphrase = String::new();
+    if let Err(error) = std::io::stdin().read_line(&mut passphrase) {
+        exit_with(error.into());
+    }
+    if let Err(error) = encryption::unlock(passphrase.trim_end_matches(&['\r', '\n'][..])) {
+        exit_with(error);
+    }
+}
+
+/// Print the error and exit with a failure.
+fn exit_with(error: Error) -> ! {
+    eprintln!("{}", error);
+    std::process::exit(1);
 }
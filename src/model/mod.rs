@@ -1,10 +0,0 @@
-mod filter;
-mod filter_file;
-mod program;
-mod provider;
-pub mod providers;
-
-pub use filter::{ChannelAttribute, FilterType, MovieAttribute, ProgramFilter};
-pub use filter_file::*;
-pub use program::{Channel, Movie, MovieBuilder, Program};
-pub use provider::Provider;
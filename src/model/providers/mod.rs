@@ -1,3 +0,0 @@
-mod tv_spielfilm;
-
-pub use tv_spielfilm::TvSpielfilm;
@@ -0,0 +1,86 @@
+use std::fmt::{Display, Formatter, Result};
+use std::io;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+pub enum Error {
+    Networking,
+    ParsingWebsite,
+    ParsingFile,
+    Speech,
+    ProviderUnavailable(Duration),
+    DecodingImage,
+    RenderingImage,
+    Encrypting,
+    Decrypting,
+    Login,
+    MissingFile(String),
+    InvalidFilter,
+    RemoteControl,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Error::Networking => write!(
+                f,
+                "A networking error occured. Are you connected to the internet?"
+            ),
+            Error::ParsingWebsite => {
+                write!(f, "Could not parse the website. Maybe it has changed?")
+            }
+            Error::ParsingFile => {
+                write!(f, "Could not parse the file about the filters.")
+            }
+            Error::ProviderUnavailable(remaining) => write!(
+                f,
+                "The provider is temporarily unavailable, retrying in {} min.",
+                remaining.as_secs().div_ceil(60)
+            ),
+            Error::DecodingImage => write!(f, "Could not decode a image."),
+            Error::RenderingImage => write!(f, "Could not render the image."),
+            Error::Encrypting => write!(f, "Could not encrypt the data."),
+            Error::Decrypting => {
+                write!(f, "Could not decrypt the data. Is the passphrase correct?")
+            }
+            Error::Login => write!(
+                f,
+                "Could not log in to the provider. Are the login and password in the preferences correct?"
+            ),
+            Error::MissingFile(path) => write!(f, "Could not read the file {}.", path),
+            Error::InvalidFilter => {
+                write!(f, "The filter cannot match a empty value or cannot be edited.")
+            }
+            Error::RemoteControl => write!(
+                f,
+                "Could not start the remote control. Is the computer connected to a network?"
+            ),
+            Error::Speech => {
+                write!(
+                    f,
+                    "Could not read out the text. Is speech-dispatcher installed?"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<reqwest::Error> for Error {
+    fn from(_error: reqwest::Error) -> Self {
+        Error::Networking
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(_error: io::Error) -> Self {
+        Error::ParsingFile
+    }
+}
+
+impl From<csv::Error> for Error {
+    fn from(_error: csv::Error) -> Self {
+        Error::ParsingFile
+    }
+}
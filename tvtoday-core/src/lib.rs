@@ -0,0 +1,6 @@
+//! The program of the television channels, how it is loaded from the providers, filtered and stored, without any user
+//! interface. The GUI of tvtoday is built on top of it, but other frontends can use it as well.
+mod error;
+pub mod model;
+
+pub use crate::error::Error;
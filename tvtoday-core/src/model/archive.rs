@@ -0,0 +1,165 @@
+use crate::model::{Movie, Program};
+
+use std::convert::TryFrom;
+
+use chrono::{Duration, NaiveDate};
+
+/// The format the date is stored in.
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// A broadcast that was seen in a loaded program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedBroadcast {
+    date: NaiveDate,
+    channel: String,
+    title: String,
+}
+
+/// The broadcasts of the previously loaded programs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Archive {
+    broadcasts: Vec<ArchivedBroadcast>,
+}
+
+impl ArchivedBroadcast {
+    /// Get the date of the broadcast.
+    pub fn get_date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// Get the name of the channel.
+    pub fn get_channel(&self) -> String {
+        self.channel.clone()
+    }
+
+    /// Get the title of the movie.
+    pub fn get_title(&self) -> String {
+        self.title.clone()
+    }
+}
+
+impl From<ArchivedBroadcast> for [String; 3] {
+    /// Convert from a `ArchivedBroadcast` to `[String; 3]`.
+    fn from(item: ArchivedBroadcast) -> [String; 3] {
+        [
+            item.date.format(DATE_FORMAT).to_string(),
+            item.channel,
+            item.title,
+        ]
+    }
+}
+
+impl TryFrom<[String; 3]> for ArchivedBroadcast {
+    type Error = ();
+
+    /// Try to convert from `[String; 3]` to a `ArchivedBroadcast`.
+    fn try_from(item: [String; 3]) -> Result<ArchivedBroadcast, ()> {
+        let date = NaiveDate::parse_from_str(&item[0], DATE_FORMAT).map_err(|_| ())?;
+
+        Ok(ArchivedBroadcast {
+            date,
+            channel: item[1].clone(),
+            title: item[2].clone(),
+        })
+    }
+}
+
+impl Archive {
+    /// Create a new, empty archive.
+    pub fn new() -> Self {
+        Archive { broadcasts: vec![] }
+    }
+
+    /// Add a archived broadcast if it is not already archived.
+    pub fn add(&mut self, broadcast: ArchivedBroadcast) {
+        if !self.broadcasts.contains(&broadcast) {
+            self.broadcasts.push(broadcast);
+        }
+    }
+
+    /// Archive all broadcasts of the program. Movies without a start time are archived at the given date.
+    pub fn add_program(&mut self, program: &Program, today: NaiveDate) {
+        for (channel, movie) in program.iter() {
+            self.add(ArchivedBroadcast {
+                date: date_of(movie, today),
+                channel: channel.get_name(),
+                title: movie.get_title(),
+            });
+        }
+    }
+
+    /// Remove all broadcasts before the given date.
+    pub fn prune(&mut self, before: NaiveDate) {
+        self.broadcasts.retain(|b| b.date >= before);
+    }
+
+    /// Weather the movie was already broadcasted at the given number of days before it. Movies without a start time are
+    /// expected to be broadcasted at the given date.
+    pub fn is_rerun(&self, movie: &Movie, today: NaiveDate, days: u32) -> bool {
+        let date = date_of(movie, today);
+        let since = date - Duration::days(days as i64);
+
+        self.broadcasts
+            .iter()
+            .any(|b| b.title == movie.get_title() && since <= b.date && b.date < date)
+    }
+
+    /// Turn the archive into a iterator.
+    pub fn iter(&self) -> std::slice::Iter<'_, ArchivedBroadcast> {
+        self.broadcasts.iter()
+    }
+}
+
+impl Default for Archive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The date the movie is broadcasted at.
+fn date_of(movie: &Movie, today: NaiveDate) -> NaiveDate {
+    movie.get_start().map(|s| s.date()).unwrap_or(today)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::{Channel, MovieBuilder};
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2021, 4, day).unwrap()
+    }
+
+    fn movie(title: &str, date: NaiveDate) -> Movie {
+        let mut builder = MovieBuilder::new(title);
+        builder.with_start(date.and_hms_opt(20, 15, 0).unwrap());
+        builder.build()
+    }
+
+    #[test]
+    fn test_is_rerun() {
+        let mut archive = Archive::new();
+        let program: Program = vec![
+            (Channel::new("ZDF"), movie("Old", day(1))),
+            (Channel::new("ZDF"), movie("Recent", day(15))),
+        ]
+        .into_iter()
+        .collect();
+        archive.add_program(&program, day(15));
+
+        assert!(archive.is_rerun(&movie("Recent", day(20)), day(20), 14));
+        assert!(!archive.is_rerun(&movie("Old", day(20)), day(20), 14));
+        assert!(!archive.is_rerun(&movie("Recent", day(15)), day(15), 14));
+        assert!(!archive.is_rerun(&movie("New", day(20)), day(20), 14));
+
+        archive.prune(day(10));
+        assert_eq!(
+            archive
+                .iter()
+                .map(|b| b.get_title())
+                .collect::<Vec<String>>(),
+            vec!["Recent"]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use crate::model::{paths, Settings};
+
+use std::fs;
+use std::io;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use serde_json::{json, Value};
+
+/// The number of bytes in a mebibyte, the unit of the limits in the settings.
+const MIB: u64 = 1024 * 1024;
+
+/// The size of the decoded channel icons of the shown program in bytes.
+static ICON_SIZE: AtomicUsize = AtomicUsize::new(0);
+/// The size of the cached descriptions in bytes.
+static DESCRIPTION_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+/// The limits of the caches in bytes, set from the settings.
+static ICON_LIMIT: AtomicUsize = AtomicUsize::new(8 * MIB as usize);
+static DESCRIPTION_LIMIT: AtomicUsize = AtomicUsize::new(2 * MIB as usize);
+static DISK_LIMIT: AtomicU64 = AtomicU64::new(50 * MIB);
+
+/// Set the limits of the caches from the settings. Caches above a new limit shrink once they are used again.
+pub fn set_limits(settings: &Settings) {
+    ICON_LIMIT.store(
+        settings.get_icon_cache_limit() as usize * MIB as usize,
+        Ordering::Relaxed,
+    );
+    DESCRIPTION_LIMIT.store(
+        settings.get_description_cache_limit() as usize * MIB as usize,
+        Ordering::Relaxed,
+    );
+    DISK_LIMIT.store(
+        settings.get_disk_cache_limit() as u64 * MIB,
+        Ordering::Relaxed,
+    );
+}
+
+/// Get the limit of the decoded channel icons in memory in bytes.
+pub fn get_icon_limit() -> usize {
+    ICON_LIMIT.load(Ordering::Relaxed)
+}
+
+/// Get the limit of the descriptions and other information cached in memory in bytes.
+pub fn get_description_limit() -> usize {
+    DESCRIPTION_LIMIT.load(Ordering::Relaxed)
+}
+
+/// Get the limit of the files in the cache directory in bytes.
+pub fn get_disk_limit() -> u64 {
+    DISK_LIMIT.load(Ordering::Relaxed)
+}
+
+/// Set the size of the decoded channel icons of the shown program in bytes.
+pub fn set_icon_size(size: usize) {
+    ICON_SIZE.store(size, Ordering::Relaxed);
+}
+
+/// Set the size of the cached descriptions in bytes.
+pub fn set_description_size(size: usize) {
+    DESCRIPTION_SIZE.store(size, Ordering::Relaxed);
+}
+
+/// Remove the least recently modified files of the cache directory until it fits into its limit.
+pub fn prune_disk_cache() -> io::Result<()> {
+    let mut files = vec![];
+    for entry in fs::read_dir(paths::user_cache_dir())? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            files.push((metadata.modified()?, metadata.len(), entry.path()));
+        }
+    }
+    files.sort();
+
+    let mut size: u64 = files.iter().map(|(_m, len, _p)| len).sum();
+    for (_modified, len, path) in files {
+        if size <= get_disk_limit() {
+            break;
+        }
+        fs::remove_file(path)?;
+        size -= len;
+    }
+
+    Ok(())
+}
+
+/// Get the size of the files in the cache directory in bytes.
+fn disk_size() -> u64 {
+    fs::read_dir(paths::user_cache_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok()?.metadata().ok())
+                .filter(|m| m.is_file())
+                .map(|m| m.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// The size of the caches in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheUsage {
+    disk: u64,
+    icons: usize,
+    descriptions: usize,
+}
+
+impl CacheUsage {
+    /// Get the current size of the caches.
+    pub fn current() -> Self {
+        CacheUsage {
+            disk: disk_size(),
+            icons: ICON_SIZE.load(Ordering::Relaxed),
+            descriptions: DESCRIPTION_SIZE.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Get the size of the files in the cache directory, e.g. the downloaded channel icons.
+    pub fn get_disk(&self) -> u64 {
+        self.disk
+    }
+
+    /// Get the size of the decoded channel icons of the shown program.
+    pub fn get_icons(&self) -> usize {
+        self.icons
+    }
+
+    /// Get the size of the descriptions and other information of the movies cached in memory.
+    pub fn get_descriptions(&self) -> usize {
+        self.descriptions
+    }
+}
+
+impl From<CacheUsage> for Value {
+    /// Convert from `CacheUsage` to a JSON object with the size and limit of each cache in bytes.
+    fn from(item: CacheUsage) -> Value {
+        json!({
+            "disk": {"size": item.disk, "limit": get_disk_limit()},
+            "icons": {"size": item.icons, "limit": get_icon_limit()},
+            "descriptions": {"size": item.descriptions, "limit": get_description_limit()},
+        })
+    }
+}
+
+/// A cache in memory limited by the size of its values in bytes. The least recently used values are evicted first.
+pub struct SizedCache<K, V> {
+    /// The entries with their size, the most recently used last.
+    entries: Vec<(K, V, usize)>,
+    size: usize,
+    limit: usize,
+}
+
+impl<K: PartialEq, V: Clone> SizedCache<K, V> {
+    /// Create a empty cache with the limit in bytes.
+    pub fn new(limit: usize) -> Self {
+        SizedCache {
+            entries: vec![],
+            size: 0,
+            limit,
+        }
+    }
+
+    /// Get a copy of the value of the key, marking it as recently used.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let index = self.entries.iter().position(|(k, _v, _s)| k == key)?;
+        let entry = self.entries.remove(index);
+        let value = entry.1.clone();
+        self.entries.push(entry);
+        Some(value)
+    }
+
+    /// Insert the value of the key with its size in bytes, evicting the least recently used values above the limit.
+    /// Values larger than the limit are not cached.
+    pub fn insert(&mut self, key: K, value: V, size: usize) {
+        if let Some(index) = self.entries.iter().position(|(k, _v, _s)| k == &key) {
+            let (_k, _v, old_size) = self.entries.remove(index);
+            self.size -= old_size;
+        }
+
+        if size <= self.limit {
+            self.entries.push((key, value, size));
+            self.size += size;
+        }
+        self.evict();
+    }
+
+    /// Set the limit in bytes, evicting the least recently used values above it.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.evict();
+    }
+
+    /// Get the size of all values in bytes.
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    /// Remove the least recently used values until the cache fits into its limit.
+    fn evict(&mut self) {
+        while self.size > self.limit && !self.entries.is_empty() {
+            let (_k, _v, size) = self.entries.remove(0);
+            self.size -= size;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sized_cache() {
+        let mut cache = SizedCache::new(10);
+        cache.insert("a", 1, 4);
+        cache.insert("b", 2, 4);
+        assert_eq!(cache.get_size(), 8);
+
+        // "a" was used more recently than "b", so "b" is evicted.
+        assert_eq!(cache.get(&"a"), Some(1));
+        cache.insert("c", 3, 4);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"c"), Some(3));
+        assert_eq!(cache.get_size(), 8);
+
+        // Replacing a value updates its size.
+        cache.insert("c", 4, 2);
+        assert_eq!(cache.get(&"c"), Some(4));
+        assert_eq!(cache.get_size(), 6);
+
+        // Too large values are not cached.
+        cache.insert("d", 5, 11);
+        assert_eq!(cache.get(&"d"), None);
+        assert_eq!(cache.get_size(), 6);
+
+        cache.set_limit(3);
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(cache.get(&"c"), Some(4));
+        assert_eq!(cache.get_size(), 2);
+    }
+}
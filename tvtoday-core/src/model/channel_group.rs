@@ -0,0 +1,108 @@
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter, Result};
+
+/// A built-in group of channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelGroup {
+    /// Channels of the public broadcasters.
+    Public,
+    /// Channels of private broadcasters.
+    Private,
+    /// The regional channels of the public broadcasters.
+    Regional,
+    /// News channels.
+    News,
+}
+
+/// The known channels and their group.
+const CHANNEL_GROUPS: &[(&str, ChannelGroup)] = &[
+    ("Das Erste", ChannelGroup::Public),
+    ("ZDF", ChannelGroup::Public),
+    ("3sat", ChannelGroup::Public),
+    ("ARTE", ChannelGroup::Public),
+    ("ZDFneo", ChannelGroup::Public),
+    ("ONE", ChannelGroup::Public),
+    ("KiKA", ChannelGroup::Public),
+    ("RTL", ChannelGroup::Private),
+    ("SAT.1", ChannelGroup::Private),
+    ("ProSieben", ChannelGroup::Private),
+    ("kabel eins", ChannelGroup::Private),
+    ("RTL II", ChannelGroup::Private),
+    ("VOX", ChannelGroup::Private),
+    ("TELE 5", ChannelGroup::Private),
+    ("ServusTV Deutschland", ChannelGroup::Private),
+    ("NITRO", ChannelGroup::Private),
+    ("DMAX", ChannelGroup::Private),
+    ("sixx", ChannelGroup::Private),
+    ("SAT.1 Gold", ChannelGroup::Private),
+    ("ProSieben MAXX", ChannelGroup::Private),
+    ("COMEDY CENTRAL", ChannelGroup::Private),
+    ("RTLplus", ChannelGroup::Private),
+    ("Super RTL", ChannelGroup::Private),
+    ("WDR", ChannelGroup::Regional),
+    ("NDR", ChannelGroup::Regional),
+    ("BR", ChannelGroup::Regional),
+    ("SWR/SR", ChannelGroup::Regional),
+    ("HR", ChannelGroup::Regional),
+    ("MDR", ChannelGroup::Regional),
+    ("RBB", ChannelGroup::Regional),
+    ("tv.berlin", ChannelGroup::Regional),
+    ("phoenix", ChannelGroup::News),
+    ("tagesschau24", ChannelGroup::News),
+    ("ZDFinfo", ChannelGroup::News),
+    ("n-tv", ChannelGroup::News),
+    ("WELT", ChannelGroup::News),
+];
+
+impl ChannelGroup {
+    /// All channel groups.
+    pub const ALL: [ChannelGroup; 4] = [
+        ChannelGroup::Public,
+        ChannelGroup::Private,
+        ChannelGroup::Regional,
+        ChannelGroup::News,
+    ];
+
+    /// Get the group of the channel with the given name if the channel is known.
+    pub fn of(channel_name: &str) -> Option<ChannelGroup> {
+        CHANNEL_GROUPS
+            .iter()
+            .find(|(name, _g)| name == &channel_name)
+            .map(|(_name, group)| *group)
+    }
+
+    /// Get a human readable name of the group.
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ChannelGroup::Public => "Public channels",
+            ChannelGroup::Private => "Private channels",
+            ChannelGroup::Regional => "Regional channels",
+            ChannelGroup::News => "News channels",
+        }
+    }
+}
+
+impl Display for ChannelGroup {
+    /// The identifier of the group, used for storing it.
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            ChannelGroup::Public => write!(f, "public"),
+            ChannelGroup::Private => write!(f, "private"),
+            ChannelGroup::Regional => write!(f, "regional"),
+            ChannelGroup::News => write!(f, "news"),
+        }
+    }
+}
+
+impl TryFrom<&str> for ChannelGroup {
+    type Error = ();
+
+    /// Try to convert from the identifier of the group to the `ChannelGroup`.
+    fn try_from(item: &str) -> std::result::Result<ChannelGroup, ()> {
+        ChannelGroup::ALL
+            .iter()
+            .find(|g| g.to_string() == item)
+            .cloned()
+            .ok_or(())
+    }
+}
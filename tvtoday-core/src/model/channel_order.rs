@@ -0,0 +1,123 @@
+use crate::model::{Channel, Movie, Program, SortOrder};
+
+/// The order of the channels chosen by the user and the favorite channels, shown at the top of the list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelOrder {
+    channels: Vec<String>,
+    favorites: Vec<String>,
+}
+
+impl ChannelOrder {
+    /// Create a new order without any channels, keeping the order of the provider.
+    pub fn new() -> Self {
+        ChannelOrder {
+            channels: vec![],
+            favorites: vec![],
+        }
+    }
+
+    /// Get the names of the ordered channels.
+    pub fn get_channels(&self) -> Vec<String> {
+        self.channels.clone()
+    }
+
+    /// Set the names of the ordered channels.
+    pub fn set_channels(&mut self, channels: Vec<String>) {
+        self.channels = channels
+    }
+
+    /// Weather the channel with the given name is a favorite.
+    pub fn is_favorite(&self, channel_name: &str) -> bool {
+        self.favorites.iter().any(|f| f == channel_name)
+    }
+
+    /// Set weather the channel with the given name is a favorite.
+    pub fn set_favorite(&mut self, channel_name: &str, favorite: bool) {
+        self.favorites.retain(|f| f != channel_name);
+        if favorite {
+            self.favorites.push(channel_name.to_string());
+        }
+    }
+
+    /// Get the given channels in this order. Channels not ordered yet are appended in their given order.
+    pub fn arrange(&self, channels: &[String]) -> Vec<String> {
+        let mut result = self.channels.clone();
+        for channel in channels {
+            if !result.contains(channel) {
+                result.push(channel.clone());
+            }
+        }
+        result
+    }
+
+    /// Get the program with the favorite channels at the top. If it is sorted by channel, the channels are also put in
+    /// this order, channels not ordered yet follow in the order of the provider.
+    pub fn apply(&self, program: &Program, order: SortOrder) -> Program {
+        let mut entries: Vec<(Channel, Movie)> = program.iter().cloned().collect();
+        entries.sort_by_key(|(c, _m)| {
+            let name = c.get_name();
+            let position = if order == SortOrder::Channel {
+                self.channels
+                    .iter()
+                    .position(|n| n == &name)
+                    .unwrap_or(self.channels.len())
+            } else {
+                0
+            };
+            (!self.is_favorite(&name), position)
+        });
+        entries.into_iter().collect()
+    }
+}
+
+impl Default for ChannelOrder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn titles(program: &Program) -> Vec<String> {
+        program.iter().map(|(_c, m)| m.get_title()).collect()
+    }
+
+    #[test]
+    fn test_apply() {
+        let program: Program = vec![
+            (Channel::new("Das Erste"), Movie::new("A")),
+            (Channel::new("ZDF"), Movie::new("B")),
+            (Channel::new("RTL"), Movie::new("C")),
+            (Channel::new("ARTE"), Movie::new("D")),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut order = ChannelOrder::new();
+        assert_eq!(
+            titles(&order.apply(&program, SortOrder::Channel)),
+            vec!["A", "B", "C", "D"]
+        );
+
+        order.set_channels(vec!["RTL".to_string(), "ZDF".to_string()]);
+        order.set_favorite("ARTE", true);
+        assert_eq!(
+            titles(&order.apply(&program, SortOrder::Channel)),
+            vec!["D", "C", "B", "A"]
+        );
+        // Other orders are kept, only the favorites are moved to the top.
+        assert_eq!(
+            titles(&order.apply(&program, SortOrder::Title)),
+            vec!["D", "A", "B", "C"]
+        );
+
+        order.set_favorite("ARTE", false);
+        assert!(!order.is_favorite("ARTE"));
+        assert_eq!(
+            order.arrange(&["ARTE".to_string(), "ZDF".to_string()]),
+            vec!["RTL", "ZDF", "ARTE"]
+        );
+    }
+}
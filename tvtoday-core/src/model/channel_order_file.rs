@@ -0,0 +1,56 @@
+use crate::model::{encryption, ChannelOrder};
+use crate::Error;
+
+use std::path::Path;
+
+use csv::{ReaderBuilder, Writer};
+
+impl ChannelOrder {
+    /// Write the order to a file at the given path. Each line consists of a channel name and weather it is a favorite,
+    /// in the order of the channels.
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let mut writer = Writer::from_writer(vec![]);
+
+        for channel in self.get_channels() {
+            let favorite = self.is_favorite(&channel).to_string();
+            writer.write_record(&[channel, favorite])?;
+        }
+
+        let content = writer.into_inner().map_err(|_| Error::ParsingFile)?;
+
+        encryption::write_file(path, &content)
+    }
+
+    /// Read the order from a file at the given path.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = encryption::read_file(path)?;
+
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(&content[..]);
+
+        let mut order = ChannelOrder::new();
+        let mut channels = vec![];
+
+        for record_res in reader.records() {
+            let record = record_res?;
+
+            if record.len() != 2 {
+                return Err(Error::ParsingFile);
+            }
+
+            let channel = record.get(0).unwrap();
+            let favorite = record
+                .get(1)
+                .unwrap()
+                .parse()
+                .map_err(|_| Error::ParsingFile)?;
+            order.set_favorite(channel, favorite);
+            channels.push(channel.to_string());
+        }
+
+        order.set_channels(channels);
+
+        Ok(order)
+    }
+}
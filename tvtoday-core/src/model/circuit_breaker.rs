@@ -0,0 +1,204 @@
+use crate::model::{Movie, ParseFailure, Program, Provider, TimeSlot};
+use crate::Error;
+
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+/// The default number of consecutive failures after which the provider is not requested anymore.
+const DEFAULT_THRESHOLD: u32 = 3;
+/// The default time the provider is not requested after too many failures.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+/// A provider that stops requesting the wrapped provider for a cooldown period after it failed repeatedly.
+pub struct CircuitBreaker<P: Provider> {
+    provider: P,
+    threshold: u32,
+    cooldown: Duration,
+    /// The number of consecutive failures.
+    failures: u32,
+    /// Until when the wrapped provider is not requested.
+    open_until: Option<Instant>,
+}
+
+impl<P: Provider> CircuitBreaker<P> {
+    /// Wrap the provider, not requesting it for `cooldown` after `threshold` consecutive failures.
+    pub fn with_config(provider: P, threshold: u32, cooldown: Duration) -> Self {
+        CircuitBreaker {
+            provider,
+            threshold: threshold.max(1),
+            cooldown,
+            failures: 0,
+            open_until: None,
+        }
+    }
+
+    /// The remaining time the wrapped provider is not requested, if any.
+    fn remaining(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.open_until
+            .filter(|until| until > &now)
+            .map(|until| until - now)
+    }
+}
+
+#[async_trait]
+impl<P: Provider + Sync> Provider for CircuitBreaker<P> {
+    fn new() -> Self {
+        CircuitBreaker::with_config(P::new(), DEFAULT_THRESHOLD, DEFAULT_COOLDOWN)
+    }
+
+    fn clone(&self) -> Self {
+        CircuitBreaker {
+            provider: self.provider.clone(),
+            threshold: self.threshold,
+            cooldown: self.cooldown,
+            failures: self.failures,
+            open_until: self.open_until,
+        }
+    }
+
+    /// Get the program. Fails with `Error::ProviderUnavailable` without requesting the wrapped provider during the
+    /// cooldown.
+    async fn get_program(&mut self) -> Result<Program, Error> {
+        self.get_program_progressively(&|_| {}).await
+    }
+
+    /// Get the program progressively, failing like `get_program` during the cooldown.
+    async fn get_program_progressively(
+        &mut self,
+        on_part: &(dyn Fn(Program) + Send + Sync),
+    ) -> Result<Program, Error> {
+        if let Some(remaining) = self.remaining() {
+            return Err(Error::ProviderUnavailable(remaining));
+        }
+
+        match self.provider.get_program_progressively(on_part).await {
+            Ok(program) => {
+                self.failures = 0;
+                self.open_until = None;
+                Ok(program)
+            }
+            Err(error) => {
+                self.failures += 1;
+                if self.failures < self.threshold {
+                    return Err(error);
+                }
+
+                self.failures = 0;
+                self.open_until = Some(Instant::now() + self.cooldown);
+                Err(Error::ProviderUnavailable(self.cooldown))
+            }
+        }
+    }
+
+    /// Get more information about the movie. Fails without a request during the cooldown.
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+        if let Some(remaining) = self.remaining() {
+            return Err(Error::ProviderUnavailable(remaining));
+        }
+
+        self.provider.get_more_information(movie).await
+    }
+
+    fn get_url(&self, movie: &Movie) -> Option<String> {
+        self.provider.get_url(movie)
+    }
+
+    fn get_parse_failure(&self) -> Option<ParseFailure> {
+        self.provider.get_parse_failure()
+    }
+
+    fn set_credentials(&mut self, login: &str, password: &str) {
+        self.provider.set_credentials(login, password)
+    }
+
+    fn set_server(&mut self, url: &str) {
+        self.provider.set_server(url)
+    }
+
+    fn set_time_slot(&mut self, time_slot: TimeSlot) {
+        self.provider.set_time_slot(time_slot)
+    }
+
+    fn set_days_ahead(&mut self, days: u32) {
+        self.provider.set_days_ahead(days)
+    }
+
+    /// Get the icons. Fails with `Error::ProviderUnavailable` without requesting the wrapped provider during the
+    /// cooldown.
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+        if let Some(remaining) = self.remaining() {
+            return Err(Error::ProviderUnavailable(remaining));
+        }
+
+        self.provider.get_icons(program).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use tokio::runtime::Runtime;
+
+    /// A provider that always fails.
+    struct Failing;
+
+    #[async_trait]
+    impl Provider for Failing {
+        fn new() -> Self {
+            Failing
+        }
+
+        fn clone(&self) -> Self {
+            Failing
+        }
+
+        async fn get_program(&mut self) -> Result<Program, Error> {
+            Err(Error::Networking)
+        }
+
+        async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+            Ok(movie.clone())
+        }
+
+        fn get_url(&self, _movie: &Movie) -> Option<String> {
+            None
+        }
+
+        fn set_time_slot(&mut self, _time_slot: TimeSlot) {}
+
+        fn set_days_ahead(&mut self, _days: u32) {}
+
+        async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+            Ok(program.clone())
+        }
+    }
+
+    #[test]
+    fn test_circuit_breaker() {
+        let rt = Runtime::new().unwrap();
+
+        let mut breaker = CircuitBreaker::with_config(Failing, 2, Duration::from_secs(60));
+        assert!(matches!(
+            rt.block_on(breaker.get_program()),
+            Err(Error::Networking)
+        ));
+        assert!(matches!(
+            rt.block_on(breaker.get_program()),
+            Err(Error::ProviderUnavailable(_))
+        ));
+        assert!(matches!(
+            rt.block_on(breaker.get_program()),
+            Err(Error::ProviderUnavailable(_))
+        ));
+
+        let mut breaker = CircuitBreaker::with_config(Failing, 1, Duration::from_secs(0));
+        assert!(matches!(
+            rt.block_on(breaker.get_program()),
+            Err(Error::ProviderUnavailable(_))
+        ));
+        assert!(breaker.remaining().is_none());
+    }
+}
@@ -0,0 +1,80 @@
+use std::time::Instant;
+
+use chrono::{Local, NaiveDateTime};
+
+/// How far the local time may differ from the elapsed time before it is considered a jump of the clock in seconds.
+const JUMP_TOLERANCE: i64 = 60;
+
+/// Detects jumps of the local time, e.g. when the clock is synced with NTP, the timezone changes or the system resumes
+/// from suspend, by comparing it with the time elapsed on a monotonic clock.
+#[derive(Debug, Clone)]
+pub struct ClockWatch {
+    local: NaiveDateTime,
+    monotonic: Instant,
+}
+
+impl ClockWatch {
+    /// Start watching the clock from now on.
+    pub fn new() -> Self {
+        ClockWatch::at(Local::now().naive_local(), Instant::now())
+    }
+
+    /// Start watching the clock from the given times.
+    fn at(local: NaiveDateTime, monotonic: Instant) -> Self {
+        ClockWatch { local, monotonic }
+    }
+
+    /// Check whether the local time jumped since the last check.
+    pub fn jumped(&mut self) -> bool {
+        self.jumped_at(Local::now().naive_local(), Instant::now())
+    }
+
+    /// Check whether the local time jumped since the last check, given the current times.
+    fn jumped_at(&mut self, local: NaiveDateTime, monotonic: Instant) -> bool {
+        let elapsed = monotonic.saturating_duration_since(self.monotonic);
+        let expected = self.local
+            + chrono::Duration::from_std(elapsed).unwrap_or_else(|_| chrono::Duration::zero());
+
+        self.local = local;
+        self.monotonic = monotonic;
+
+        (local - expected).num_seconds().abs() > JUMP_TOLERANCE
+    }
+}
+
+impl Default for ClockWatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::time::Duration;
+
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_jumped() {
+        let start = NaiveDate::from_ymd_opt(2021, 4, 20)
+            .unwrap()
+            .and_hms_opt(20, 0, 0)
+            .unwrap();
+        let monotonic = Instant::now();
+        let mut clock = ClockWatch::at(start, monotonic);
+
+        let later = |secs| monotonic + Duration::from_secs(secs);
+        let local = |secs| start + chrono::Duration::seconds(secs);
+
+        assert!(!clock.jumped_at(local(30), later(30)));
+        // Small corrections are no jump.
+        assert!(!clock.jumped_at(local(70), later(60)));
+        // The timezone changed by a hour.
+        assert!(clock.jumped_at(local(3690), later(90)));
+        // The clock is compared to the last check.
+        assert!(!clock.jumped_at(local(3720), later(120)));
+        assert!(clock.jumped_at(local(0), later(150)));
+    }
+}
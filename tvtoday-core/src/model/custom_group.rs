@@ -0,0 +1,118 @@
+use crate::model::{Channel, ChannelAttribute, FilterType};
+
+/// A named set of channels defined by the user, e.g. "Kids".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomGroup {
+    name: String,
+    channels: Vec<String>,
+}
+
+/// All channel groups defined by the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomGroups {
+    groups: Vec<CustomGroup>,
+}
+
+impl CustomGroup {
+    /// Create a new group with the given name and no channels.
+    pub fn new(name: &str) -> Self {
+        CustomGroup {
+            name: name.to_string(),
+            channels: vec![],
+        }
+    }
+
+    /// Get the name of the group.
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Get the names of the channels in the group.
+    pub fn get_channels(&self) -> Vec<String> {
+        self.channels.clone()
+    }
+
+    /// Add the channel with the given name to the group.
+    pub fn add_channel(&mut self, channel_name: &str) {
+        if !self.channels.iter().any(|c| c == channel_name) {
+            self.channels.push(channel_name.to_string());
+        }
+    }
+
+    /// Weather the channel is part of the group.
+    pub fn contains(&self, channel: &Channel) -> bool {
+        self.channels.contains(&channel.get_name())
+    }
+
+    /// Get the filters blocking all channels of the group.
+    pub fn to_filters(&self) -> Vec<FilterType> {
+        self.channels
+            .iter()
+            .map(|c| FilterType::Channel(ChannelAttribute::Name(c.clone())))
+            .collect()
+    }
+}
+
+impl CustomGroups {
+    /// Create a new, empty list of groups.
+    pub fn new() -> Self {
+        CustomGroups { groups: vec![] }
+    }
+
+    /// Add the group. A existing group with the same name is replaced.
+    pub fn set(&mut self, group: CustomGroup) {
+        if let Some(existing) = self.groups.iter_mut().find(|g| g.name == group.name) {
+            *existing = group;
+        } else {
+            self.groups.push(group);
+        }
+    }
+
+    /// Remove the group with the given name.
+    pub fn remove(&mut self, name: &str) {
+        self.groups.retain(|g| g.name != name);
+    }
+
+    /// Get the group with the given name.
+    pub fn get(&self, name: &str) -> Option<&CustomGroup> {
+        self.groups.iter().find(|g| g.name == name)
+    }
+
+    /// Turn the groups into a iterator.
+    pub fn iter(&self) -> std::slice::Iter<'_, CustomGroup> {
+        self.groups.iter()
+    }
+}
+
+impl Default for CustomGroups {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_set() {
+        let mut kids = CustomGroup::new("Kids");
+        kids.add_channel("KiKA");
+        kids.add_channel("KiKA");
+
+        let mut groups = CustomGroups::new();
+        groups.set(kids.clone());
+        assert_eq!(groups.get("Kids").unwrap().get_channels(), vec!["KiKA"]);
+
+        kids.add_channel("Super RTL");
+        groups.set(kids);
+        assert_eq!(groups.iter().count(), 1);
+        assert!(groups
+            .get("Kids")
+            .unwrap()
+            .contains(&Channel::new("Super RTL")));
+
+        groups.remove("Kids");
+        assert!(groups.get("Kids").is_none());
+    }
+}
@@ -0,0 +1,52 @@
+use crate::model::{encryption, CustomGroup, CustomGroups};
+use crate::Error;
+
+use std::path::Path;
+
+use csv::{ReaderBuilder, Writer};
+
+impl CustomGroups {
+    /// Write the groups to a file at the given path. Each line consists of the group name and a channel name.
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let mut writer = Writer::from_writer(vec![]);
+
+        for group in self.iter() {
+            for channel in group.get_channels() {
+                writer.write_record(&[group.get_name(), channel])?;
+            }
+        }
+
+        let content = writer.into_inner().map_err(|_| Error::ParsingFile)?;
+
+        encryption::write_file(path, &content)
+    }
+
+    /// Read the groups from a file at the given path.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = encryption::read_file(path)?;
+
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(&content[..]);
+
+        let mut groups = CustomGroups::new();
+
+        for record_res in reader.records() {
+            let record = record_res?;
+
+            if record.len() != 2 {
+                return Err(Error::ParsingFile);
+            }
+
+            let name = record.get(0).unwrap();
+            let mut group = groups
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| CustomGroup::new(name));
+            group.add_channel(record.get(1).unwrap());
+            groups.set(group);
+        }
+
+        Ok(groups)
+    }
+}
@@ -0,0 +1,112 @@
+use crate::model::{Channel, Movie, Program};
+
+use chrono::{Duration, NaiveDate};
+
+/// The number of days covered by the weekly digest.
+pub const DIGEST_DAYS: u32 = 7;
+
+/// Create a HTML digest of the broadcasts starting in the given number of days from the first date, grouped by day.
+/// Broadcasts without a start time or outside of the days are left out, the same broadcast is listed only once.
+pub fn digest_html(program: &Program, first: NaiveDate, days: u32) -> String {
+    let mut entries: Vec<&(Channel, Movie)> = vec![];
+    for entry in program.iter() {
+        let duplicate = entries.iter().any(|(c, m)| {
+            c.get_name() == entry.0.get_name()
+                && m.get_title() == entry.1.get_title()
+                && m.get_start() == entry.1.get_start()
+        });
+        if !duplicate {
+            entries.push(entry);
+        }
+    }
+    entries.sort_by_key(|(_c, m)| m.get_start());
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!(
+        "<title>TV Today: {} to {}</title>\n</head>\n<body>\n",
+        first.format("%d.%m."),
+        (first + Duration::days(days as i64 - 1)).format("%d.%m.")
+    ));
+    html.push_str(&format!("<h1>The next {} days</h1>\n", days));
+
+    for offset in 0..days {
+        let date = first + Duration::days(offset as i64);
+        html.push_str(&format!("<h2>{}</h2>\n", date.format("%A, %d.%m.")));
+
+        let broadcasts: Vec<String> = entries
+            .iter()
+            .filter_map(|(c, m)| {
+                let start = m.get_start().filter(|s| s.date() == date)?;
+                Some(format!(
+                    "<li><b>{}</b> {} ({})</li>\n",
+                    start.format("%H:%M"),
+                    escape(&m.get_title()),
+                    escape(&c.get_name())
+                ))
+            })
+            .collect();
+
+        if broadcasts.is_empty() {
+            html.push_str("<p>Nothing found.</p>\n");
+        } else {
+            html.push_str("<ul>\n");
+            html.push_str(&broadcasts.concat());
+            html.push_str("</ul>\n");
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Escape the text to be used as content of a HTML element.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::MovieBuilder;
+
+    fn movie(title: &str, day: u32, hour: u32) -> Movie {
+        let mut builder = MovieBuilder::new(title);
+        builder.with_start(
+            NaiveDate::from_ymd_opt(2021, 4, day)
+                .unwrap()
+                .and_hms_opt(hour, 15, 0)
+                .unwrap(),
+        );
+        builder.build()
+    }
+
+    #[test]
+    fn test_digest_html() {
+        let program: Program = vec![
+            (Channel::new("ZDF"), movie("Late", 20, 22)),
+            (Channel::new("Das Erste"), movie("Tom & Jerry", 20, 20)),
+            (Channel::new("ZDF"), movie("Late", 20, 22)),
+            (Channel::new("ARTE"), movie("Too late", 23, 20)),
+            (Channel::new("ARTE"), Movie::new("Unknown")),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            digest_html(&program, NaiveDate::from_ymd_opt(2021, 4, 20).unwrap(), 2),
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>TV Today: 20.04. to 21.04.</title>\n</head>\n<body>\n\
+             <h1>The next 2 days</h1>\n\
+             <h2>Tuesday, 20.04.</h2>\n<ul>\n\
+             <li><b>20:15</b> Tom &amp; Jerry (Das Erste)</li>\n\
+             <li><b>22:15</b> Late (ZDF)</li>\n\
+             </ul>\n\
+             <h2>Wednesday, 21.04.</h2>\n<p>Nothing found.</p>\n\
+             </body>\n</html>\n"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+/// The byte order mark some editors on Windows put at the start of UTF-8 files.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Normalize the content of a file edited by hand, e.g. on Windows, to UTF-8 with `\n` line endings.
+///
+/// A UTF-8 byte order mark is removed, UTF-16 with a byte order mark is converted and content that is no valid UTF-8 is
+/// read as Windows-1252, the usual encoding of older Windows editors. Windows (`\r\n`) and old Mac (`\r`) line endings
+/// are replaced by `\n`.
+pub fn normalize(content: &[u8]) -> String {
+    let text = if let Some(rest) = content.strip_prefix(UTF8_BOM) {
+        String::from_utf8_lossy(rest).to_string()
+    } else if let Some(rest) = content.strip_prefix(b"\xFF\xFE") {
+        utf16(rest, u16::from_le_bytes)
+    } else if let Some(rest) = content.strip_prefix(b"\xFE\xFF") {
+        utf16(rest, u16::from_be_bytes)
+    } else {
+        match std::str::from_utf8(content) {
+            Ok(text) => text.to_string(),
+            Err(_) => content.iter().map(|b| windows_1252(*b)).collect(),
+        }
+    };
+
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Decode UTF-16 with the given byte order, replacing invalid characters.
+fn utf16(content: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = content
+        .chunks_exact(2)
+        .map(|c| from_bytes([c[0], c[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Decode a byte in Windows-1252. It only differs from Latin-1 in the range 0x80 to 0x9F, where the characters used in
+/// german texts are mapped.
+fn windows_1252(byte: u8) -> char {
+    match byte {
+        0x80 => '€',
+        0x84 => '„',
+        0x85 => '…',
+        0x91 => '‘',
+        0x92 => '’',
+        0x93 => '“',
+        0x94 => '”',
+        0x96 => '–',
+        0x97 => '—',
+        _ => byte as char,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(b"a,b\nc,d\n"), "a,b\nc,d\n");
+        assert_eq!(normalize(b"\xEF\xBB\xBFa,b\r\nc,d\r\n"), "a,b\nc,d\n");
+        assert_eq!(normalize(b"a\rb"), "a\nb");
+        assert_eq!(normalize(b"\xFF\xFEa\x00\xE4\x00\r\x00\n\x00"), "a\u{e4}\n");
+        assert_eq!(normalize(b"\xFE\xFF\x00a\x00\xE4"), "a\u{e4}");
+        assert_eq!(normalize(b"M\xE4rchen \x84Tatort\x93"), "Märchen „Tatort“");
+        assert_eq!(normalize(b""), "");
+    }
+}
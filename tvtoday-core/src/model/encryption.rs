@@ -0,0 +1,332 @@
+use crate::model::paths;
+use crate::Error;
+
+use std::fs;
+use std::io;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// The start of encrypted files, followed by the nonce and the encrypted content.
+const MAGIC: &[u8] = b"tvtoday-encrypted-1\n";
+
+/// The file in the data directory containing the salt of the passphrase. It is stored next to the data, so devices
+/// syncing the data directory can unlock it with the same passphrase.
+const SALT_FILE: &str = "encryption.salt";
+/// The file in the data directory containing a known text, used to check the passphrase on unlocking.
+const CHECK_FILE: &str = "encryption.check";
+const CHECK_TEXT: &[u8] = b"tvtoday";
+
+/// The permissions of files only readable by the user.
+#[cfg(unix)]
+const PRIVATE_MODE: u32 = 0o600;
+
+/// The extension appended to the files while they are written, before they replace the previous ones.
+const STAGING_EXTENSION: &str = "new";
+
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+/// The key the user data is encrypted with, if it is unlocked.
+static KEY: Mutex<Option<[u8; KEY_LEN]>> = Mutex::new(None);
+
+/// Get whether the user data is encrypted.
+pub fn is_enabled() -> bool {
+    paths::user_data_file(CHECK_FILE).exists()
+}
+
+/// Get whether the encrypted user data was unlocked.
+pub fn is_unlocked() -> bool {
+    KEY.lock().unwrap().is_some()
+}
+
+/// Unlock the encrypted user data with the passphrase. Fails if the passphrase is wrong.
+pub fn unlock(passphrase: &str) -> Result<(), Error> {
+    let salt = fs::read(paths::user_data_file(SALT_FILE))?;
+    let key = derive_key(passphrase, &salt);
+
+    let check = decrypt(&key, &fs::read(paths::user_data_file(CHECK_FILE))?)?;
+    if check != CHECK_TEXT {
+        return Err(Error::Decrypting);
+    }
+
+    *KEY.lock().unwrap() = Some(key);
+    Ok(())
+}
+
+/// Encrypt all user data with the passphrase. If it is encrypted already, it must be unlocked and the passphrase is
+/// changed.
+///
+/// All files are written next to the previous ones first and only replace them once every file was written, so a
+/// failure keeps the previous passphrase working.
+pub fn enable(passphrase: &str) -> Result<(), Error> {
+    let files = read_user_data()?;
+
+    let mut salt = [0; SALT_LEN];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| Error::Encrypting)?;
+    let key = derive_key(passphrase, &salt);
+
+    let mut encrypted = vec![];
+    for (path, content) in files {
+        encrypted.push((path, encrypt(&key, &content)?));
+    }
+    encrypted.push((paths::user_data_file(SALT_FILE), salt.to_vec()));
+    // Replaced last, so the data is not considered encrypted with the new passphrase before all of it is.
+    encrypted.push((
+        paths::user_data_file(CHECK_FILE),
+        encrypt(&key, CHECK_TEXT)?,
+    ));
+
+    replace_files(&encrypted)?;
+    *KEY.lock().unwrap() = Some(key);
+
+    Ok(())
+}
+
+/// Decrypt all user data, it must be unlocked. Like `enable`, the files are only replaced once all were written.
+pub fn disable() -> Result<(), Error> {
+    let files = read_user_data()?;
+
+    replace_files(&files)?;
+    fs::remove_file(paths::user_data_file(CHECK_FILE))?;
+    *KEY.lock().unwrap() = None;
+    let _ = fs::remove_file(paths::user_data_file(SALT_FILE));
+
+    Ok(())
+}
+
+/// Forget the key and remove the salt and the check of the passphrase, e.g. after the user data was removed.
+pub fn forget() {
+    let _ = fs::remove_file(paths::user_data_file(CHECK_FILE));
+    let _ = fs::remove_file(paths::user_data_file(SALT_FILE));
+    *KEY.lock().unwrap() = None;
+}
+
+/// Read the file at the given path, decrypting it if it is encrypted.
+pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, Error> {
+    let content = fs::read(path)?;
+    if !content.starts_with(MAGIC) {
+        return Ok(content);
+    }
+
+    match *KEY.lock().unwrap() {
+        Some(key) => decrypt(&key, &content),
+        None => Err(Error::Decrypting),
+    }
+}
+
+/// Write the content to the file at the given path, encrypting it if the user data is encrypted. Fails if it is still
+/// locked, instead of writing the content unencrypted.
+pub fn write_file<P: AsRef<Path>>(path: P, content: &[u8]) -> Result<(), Error> {
+    let key = *KEY.lock().unwrap();
+    match key {
+        Some(key) => fs::write(path, encrypt(&key, content)?)?,
+        None if is_enabled() => return Err(Error::Encrypting),
+        None => fs::write(path, content)?,
+    }
+
+    Ok(())
+}
+
+/// Write the content like `write_file`, with the file only readable by the user, e.g. for passwords.
+pub fn write_private_file<P: AsRef<Path>>(path: P, content: &[u8]) -> Result<(), Error> {
+    restrict_permissions(path.as_ref())?;
+    write_file(path, content)
+}
+
+/// Create the file only readable by the user before anything is written to it, or restrict it if it existed before.
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> io::Result<()> {
+    use std::fs::{OpenOptions, Permissions};
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .mode(PRIVATE_MODE)
+        .open(path)?;
+    fs::set_permissions(path, Permissions::from_mode(PRIVATE_MODE))
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Replace the files with the contents, in the given order, keeping their permissions. All contents are written to
+/// staging files first, which are removed again if any of them fails, leaving the previous files untouched.
+fn replace_files(files: &[(PathBuf, Vec<u8>)]) -> Result<(), Error> {
+    let mut staged = vec![];
+    for (path, content) in files {
+        let staging = staging_path(path);
+        let written = fs::write(&staging, content).and_then(|_| match fs::metadata(path) {
+            Ok(metadata) => fs::set_permissions(&staging, metadata.permissions()),
+            Err(_) => Ok(()),
+        });
+        if let Err(error) = written {
+            for (staging, _path) in staged {
+                let _ = fs::remove_file(staging);
+            }
+            return Err(error.into());
+        }
+        staged.push((staging, path));
+    }
+
+    for (staging, path) in staged {
+        fs::rename(staging, path)?;
+    }
+
+    Ok(())
+}
+
+/// Get the path the file is written to before it replaces the file at the given path.
+fn staging_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(STAGING_EXTENSION);
+    path.with_file_name(name)
+}
+
+/// Read all files with user data in the data directory.
+fn read_user_data() -> Result<Vec<(PathBuf, Vec<u8>)>, Error> {
+    let mut files = vec![];
+    for path in paths::user_data_files()? {
+        let content = read_file(&path)?;
+        files.push((path, content));
+    }
+
+    Ok(files)
+}
+
+/// Derive the key from the passphrase and the salt.
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; KEY_LEN] {
+    let mut key = [0; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(PBKDF2_ITERATIONS).unwrap(),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    key
+}
+
+/// Encrypt the content with the key. The result starts with `MAGIC` and a random nonce.
+fn encrypt(key: &[u8; KEY_LEN], content: &[u8]) -> Result<Vec<u8>, Error> {
+    let key =
+        LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, key).map_err(|_| Error::Encrypting)?);
+
+    let mut nonce = [0; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| Error::Encrypting)?;
+
+    let mut in_out = content.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::empty(),
+        &mut in_out,
+    )
+    .map_err(|_| Error::Encrypting)?;
+
+    let mut result = MAGIC.to_vec();
+    result.extend_from_slice(&nonce);
+    result.append(&mut in_out);
+    Ok(result)
+}
+
+/// Decrypt the content encrypted with the key. Fails if the key is wrong or the content was modified.
+fn decrypt(key: &[u8; KEY_LEN], content: &[u8]) -> Result<Vec<u8>, Error> {
+    if !content.starts_with(MAGIC) || content.len() < MAGIC.len() + NONCE_LEN {
+        return Err(Error::Decrypting);
+    }
+
+    let key =
+        LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, key).map_err(|_| Error::Decrypting)?);
+
+    let mut nonce = [0; NONCE_LEN];
+    nonce.copy_from_slice(&content[MAGIC.len()..MAGIC.len() + NONCE_LEN]);
+
+    let mut in_out = content[MAGIC.len() + NONCE_LEN..].to_vec();
+    let plain = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| Error::Decrypting)?;
+    Ok(plain.to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let key = derive_key("correct horse", b"salt");
+        let encrypted = encrypt(&key, b"Tatort,ARD").unwrap();
+
+        assert!(encrypted.starts_with(MAGIC));
+        assert!(!encrypted.windows(6).any(|w| w == b"Tatort"));
+        assert_eq!(decrypt(&key, &encrypted).unwrap(), b"Tatort,ARD".to_vec());
+
+        let wrong_key = derive_key("wrong horse", b"salt");
+        assert!(decrypt(&wrong_key, &encrypted).is_err());
+        assert!(decrypt(&key, &encrypted[..encrypted.len() - 1]).is_err());
+        assert!(decrypt(&key, b"Tatort,ARD").is_err());
+    }
+
+    #[test]
+    fn test_enable_failure() {
+        let dir = std::env::temp_dir().join(format!("tvtoday-encryption-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        paths::set_user_data_dir_override(Some(dir.clone()));
+
+        let filters = dir.join("filters.csv");
+        let reminders = dir.join("reminders.csv");
+        fs::write(&filters, "Tatort,ARD").unwrap();
+        fs::write(&reminders, "Tatort,ARD,10").unwrap();
+        let credentials = dir.join("credentials.csv");
+        write_private_file(&credentials, b"secret").unwrap();
+        enable("correct horse").unwrap();
+
+        // Writing the reminders fails while changing the passphrase.
+        fs::create_dir(staging_path(&reminders)).unwrap();
+        assert!(enable("battery staple").is_err());
+        fs::remove_dir(staging_path(&reminders)).unwrap();
+
+        assert!(fs::read_dir(&dir)
+            .unwrap()
+            .all(|e| e.unwrap().path().extension() != Some(STAGING_EXTENSION.as_ref())));
+
+        *KEY.lock().unwrap() = None;
+        assert!(unlock("battery staple").is_err());
+        unlock("correct horse").unwrap();
+        assert_eq!(read_file(&filters).unwrap(), b"Tatort,ARD".to_vec());
+        assert_eq!(read_file(&reminders).unwrap(), b"Tatort,ARD,10".to_vec());
+        assert_eq!(read_file(&credentials).unwrap(), b"secret".to_vec());
+
+        disable().unwrap();
+        assert!(!is_enabled());
+        assert_eq!(fs::read(&filters).unwrap(), b"Tatort,ARD".to_vec());
+        // Replacing the files keeps them private.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&credentials).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, PRIVATE_MODE);
+        }
+
+        paths::set_user_data_dir_override(None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,1327 @@
+use crate::model::search::same_text;
+#[cfg(test)]
+use crate::model::MovieBuilder;
+use crate::model::{Channel, ChannelGroup, Movie, Program};
+use crate::Error;
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+
+use chrono::{Duration, NaiveDateTime};
+use serde_json::{json, Value};
+
+/// The version of the structured format of the filters. Increase it if the format changes in a incompatible way.
+const FILTER_FORMAT_VERSION: u64 = 1;
+
+/// The format of the start of hidden broadcasts and the expiry of filters in the stored filters.
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+/// The end of broadcasts is not known, so a hidden broadcast expires this many hours after its start.
+const HIDDEN_BROADCAST_HOURS: i64 = 4;
+
+/// A stored filter as its type and value, with the time it expires at if it expires.
+type StoredFilter = ([String; 2], Option<NaiveDateTime>);
+
+/// A `Filter` for the type `T`.
+pub trait Filter<T> {
+    /// Weather the filter matches the given item.
+    fn matches(&self, item: &T) -> bool;
+}
+
+/// A group of filters of type `F` filtering type `T`.
+pub trait FilterGroup<T, F: Filter<T>> {
+    /// Create a new group with no filters.
+    fn new() -> Self
+    where
+        Self: Sized;
+    /// Add a filter to the group.
+    fn add(&mut self, filter: F);
+    /// Remove all filters equal to the given filter from the group.
+    fn remove(&mut self, filter: &F);
+    /// Weather at least one filter matches.
+    fn matches(&self, iterm: &T) -> bool;
+}
+
+/// The attributes of a `Channel` used for filtering.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChannelAttribute {
+    /// The channel name.
+    Name(String),
+    /// The built-in group of the channel.
+    Group(ChannelGroup),
+}
+
+impl From<ChannelAttribute> for [String; 2] {
+    /// Convert from a `ChannelAttribute` to `[String; 2]`.
+    fn from(item: ChannelAttribute) -> [String; 2] {
+        match item {
+            ChannelAttribute::Name(name) => ["name".to_string(), name],
+            ChannelAttribute::Group(group) => ["group".to_string(), group.to_string()],
+        }
+    }
+}
+
+impl TryFrom<[String; 2]> for ChannelAttribute {
+    type Error = ();
+
+    /// Try to convert from `[String; 2]` to a `ChannelAttribute`.
+    fn try_from(item: [String; 2]) -> Result<ChannelAttribute, ()> {
+        match &item[0][..] {
+            "name" => Ok(ChannelAttribute::Name(item[1].clone())),
+            "group" => ChannelGroup::try_from(&item[1][..]).map(ChannelAttribute::Group),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Filter<Channel> for ChannelAttribute {
+    fn matches(&self, channel: &Channel) -> bool {
+        match self {
+            ChannelAttribute::Name(name) => *name == channel.get_name(),
+            ChannelAttribute::Group(group) => Some(*group) == ChannelGroup::of(&channel.get_name()),
+        }
+    }
+}
+
+/// The attributes of a `Movie` used for filtering.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MovieAttribute {
+    /// The movie title.
+    Title(String),
+    /// The movie genre.
+    Genre(String),
+    /// The movie division.
+    Division(String),
+    /// A actor of the movie. Only matches movies with a known cast.
+    Actor(String),
+    /// The director of the movie. Only matches movies with a known director.
+    Director(String),
+}
+
+impl From<MovieAttribute> for [String; 2] {
+    /// Convert from a `MovieAttribute` to `[String; 2]`.
+    fn from(item: MovieAttribute) -> [String; 2] {
+        match item {
+            MovieAttribute::Title(title) => ["title".to_string(), title],
+            MovieAttribute::Genre(genre) => ["genre".to_string(), genre],
+            MovieAttribute::Division(division) => ["division".to_string(), division],
+            MovieAttribute::Actor(actor) => ["actor".to_string(), actor],
+            MovieAttribute::Director(director) => ["director".to_string(), director],
+        }
+    }
+}
+
+impl TryFrom<[String; 2]> for MovieAttribute {
+    type Error = ();
+
+    /// Try to convert from a `[String; 2]` to a `MovieAttribute`.
+    fn try_from(item: [String; 2]) -> Result<MovieAttribute, ()> {
+        match &item[0][..] {
+            "title" => Ok(MovieAttribute::Title(item[1].clone())),
+            "genre" => Ok(MovieAttribute::Genre(item[1].clone())),
+            "division" => Ok(MovieAttribute::Division(item[1].clone())),
+            "actor" => Ok(MovieAttribute::Actor(item[1].clone())),
+            "director" => Ok(MovieAttribute::Director(item[1].clone())),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Filter<Movie> for MovieAttribute {
+    /// The texts are compared independent of their spelling, see `search::fold`.
+    fn matches(&self, movie: &Movie) -> bool {
+        let same_as =
+            |text: &str, other: Option<String>| other.map(|o| same_text(text, &o)).unwrap_or(false);
+
+        match self {
+            MovieAttribute::Title(title) => same_text(title, &movie.get_title()),
+            MovieAttribute::Genre(genre) => same_as(genre, movie.get_genre()),
+            MovieAttribute::Division(division) => same_as(division, movie.get_division()),
+            MovieAttribute::Actor(actor) => movie.get_cast().iter().any(|a| same_text(actor, a)),
+            MovieAttribute::Director(director) => same_as(director, movie.get_director()),
+        }
+    }
+}
+
+/// A single broadcast, identified by its channel, title and start, hidden independent of the filter mode until it
+/// ended. E.g. to skip a episode tonight without blocking the series.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HiddenBroadcast {
+    channel: String,
+    title: String,
+    start: NaiveDateTime,
+}
+
+impl HiddenBroadcast {
+    /// Create the hidden broadcast of the movie on the channel. Movies without a start can not be hidden.
+    pub fn of(channel: &Channel, movie: &Movie) -> Option<Self> {
+        Some(HiddenBroadcast {
+            channel: channel.get_name(),
+            title: movie.get_title(),
+            start: movie.get_start()?,
+        })
+    }
+
+    /// Get the time the broadcast is hidden until.
+    pub fn get_expiry(&self) -> NaiveDateTime {
+        self.start + Duration::hours(HIDDEN_BROADCAST_HOURS)
+    }
+
+    /// Weather the broadcast has ended at the given time, so it does not need to be hidden any more.
+    pub fn is_expired(&self, now: NaiveDateTime) -> bool {
+        self.get_expiry() <= now
+    }
+
+    /// Weather the movie on the channel is the hidden broadcast.
+    pub fn matches(&self, channel: &Channel, movie: &Movie) -> bool {
+        self.channel == channel.get_name()
+            && self.title == movie.get_title()
+            && Some(self.start) == movie.get_start()
+    }
+}
+
+impl From<HiddenBroadcast> for String {
+    /// Convert from `HiddenBroadcast` to a `String` of the form `{start}/{channel}/{title}`.
+    fn from(item: HiddenBroadcast) -> String {
+        format!(
+            "{}/{}/{}",
+            item.start.format(TIME_FORMAT),
+            item.channel,
+            item.title
+        )
+    }
+}
+
+impl TryFrom<&str> for HiddenBroadcast {
+    type Error = ();
+
+    /// Try to convert from a `&str` of the form `{start}/{channel}/{title}` to a `HiddenBroadcast`. The title may
+    /// contain slashes.
+    fn try_from(item: &str) -> Result<HiddenBroadcast, ()> {
+        let mut parts = item.splitn(3, '/');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(start), Some(channel), Some(title)) => Ok(HiddenBroadcast {
+                channel: channel.to_string(),
+                title: title.to_string(),
+                start: NaiveDateTime::parse_from_str(start, TIME_FORMAT).map_err(|_| ())?,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A filter group.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Filters<T, F: Filter<T>> {
+    filters: Vec<F>,
+    phantom: PhantomData<T>,
+}
+
+impl<T: Clone, F: Filter<T> + Clone> Clone for Filters<T, F> {
+    fn clone(&self) -> Self {
+        Filters {
+            filters: self.filters.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, F: Filter<T>> FromIterator<F> for Filters<T, F> {
+    /// Collect a `Filters` from a iterator over `Filter<T>`.
+    fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Self {
+        let vector = iter.into_iter().collect();
+        Filters {
+            filters: vector,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, F: Filter<T> + Clone + Into<[String; 2]>> From<Filters<T, F>> for Vec<[String; 2]> {
+    /// Convert from `Filters<T, F>` into a `Vec<[String; 2]>` if `F` can be cloned and turned into `[String; 2]`.
+    fn from(item: Filters<T, F>) -> Vec<[String; 2]> {
+        item.filters.iter().map(|f| f.clone().into()).collect()
+    }
+}
+
+impl<T, F: Filter<T> + TryFrom<[String; 2], Error = ()>> TryFrom<Vec<[String; 2]>>
+    for Filters<T, F>
+{
+    type Error = ();
+
+    /// Try to convert from a `Vec<[String; 2]>` to a `Filters` if `F` can be built from `[String; 2]`.
+    fn try_from(item: Vec<[String; 2]>) -> Result<Filters<T, F>, ()> {
+        let results: Vec<Result<F, ()>> = item.into_iter().map(|i| F::try_from(i)).collect();
+
+        if results.iter().any(|r| r.is_err()) {
+            Err(())
+        } else {
+            Ok(Filters {
+                filters: results.into_iter().map(|r| r.unwrap()).collect(),
+                phantom: PhantomData,
+            })
+        }
+    }
+}
+
+impl<T, F: Filter<T> + PartialEq> FilterGroup<T, F> for Filters<T, F> {
+    fn new() -> Self {
+        Filters {
+            filters: vec![],
+            phantom: PhantomData,
+        }
+    }
+
+    fn add(&mut self, filter: F) {
+        if !self.filters.contains(&filter) {
+            self.filters.push(filter);
+        }
+    }
+
+    fn remove(&mut self, filter: &F) {
+        self.filters.retain(|f| f != filter);
+    }
+
+    fn matches(&self, item: &T) -> bool {
+        self.filters.iter().any(|f| f.matches(item))
+    }
+}
+
+impl<T, F: Filter<T> + PartialEq> Filters<T, F> {
+    /// Replace the filter by another one at the same position. If the other one is already part of the group, the
+    /// filter is only removed.
+    fn replace(&mut self, old: &F, new: F) {
+        if self.filters.contains(&new) {
+            self.remove(old);
+        } else if let Some(index) = self.filters.iter().position(|f| f == old) {
+            self.filters[index] = new;
+        }
+    }
+}
+
+/// The filters for the channel and movie.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilterType {
+    Channel(ChannelAttribute),
+    Movie(MovieAttribute),
+    Broadcast(HiddenBroadcast),
+}
+
+impl FilterType {
+    /// Get a human readable name of the kind of the filter.
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            FilterType::Channel(ChannelAttribute::Name(_)) => "Channel",
+            FilterType::Channel(ChannelAttribute::Group(_)) => "Channel group",
+            FilterType::Movie(MovieAttribute::Title(_)) => "Title",
+            FilterType::Movie(MovieAttribute::Genre(_)) => "Genre",
+            FilterType::Movie(MovieAttribute::Division(_)) => "Division",
+            FilterType::Movie(MovieAttribute::Actor(_)) => "Actor",
+            FilterType::Movie(MovieAttribute::Director(_)) => "Director",
+            FilterType::Broadcast(_) => "Broadcast",
+        }
+    }
+
+    /// Get the value the filter matches in a human readable form, e.g. the title.
+    pub fn get_value(&self) -> String {
+        match self {
+            FilterType::Channel(ChannelAttribute::Name(value))
+            | FilterType::Movie(MovieAttribute::Title(value))
+            | FilterType::Movie(MovieAttribute::Genre(value))
+            | FilterType::Movie(MovieAttribute::Division(value))
+            | FilterType::Movie(MovieAttribute::Actor(value))
+            | FilterType::Movie(MovieAttribute::Director(value)) => value.clone(),
+            FilterType::Channel(ChannelAttribute::Group(group)) => group.get_label().to_string(),
+            FilterType::Broadcast(broadcast) => format!(
+                "{} on {} at {}",
+                broadcast.title,
+                broadcast.channel,
+                broadcast.start.format("%d.%m. %H:%M")
+            ),
+        }
+    }
+
+    /// Weather the value of the filter can be edited. Channel groups and hidden broadcasts can only be removed.
+    pub fn is_editable(&self) -> bool {
+        self.with_value(&self.get_value()).is_ok()
+    }
+
+    /// Create a filter of the same kind matching the given value instead, e.g. after editing it. Surrounding whitespace
+    /// is removed. Fails if the value is empty or the filter is not editable.
+    pub fn with_value(&self, value: &str) -> Result<FilterType, Error> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(Error::InvalidFilter);
+        }
+
+        let value = value.to_string();
+        match self {
+            FilterType::Channel(ChannelAttribute::Name(_)) => {
+                Ok(FilterType::Channel(ChannelAttribute::Name(value)))
+            }
+            FilterType::Movie(MovieAttribute::Title(_)) => {
+                Ok(FilterType::Movie(MovieAttribute::Title(value)))
+            }
+            FilterType::Movie(MovieAttribute::Genre(_)) => {
+                Ok(FilterType::Movie(MovieAttribute::Genre(value)))
+            }
+            FilterType::Movie(MovieAttribute::Division(_)) => {
+                Ok(FilterType::Movie(MovieAttribute::Division(value)))
+            }
+            FilterType::Movie(MovieAttribute::Actor(_)) => {
+                Ok(FilterType::Movie(MovieAttribute::Actor(value)))
+            }
+            FilterType::Movie(MovieAttribute::Director(_)) => {
+                Ok(FilterType::Movie(MovieAttribute::Director(value)))
+            }
+            FilterType::Channel(ChannelAttribute::Group(_)) | FilterType::Broadcast(_) => {
+                Err(Error::InvalidFilter)
+            }
+        }
+    }
+}
+
+/// How the filters of a `ProgramFilter` are applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterMode {
+    /// Filter out everything matching a filter.
+    Block,
+    /// Filter out everything not matching a filter.
+    Allow,
+}
+
+/// Filter out the a program. In the `FilterMode::Block` mode this will filter out `(Channel, Movie)` if the channel or the movie matches one of the filters,
+/// in the `FilterMode::Allow` mode if neither of them matches.
+/// Independent of the mode, movies with a age rating above the maximum age rating and hidden broadcasts are filtered out.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ProgramFilter {
+    channel_filters: Filters<Channel, ChannelAttribute>,
+    movie_filters: Filters<Movie, MovieAttribute>,
+    hidden_broadcasts: Vec<HiddenBroadcast>,
+    /// The time filters added only for a while expire at.
+    expiries: Vec<(FilterType, NaiveDateTime)>,
+    mode: FilterMode,
+    max_age_rating: Option<u32>,
+}
+
+impl From<ProgramFilter> for Vec<[String; 2]> {
+    /// Convert from `ProgramFilter` to `Vec<[String; 2]>`. The filters for the channel will always be first, the hidden
+    /// broadcasts last. The expiry of filters is not stored in this format used before the versioned format.
+    /// The mode is only stored if it is `FilterMode::Allow` and the maximum age rating only if it is set, both before all filters.
+    fn from(item: ProgramFilter) -> Vec<[String; 2]> {
+        let mut result: Vec<[String; 2]> = vec![];
+        if item.mode == FilterMode::Allow {
+            result.push(["mode".to_string(), "allow".to_string()]);
+        }
+        if let Some(max_age_rating) = item.max_age_rating {
+            result.push(["max_age_rating".to_string(), max_age_rating.to_string()]);
+        }
+
+        let mut channels: Vec<[String; 2]> = item.channel_filters.clone().into();
+        let mut movies: Vec<[String; 2]> = item.movie_filters.clone().into();
+
+        result.append(&mut channels);
+        result.append(&mut movies);
+        for broadcast in item.hidden_broadcasts {
+            result.push(["broadcast".to_string(), broadcast.into()]);
+        }
+
+        result
+    }
+}
+
+impl TryFrom<Vec<[String; 2]>> for ProgramFilter {
+    type Error = ();
+
+    /// Try to convert from `Vec<[String; 2]>` to a `ProgramFilter`.
+    fn try_from(item: Vec<[String; 2]>) -> Result<ProgramFilter, ()> {
+        let mut mode = FilterMode::Block;
+        let mut max_age_rating = None;
+        let mut hidden_broadcasts = vec![];
+        let mut rows = vec![];
+        for i in item {
+            match &i[0][..] {
+                "mode" => {
+                    mode = match &i[1][..] {
+                        "block" => FilterMode::Block,
+                        "allow" => FilterMode::Allow,
+                        _ => return Err(()),
+                    };
+                }
+                "max_age_rating" => max_age_rating = Some(i[1].parse().map_err(|_| ())?),
+                "broadcast" => hidden_broadcasts.push(HiddenBroadcast::try_from(&i[1][..])?),
+                _ => rows.push(i),
+            }
+        }
+
+        let filters: Vec<(Result<ChannelAttribute, ()>, Result<MovieAttribute, ()>)> = rows
+            .into_iter()
+            .map(|i| {
+                (
+                    ChannelAttribute::try_from(i.clone()),
+                    MovieAttribute::try_from(i),
+                )
+            })
+            .collect();
+
+        if filters
+            .iter()
+            .any(|(c, m)| c.is_err() && m.is_err() || c.is_ok() && m.is_ok())
+        {
+            Err(())
+        } else {
+            let channel_filters = filters.iter().cloned().flat_map(|(c, _m)| c).collect();
+            let movie_filters = filters.iter().cloned().flat_map(|(_c, m)| m).collect();
+
+            Ok(ProgramFilter {
+                channel_filters,
+                movie_filters,
+                hidden_broadcasts,
+                expiries: vec![],
+                mode,
+                max_age_rating,
+            })
+        }
+    }
+}
+
+impl From<ProgramFilter> for Value {
+    /// Convert from `ProgramFilter` to a versioned JSON `Value`. Filters only added for a while have the time they
+    /// expire at.
+    fn from(item: ProgramFilter) -> Value {
+        let to_json = |filter: FilterType, [attribute, value]: [String; 2]| -> Value {
+            let mut entry = json!({"attribute": attribute, "value": value});
+            if let Some(expiry) = item.get_expiry(&filter) {
+                entry["expires"] = json!(expiry.format(TIME_FORMAT).to_string());
+            }
+            entry
+        };
+        let channel_filters: Vec<Value> = item
+            .channel_filters
+            .filters
+            .iter()
+            .map(|c| to_json(FilterType::Channel(c.clone()), c.clone().into()))
+            .collect();
+        let movie_filters: Vec<Value> = item
+            .movie_filters
+            .filters
+            .iter()
+            .map(|m| to_json(FilterType::Movie(m.clone()), m.clone().into()))
+            .collect();
+
+        json!({
+            "version": FILTER_FORMAT_VERSION,
+            "mode": match item.mode {
+                FilterMode::Block => "block",
+                FilterMode::Allow => "allow",
+            },
+            "max_age_rating": item.max_age_rating,
+            "channel_filters": channel_filters,
+            "movie_filters": movie_filters,
+            "hidden_broadcasts": item
+                .hidden_broadcasts
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<String>>(),
+        })
+    }
+}
+
+impl TryFrom<Value> for ProgramFilter {
+    type Error = ();
+
+    /// Try to convert from a versioned JSON `Value` to a `ProgramFilter`. Fails for unknown versions. The hidden
+    /// broadcasts and expiries are optional, as they were added later.
+    fn try_from(item: Value) -> Result<ProgramFilter, ()> {
+        if item["version"].as_u64() != Some(FILTER_FORMAT_VERSION) {
+            return Err(());
+        }
+
+        let from_json = |filters: &Value| -> Result<Vec<StoredFilter>, ()> {
+            filters
+                .as_array()
+                .ok_or(())?
+                .iter()
+                .map(|f| {
+                    let expiry = match &f["expires"] {
+                        Value::Null => None,
+                        expiry => Some(
+                            NaiveDateTime::parse_from_str(expiry.as_str().ok_or(())?, TIME_FORMAT)
+                                .map_err(|_| ())?,
+                        ),
+                    };
+                    Ok((
+                        [
+                            f["attribute"].as_str().ok_or(())?.to_string(),
+                            f["value"].as_str().ok_or(())?.to_string(),
+                        ],
+                        expiry,
+                    ))
+                })
+                .collect()
+        };
+
+        let mode = match item["mode"].as_str() {
+            Some("block") => FilterMode::Block,
+            Some("allow") => FilterMode::Allow,
+            _ => return Err(()),
+        };
+
+        let max_age_rating = match &item["max_age_rating"] {
+            Value::Null => None,
+            value => Some(value.as_u64().ok_or(())? as u32),
+        };
+
+        let hidden_broadcasts = match &item["hidden_broadcasts"] {
+            Value::Null => vec![],
+            value => value
+                .as_array()
+                .ok_or(())?
+                .iter()
+                .map(|b| HiddenBroadcast::try_from(b.as_str().ok_or(())?))
+                .collect::<Result<_, ()>>()?,
+        };
+
+        let mut filter = ProgramFilter::new();
+        filter.mode = mode;
+        filter.max_age_rating = max_age_rating;
+        filter.hidden_broadcasts = hidden_broadcasts;
+        for (attribute, expiry) in from_json(&item["channel_filters"])? {
+            filter.add_with_expiry(
+                FilterType::Channel(ChannelAttribute::try_from(attribute)?),
+                expiry,
+            );
+        }
+        for (attribute, expiry) in from_json(&item["movie_filters"])? {
+            filter.add_with_expiry(
+                FilterType::Movie(MovieAttribute::try_from(attribute)?),
+                expiry,
+            );
+        }
+
+        Ok(filter)
+    }
+}
+
+impl From<ProgramFilter> for BTreeMap<String, String> {
+    /// Convert from `ProgramFilter` to key-value pairs to be synced. Each filter is a key of the form
+    /// `{channel|movie}/{attribute}/{value}` or `broadcast/{broadcast}`, so filters added and removed on different devices
+    /// merge. The value is the time the filter expires at, or empty.
+    fn from(item: ProgramFilter) -> BTreeMap<String, String> {
+        let mut result = BTreeMap::new();
+        result.insert(
+            "mode".to_string(),
+            match item.mode {
+                FilterMode::Block => "block",
+                FilterMode::Allow => "allow",
+            }
+            .to_string(),
+        );
+        if let Some(max_age_rating) = item.max_age_rating {
+            result.insert("max_age_rating".to_string(), max_age_rating.to_string());
+        }
+
+        let expiry = |filter: FilterType| -> String {
+            item.get_expiry(&filter)
+                .map(|e| e.format(TIME_FORMAT).to_string())
+                .unwrap_or_default()
+        };
+        for c in item.channel_filters.filters.iter() {
+            let [attribute, value] = <[String; 2]>::from(c.clone());
+            result.insert(
+                format!("channel/{}/{}", attribute, value),
+                expiry(FilterType::Channel(c.clone())),
+            );
+        }
+        for m in item.movie_filters.filters.iter() {
+            let [attribute, value] = <[String; 2]>::from(m.clone());
+            result.insert(
+                format!("movie/{}/{}", attribute, value),
+                expiry(FilterType::Movie(m.clone())),
+            );
+        }
+        for broadcast in item.hidden_broadcasts {
+            result.insert(
+                format!("broadcast/{}", String::from(broadcast)),
+                String::new(),
+            );
+        }
+
+        result
+    }
+}
+
+impl From<BTreeMap<String, String>> for ProgramFilter {
+    /// Convert from synced key-value pairs to a `ProgramFilter`. Unknown keys and filters, e.g. synced from a newer
+    /// version on another device, are ignored. Filters with a unknown expiry never expire.
+    fn from(item: BTreeMap<String, String>) -> ProgramFilter {
+        let mut filter = ProgramFilter::new();
+
+        for (key, value) in item {
+            let expiry = NaiveDateTime::parse_from_str(&value, TIME_FORMAT).ok();
+            if let Some(broadcast) = key.strip_prefix("broadcast/") {
+                if let Ok(b) = HiddenBroadcast::try_from(broadcast) {
+                    filter.hide_broadcast(b);
+                }
+                continue;
+            }
+
+            let mut parts = key.splitn(3, '/');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("mode"), None, None) => match &value[..] {
+                    "block" => filter.mode = FilterMode::Block,
+                    "allow" => filter.mode = FilterMode::Allow,
+                    _ => {}
+                },
+                (Some("max_age_rating"), None, None) => {
+                    filter.max_age_rating = value.parse().ok();
+                }
+                (Some("channel"), Some(attribute), Some(value)) => {
+                    if let Ok(c) =
+                        ChannelAttribute::try_from([attribute.to_string(), value.to_string()])
+                    {
+                        filter.add_with_expiry(FilterType::Channel(c), expiry);
+                    }
+                }
+                (Some("movie"), Some(attribute), Some(value)) => {
+                    if let Ok(m) =
+                        MovieAttribute::try_from([attribute.to_string(), value.to_string()])
+                    {
+                        filter.add_with_expiry(FilterType::Movie(m), expiry);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        filter
+    }
+}
+
+impl ProgramFilter {
+    /// Create a new, empty `ProgramFilter`.
+    pub fn new() -> Self {
+        ProgramFilter {
+            channel_filters: Filters::new(),
+            movie_filters: Filters::new(),
+            hidden_broadcasts: vec![],
+            expiries: vec![],
+            mode: FilterMode::Block,
+            max_age_rating: None,
+        }
+    }
+
+    /// Get the maximum age rating (FSK) of movies.
+    pub fn get_max_age_rating(&self) -> Option<u32> {
+        self.max_age_rating
+    }
+
+    /// Set the maximum age rating (FSK) of movies. Movies without a age rating are never filtered out by this.
+    pub fn set_max_age_rating(&mut self, max_age_rating: Option<u32>) {
+        self.max_age_rating = max_age_rating
+    }
+
+    /// Get the mode the filters are applied with.
+    pub fn get_mode(&self) -> FilterMode {
+        self.mode
+    }
+
+    /// Set the mode the filters are applied with.
+    pub fn set_mode(&mut self, mode: FilterMode) {
+        self.mode = mode
+    }
+
+    /// Add a filter of type `FilterType` to the filters. A filter added before only for a while does no longer expire.
+    pub fn add(&mut self, filter: FilterType) {
+        self.expiries.retain(|(f, _e)| f != &filter);
+        match filter {
+            FilterType::Channel(c) => self.add_channel_filter(c),
+            FilterType::Movie(m) => self.add_movie_filter(m),
+            FilterType::Broadcast(b) => self.hide_broadcast(b),
+        }
+    }
+
+    /// Add a filter of type `FilterType` to the filters until the given time, after which `remove_expired` removes it.
+    pub fn add_until(&mut self, filter: FilterType, expiry: NaiveDateTime) {
+        self.add(filter.clone());
+        self.expiries.push((filter, expiry));
+    }
+
+    /// Add a filter of type `FilterType` until the time if given, otherwise forever.
+    fn add_with_expiry(&mut self, filter: FilterType, expiry: Option<NaiveDateTime>) {
+        match expiry {
+            Some(expiry) => self.add_until(filter, expiry),
+            None => self.add(filter),
+        }
+    }
+
+    /// Get the time the filter expires at, if it was only added for a while.
+    pub fn get_expiry(&self, filter: &FilterType) -> Option<NaiveDateTime> {
+        self.expiries
+            .iter()
+            .find(|(f, _e)| f == filter)
+            .map(|(_f, e)| *e)
+    }
+
+    /// Remove a filter of type `FilterType` from the filters.
+    pub fn remove(&mut self, filter: &FilterType) {
+        self.expiries.retain(|(f, _e)| f != filter);
+        match filter {
+            FilterType::Channel(c) => self.channel_filters.remove(c),
+            FilterType::Movie(m) => self.movie_filters.remove(m),
+            FilterType::Broadcast(b) => self.hidden_broadcasts.retain(|h| h != b),
+        }
+    }
+
+    /// Weather the given filter is part of the filters.
+    pub fn contains(&self, filter: &FilterType) -> bool {
+        match filter {
+            FilterType::Channel(c) => self.channel_filters.filters.contains(c),
+            FilterType::Movie(m) => self.movie_filters.filters.contains(m),
+            FilterType::Broadcast(b) => self.hidden_broadcasts.contains(b),
+        }
+    }
+
+    /// Get all filters, the filters for the channel first and the hidden broadcasts last.
+    pub fn get_filters(&self) -> Vec<FilterType> {
+        self.channel_filters
+            .filters
+            .iter()
+            .cloned()
+            .map(FilterType::Channel)
+            .chain(
+                self.movie_filters
+                    .filters
+                    .iter()
+                    .cloned()
+                    .map(FilterType::Movie),
+            )
+            .chain(
+                self.hidden_broadcasts
+                    .iter()
+                    .cloned()
+                    .map(FilterType::Broadcast),
+            )
+            .collect()
+    }
+
+    /// Replace a filter by another one, e.g. after editing it. The filter keeps its position and expiry.
+    pub fn replace(&mut self, old: &FilterType, new: FilterType) {
+        if old == &new || !self.contains(old) {
+            return;
+        }
+        if self.contains(&new) {
+            self.remove(old);
+            return;
+        }
+
+        let expiry = self.get_expiry(old);
+        match (old, new.clone()) {
+            (FilterType::Channel(o), FilterType::Channel(n)) => self.channel_filters.replace(o, n),
+            (FilterType::Movie(o), FilterType::Movie(n)) => self.movie_filters.replace(o, n),
+            (old, new) => {
+                self.remove(old);
+                self.add(new);
+            }
+        }
+
+        self.expiries.retain(|(f, _e)| f != old);
+        if let Some(expiry) = expiry {
+            self.expiries.push((new, expiry));
+        }
+    }
+
+    /// Add a `ChannelAttribute` filter.
+    pub fn add_channel_filter(&mut self, filter: ChannelAttribute) {
+        self.channel_filters.add(filter)
+    }
+
+    /// Add a `MovieAttributes` filter.
+    pub fn add_movie_filter(&mut self, filter: MovieAttribute) {
+        self.movie_filters.add(filter)
+    }
+
+    /// Hide a single broadcast until it ended.
+    pub fn hide_broadcast(&mut self, broadcast: HiddenBroadcast) {
+        if !self.hidden_broadcasts.contains(&broadcast) {
+            self.hidden_broadcasts.push(broadcast);
+        }
+    }
+
+    /// Remove the filters that expired and the hidden broadcasts that ended at the given time. Returns weather any was
+    /// removed.
+    pub fn remove_expired(&mut self, now: NaiveDateTime) -> bool {
+        let expired: Vec<FilterType> = self
+            .expiries
+            .iter()
+            .filter(|(_f, e)| *e <= now)
+            .map(|(f, _e)| f.clone())
+            .collect();
+        for filter in expired.iter() {
+            self.remove(filter);
+        }
+
+        let count = self.hidden_broadcasts.len();
+        self.hidden_broadcasts.retain(|b| !b.is_expired(now));
+        !expired.is_empty() || self.hidden_broadcasts.len() != count
+    }
+
+    /// Check if any filter matches the given tuple.
+    pub fn matches(&self, (channel, movie): (&Channel, &Movie)) -> bool {
+        self.channel_filters.matches(channel) || self.movie_filters.matches(movie)
+    }
+
+    /// Filter out the unwanted `(Channel, Movie)` tuples out of the program.
+    pub fn filter(&self, program: &Program) -> Program {
+        let keep_matching = self.mode == FilterMode::Allow;
+        program
+            .iter()
+            .filter(|(c, m)| self.matches((c, m)) == keep_matching)
+            .filter(|(c, m)| !self.hidden_broadcasts.iter().any(|b| b.matches(c, m)))
+            .filter(|(_c, m)| match (self.max_age_rating, m.get_age_rating()) {
+                (Some(max), Some(rating)) => rating <= max,
+                _ => true,
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for ProgramFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::convert::TryInto;
+
+    #[test]
+    fn test_to_str_array() {
+        assert_eq!(
+            ["title".to_string(), "Hello".to_string()],
+            <[String; 2]>::from(MovieAttribute::Title("Hello".to_string()))
+        );
+
+        assert_eq!(
+            ["genre".to_string(), "Bye".to_string()],
+            <[String; 2]>::from(MovieAttribute::Genre("Bye".to_string()))
+        );
+
+        assert_eq!(
+            ["division".to_string(), "World".to_string()],
+            <[String; 2]>::from(MovieAttribute::Division("World".to_string()))
+        );
+
+        assert_eq!(
+            ["name".to_string(), "World2".to_string()],
+            <[String; 2]>::from(ChannelAttribute::Name("World2".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_to_vec_str_array() {
+        let mut filter = Filters::new();
+        filter.add(MovieAttribute::Title("Hello".to_string()));
+        filter.add(MovieAttribute::Genre("Bye".to_string()));
+        filter.add(MovieAttribute::Division("World".to_string()));
+
+        assert_eq!(
+            vec![
+                ["title".to_string(), "Hello".to_string()],
+                ["genre".to_string(), "Bye".to_string()],
+                ["division".to_string(), "World".to_string()],
+            ],
+            <Vec<[String; 2]>>::from(filter)
+        );
+    }
+    #[test]
+    fn test_program_to_vec_str_array() {
+        let mut filter = ProgramFilter::new();
+        filter.add_movie_filter(MovieAttribute::Title("Hello".to_string()));
+        filter.add_movie_filter(MovieAttribute::Genre("Bye".to_string()));
+        filter.add_movie_filter(MovieAttribute::Division("World".to_string()));
+        filter.add_channel_filter(ChannelAttribute::Name("World2".to_string()));
+
+        assert_eq!(
+            vec![
+                ["name".to_string(), "World2".to_string()],
+                ["title".to_string(), "Hello".to_string()],
+                ["genre".to_string(), "Bye".to_string()],
+                ["division".to_string(), "World".to_string()],
+            ],
+            <Vec<[String; 2]>>::from(filter)
+        );
+    }
+
+    #[test]
+    fn test_from_str_array() {
+        assert_eq!(
+            MovieAttribute::try_from(["title".to_string(), "Hello".to_string()]),
+            Ok(MovieAttribute::Title("Hello".to_string()))
+        );
+
+        assert_eq!(
+            MovieAttribute::try_from(["genre".to_string(), "Bye".to_string()]),
+            Ok(MovieAttribute::Genre("Bye".to_string()))
+        );
+
+        assert_eq!(
+            MovieAttribute::try_from(["division".to_string(), "World".to_string()]),
+            Ok(MovieAttribute::Division("World".to_string()))
+        );
+
+        assert_eq!(
+            ChannelAttribute::try_from(["name".to_string(), "World2".to_string()]),
+            Ok(ChannelAttribute::Name("World2".to_string()))
+        );
+
+        assert_eq!(
+            ChannelAttribute::try_from(["nan".to_string(), "World2".to_string()]),
+            Err(())
+        );
+
+        assert_eq!(
+            ChannelAttribute::try_from(["group".to_string(), "regional".to_string()]),
+            Ok(ChannelAttribute::Group(ChannelGroup::Regional))
+        );
+
+        assert_eq!(
+            ChannelAttribute::try_from(["group".to_string(), "unknown".to_string()]),
+            Err(())
+        );
+    }
+
+    #[test]
+    fn test_group_matches() {
+        let filter = ChannelAttribute::Group(ChannelGroup::Public);
+
+        assert!(filter.matches(&Channel::new("ZDF")));
+        assert!(!filter.matches(&Channel::new("RTL")));
+        assert!(!filter.matches(&Channel::new("Unknown")));
+    }
+
+    #[test]
+    fn test_people_matches() {
+        let mut movie = MovieBuilder::new("Hello");
+        movie.with_director("Jane Doe").with_actor("John Doe");
+        let movie = movie.build();
+
+        assert!(MovieAttribute::Actor("John Doe".to_string()).matches(&movie));
+        assert!(!MovieAttribute::Actor("Jane Doe".to_string()).matches(&movie));
+        assert!(MovieAttribute::Director("Jane Doe".to_string()).matches(&movie));
+        assert!(!MovieAttribute::Director("Jane Doe".to_string()).matches(&Movie::new("Hello")));
+        assert_eq!(
+            MovieAttribute::try_from(["actor".to_string(), "John Doe".to_string()]),
+            Ok(MovieAttribute::Actor("John Doe".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_folded_matches() {
+        let mut movie = MovieBuilder::new("Die Fälscher");
+        movie
+            .with_genre("Drama – Krieg")
+            .with_actor("Karl Markovics");
+        let movie = movie.build();
+
+        assert!(MovieAttribute::Title("Die Faelscher".to_string()).matches(&movie));
+        assert!(MovieAttribute::Title("DIE FÄLSCHER".to_string()).matches(&movie));
+        assert!(!MovieAttribute::Title("Die Falscher".to_string()).matches(&movie));
+        assert!(MovieAttribute::Genre("drama - krieg".to_string()).matches(&movie));
+        assert!(MovieAttribute::Actor("karl markovics".to_string()).matches(&movie));
+    }
+
+    #[test]
+    fn test_allow_mode() {
+        let mut filter = ProgramFilter::new();
+        filter.set_mode(FilterMode::Allow);
+        filter.add_channel_filter(ChannelAttribute::Name("ZDF".to_string()));
+
+        let program: Program = vec![
+            (Channel::new("ZDF"), Movie::new("Hello")),
+            (Channel::new("RTL"), Movie::new("Bye")),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            filter.filter(&program),
+            vec![(Channel::new("ZDF"), Movie::new("Hello"))]
+                .into_iter()
+                .collect()
+        );
+
+        let content = <Vec<[String; 2]>>::from(filter.clone());
+        assert_eq!(content[0], ["mode".to_string(), "allow".to_string()]);
+        assert_eq!(ProgramFilter::try_from(content), Ok(filter));
+    }
+
+    #[test]
+    fn test_max_age_rating() {
+        let mut filter = ProgramFilter::new();
+        filter.set_max_age_rating(Some(12));
+
+        let mut movie_12 = MovieBuilder::new("12");
+        movie_12.with_age_rating(12);
+        let mut movie_16 = MovieBuilder::new("16");
+        movie_16.with_age_rating(16);
+
+        let program: Program = vec![
+            (Channel::new("ZDF"), movie_12.build()),
+            (Channel::new("ZDF"), movie_16.build()),
+            (Channel::new("ZDF"), Movie::new("Unknown")),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            filter
+                .filter(&program)
+                .iter()
+                .map(|(_c, m)| m.get_title())
+                .collect::<Vec<String>>(),
+            vec!["12", "Unknown"]
+        );
+
+        let content = <Vec<[String; 2]>>::from(filter.clone());
+        assert_eq!(
+            content,
+            vec![["max_age_rating".to_string(), "12".to_string()]]
+        );
+        assert_eq!(ProgramFilter::try_from(content), Ok(filter));
+    }
+
+    #[test]
+    fn test_json() {
+        let mut filter = ProgramFilter::new();
+        filter.set_mode(FilterMode::Allow);
+        filter.set_max_age_rating(Some(12));
+        filter.add_channel_filter(ChannelAttribute::Name("ZDF".to_string()));
+        filter.add_movie_filter(MovieAttribute::Title("Hello".to_string()));
+
+        let value = Value::from(filter.clone());
+        assert_eq!(
+            value,
+            json!({
+                "version": 1,
+                "mode": "allow",
+                "max_age_rating": 12,
+                "channel_filters": [{"attribute": "name", "value": "ZDF"}],
+                "movie_filters": [{"attribute": "title", "value": "Hello"}],
+                "hidden_broadcasts": [],
+            })
+        );
+        assert_eq!(ProgramFilter::try_from(value), Ok(filter));
+
+        let mut future = Value::from(ProgramFilter::new());
+        future["version"] = json!(2);
+        assert_eq!(ProgramFilter::try_from(future), Err(()));
+    }
+
+    #[test]
+    fn test_synced_values() {
+        let mut filter = ProgramFilter::new();
+        filter.set_mode(FilterMode::Allow);
+        filter.set_max_age_rating(Some(12));
+        filter.add_channel_filter(ChannelAttribute::Name("ZDF".to_string()));
+        filter.add_movie_filter(MovieAttribute::Title("AC/DC".to_string()));
+
+        let values: BTreeMap<String, String> = filter.clone().into();
+        assert_eq!(
+            values.keys().cloned().collect::<Vec<String>>(),
+            vec![
+                "channel/name/ZDF",
+                "max_age_rating",
+                "mode",
+                "movie/title/AC/DC"
+            ]
+        );
+        assert_eq!(ProgramFilter::from(values.clone()), filter);
+
+        let mut future = values;
+        future.insert("movie/mood/happy".to_string(), String::new());
+        future.insert("sort".to_string(), "title".to_string());
+        assert_eq!(ProgramFilter::from(future), filter);
+    }
+
+    #[test]
+    fn test_hidden_broadcast() {
+        let start = NaiveDateTime::parse_from_str("2021-04-01T20:15", TIME_FORMAT).unwrap();
+        let mut tonight = MovieBuilder::new("AC/DC");
+        tonight.with_start(start);
+        let tonight = tonight.build();
+        let mut tomorrow = MovieBuilder::new("AC/DC");
+        tomorrow.with_start(start + Duration::days(1));
+        let tomorrow = tomorrow.build();
+
+        let broadcast = HiddenBroadcast::of(&Channel::new("ZDF"), &tonight).unwrap();
+        assert_eq!(
+            HiddenBroadcast::of(&Channel::new("ZDF"), &Movie::new("AC/DC")),
+            None
+        );
+
+        // Only the broadcast is hidden, also in the allow mode.
+        let mut filter = ProgramFilter::new();
+        filter.set_mode(FilterMode::Allow);
+        filter.add_channel_filter(ChannelAttribute::Name("ZDF".to_string()));
+        filter.add(FilterType::Broadcast(broadcast.clone()));
+
+        let program: Program = vec![
+            (Channel::new("ZDF"), tonight.clone()),
+            (Channel::new("ZDF"), tomorrow.clone()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            filter.filter(&program),
+            vec![(Channel::new("ZDF"), tomorrow)].into_iter().collect()
+        );
+
+        // The title may contain slashes.
+        let content = <Vec<[String; 2]>>::from(filter.clone());
+        assert_eq!(
+            content.last(),
+            Some(&[
+                "broadcast".to_string(),
+                "2021-04-01T20:15/ZDF/AC/DC".to_string()
+            ])
+        );
+        assert_eq!(ProgramFilter::try_from(content), Ok(filter.clone()));
+        assert_eq!(
+            ProgramFilter::try_from(Value::from(filter.clone())),
+            Ok(filter.clone())
+        );
+        let values: BTreeMap<String, String> = filter.clone().into();
+        assert_eq!(ProgramFilter::from(values), filter);
+
+        // It expires after the broadcast ended.
+        assert!(!filter.remove_expired(start + Duration::hours(1)));
+        assert!(filter.remove_expired(start + Duration::hours(HIDDEN_BROADCAST_HOURS)));
+        assert!(!filter.contains(&FilterType::Broadcast(broadcast)));
+    }
+
+    #[test]
+    fn test_expiry() {
+        let now = NaiveDateTime::parse_from_str("2021-04-01T20:15", TIME_FORMAT).unwrap();
+        let week = FilterType::Movie(MovieAttribute::Title("AC/DC".to_string()));
+        let forever = FilterType::Channel(ChannelAttribute::Name("ZDF".to_string()));
+
+        let mut filter = ProgramFilter::new();
+        filter.add_until(week.clone(), now + Duration::days(7));
+        filter.add(forever.clone());
+        assert_eq!(filter.get_expiry(&week), Some(now + Duration::days(7)));
+        assert_eq!(filter.get_expiry(&forever), None);
+
+        let value = Value::from(filter.clone());
+        assert_eq!(
+            value["movie_filters"],
+            json!([{"attribute": "title", "value": "AC/DC", "expires": "2021-04-08T20:15"}])
+        );
+        assert_eq!(ProgramFilter::try_from(value), Ok(filter.clone()));
+
+        let values: BTreeMap<String, String> = filter.clone().into();
+        assert_eq!(values["movie/title/AC/DC"], "2021-04-08T20:15");
+        assert_eq!(values["channel/name/ZDF"], "");
+        assert_eq!(ProgramFilter::from(values), filter);
+
+        // Adding the filter again keeps it forever.
+        let mut again = filter.clone();
+        again.add(week.clone());
+        assert_eq!(again.get_expiry(&week), None);
+
+        assert!(!filter.remove_expired(now + Duration::days(6)));
+        assert!(filter.remove_expired(now + Duration::days(7)));
+        assert!(!filter.contains(&week));
+        assert!(filter.contains(&forever));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut filter = ProgramFilter::new();
+        filter.add_channel_filter(ChannelAttribute::Group(ChannelGroup::News));
+        filter.add_movie_filter(MovieAttribute::Title("Hello".to_string()));
+
+        filter.remove(&FilterType::Channel(ChannelAttribute::Group(
+            ChannelGroup::News,
+        )));
+
+        assert!(
+            !filter.contains(&FilterType::Channel(ChannelAttribute::Group(
+                ChannelGroup::News
+            )))
+        );
+        assert!(filter.contains(&FilterType::Movie(MovieAttribute::Title(
+            "Hello".to_string()
+        ))));
+    }
+
+    #[test]
+    fn test_with_value() {
+        let title = FilterType::Movie(MovieAttribute::Title("Tatort".to_string()));
+        assert_eq!(title.get_label(), "Title");
+        assert_eq!(title.get_value(), "Tatort");
+        assert!(title.is_editable());
+        assert_eq!(
+            title.with_value(" Polizeiruf 110 ").ok(),
+            Some(FilterType::Movie(MovieAttribute::Title(
+                "Polizeiruf 110".to_string()
+            )))
+        );
+        assert!(matches!(title.with_value("  "), Err(Error::InvalidFilter)));
+
+        let group = FilterType::Channel(ChannelAttribute::Group(ChannelGroup::News));
+        assert_eq!(group.get_value(), "News channels");
+        assert!(!group.is_editable());
+        assert!(matches!(
+            group.with_value("Private channels"),
+            Err(Error::InvalidFilter)
+        ));
+    }
+
+    #[test]
+    fn test_replace() {
+        let now = NaiveDateTime::parse_from_str("2021-04-01T20:15", TIME_FORMAT).unwrap();
+        let first = FilterType::Movie(MovieAttribute::Title("Tatort".to_string()));
+        let second = FilterType::Movie(MovieAttribute::Genre("Krimi".to_string()));
+        let edited = FilterType::Movie(MovieAttribute::Title("Polizeiruf 110".to_string()));
+
+        let mut filter = ProgramFilter::new();
+        filter.add_until(first.clone(), now);
+        filter.add(second.clone());
+
+        filter.replace(&first, edited.clone());
+        assert_eq!(filter.get_filters(), vec![edited.clone(), second.clone()]);
+        assert_eq!(filter.get_expiry(&edited), Some(now));
+        assert_eq!(filter.get_expiry(&first), None);
+
+        // Editing a filter to the value of another one merges them.
+        filter.replace(&edited, second.clone());
+        assert_eq!(filter.get_filters(), vec![second]);
+    }
+
+    #[test]
+    fn test_program_from_vec_str_array() {
+        let mut program_filter = ProgramFilter::new();
+        program_filter.add_channel_filter(ChannelAttribute::Name("World2".to_string()));
+        program_filter.add_movie_filter(MovieAttribute::Title("Hello".to_string()));
+        program_filter.add_movie_filter(MovieAttribute::Genre("Bye".to_string()));
+        program_filter.add_movie_filter(MovieAttribute::Division("World".to_string()));
+
+        assert_eq!(
+            vec![
+                ["name".to_string(), "World2".to_string()],
+                ["title".to_string(), "Hello".to_string()],
+                ["genre".to_string(), "Bye".to_string()],
+                ["division".to_string(), "World".to_string()],
+            ]
+            .try_into(),
+            Ok(program_filter)
+        );
+    }
+}
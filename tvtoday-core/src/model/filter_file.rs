@@ -0,0 +1,114 @@
+use crate::model::{encoding, encryption, ProgramFilter, SyncLog};
+use crate::Error;
+
+use std::convert::{TryFrom, TryInto};
+use std::fs;
+use std::path::Path;
+
+use chrono::Local;
+use csv::ReaderBuilder;
+use serde_json::Value;
+
+/// The name of the synced change logs of the filters.
+const FILTER_LOG_NAME: &str = "filters";
+
+impl ProgramFilter {
+    /// Open the synced change log of the filters of the device in the directory.
+    pub fn open_log<P: AsRef<Path>>(dir: P, device: &str) -> SyncLog {
+        SyncLog::read_from_dir(&dir, FILTER_LOG_NAME, device)
+            .unwrap_or_else(|_| SyncLog::new(dir, FILTER_LOG_NAME, device))
+    }
+
+    /// Write the filters to a file at the given path in the versioned JSON format.
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let content = serde_json::to_string_pretty(&Value::from(self.clone()))
+            .map_err(|_| Error::ParsingFile)?;
+
+        encryption::write_file(path, content.as_bytes())
+    }
+
+    /// Read the filters from a file at the given path in the versioned JSON format. Files edited by hand with a byte
+    /// order mark, Windows line endings or in a other encoding are read as well, empty files contain no filters.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = encoding::normalize(&encryption::read_file(path)?);
+        if content.trim().is_empty() {
+            return Ok(ProgramFilter::new());
+        }
+
+        let value: Value = serde_json::from_str(&content).map_err(|_| Error::ParsingFile)?;
+
+        ProgramFilter::try_from(value).map_err(|_| Error::ParsingFile)
+    }
+
+    /// Read the filters from the synced change log. If it is empty, the filters are migrated from the JSON file at the
+    /// legacy path used before the change logs, or from the older CSV file. Expired filters are removed from the log.
+    pub fn read_from_log_or_migrate<P: AsRef<Path>, L: AsRef<Path>>(
+        log: &mut SyncLog,
+        json_path: P,
+        csv_path: L,
+    ) -> Result<Self, Error> {
+        let values = log.values();
+        if !values.is_empty() {
+            let mut filter = ProgramFilter::from(values);
+            if filter.remove_expired(Local::now().naive_local()) {
+                log.write(&filter.clone().into())?;
+            }
+            return Ok(filter);
+        }
+        if !json_path.as_ref().exists() && !csv_path.as_ref().exists() {
+            return Ok(ProgramFilter::new());
+        }
+
+        let filter = ProgramFilter::read_or_migrate(&json_path, csv_path)?;
+        log.write(&filter.clone().into())?;
+        let _ = fs::remove_file(json_path);
+
+        Ok(filter)
+    }
+
+    /// Read the filters from a file at the given path. If it does not exist, the filters are migrated from the CSV file
+    /// at the legacy path used before the versioned format.
+    pub fn read_or_migrate<P: AsRef<Path>, L: AsRef<Path>>(
+        path: P,
+        legacy_path: L,
+    ) -> Result<Self, Error> {
+        if path.as_ref().exists() || !legacy_path.as_ref().exists() {
+            return ProgramFilter::read_from_path(path);
+        }
+
+        let filter = ProgramFilter::read_from_csv_path(&legacy_path)?;
+        filter.write_to_path(path)?;
+        let _ = fs::remove_file(legacy_path);
+
+        Ok(filter)
+    }
+
+    /// Read the filters from a CSV file at the given path in the format used before the versioned format. Like the
+    /// versioned format, it may have been edited by hand.
+    fn read_from_csv_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = encoding::normalize(&encryption::read_file(path)?);
+
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(content.as_bytes());
+
+        let mut content: Vec<[String; 2]> = vec![];
+
+        for record_res in reader.records() {
+            let record = record_res?;
+
+            if record.len() != 2 {
+                return Err(Error::ParsingFile);
+            }
+
+            let record_arr = [
+                record.get(0).unwrap().to_string(),
+                record.get(1).unwrap().to_string(),
+            ];
+
+            content.push(record_arr);
+        }
+
+        content.try_into().map_err(|_| Error::ParsingFile)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::Error;
+
+use reqwest::header::CONTENT_TYPE;
+use serde_json::{json, Value};
+
+/// A part of a website a provider could not read, e.g. as the website changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseFailure {
+    provider: String,
+    selector: String,
+}
+
+impl ParseFailure {
+    /// Create a failure of the provider to find the selector on its website.
+    pub fn new(provider: &str, selector: &str) -> Self {
+        ParseFailure {
+            provider: provider.to_string(),
+            selector: selector.to_string(),
+        }
+    }
+
+    /// Get the name of the provider, e.g. the domain of its website.
+    pub fn get_provider(&self) -> String {
+        self.provider.clone()
+    }
+
+    /// Get the CSS selector that did not match.
+    pub fn get_selector(&self) -> String {
+        self.selector.clone()
+    }
+}
+
+/// The report about the failure sent to the maintainers if the user opted in. It only contains the provider, the
+/// selector and the version of the app, nothing about the user or the program.
+pub fn health_report(failure: &ParseFailure, version: &str) -> Value {
+    json!({
+        "provider": failure.provider,
+        "selector": failure.selector,
+        "version": version,
+    })
+}
+
+/// Send the report about the failure to the endpoint at the given URL.
+pub async fn send_health_report(
+    url: &str,
+    failure: &ParseFailure,
+    version: &str,
+) -> Result<(), Error> {
+    reqwest::Client::new()
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(health_report(failure, version).to_string())
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_health_report() {
+        let failure = ParseFailure::new("tvspielfilm.de", ".col-3 span a strong");
+
+        assert_eq!(
+            health_report(&failure, "0.1.0"),
+            json!({
+                "provider": "tvspielfilm.de",
+                "selector": ".col-3 span a strong",
+                "version": "0.1.0",
+            })
+        );
+    }
+}
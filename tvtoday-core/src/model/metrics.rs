@@ -0,0 +1,145 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// The time spent in each step of the last reload in microseconds.
+static NETWORK: AtomicU64 = AtomicU64::new(0);
+static PARSE: AtomicU64 = AtomicU64::new(0);
+static DECODE_ICONS: AtomicU64 = AtomicU64::new(0);
+static BUILD_LIST: AtomicU64 = AtomicU64::new(0);
+
+/// The time from creating the list until the window was drawn the first time in microseconds.
+static FIRST_PAINT: AtomicU64 = AtomicU64::new(0);
+
+/// A step of reloading the program that is timed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadStep {
+    /// Waiting for the program and the channel icons from the network.
+    Network,
+    /// Parsing the program.
+    Parse,
+    /// Decoding the channel icons.
+    DecodeIcons,
+    /// Creating the rows of the list.
+    BuildList,
+}
+
+impl ReloadStep {
+    fn counter(self) -> &'static AtomicU64 {
+        match self {
+            ReloadStep::Network => &NETWORK,
+            ReloadStep::Parse => &PARSE,
+            ReloadStep::DecodeIcons => &DECODE_ICONS,
+            ReloadStep::BuildList => &BUILD_LIST,
+        }
+    }
+}
+
+/// Start timing a new reload, forgetting the times of the last one.
+pub fn start_reload() {
+    for step in &[
+        ReloadStep::Network,
+        ReloadStep::Parse,
+        ReloadStep::DecodeIcons,
+        ReloadStep::BuildList,
+    ] {
+        reset(*step);
+    }
+}
+
+/// Forget the time spent in the step, e.g. when the list is built again.
+pub fn reset(step: ReloadStep) {
+    step.counter().store(0, Ordering::Relaxed);
+}
+
+/// Add the duration to the time spent in the step. Steps may be done in parts, e.g. the program is parsed each time a
+/// part of it was received.
+pub fn record(step: ReloadStep, duration: Duration) {
+    step.counter()
+        .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+}
+
+/// Run the function and add the time it took to the step.
+pub fn time<R, F: FnOnce() -> R>(step: ReloadStep, f: F) -> R {
+    let start = Instant::now();
+    let result = f();
+    record(step, start.elapsed());
+    result
+}
+
+/// Remember how long it took until the window was drawn the first time after starting.
+pub fn record_first_paint(duration: Duration) {
+    FIRST_PAINT.store(duration.as_micros() as u64, Ordering::Relaxed);
+}
+
+/// Get how long it took until the window was drawn the first time after starting, zero if it was not drawn yet.
+pub fn first_paint() -> Duration {
+    Duration::from_micros(FIRST_PAINT.load(Ordering::Relaxed))
+}
+
+/// The time spent in each step of the last reload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReloadTimings {
+    network: Duration,
+    parse: Duration,
+    decode_icons: Duration,
+    build_list: Duration,
+}
+
+impl ReloadTimings {
+    /// Get the times of the last reload, also if it is still running.
+    pub fn last() -> Self {
+        let get = |step: ReloadStep| Duration::from_micros(step.counter().load(Ordering::Relaxed));
+
+        ReloadTimings {
+            network: get(ReloadStep::Network),
+            parse: get(ReloadStep::Parse),
+            decode_icons: get(ReloadStep::DecodeIcons),
+            build_list: get(ReloadStep::BuildList),
+        }
+    }
+
+    /// Get the time spent waiting for the program and the channel icons from the network.
+    pub fn get_network(&self) -> Duration {
+        self.network
+    }
+
+    /// Get the time spent parsing the program.
+    pub fn get_parse(&self) -> Duration {
+        self.parse
+    }
+
+    /// Get the time spent decoding the channel icons.
+    pub fn get_decode_icons(&self) -> Duration {
+        self.decode_icons
+    }
+
+    /// Get the time spent creating the rows of the list.
+    pub fn get_build_list(&self) -> Duration {
+        self.build_list
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_timings() {
+        start_reload();
+        record(ReloadStep::Parse, Duration::from_millis(5));
+        record(ReloadStep::Parse, Duration::from_millis(7));
+        assert_eq!(time(ReloadStep::BuildList, || 42), 42);
+
+        let timings = ReloadTimings::last();
+        assert_eq!(timings.get_parse(), Duration::from_millis(12));
+        assert_eq!(timings.get_network(), Duration::from_millis(0));
+
+        reset(ReloadStep::Parse);
+        assert_eq!(ReloadTimings::last().get_parse(), Duration::from_millis(0));
+
+        // The first paint is not part of a reload.
+        record_first_paint(Duration::from_millis(80));
+        start_reload();
+        assert_eq!(first_paint(), Duration::from_millis(80));
+    }
+}
@@ -0,0 +1,80 @@
+mod archive;
+mod archive_file;
+pub mod cache;
+mod channel_group;
+mod channel_order;
+mod channel_order_file;
+mod circuit_breaker;
+mod clock;
+mod custom_group;
+mod custom_group_file;
+mod digest;
+mod encoding;
+pub mod encryption;
+mod filter;
+mod filter_file;
+mod health;
+pub mod metrics;
+pub mod paths;
+mod personal_ratings;
+mod personal_ratings_file;
+mod poster;
+mod preset;
+mod preset_file;
+mod program;
+mod program_day;
+mod program_file;
+mod provider;
+mod provider_handle;
+pub mod providers;
+mod ratings;
+mod reminder;
+mod reminder_file;
+pub mod remote;
+mod retry;
+pub mod search;
+mod settings;
+mod settings_file;
+mod speech;
+mod started_broadcasts;
+mod summary;
+mod sync_log;
+mod sync_log_file;
+mod time_slot;
+mod title_match;
+mod translation;
+mod widget;
+mod widget_file;
+
+pub use archive::{Archive, ArchivedBroadcast};
+pub use channel_group::ChannelGroup;
+pub use channel_order::ChannelOrder;
+pub use circuit_breaker::CircuitBreaker;
+pub use clock::ClockWatch;
+pub use custom_group::{CustomGroup, CustomGroups};
+pub use digest::{digest_html, DIGEST_DAYS};
+pub use filter::{
+    ChannelAttribute, FilterMode, FilterType, HiddenBroadcast, MovieAttribute, ProgramFilter,
+};
+pub use health::{health_report, send_health_report, ParseFailure};
+pub use personal_ratings::{PersonalRating, PersonalRatings};
+pub use poster::get_poster;
+pub use preset::{Preset, Presets};
+pub use program::{Channel, Movie, MovieBuilder, Program, SortOrder};
+pub use program_day::ProgramDay;
+pub use provider::Provider;
+pub use provider_handle::{BoxedProvider, ProviderHandle};
+pub use ratings::{get_ratings, Ratings};
+pub use reminder::{Reminder, ReminderChange, Reminders};
+pub use retry::Retrying;
+pub use settings::Settings;
+pub use speech::speak;
+pub use started_broadcasts::StartedBroadcasts;
+pub use summary::{summarize, tonights_picks};
+pub use sync_log::{Change, SyncState};
+pub use sync_log_file::*;
+pub use time_slot::TimeSlot;
+pub use title_match::TitleIndex;
+pub use translation::translate;
+pub use widget::{waybar_json, widget_json, widget_text, WIDGET_VERSION};
+pub use widget_file::*;
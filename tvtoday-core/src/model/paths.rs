@@ -0,0 +1,144 @@
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The name of the directories the application stores its data in.
+const APPLICATION_DIR: &str = "tvtoday";
+
+/// The directory the user data is stored in instead of the default one, if set.
+static USER_DATA_DIR_OVERRIDE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Get the base directory of the XDG Base Directory Specification set by the environment variable, or its default
+/// relative to the home directory.
+fn xdg_dir(variable: &str, default: &str) -> Option<PathBuf> {
+    std::env::var_os(variable)
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(default)))
+}
+
+/// Override the directory the user data is stored in. `None` restores the default directory.
+/// Must be set before any user data is read.
+pub fn set_user_data_dir_override(dir: Option<PathBuf>) {
+    *USER_DATA_DIR_OVERRIDE.lock().unwrap() = dir;
+}
+
+/// Get the directory the user data is stored in. The directory is created if it does not exist.
+pub fn user_data_dir() -> PathBuf {
+    let override_dir = USER_DATA_DIR_OVERRIDE.lock().unwrap().clone();
+    let user_data_dir = override_dir.unwrap_or_else(|| {
+        let mut dir =
+            xdg_dir("XDG_DATA_HOME", ".local/share").expect("Could not get user data directory");
+        dir.push(APPLICATION_DIR);
+        dir
+    });
+
+    if !user_data_dir.exists() {
+        std::fs::create_dir_all(user_data_dir.clone())
+            .expect("Could not create the user data directory");
+    }
+
+    user_data_dir
+}
+
+/// Get the path of the file with the given name in the user data directory.
+pub fn user_data_file(name: &str) -> PathBuf {
+    let mut path = user_data_dir();
+    path.push(name);
+    path
+}
+
+/// Get the files with user data in the user data directory. These are the CSV, JSON and log files, the directory may be
+/// chosen by the user and contain other files.
+pub fn user_data_files() -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in std::fs::read_dir(user_data_dir())? {
+        let path = entry?.path();
+        let is_user_data = path.is_file()
+            && path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| ["csv", "json", "log"].contains(&e))
+                .unwrap_or(false);
+        if is_user_data {
+            files.push(path);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Get the directory the configuration is stored in. The directory is created if it does not exist.
+pub fn user_config_dir() -> PathBuf {
+    let mut user_config_dir =
+        xdg_dir("XDG_CONFIG_HOME", ".config").expect("Could not get user config directory");
+    user_config_dir.push(APPLICATION_DIR);
+
+    if !user_config_dir.exists() {
+        std::fs::create_dir_all(user_config_dir.clone())
+            .expect("Could not create the user config directory");
+    }
+
+    user_config_dir
+}
+
+/// Get the path of the file with the given name in the user config directory.
+pub fn user_config_file(name: &str) -> PathBuf {
+    let mut path = user_config_dir();
+    path.push(name);
+    path
+}
+
+/// Get the directory cached data is stored in. The directory is created if it does not exist.
+pub fn user_cache_dir() -> PathBuf {
+    let mut user_cache_dir =
+        xdg_dir("XDG_CACHE_HOME", ".cache").expect("Could not get user cache directory");
+    user_cache_dir.push(APPLICATION_DIR);
+
+    if !user_cache_dir.exists() {
+        std::fs::create_dir_all(user_cache_dir.clone())
+            .expect("Could not create the user cache directory");
+    }
+
+    user_cache_dir
+}
+
+/// Remove all cached data. The directory is created again once it is used.
+pub fn clear_user_cache_dir() -> io::Result<()> {
+    let user_cache_dir = user_cache_dir();
+    std::fs::remove_dir_all(user_cache_dir)
+}
+
+/// Get the path of the file with the given name in the user cache directory.
+pub fn user_cache_file(name: &str) -> PathBuf {
+    let mut path = user_cache_dir();
+    path.push(name);
+    path
+}
+
+/// Get the id of this device, naming the files only this device writes to in a synced data directory. It is generated
+/// once and stored in the config directory, which is not synced.
+pub fn device_id() -> String {
+    let path = user_config_file("device-id");
+    if let Ok(id) = std::fs::read_to_string(&path) {
+        if !id.trim().is_empty() {
+            return id.trim().to_string();
+        }
+    }
+
+    // The host name alone is not unique, e.g. for two phones of the same model.
+    let host: String = std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .map(|h| h.chars().filter(|c| c.is_ascii_alphanumeric()).collect())
+        .unwrap_or_default();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let id = format!("{}-{:x}{:x}", host, nanos, std::process::id());
+
+    let _ = std::fs::write(&path, &id);
+    id
+}
@@ -0,0 +1,174 @@
+use crate::model::{Movie, TitleIndex};
+
+use std::convert::TryFrom;
+
+/// A rating the user gave a movie on IMDb, from 1 to 10.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalRating {
+    title: String,
+    original_title: Option<String>,
+    year: Option<u32>,
+    rating: u32,
+}
+
+/// All ratings the user imported from IMDb, matched to movies by their normalized title and year.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalRatings {
+    ratings: Vec<PersonalRating>,
+    index: TitleIndex<u32>,
+}
+
+impl PersonalRating {
+    /// Create the rating of the movie with the title, and the original title if it differs, released in the year.
+    pub fn new(title: &str, original_title: Option<&str>, year: Option<u32>, rating: u32) -> Self {
+        PersonalRating {
+            title: title.to_string(),
+            original_title: original_title
+                .filter(|o| *o != title)
+                .map(|o| o.to_string()),
+            year,
+            rating,
+        }
+    }
+}
+
+impl From<PersonalRating> for [String; 4] {
+    /// Convert from a `PersonalRating` to `[String; 4]`. A missing original title or year is empty.
+    fn from(item: PersonalRating) -> [String; 4] {
+        [
+            item.title,
+            item.original_title.unwrap_or_default(),
+            item.year.map(|y| y.to_string()).unwrap_or_default(),
+            item.rating.to_string(),
+        ]
+    }
+}
+
+impl TryFrom<[String; 4]> for PersonalRating {
+    type Error = ();
+
+    /// Try to convert from `[String; 4]` to a `PersonalRating`.
+    fn try_from(item: [String; 4]) -> Result<PersonalRating, ()> {
+        let year = if item[2].is_empty() {
+            None
+        } else {
+            Some(item[2].parse().map_err(|_| ())?)
+        };
+
+        Ok(PersonalRating::new(
+            &item[0],
+            Some(&item[1][..]).filter(|o| !o.is_empty()),
+            year,
+            item[3].parse().map_err(|_| ())?,
+        ))
+    }
+}
+
+impl PersonalRatings {
+    /// Create a new, empty collection of ratings.
+    pub fn new() -> Self {
+        PersonalRatings {
+            ratings: vec![],
+            index: TitleIndex::new(),
+        }
+    }
+
+    /// Add a rating, matched by its title and original title.
+    pub fn add(&mut self, rating: PersonalRating) {
+        self.index.insert(&rating.title, rating.year, rating.rating);
+        if let Some(original_title) = &rating.original_title {
+            self.index
+                .insert(original_title, rating.year, rating.rating);
+        }
+        self.ratings.push(rating);
+    }
+
+    /// Get the rating the user gave the movie, if it was rated.
+    pub fn get(&self, movie: &Movie) -> Option<u32> {
+        self.index
+            .get(&movie.get_title(), movie.get_year())
+            .copied()
+    }
+
+    /// Get an iterator over all ratings.
+    pub fn iter(&self) -> std::slice::Iter<'_, PersonalRating> {
+        self.ratings.iter()
+    }
+
+    /// Weather no movie was rated.
+    pub fn is_empty(&self) -> bool {
+        self.ratings.is_empty()
+    }
+}
+
+impl Default for PersonalRatings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::MovieBuilder;
+
+    #[test]
+    fn test_from_imdb_csv() {
+        let content = "Const,Your Rating,Date Rated,Title,Original Title,URL,Title Type,IMDb Rating,Runtime (mins),Year,Genres,Num Votes,Release Date,Directors
+tt0017136,9,2021-03-01,Metropolis,Metropolis,https://www.imdb.com/title/tt0017136/,movie,8.3,153,1927,\"Drama, Sci-Fi\",170000,1927-01-10,Fritz Lang
+tt0097165,4,2021-03-02,Der Club der toten Dichter,Dead Poets Society,https://www.imdb.com/title/tt0097165/,movie,8.1,128,1989,Drama,450000,1989-06-02,Peter Weir
+";
+        let ratings = PersonalRatings::from_imdb_csv(content.as_bytes()).unwrap();
+        assert_eq!(ratings.iter().count(), 2);
+
+        let mut metropolis = MovieBuilder::new("Metropolis");
+        metropolis.with_year(1927);
+        assert_eq!(ratings.get(&metropolis.build()), Some(9));
+
+        // Both the title and the original title match, the year may differ by one.
+        let mut dichter = MovieBuilder::new("Der Club der toten Dichter");
+        dichter.with_year(1990);
+        assert_eq!(ratings.get(&dichter.build()), Some(4));
+        assert_eq!(ratings.get(&Movie::new("Dead Poets Society")), Some(4));
+        assert_eq!(ratings.get(&Movie::new("Tatort")), None);
+
+        // The older export without the original title can be read too.
+        let content = "Const,Your Rating,Date Rated,Title,URL,Title Type,IMDb Rating,Runtime (mins),Year,Genres,Num Votes,Release Date,Directors
+tt0017136,9,2021-03-01,Metropolis,https://www.imdb.com/title/tt0017136/,movie,8.3,153,1927,\"Drama, Sci-Fi\",170000,1927-01-10,Fritz Lang
+";
+        assert_eq!(
+            PersonalRatings::from_imdb_csv(content.as_bytes())
+                .unwrap()
+                .get(&Movie::new("Metropolis")),
+            Some(9)
+        );
+        assert!(PersonalRatings::from_imdb_csv(b"Title,Year\nMetropolis,1927\n").is_err());
+    }
+
+    #[test]
+    fn test_str_array() {
+        let rating = PersonalRating::new(
+            "Der Club der toten Dichter",
+            Some("Dead Poets Society"),
+            Some(1989),
+            4,
+        );
+        let content = <[String; 4]>::from(rating.clone());
+        assert_eq!(PersonalRating::try_from(content), Ok(rating));
+
+        let rating = PersonalRating::new("Metropolis", Some("Metropolis"), None, 9);
+        assert_eq!(rating.original_title, None);
+        let content = <[String; 4]>::from(rating.clone());
+        assert_eq!(
+            content,
+            [
+                "Metropolis".to_string(),
+                String::new(),
+                String::new(),
+                "9".to_string()
+            ]
+        );
+        assert_eq!(PersonalRating::try_from(content), Ok(rating));
+    }
+}
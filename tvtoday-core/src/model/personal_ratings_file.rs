@@ -0,0 +1,101 @@
+use crate::model::{encryption, PersonalRating, PersonalRatings};
+use crate::Error;
+
+use std::convert::TryFrom;
+use std::fs;
+use std::path::Path;
+
+use csv::{ReaderBuilder, Writer};
+
+impl PersonalRatings {
+    /// Write the ratings to a file at the given path.
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let mut writer = Writer::from_writer(vec![]);
+
+        for rating in self.iter() {
+            writer.write_record(<[String; 4]>::from(rating.clone()))?;
+        }
+
+        let content = writer.into_inner().map_err(|_| Error::ParsingFile)?;
+
+        encryption::write_file(path, &content)
+    }
+
+    /// Read the ratings from a file at the given path.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = encryption::read_file(path)?;
+
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(&content[..]);
+
+        let mut ratings = PersonalRatings::new();
+
+        for record_res in reader.records() {
+            let record = record_res?;
+
+            if record.len() != 4 {
+                return Err(Error::ParsingFile);
+            }
+
+            let record_arr = [
+                record.get(0).unwrap().to_string(),
+                record.get(1).unwrap().to_string(),
+                record.get(2).unwrap().to_string(),
+                record.get(3).unwrap().to_string(),
+            ];
+
+            ratings.add(PersonalRating::try_from(record_arr).map_err(|_| Error::ParsingFile)?);
+        }
+
+        Ok(ratings)
+    }
+
+    /// Read the ratings from the CSV file exported on the "Your Ratings" page of IMDb at the given path.
+    pub fn read_from_imdb_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = fs::read(path).map_err(|_| Error::ParsingFile)?;
+
+        PersonalRatings::from_imdb_csv(&content)
+    }
+
+    /// Parse the ratings from the CSV exported by IMDb. The columns are found by their header, as IMDb added columns
+    /// over time. Rows without a valid rating are skipped.
+    pub fn from_imdb_csv(content: &[u8]) -> Result<Self, Error> {
+        let mut reader = ReaderBuilder::new().flexible(true).from_reader(content);
+
+        let headers = reader.headers()?.clone();
+        let column = |name: &str| headers.iter().position(|h| h.trim() == name);
+        let title_column = column("Title").ok_or(Error::ParsingFile)?;
+        let rating_column = column("Your Rating").ok_or(Error::ParsingFile)?;
+        let year_column = column("Year");
+        let original_title_column = column("Original Title");
+
+        let mut ratings = PersonalRatings::new();
+
+        for record_res in reader.records() {
+            let record = record_res?;
+
+            let title = match record.get(title_column) {
+                Some(title) if !title.is_empty() => title,
+                _ => continue,
+            };
+            let rating = match record
+                .get(rating_column)
+                .and_then(|r| r.trim().parse().ok())
+            {
+                Some(rating) => rating,
+                None => continue,
+            };
+            let year = year_column
+                .and_then(|c| record.get(c))
+                .and_then(|y| y.trim().parse().ok());
+            let original_title = original_title_column
+                .and_then(|c| record.get(c))
+                .filter(|o| !o.is_empty());
+
+            ratings.add(PersonalRating::new(title, original_title, year, rating));
+        }
+
+        Ok(ratings)
+    }
+}
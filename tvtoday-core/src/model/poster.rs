@@ -0,0 +1,7 @@
+use crate::Error;
+
+/// Download the poster at the given URL. The encoded image is returned.
+pub async fn get_poster(url: &str) -> Result<Vec<u8>, Error> {
+    let bytes = reqwest::get(url).await?.bytes().await?;
+    Ok(bytes.to_vec())
+}
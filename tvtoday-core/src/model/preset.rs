@@ -0,0 +1,178 @@
+use crate::model::{SortOrder, TimeSlot};
+
+use std::convert::TryFrom;
+
+/// A named combination of the settings of the movie list, e.g. "Sports night".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preset {
+    name: String,
+    sort_order: SortOrder,
+    grouped: bool,
+    view_group: Option<String>,
+    time_slot: Option<TimeSlot>,
+}
+
+/// All presets of the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Presets {
+    presets: Vec<Preset>,
+}
+
+impl Preset {
+    /// Create a new preset.
+    pub fn new(
+        name: &str,
+        sort_order: SortOrder,
+        grouped: bool,
+        view_group: Option<String>,
+        time_slot: Option<TimeSlot>,
+    ) -> Self {
+        Preset {
+            name: name.to_string(),
+            sort_order,
+            grouped,
+            view_group,
+            time_slot,
+        }
+    }
+
+    /// Get the name of the preset.
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Get the order the list is sorted by.
+    pub fn get_sort_order(&self) -> SortOrder {
+        self.sort_order
+    }
+
+    /// Get weather the list is grouped by channel.
+    pub fn get_grouped(&self) -> bool {
+        self.grouped
+    }
+
+    /// Get the name of the custom channel group that is exclusively shown.
+    pub fn get_view_group(&self) -> Option<String> {
+        self.view_group.clone()
+    }
+
+    /// Get the time slot of the program, if the preset changes it.
+    pub fn get_time_slot(&self) -> Option<TimeSlot> {
+        self.time_slot
+    }
+}
+
+impl From<Preset> for [String; 5] {
+    /// Convert from a `Preset` to `[String; 5]`. A missing view group or time slot is stored as a empty string.
+    fn from(item: Preset) -> [String; 5] {
+        [
+            item.name,
+            item.sort_order.to_string(),
+            item.grouped.to_string(),
+            item.view_group.unwrap_or_default(),
+            item.time_slot.map(|t| t.to_string()).unwrap_or_default(),
+        ]
+    }
+}
+
+impl TryFrom<[String; 5]> for Preset {
+    type Error = ();
+
+    /// Try to convert from `[String; 5]` to a `Preset`.
+    fn try_from(item: [String; 5]) -> Result<Preset, ()> {
+        let sort_order = SortOrder::try_from(&item[1][..])?;
+        let grouped = item[2].parse().map_err(|_| ())?;
+        let view_group = if item[3].is_empty() {
+            None
+        } else {
+            Some(item[3].clone())
+        };
+        let time_slot = if item[4].is_empty() {
+            None
+        } else {
+            Some(TimeSlot::try_from(&item[4][..])?)
+        };
+
+        Ok(Preset {
+            name: item[0].clone(),
+            sort_order,
+            grouped,
+            view_group,
+            time_slot,
+        })
+    }
+}
+
+impl Presets {
+    /// Create a new, empty list of presets.
+    pub fn new() -> Self {
+        Presets { presets: vec![] }
+    }
+
+    /// Add the preset. A existing preset with the same name is replaced.
+    pub fn set(&mut self, preset: Preset) {
+        if let Some(existing) = self.presets.iter_mut().find(|p| p.name == preset.name) {
+            *existing = preset;
+        } else {
+            self.presets.push(preset);
+        }
+    }
+
+    /// Remove the preset with the given name.
+    pub fn remove(&mut self, name: &str) {
+        self.presets.retain(|p| p.name != name);
+    }
+
+    /// Get the preset with the given name.
+    pub fn get(&self, name: &str) -> Option<&Preset> {
+        self.presets.iter().find(|p| p.name == name)
+    }
+
+    /// Turn the presets into a iterator.
+    pub fn iter(&self) -> std::slice::Iter<'_, Preset> {
+        self.presets.iter()
+    }
+}
+
+impl Default for Presets {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_str_array() {
+        let preset = Preset::new(
+            "Kids",
+            SortOrder::Start,
+            true,
+            Some("Kids".to_string()),
+            Some(TimeSlot::Now),
+        );
+
+        assert_eq!(
+            <[String; 5]>::from(preset.clone()),
+            [
+                "Kids".to_string(),
+                "start".to_string(),
+                "true".to_string(),
+                "Kids".to_string(),
+                "now".to_string()
+            ]
+        );
+        assert_eq!(
+            Preset::try_from(<[String; 5]>::from(preset.clone())),
+            Ok(preset)
+        );
+
+        let preset = Preset::new("All", SortOrder::Title, false, None, None);
+        assert_eq!(
+            Preset::try_from(<[String; 5]>::from(preset.clone())),
+            Ok(preset)
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::model::{encryption, Preset, Presets};
+use crate::Error;
+
+use std::convert::TryFrom;
+use std::path::Path;
+
+use csv::{ReaderBuilder, Writer};
+
+impl Presets {
+    /// Write the presets to a file at the given path.
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let mut writer = Writer::from_writer(vec![]);
+
+        for preset in self.iter() {
+            writer.write_record(<[String; 5]>::from(preset.clone()))?;
+        }
+
+        let content = writer.into_inner().map_err(|_| Error::ParsingFile)?;
+
+        encryption::write_file(path, &content)
+    }
+
+    /// Read the presets from a file at the given path.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = encryption::read_file(path)?;
+
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(&content[..]);
+
+        let mut presets = Presets::new();
+
+        for record_res in reader.records() {
+            let record = record_res?;
+
+            // Presets stored before time slots were added have no time slot.
+            if record.len() != 4 && record.len() != 5 {
+                return Err(Error::ParsingFile);
+            }
+
+            let record_arr = [
+                record.get(0).unwrap().to_string(),
+                record.get(1).unwrap().to_string(),
+                record.get(2).unwrap().to_string(),
+                record.get(3).unwrap().to_string(),
+                record.get(4).unwrap_or_default().to_string(),
+            ];
+
+            presets.set(Preset::try_from(record_arr).map_err(|_| Error::ParsingFile)?);
+        }
+
+        Ok(presets)
+    }
+}
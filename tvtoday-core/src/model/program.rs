@@ -0,0 +1,955 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::iter::FromIterator;
+use std::ops::{Index, RangeBounds};
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+use image::RgbaImage;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The television program consisiting of many channels and their movie
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    content: Vec<(Channel, Movie)>,
+    /// The indices of the entries of each channel name.
+    by_channel: HashMap<String, Vec<usize>>,
+    /// The indices of the entries starting at each time.
+    by_start: BTreeMap<NaiveDateTime, Vec<usize>>,
+}
+
+/// A channel must have a name and a optional icon. The icon is shared by the copies of the channel in each entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Channel {
+    name: String,
+    icon: Option<Arc<RgbaImage>>,
+}
+
+/// A movie must have a title, a optional start time, year, genre, division, age rating, director, poster and description and a possibly empty cast.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Movie {
+    title: String,
+    start: Option<NaiveDateTime>,
+    year: Option<u32>,
+    genre: Option<String>,
+    division: Option<String>,
+    age_rating: Option<u32>,
+    director: Option<String>,
+    #[serde(default)]
+    cast: Vec<String>,
+    poster_url: Option<String>,
+    description: Option<String>,
+}
+
+/// A broadcast of a serialized `Program`, the name of the channel next to the fields of the movie.
+#[derive(Serialize)]
+struct BroadcastRef<'a> {
+    channel: &'a Channel,
+    #[serde(flatten)]
+    movie: &'a Movie,
+}
+
+/// A broadcast of a deserialized `Program`.
+#[derive(Deserialize)]
+struct Broadcast {
+    channel: Channel,
+    #[serde(flatten)]
+    movie: Movie,
+}
+
+/// Build movies.
+pub struct MovieBuilder {
+    movie: Movie,
+}
+
+/// The orders a `Program` can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// The order of the channels as given by the provider.
+    Channel,
+    /// The movie title, ignoring the case.
+    Title,
+    /// The year of the movie, newest first.
+    Year,
+    /// The genre of the movie.
+    Genre,
+    /// The start time of the movie.
+    Start,
+}
+
+impl SortOrder {
+    /// All orders a `Program` can be sorted by.
+    pub const ALL: [SortOrder; 5] = [
+        SortOrder::Channel,
+        SortOrder::Start,
+        SortOrder::Title,
+        SortOrder::Year,
+        SortOrder::Genre,
+    ];
+}
+
+impl Display for SortOrder {
+    /// The identifier of the order, used for storing it.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            SortOrder::Channel => write!(f, "channel"),
+            SortOrder::Title => write!(f, "title"),
+            SortOrder::Year => write!(f, "year"),
+            SortOrder::Genre => write!(f, "genre"),
+            SortOrder::Start => write!(f, "start"),
+        }
+    }
+}
+
+impl TryFrom<&str> for SortOrder {
+    type Error = ();
+
+    /// Try to convert from the identifier of the order to the `SortOrder`.
+    fn try_from(item: &str) -> Result<SortOrder, ()> {
+        SortOrder::ALL
+            .iter()
+            .find(|o| o.to_string() == item)
+            .cloned()
+            .ok_or(())
+    }
+}
+
+impl Program {
+    /// Create a new, empty program.
+    pub fn new() -> Self {
+        Program {
+            content: vec![],
+            by_channel: HashMap::new(),
+            by_start: BTreeMap::new(),
+        }
+    }
+
+    /// Add a channel and its movie to the program.
+    pub fn add(&mut self, channel: Channel, movie: Movie) {
+        self.index_entry(self.content.len(), &channel, &movie);
+        self.content.push((channel, movie));
+    }
+
+    /// Get the number of entries in the program.
+    pub fn len(&self) -> usize {
+        self.content.len()
+    }
+
+    /// Weather the program has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
+    /// Get the entries of the channel with the given name in program order.
+    pub fn channel(&self, name: &str) -> Vec<&(Channel, Movie)> {
+        self.by_channel
+            .get(name)
+            .map(|indices| indices.iter().map(|i| &self.content[*i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the entries starting in the given range of time, ordered by their start time.
+    pub fn starting<R: RangeBounds<NaiveDateTime>>(&self, range: R) -> Vec<&(Channel, Movie)> {
+        self.by_start
+            .range(range)
+            .flat_map(|(_start, indices)| indices.iter().map(|i| &self.content[*i]))
+            .collect()
+    }
+
+    /// Add the entry at the given index to the indices.
+    fn index_entry(&mut self, index: usize, channel: &Channel, movie: &Movie) {
+        self.by_channel
+            .entry(channel.name.clone())
+            .or_default()
+            .push(index);
+        if let Some(start) = movie.start {
+            self.by_start.entry(start).or_default().push(index);
+        }
+    }
+
+    /// Rebuild the indices after the order of the entries changed.
+    fn reindex(&mut self) {
+        self.by_channel.clear();
+        self.by_start.clear();
+
+        let content = std::mem::take(&mut self.content);
+        for (index, (channel, movie)) in content.iter().enumerate() {
+            self.index_entry(index, channel, movie);
+        }
+        self.content = content;
+    }
+
+    /// Turn the program into a iterator over the channels and their movies.
+    pub fn iter(&self) -> std::slice::Iter<'_, (Channel, Movie)> {
+        self.content.iter()
+    }
+
+    /// Sort the program by the given order. The sort is stable, equal entries keep their channel order.
+    /// Movies missing the attribute that is sorted by are put at the end.
+    pub fn sort(&mut self, order: SortOrder) {
+        match order {
+            SortOrder::Channel => {}
+            SortOrder::Title => self
+                .content
+                .sort_by_key(|(_c, m)| m.get_title().to_lowercase()),
+            SortOrder::Year => self.content.sort_by_key(|(_c, m)| {
+                let year = m.get_year();
+                (year.is_none(), Reverse(year))
+            }),
+            SortOrder::Genre => self.content.sort_by_key(|(_c, m)| {
+                let genre = m.get_genre();
+                (genre.is_none(), genre)
+            }),
+            SortOrder::Start => self.content.sort_by_key(|(_c, m)| {
+                let start = m.get_start();
+                (start.is_none(), start)
+            }),
+        }
+
+        self.reindex();
+    }
+
+    /// Merge the other program into this one. Entries with the same channel name, title and start time are the same
+    /// broadcast and only kept once, at the position they appeared first, also within the other program.
+    /// New entries are appended in their order. A channel icon missing in this program is taken from the other one.
+    pub fn merge(&mut self, other: Program) {
+        for (channel, movie) in other.content {
+            match self.position(&channel, &movie) {
+                Some(index) => {
+                    let c = &mut self.content[index].0;
+                    if c.icon.is_none() {
+                        c.icon = channel.icon;
+                    }
+                }
+                None => self.add(channel, movie),
+            }
+        }
+    }
+
+    /// Get the entries of this program that are not in the old one, e.g. the broadcasts added by a refresh. Entries with
+    /// the same channel name, title and start time are the same broadcast.
+    pub fn diff(&self, old: &Program) -> Program {
+        self.content
+            .iter()
+            .filter(|(channel, movie)| old.position(channel, movie).is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Get a copy of the program keeping each movie broadcasted on multiple channels at the same time, e.g. on the
+    /// regional variants of ARD, only once at the position it appears first. Movies without a start time are kept.
+    pub fn dedup_movies(&self) -> Program {
+        self.content
+            .iter()
+            .enumerate()
+            .filter(|(index, (_c, movie))| {
+                self.same_movie(movie)
+                    .first()
+                    .map(|first| first == index)
+                    .unwrap_or(true)
+            })
+            .map(|(_index, entry)| entry.clone())
+            .collect()
+    }
+
+    /// Get the channels broadcasting the movie with the same title at the same time in program order. It is empty if
+    /// the movie has no start time.
+    pub fn channels_of(&self, movie: &Movie) -> Vec<Channel> {
+        self.same_movie(movie)
+            .into_iter()
+            .map(|i| self.content[i].0.clone())
+            .collect()
+    }
+
+    /// Get the indices of the entries with the same title and start time as the movie in program order.
+    fn same_movie(&self, movie: &Movie) -> Vec<usize> {
+        movie
+            .start
+            .and_then(|start| self.by_start.get(&start))
+            .map(|indices| {
+                indices
+                    .iter()
+                    .filter(|i| self.content[**i].1.title == movie.title)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Get the index of the entry of the same broadcast, with the same channel name, title and start time.
+    fn position(&self, channel: &Channel, movie: &Movie) -> Option<usize> {
+        self.by_channel
+            .get(&channel.name)
+            .and_then(|indices| {
+                indices.iter().find(|i| {
+                    let m = &self.content[**i].1;
+                    m.title == movie.title && m.start == movie.start
+                })
+            })
+            .cloned()
+    }
+
+    /// Get a copy of the program sorted by the given order.
+    pub fn sorted(&self, order: SortOrder) -> Program {
+        let mut program = self.clone();
+        program.sort(order);
+        program
+    }
+
+    /// Get the size of the decoded channel icons in bytes. Each icon is counted once, as it is shared by the entries.
+    pub fn icon_size(&self) -> usize {
+        self.by_channel
+            .values()
+            .filter_map(|indices| indices.first())
+            .map(|i| self.content[*i].0.icon_size())
+            .sum()
+    }
+
+    /// Get a copy of the program keeping the channel icons only as long as they fit into the limit in bytes. The
+    /// icons of the channels appearing first are kept.
+    pub fn limit_icons(&self, limit: usize) -> Program {
+        let mut kept: Vec<String> = vec![];
+        let mut size = 0;
+        for (channel, _movie) in self.content.iter() {
+            if kept.contains(&channel.name) || channel.icon.is_none() {
+                continue;
+            }
+            if size + channel.icon_size() > limit {
+                break;
+            }
+            size += channel.icon_size();
+            kept.push(channel.name.clone());
+        }
+
+        self.content
+            .iter()
+            .map(|(channel, movie)| {
+                let mut channel = channel.clone();
+                if !kept.contains(&channel.name) {
+                    channel.icon = None;
+                }
+                (channel, movie.clone())
+            })
+            .collect()
+    }
+}
+
+impl Default for Program {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Index<usize> for Program {
+    type Output = (Channel, Movie);
+
+    /// A program can be indexed.
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.content[index]
+    }
+}
+
+impl FromIterator<(Channel, Movie)> for Program {
+    /// Convert a iterator over `(Channel, Movie)` into a program.
+    fn from_iter<I: IntoIterator<Item = (Channel, Movie)>>(iter: I) -> Self {
+        let mut program = Program::new();
+        for (channel, movie) in iter {
+            program.add(channel, movie);
+        }
+        program
+    }
+}
+
+impl Serialize for Program {
+    /// A program is serialized as a sequence of its broadcasts.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            self.content
+                .iter()
+                .map(|(channel, movie)| BroadcastRef { channel, movie }),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for Program {
+    /// A program is deserialized from a sequence of its broadcasts.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<Broadcast>::deserialize(deserializer)?
+            .into_iter()
+            .map(|broadcast| (broadcast.channel, broadcast.movie))
+            .collect())
+    }
+}
+
+impl Serialize for Channel {
+    /// A channel is serialized as its name. The icon is left out, it is loaded again by the provider.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name)
+    }
+}
+
+impl<'de> Deserialize<'de> for Channel {
+    /// A channel is deserialized from its name, without icon.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Channel::new(&String::deserialize(deserializer)?))
+    }
+}
+
+impl Channel {
+    /// Create a new `Channel` with the given name and no icon.
+    pub fn new(name: &str) -> Self {
+        Channel {
+            name: name.to_string(),
+            icon: None,
+        }
+    }
+
+    /// Get the name of the `Channel`.
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Set the ivon of the `Channel`.
+    pub fn set_icon(&mut self, icon: Option<RgbaImage>) {
+        self.icon = icon.map(Arc::new);
+    }
+
+    /// Get the size of the decoded icon in bytes, 0 without icon.
+    pub fn icon_size(&self) -> usize {
+        self.icon.as_ref().map(|i| i.as_raw().len()).unwrap_or(0)
+    }
+
+    /// Get the icon of the `Channel`.
+    pub fn get_icon(&self) -> Option<&RgbaImage> {
+        self.icon.as_deref()
+    }
+}
+
+impl Movie {
+    /// Create a new `Movie` with the given title. All other attributes are not set.
+    pub fn new(title: &str) -> Self {
+        Movie {
+            title: title.to_string(),
+            start: None,
+            year: None,
+            genre: None,
+            division: None,
+            age_rating: None,
+            director: None,
+            cast: vec![],
+            poster_url: None,
+            description: None,
+        }
+    }
+
+    /// Get the title.
+    pub fn get_title(&self) -> String {
+        self.title.clone()
+    }
+
+    /// Get the optional start time in local time.
+    pub fn get_start(&self) -> Option<NaiveDateTime> {
+        self.start
+    }
+
+    /// Set the optional start time in local time.
+    pub fn set_start(&mut self, start: Option<NaiveDateTime>) {
+        self.start = start
+    }
+
+    /// Get whether the movie started more than the given minutes before `now`. Movies without a start time never
+    /// started.
+    pub fn started_before(&self, now: NaiveDateTime, minutes: u32) -> bool {
+        self.start
+            .map(|s| s + chrono::Duration::minutes(minutes as i64) < now)
+            .unwrap_or(false)
+    }
+
+    /// Get the optional year.
+    pub fn get_year(&self) -> Option<u32> {
+        self.year
+    }
+
+    /// Set the optional year.
+    pub fn set_year(&mut self, year: Option<u32>) {
+        self.year = year
+    }
+
+    /// Get the optional genre.
+    pub fn get_genre(&self) -> Option<String> {
+        self.genre.clone()
+    }
+
+    /// Set the optional genre.
+    pub fn set_genre(&mut self, genre: Option<String>) {
+        self.genre = genre
+    }
+
+    /// Get the optional division.
+    pub fn get_division(&self) -> Option<String> {
+        self.division.clone()
+    }
+
+    /// Set the optional division.
+    pub fn set_division(&mut self, disision: Option<String>) {
+        self.division = disision
+    }
+
+    /// Get the optional age rating (FSK).
+    pub fn get_age_rating(&self) -> Option<u32> {
+        self.age_rating
+    }
+
+    /// Set the optional age rating (FSK).
+    pub fn set_age_rating(&mut self, age_rating: Option<u32>) {
+        self.age_rating = age_rating
+    }
+
+    /// Get the optional director.
+    pub fn get_director(&self) -> Option<String> {
+        self.director.clone()
+    }
+
+    /// Set the optional director.
+    pub fn set_director(&mut self, director: Option<String>) {
+        self.director = director
+    }
+
+    /// Get the names of the actors.
+    pub fn get_cast(&self) -> Vec<String> {
+        self.cast.clone()
+    }
+
+    /// Set the names of the actors.
+    pub fn set_cast(&mut self, cast: Vec<String>) {
+        self.cast = cast
+    }
+
+    /// Get the optional URL of the poster.
+    pub fn get_poster_url(&self) -> Option<String> {
+        self.poster_url.clone()
+    }
+
+    /// Set the optional URL of the poster.
+    pub fn set_poster_url(&mut self, poster_url: Option<String>) {
+        self.poster_url = poster_url
+    }
+
+    /// Get the optional description.
+    pub fn get_description(&self) -> Option<String> {
+        self.description.clone()
+    }
+
+    /// Set the optional description.
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description
+    }
+}
+
+impl MovieBuilder {
+    /// Create a `MovieBuilder` building a `Movie` with the given title.
+    pub fn new(title: &str) -> Self {
+        MovieBuilder {
+            movie: Movie::new(title),
+        }
+    }
+
+    /// Set the start time of the `Movie`.
+    pub fn with_start(&mut self, start: NaiveDateTime) -> &mut Self {
+        self.movie.set_start(Some(start));
+        self
+    }
+
+    /// Set the year of the `Movie`.
+    pub fn with_year(&mut self, year: u32) -> &mut Self {
+        self.movie.set_year(Some(year));
+        self
+    }
+
+    /// Set the genre of the `Movie`.
+    pub fn with_genre(&mut self, genre: &str) -> &mut Self {
+        self.movie.set_genre(Some(genre.to_string()));
+        self
+    }
+
+    /// Set the division of the `Movie`.
+    pub fn with_division(&mut self, division: &str) -> &mut Self {
+        self.movie.set_division(Some(division.to_string()));
+        self
+    }
+
+    /// Set the age rating (FSK) of the `Movie`.
+    pub fn with_age_rating(&mut self, age_rating: u32) -> &mut Self {
+        self.movie.set_age_rating(Some(age_rating));
+        self
+    }
+
+    /// Set the director of the `Movie`.
+    pub fn with_director(&mut self, director: &str) -> &mut Self {
+        self.movie.set_director(Some(director.to_string()));
+        self
+    }
+
+    /// Add a actor to the cast of the `Movie`.
+    pub fn with_actor(&mut self, actor: &str) -> &mut Self {
+        self.movie.cast.push(actor.to_string());
+        self
+    }
+
+    /// Set the URL of the poster of the `Movie`.
+    pub fn with_poster_url(&mut self, poster_url: &str) -> &mut Self {
+        self.movie.set_poster_url(Some(poster_url.to_string()));
+        self
+    }
+
+    /// Build the `Movie`.
+    pub fn build(self) -> Movie {
+        self.movie
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use serde_json::json;
+
+    fn program() -> Program {
+        let mut movie_a = MovieBuilder::new("b");
+        movie_a.with_year(2001).with_genre("Krimi");
+        let mut movie_b = MovieBuilder::new("A");
+        movie_b.with_year(2010);
+        let mut movie_c = MovieBuilder::new("c");
+        movie_c.with_genre("Action");
+
+        vec![
+            (Channel::new("1"), movie_a.build()),
+            (Channel::new("2"), movie_b.build()),
+            (Channel::new("3"), movie_c.build()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn channel_names(program: &Program) -> Vec<String> {
+        program.iter().map(|(c, _m)| c.get_name()).collect()
+    }
+
+    #[test]
+    fn test_sort() {
+        let program = program();
+
+        assert_eq!(
+            channel_names(&program.sorted(SortOrder::Channel)),
+            vec!["1", "2", "3"]
+        );
+        assert_eq!(
+            channel_names(&program.sorted(SortOrder::Title)),
+            vec!["2", "1", "3"]
+        );
+        assert_eq!(
+            channel_names(&program.sorted(SortOrder::Year)),
+            vec!["2", "1", "3"]
+        );
+        assert_eq!(
+            channel_names(&program.sorted(SortOrder::Genre)),
+            vec!["3", "1", "2"]
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let start = |hour| {
+            chrono::NaiveDate::from_ymd_opt(2021, 4, 20)
+                .unwrap()
+                .and_hms_opt(hour, 15, 0)
+                .unwrap()
+        };
+        let movie = |title: &str, hour| {
+            let mut builder = MovieBuilder::new(title);
+            builder.with_start(start(hour));
+            builder.build()
+        };
+
+        let mut merged = program();
+        merged.merge(Program::new());
+        assert_eq!(merged, program());
+
+        let mut merged = Program::new();
+        merged.merge(program());
+        assert_eq!(merged, program());
+
+        let mut with_icon = Channel::new("4");
+        with_icon.set_icon(Some(RgbaImage::new(1, 1)));
+
+        let mut merged: Program = vec![
+            (Channel::new("4"), movie("Late", 22)),
+            (Channel::new("5"), movie("Early", 20)),
+        ]
+        .into_iter()
+        .collect();
+        merged.merge(
+            vec![
+                (Channel::new("6"), movie("New", 21)),
+                (with_icon.clone(), movie("Late", 22)),
+                (Channel::new("5"), movie("Early", 21)),
+                (Channel::new("6"), movie("New", 21)),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert_eq!(channel_names(&merged), vec!["4", "5", "6", "5"]);
+        assert_eq!(merged[0].0, with_icon);
+        assert_eq!(merged[3].1.get_start(), Some(start(21)));
+    }
+
+    #[test]
+    fn test_diff() {
+        let start = |hour| {
+            chrono::NaiveDate::from_ymd_opt(2021, 4, 20)
+                .unwrap()
+                .and_hms_opt(hour, 15, 0)
+                .unwrap()
+        };
+        let entry = |channel: &str, title: &str, hour| {
+            let mut builder = MovieBuilder::new(title);
+            builder.with_start(start(hour));
+            (Channel::new(channel), builder.build())
+        };
+
+        let old: Program = vec![entry("1", "a", 20), entry("2", "b", 20)]
+            .into_iter()
+            .collect();
+        let new: Program = vec![
+            entry("1", "a", 20),
+            entry("2", "b", 22),
+            entry("3", "c", 22),
+        ]
+        .into_iter()
+        .collect();
+
+        let added: Vec<(String, String)> = new
+            .diff(&old)
+            .iter()
+            .map(|(c, m)| (c.get_name(), m.get_title()))
+            .collect();
+        assert_eq!(
+            added,
+            vec![
+                ("2".to_string(), "b".to_string()),
+                ("3".to_string(), "c".to_string())
+            ]
+        );
+        assert_eq!(old.diff(&old).iter().count(), 0);
+        assert_eq!(old.diff(&Program::new()).iter().count(), 2);
+    }
+
+    #[test]
+    fn test_dedup_movies() {
+        let start = chrono::NaiveDate::from_ymd_opt(2021, 4, 20)
+            .unwrap()
+            .and_hms_opt(20, 15, 0)
+            .unwrap();
+        let entry = |channel: &str, title: &str, with_start: bool| {
+            let mut builder = MovieBuilder::new(title);
+            if with_start {
+                builder.with_start(start);
+            }
+            (Channel::new(channel), builder.build())
+        };
+
+        let program: Program = vec![
+            entry("ARD", "Tatort", true),
+            entry("ZDF", "Der Alte", true),
+            entry("WDR", "Tatort", true),
+            entry("NDR", "Tatort", true),
+            entry("3sat", "Kulturzeit", false),
+            entry("ARTE", "Kulturzeit", false),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            channel_names(&program.dedup_movies()),
+            vec!["ARD", "ZDF", "3sat", "ARTE"]
+        );
+        let tatort = &program[2].1;
+        assert_eq!(
+            program
+                .channels_of(tatort)
+                .iter()
+                .map(|c| c.get_name())
+                .collect::<Vec<_>>(),
+            vec!["ARD", "WDR", "NDR"]
+        );
+        assert!(program.channels_of(&program[4].1).is_empty());
+    }
+
+    #[test]
+    fn test_limit_icons() {
+        let with_icon = |name: &str, width| {
+            let mut channel = Channel::new(name);
+            channel.set_icon(Some(RgbaImage::new(width, 1)));
+            channel
+        };
+
+        let program: Program = vec![
+            (with_icon("1", 2), Movie::new("a")),
+            (with_icon("2", 4), Movie::new("b")),
+            (with_icon("1", 2), Movie::new("c")),
+            (Channel::new("3"), Movie::new("d")),
+            (with_icon("4", 1), Movie::new("e")),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(program.icon_size(), 4 * (2 + 4 + 1));
+
+        let limited = program.limit_icons(4 * 3);
+        assert_eq!(limited.icon_size(), 4 * 2);
+        assert_eq!(limited[0].0, with_icon("1", 2));
+        assert_eq!(limited[1].0, Channel::new("2"));
+        assert_eq!(limited[2].0, with_icon("1", 2));
+        assert_eq!(limited[4].0, Channel::new("4"));
+
+        assert_eq!(program.limit_icons(usize::MAX), program);
+        assert_eq!(program.limit_icons(0).icon_size(), 0);
+    }
+
+    #[test]
+    fn test_indices() {
+        let start = |hour| {
+            chrono::NaiveDate::from_ymd_opt(2021, 4, 20)
+                .unwrap()
+                .and_hms_opt(hour, 15, 0)
+                .unwrap()
+        };
+        let movie = |title: &str, hour| {
+            let mut builder = MovieBuilder::new(title);
+            builder.with_start(start(hour));
+            builder.build()
+        };
+
+        let mut program: Program = vec![
+            (Channel::new("1"), movie("c", 22)),
+            (Channel::new("2"), movie("b", 20)),
+            (Channel::new("1"), movie("a", 20)),
+            (Channel::new("3"), Movie::new("d")),
+        ]
+        .into_iter()
+        .collect();
+
+        let titles = |entries: Vec<&(Channel, Movie)>| -> Vec<String> {
+            entries.iter().map(|(_c, m)| m.get_title()).collect()
+        };
+
+        assert_eq!(program.len(), 4);
+        assert!(!program.is_empty());
+        assert!(Program::new().is_empty());
+
+        assert_eq!(titles(program.channel("1")), vec!["c", "a"]);
+        assert_eq!(titles(program.channel("4")), Vec::<String>::new());
+        assert_eq!(
+            titles(program.starting(start(20)..start(22))),
+            vec!["b", "a"]
+        );
+        assert_eq!(titles(program.starting(start(21)..)), vec!["c"]);
+
+        // Broadcasts starting at the same time keep the order of the program.
+        program.sort(SortOrder::Title);
+        assert_eq!(titles(program.channel("1")), vec!["a", "c"]);
+        assert_eq!(titles(program.starting(..)), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_started_before() {
+        let at = |hour, minute| {
+            chrono::NaiveDate::from_ymd_opt(2021, 4, 20)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap()
+        };
+        let mut builder = MovieBuilder::new("a");
+        builder.with_start(at(20, 15));
+        let movie = builder.build();
+
+        assert!(!movie.started_before(at(20, 0), 10));
+        assert!(!movie.started_before(at(20, 25), 10));
+        assert!(movie.started_before(at(20, 26), 10));
+        assert!(movie.started_before(at(20, 16), 0));
+        assert!(!MovieBuilder::new("b").build().started_before(at(23, 0), 0));
+    }
+
+    #[test]
+    fn test_json() {
+        let mut movie = MovieBuilder::new("Tatort");
+        movie
+            .with_start(
+                chrono::NaiveDate::from_ymd_opt(2021, 4, 11)
+                    .unwrap()
+                    .and_hms_opt(20, 15, 0)
+                    .unwrap(),
+            )
+            .with_genre("Krimi");
+        let program: Program = vec![(Channel::new("ARD"), movie.build())]
+            .into_iter()
+            .collect();
+
+        // Other tools read the same JSON as saved programs.
+        assert_eq!(
+            serde_json::to_value(&program).unwrap(),
+            json!([{
+                "channel": "ARD",
+                "title": "Tatort",
+                "start": "2021-04-11T20:15:00",
+                "year": null,
+                "genre": "Krimi",
+                "division": null,
+                "age_rating": null,
+                "director": null,
+                "cast": [],
+                "poster_url": null,
+                "description": null,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        let mut movie = MovieBuilder::new("Tatort");
+        movie
+            .with_start(
+                chrono::NaiveDate::from_ymd_opt(2021, 4, 11)
+                    .unwrap()
+                    .and_hms_opt(20, 15, 0)
+                    .unwrap(),
+            )
+            .with_actor("Axel Milberg");
+        let mut channel = Channel::new("ARD");
+        channel.set_icon(Some(RgbaImage::new(1, 1)));
+        let program: Program = vec![
+            (channel, movie.build()),
+            (Channel::new("ZDF"), Movie::new("heute")),
+        ]
+        .into_iter()
+        .collect();
+
+        let json = serde_json::to_value(&program).unwrap();
+        assert_eq!(json[0]["channel"], "ARD");
+        assert_eq!(json[0]["title"], "Tatort");
+        assert_eq!(json[0]["start"], "2021-04-11T20:15:00");
+        assert_eq!(json[0]["cast"], json!(["Axel Milberg"]));
+
+        // The icon is not serialized.
+        let deserialized: Program = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, program.limit_icons(0));
+        assert_eq!(deserialized.channel("ZDF").len(), 1);
+
+        // Missing optional fields are empty.
+        let program: Program =
+            serde_json::from_value(json!([{"channel": "ARTE", "title": "Metropolis"}])).unwrap();
+        assert_eq!(program[0], (Channel::new("ARTE"), Movie::new("Metropolis")));
+    }
+}
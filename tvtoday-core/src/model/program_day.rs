@@ -0,0 +1,78 @@
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+use chrono::{Duration, NaiveDate};
+
+/// The day of the program, relative to today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProgramDay {
+    Today,
+    Tomorrow,
+}
+
+impl ProgramDay {
+    /// All days a program can be shown for.
+    pub const ALL: [ProgramDay; 2] = [ProgramDay::Today, ProgramDay::Tomorrow];
+
+    /// The label of the day shown to the user.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProgramDay::Today => "Today",
+            ProgramDay::Tomorrow => "Tomorrow",
+        }
+    }
+
+    /// Get the number of days from today to the day.
+    pub fn days_ahead(&self) -> u32 {
+        match self {
+            ProgramDay::Today => 0,
+            ProgramDay::Tomorrow => 1,
+        }
+    }
+
+    /// Get the date of the day, given the date of today.
+    pub fn date(&self, today: NaiveDate) -> NaiveDate {
+        today + Duration::days(self.days_ahead() as i64)
+    }
+}
+
+impl Display for ProgramDay {
+    /// The identifier of the day, used for storing it.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ProgramDay::Today => write!(f, "today"),
+            ProgramDay::Tomorrow => write!(f, "tomorrow"),
+        }
+    }
+}
+
+impl TryFrom<&str> for ProgramDay {
+    type Error = ();
+
+    /// Try to convert from the identifier of the day to the `ProgramDay`.
+    fn try_from(item: &str) -> Result<ProgramDay, ()> {
+        ProgramDay::ALL
+            .iter()
+            .find(|d| d.to_string() == item)
+            .cloned()
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_date() {
+        let today = NaiveDate::from_ymd_opt(2021, 12, 31).unwrap();
+        assert_eq!(ProgramDay::Today.date(today), today);
+        assert_eq!(
+            ProgramDay::Tomorrow.date(today),
+            NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()
+        );
+        assert_eq!(ProgramDay::Tomorrow.days_ahead(), 1);
+        assert_eq!(ProgramDay::try_from("tomorrow"), Ok(ProgramDay::Tomorrow));
+        assert_eq!(ProgramDay::try_from("yesterday"), Err(()));
+    }
+}
@@ -0,0 +1,33 @@
+use crate::model::Program;
+use crate::Error;
+
+use std::fs;
+use std::path::Path;
+
+impl Program {
+    /// Write the program as readable JSON to a file at the given path, e.g. to be used by other tools. It can also be
+    /// opened again with `read_from_path`.
+    pub fn write_json_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let content = serde_json::to_string_pretty(self).map_err(|_| Error::ParsingFile)?;
+
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+
+    /// Save the program to a file at the given path, so it can be opened again with `read_from_path`.
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let content = serde_json::to_string(self).map_err(|_| Error::ParsingFile)?;
+
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+
+    /// Open a program saved with `write_to_path`. The icons of the channels are not saved.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Program, Error> {
+        let content = fs::read_to_string(path)?;
+
+        serde_json::from_str(&content).map_err(|_| Error::ParsingFile)
+    }
+}
@@ -0,0 +1,67 @@
+use crate::model::{Movie, ParseFailure, Program, ProgramDay, TimeSlot};
+use crate::Error;
+
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait Provider: Send {
+    /// Create a new provider.
+    fn new() -> Self
+    where
+        Self: Sized;
+
+    /// Clone the current provider. #[derive(Clone)] can not be used as `Self` needs to be `Sized`.
+    fn clone(&self) -> Self
+    where
+        Self: Sized;
+
+    /// Set the time slot of the program to get.
+    fn set_time_slot(&mut self, time_slot: TimeSlot);
+
+    /// Set the day of the program to get.
+    fn set_day(&mut self, day: ProgramDay) {
+        self.set_days_ahead(day.days_ahead())
+    }
+
+    /// Set the day of the program to get as the number of days from today, e.g. for days after tomorrow.
+    fn set_days_ahead(&mut self, days: u32);
+
+    /// Get the current program. This does not need to fill out all information about the movie.
+    async fn get_program(&mut self) -> Result<Program, Error>;
+
+    /// Get the current program like `get_program`, passing parts of it to `on_part` as soon as they are available, so
+    /// they can be shown while the rest is still loaded. Parts may be passed again if loading is retried.
+    async fn get_program_progressively(
+        &mut self,
+        on_part: &(dyn Fn(Program) + Send + Sync),
+    ) -> Result<Program, Error> {
+        let program = self.get_program().await?;
+        on_part(program.clone());
+        Ok(program)
+    }
+
+    /// Get more information regarding the movie. This will be called when clicking on a movie in the list.
+    /// If the provider has no more information about the movie, the given movie is returned unchanged; failed requests
+    /// are returned as errors.
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error>;
+
+    /// Get the URL of a website about the movie, if the provider knows one. The movie must be one of the last program.
+    fn get_url(&self, movie: &Movie) -> Option<String>;
+
+    /// Get the program with the icons of the channels set. This will be called after the program was already shown, so
+    /// slow work like decoding images should be done here instead of in `get_program`.
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error>;
+
+    /// Get the part of the website that could not be read the last time the program failed to load, if the provider
+    /// knows it. It may be reported to the maintainers if the user opted in.
+    fn get_parse_failure(&self) -> Option<ParseFailure> {
+        None
+    }
+
+    /// Set the login and password of the account at the provider, for providers that need one. Ignored by the others.
+    fn set_credentials(&mut self, _login: &str, _password: &str) {}
+
+    /// Set the address of the server, for providers reading the program from a server of the user like tvheadend.
+    /// Ignored by the others.
+    fn set_server(&mut self, _url: &str) {}
+}
@@ -0,0 +1,70 @@
+use crate::model::Provider;
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A provider that can be cloned without knowing its type, so it can be kept behind a `ProviderHandle`.
+pub trait BoxedProvider: Provider {
+    /// Clone the provider into a new box.
+    fn clone_boxed(&self) -> Box<dyn BoxedProvider>;
+}
+
+impl<P: 'static + Provider> BoxedProvider for P {
+    fn clone_boxed(&self) -> Box<dyn BoxedProvider> {
+        Box::new(Provider::clone(self))
+    }
+}
+
+/// A handle to the provider shared by all parts of the application, so they neither need to know its type nor pass it
+/// around. Cloning the handle shares the provider.
+///
+/// Slow work like loading the program is done on a copy from `get`, which is stored with `set` once it is done, so the
+/// provider is not locked while waiting for the network.
+#[derive(Clone)]
+pub struct ProviderHandle {
+    provider: Arc<Mutex<Box<dyn BoxedProvider>>>,
+}
+
+impl ProviderHandle {
+    /// Create a handle to the provider.
+    pub fn new<P: 'static + Provider>(provider: P) -> Self {
+        ProviderHandle {
+            provider: Arc::new(Mutex::new(Box::new(provider))),
+        }
+    }
+
+    /// Get a copy of the provider, e.g. to load the program in a task.
+    pub fn get(&self) -> Box<dyn BoxedProvider> {
+        self.lock().clone_boxed()
+    }
+
+    /// Replace the provider, e.g. by the copy that loaded the program and now knows more about it.
+    pub fn set(&self, provider: Box<dyn BoxedProvider>) {
+        *self.lock() = provider;
+    }
+
+    /// Lock the provider for a quick call like `get_url`.
+    pub fn lock(&self) -> MutexGuard<'_, Box<dyn BoxedProvider>> {
+        self.provider.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::providers::Saved;
+    use crate::model::{Channel, MovieBuilder, Program};
+
+    #[test]
+    fn test_shared() {
+        let handle = ProviderHandle::new(Saved::new());
+        let shared = handle.clone();
+
+        let mut program = Program::new();
+        program.add(Channel::new("ZDF"), MovieBuilder::new("Tatort").build());
+        handle.set(Box::new(Saved::with_program(program.clone())));
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let mut provider = shared.get();
+        assert_eq!(rt.block_on(provider.get_program()).ok(), Some(program));
+    }
+}
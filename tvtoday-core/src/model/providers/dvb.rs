@@ -0,0 +1,250 @@
+use crate::model::metrics::{self, ReloadStep};
+use crate::model::paths;
+use crate::model::providers::eit::{self, Event};
+use crate::model::providers::slot::{local_time, slot_timestamp};
+use crate::model::{Channel, Movie, MovieBuilder, Program, Provider, TimeSlot};
+use crate::Error;
+
+use std::path::Path;
+
+use async_trait::async_trait;
+
+/// The file in the user data directory with the channels found by a scan, e.g. of `dvbv5-scan` or `w_scan`.
+const CHANNELS_FILE: &str = "dvb-channels.conf";
+
+/// The file in the user data directory with the EIT received from the antenna, e.g. the packets of PID 0x12.
+const EIT_FILE: &str = "dvb-eit.ts";
+
+/// The program received from the antenna without any network access, read from a dump of the EIT (event information
+/// table) of the DVB signal. The channels are named after the channels file of a scan, in its order.
+///
+/// Both files are in the user data directory, the channels in `dvb-channels.conf` and the EIT in `dvb-eit.ts`. They are
+/// read again on every reload, so a cron job can update the dump.
+pub struct Dvb {
+    /// The time slot of the program.
+    time_slot: TimeSlot,
+    /// The day of the program as the number of days from today.
+    days_ahead: u32,
+}
+
+#[async_trait]
+impl Provider for Dvb {
+    fn new() -> Self {
+        Dvb {
+            time_slot: TimeSlot::Evening,
+            days_ahead: 0,
+        }
+    }
+
+    fn clone(&self) -> Self {
+        Dvb {
+            time_slot: self.time_slot,
+            days_ahead: self.days_ahead,
+        }
+    }
+
+    /// Get the events running at the time slot on each channel of the channels file.
+    async fn get_program(&mut self) -> Result<Program, Error> {
+        let time = slot_timestamp(self.time_slot, self.days_ahead);
+        let conf = String::from_utf8_lossy(&read(&paths::user_data_file(CHANNELS_FILE)).await?)
+            .into_owned();
+        let dump = read(&paths::user_data_file(EIT_FILE)).await?;
+
+        metrics::time(ReloadStep::Parse, || {
+            let channels = parse_channels(&conf);
+            let events = eit::parse_dump(&dump);
+            Ok(program_at(&channels, &events, time))
+        })
+    }
+
+    /// The EIT already contains all information about the movie.
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+        Ok(movie.clone())
+    }
+
+    /// The broadcasts from the antenna have no pages.
+    fn get_url(&self, _movie: &Movie) -> Option<String> {
+        None
+    }
+
+    fn set_time_slot(&mut self, time_slot: TimeSlot) {
+        self.time_slot = time_slot;
+    }
+
+    fn set_days_ahead(&mut self, days: u32) {
+        self.days_ahead = days;
+    }
+
+    /// The channels of the antenna have no icons.
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+        Ok(program.clone())
+    }
+}
+
+/// Read a file of the provider, failing with the path if it is missing.
+async fn read(path: &Path) -> Result<Vec<u8>, Error> {
+    tokio::fs::read(path)
+        .await
+        .map_err(|_| Error::MissingFile(path.display().to_string()))
+}
+
+/// Parse the service ids and names of the channels of a scan. Understood are the format of `dvbv5-scan`, the format of
+/// VDR written by `w_scan` and the format of `szap`/`tzap` with the service id in the last field.
+fn parse_channels(conf: &str) -> Vec<(u16, String)> {
+    let mut channels = vec![];
+    // The name of the current section of the format of `dvbv5-scan`.
+    let mut section: Option<String> = None;
+
+    for line in conf.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            section = Some(line[1..line.len() - 1].trim().to_string());
+        } else if let Some(name) = &section {
+            let mut key_value = line.splitn(2, '=');
+            if let (Some("SERVICE_ID"), Some(value)) = (
+                key_value.next().map(str::trim),
+                key_value.next().map(str::trim),
+            ) {
+                if let Ok(service_id) = value.parse() {
+                    channels.push((service_id, name.clone()));
+                }
+            }
+        } else {
+            let fields: Vec<&str> = line.split(':').collect();
+            // VDR has the service id in the tenth field, where the zap format of DVB-T has the hierarchy.
+            let service_id: Option<u16> = fields
+                .get(9)
+                .and_then(|field| field.trim().parse().ok())
+                .filter(|_service_id| fields.len() >= 13)
+                .or_else(|| fields[fields.len() - 1].trim().parse().ok());
+            // VDR has the name with the provider after a `;` and short names after a `,` in front.
+            let name = fields[0]
+                .split(';')
+                .next()
+                .and_then(|name| name.split(',').next())
+                .unwrap_or_default()
+                .trim();
+            if let Some(service_id) = service_id {
+                channels.push((service_id, name.to_string()));
+            }
+        }
+    }
+
+    channels.retain(|(_service_id, name)| !name.is_empty());
+    channels
+}
+
+/// The program of the events running at the unix timestamp, on the channels in their order. Channels found twice in a
+/// scan are only shown once.
+fn program_at(channels: &[(u16, String)], events: &[Event], time: i64) -> Program {
+    let mut program = Program::new();
+    let mut shown: Vec<u16> = vec![];
+    for (service_id, name) in channels {
+        if shown.contains(service_id) {
+            continue;
+        }
+        let running = events.iter().find(|event| {
+            event.service_id == *service_id
+                && event.start <= time
+                && time < event.start + event.duration
+        });
+        if let Some(event) = running {
+            shown.push(*service_id);
+            program.add(Channel::new(name), movie_of(event));
+        }
+    }
+    program
+}
+
+/// The movie of the event of the EIT.
+fn movie_of(event: &Event) -> Movie {
+    let mut movie_builder = MovieBuilder::new(&event.title);
+    movie_builder.with_start(local_time(event.start));
+    if let Some(content) = event.content {
+        let (genre, division) = eit::genre_and_division(content);
+        if let Some(genre) = genre {
+            movie_builder.with_genre(genre);
+        }
+        if let Some(division) = division {
+            movie_builder.with_division(division);
+        }
+    }
+    if let Some(age_rating) = event.age_rating {
+        movie_builder.with_age_rating(age_rating);
+    }
+
+    let mut movie = movie_builder.build();
+    movie.set_description(event.description.clone());
+    movie
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_channels() {
+        let dvbv5 = "# dvbv5-scan\n[Das Erste HD]\n\tSERVICE_ID = 10301\n\tVIDEO_PID = 5101\n\n[arte HD]\n\tSERVICE_ID = 10302\n";
+        assert_eq!(
+            parse_channels(dvbv5),
+            vec![
+                (10301, "Das Erste HD".to_string()),
+                (10302, "arte HD".to_string())
+            ]
+        );
+
+        let vdr = "Das Erste HD;ARD:11494:HC23M5O35P0S1:S19.2E:22000:5101=27:5102=deu@106:5104:0:10301:1:1019:0\n\
+                   ZDF,ZDF HD;ZDFvision:11362:HC23M5O35P0S1:S19.2E:22000:6110=27:6120=deu@106:6130:0:11110:1:1011:0\n";
+        assert_eq!(
+            parse_channels(vdr),
+            vec![
+                (10301, "Das Erste HD".to_string()),
+                (11110, "ZDF".to_string())
+            ]
+        );
+
+        let zap = "3sat:506000000:INVERSION_AUTO:BANDWIDTH_8_MHZ:FEC_2_3:FEC_AUTO:QAM_16:TRANSMISSION_MODE_8K:GUARD_INTERVAL_1_4:HIERARCHY_NONE:561:562:28007\n";
+        assert_eq!(parse_channels(zap), vec![(28007, "3sat".to_string())]);
+    }
+
+    #[test]
+    fn test_program_at() {
+        let event = |service_id, start, title: &str| Event {
+            service_id,
+            start,
+            duration: 100,
+            title: title.to_string(),
+            description: None,
+            content: Some(0x11),
+            age_rating: Some(12),
+        };
+        let events = vec![
+            event(1, 0, "Tagesschau"),
+            event(1, 100, "Tatort"),
+            event(2, 50, "Metropolis"),
+        ];
+        let channels = vec![
+            (2, "arte".to_string()),
+            (3, "3sat".to_string()),
+            (1, "Das Erste".to_string()),
+            (1, "Das Erste HD".to_string()),
+        ];
+
+        let movies: Vec<(String, Movie)> = program_at(&channels, &events, 120)
+            .iter()
+            .map(|(channel, movie)| (channel.get_name(), movie.clone()))
+            .collect();
+        assert_eq!(movies.len(), 2);
+        assert_eq!(movies[0].0, "arte");
+        assert_eq!(movies[0].1.get_title(), "Metropolis");
+        let (channel, tatort) = &movies[1];
+        assert_eq!(channel, "Das Erste");
+        assert_eq!(tatort.get_title(), "Tatort");
+        assert_eq!(tatort.get_genre(), Some("Krimi".to_string()));
+        assert_eq!(tatort.get_age_rating(), Some(12));
+        assert_eq!(tatort.get_start(), Some(local_time(100)));
+    }
+}
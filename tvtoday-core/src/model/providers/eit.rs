@@ -0,0 +1,468 @@
+use unicode_normalization::UnicodeNormalization;
+
+/// The PID of the packets of a transport stream carrying the EIT.
+const EIT_PID: u16 = 0x12;
+
+/// The modified julian date of the start of the unix time, 1970-01-01.
+const MJD_UNIX_EPOCH: i64 = 40587;
+
+/// The size of a packet of a transport stream.
+const PACKET_SIZE: usize = 188;
+
+/// The first byte of each packet of a transport stream.
+const SYNC_BYTE: u8 = 0x47;
+
+/// The tag of the short event descriptor with the title.
+const SHORT_EVENT_DESCRIPTOR: u8 = 0x4d;
+
+/// The tag of the extended event descriptor with the description.
+const EXTENDED_EVENT_DESCRIPTOR: u8 = 0x4e;
+
+/// The tag of the content descriptor with the genre.
+const CONTENT_DESCRIPTOR: u8 = 0x54;
+
+/// The tag of the parental rating descriptor with the minimum age.
+const PARENTAL_RATING_DESCRIPTOR: u8 = 0x55;
+
+/// The combining diacritical marks of the non-spacing characters of ISO/IEC 6937, the default encoding of the texts.
+const DIACRITICS: &[(u8, char)] = &[
+    (0xc1, '\u{300}'),
+    (0xc2, '\u{301}'),
+    (0xc3, '\u{302}'),
+    (0xc4, '\u{303}'),
+    (0xc5, '\u{304}'),
+    (0xc6, '\u{306}'),
+    (0xc7, '\u{307}'),
+    (0xc8, '\u{308}'),
+    (0xca, '\u{30a}'),
+    (0xcb, '\u{327}'),
+    (0xcd, '\u{30b}'),
+    (0xce, '\u{328}'),
+    (0xcf, '\u{30c}'),
+];
+
+/// A event of the EIT (ETSI EN 300 468, event information table), a broadcast of a service.
+pub(super) struct Event {
+    /// The id of the service, the channel, broadcasting the event.
+    pub(super) service_id: u16,
+    /// The start as a unix timestamp.
+    pub(super) start: i64,
+    /// The duration in seconds.
+    pub(super) duration: i64,
+    pub(super) title: String,
+    /// The texts of the short and the extended event descriptors.
+    pub(super) description: Option<String>,
+    /// The first content nibbles.
+    pub(super) content: Option<u8>,
+    /// The minimum age of the first parental rating.
+    pub(super) age_rating: Option<u32>,
+}
+
+/// Parse the events of a dump of the EIT, either a transport stream like recorded from the DVR device or by
+/// `dvbv5-zap -P`, or its sections one after another like written by `dvbsnoop -b`. Sections and events that can not be
+/// read are left out.
+pub(super) fn parse_dump(bytes: &[u8]) -> Vec<Event> {
+    let sections = if is_transport_stream(bytes) {
+        sections_of_transport_stream(bytes)
+    } else {
+        split_sections(bytes)
+    };
+    sections
+        .iter()
+        .flat_map(|section| parse_section(section))
+        .collect()
+}
+
+/// Whether the bytes are packets of a transport stream instead of sections.
+fn is_transport_stream(bytes: &[u8]) -> bool {
+    !bytes.is_empty()
+        && bytes.len().is_multiple_of(PACKET_SIZE)
+        && bytes
+            .chunks(PACKET_SIZE)
+            .all(|packet| packet[0] == SYNC_BYTE)
+}
+
+/// Get the sections of the EIT from the packets of a transport stream. The packets of other PIDs are left out.
+fn sections_of_transport_stream(bytes: &[u8]) -> Vec<Vec<u8>> {
+    let mut sections = vec![];
+    // The sections since the last packet starting one, none before the first such packet.
+    let mut buffer: Option<Vec<u8>> = None;
+
+    for packet in bytes.chunks(PACKET_SIZE) {
+        let pid = (u16::from(packet[1] & 0x1f) << 8) | u16::from(packet[2]);
+        let unit_start = packet[1] & 0x40 != 0;
+        let adaptation_field_control = (packet[3] >> 4) & 0x3;
+        if pid != EIT_PID || adaptation_field_control & 0x1 == 0 {
+            continue;
+        }
+
+        let mut payload = &packet[4..];
+        if adaptation_field_control & 0x2 != 0 {
+            payload = payload.get(payload[0] as usize + 1..).unwrap_or(&[]);
+        }
+
+        if !unit_start {
+            if let Some(buffer) = &mut buffer {
+                buffer.extend_from_slice(payload);
+            }
+            continue;
+        }
+
+        // The pointer field tells where the new section starts, the bytes before it end the last one.
+        let pointer = match payload.first() {
+            Some(&pointer) if (pointer as usize) < payload.len() => pointer as usize,
+            _ => {
+                buffer = None;
+                continue;
+            }
+        };
+        if let Some(mut buffer) = buffer.take() {
+            buffer.extend_from_slice(&payload[1..1 + pointer]);
+            sections.extend(split_sections(&buffer));
+        }
+        buffer = Some(payload[1 + pointer..].to_vec());
+    }
+    if let Some(buffer) = buffer {
+        sections.extend(split_sections(&buffer));
+    }
+
+    sections
+}
+
+/// Split sections following each other, up to the first incomplete one or the stuffing.
+fn split_sections(mut bytes: &[u8]) -> Vec<Vec<u8>> {
+    let mut sections = vec![];
+    while bytes.len() >= 3 && bytes[0] != 0xff {
+        let length = 3 + ((usize::from(bytes[1] & 0x0f) << 8) | usize::from(bytes[2]));
+        if length > bytes.len() {
+            break;
+        }
+        sections.push(bytes[..length].to_vec());
+        bytes = &bytes[length..];
+    }
+    sections
+}
+
+/// Parse the events of a section of the EIT. Sections of other tables are left out.
+fn parse_section(section: &[u8]) -> Vec<Event> {
+    // The tables of the present and following events are 0x4e and 0x4f, the ones of the schedules 0x50 to 0x6f.
+    if section.len() < 18 || !(0x4e..=0x6f).contains(&section[0]) {
+        return vec![];
+    }
+    let service_id = u16::from_be_bytes([section[3], section[4]]);
+    // The section ends with a CRC.
+    let end = section.len() - 4;
+
+    let mut events = vec![];
+    let mut i = 14;
+    while i + 12 <= end {
+        let descriptors_length =
+            (usize::from(section[i + 10] & 0x0f) << 8) | usize::from(section[i + 11]);
+        let descriptors_end = i + 12 + descriptors_length;
+        if descriptors_end > end {
+            break;
+        }
+
+        let start = start_time(&section[i + 2..i + 7]);
+        let duration = duration(&section[i + 7..i + 10]);
+        if let (Some(start), Some(duration)) = (start, duration) {
+            if let Some(event) = parse_event(
+                service_id,
+                start,
+                duration,
+                &section[i + 12..descriptors_end],
+            ) {
+                events.push(event);
+            }
+        }
+        i = descriptors_end;
+    }
+    events
+}
+
+/// Parse the descriptors of a event. Events without a title are left out.
+fn parse_event(service_id: u16, start: i64, duration: i64, descriptors: &[u8]) -> Option<Event> {
+    let mut title = None;
+    let mut texts: Vec<String> = vec![];
+    let mut extended_text = String::new();
+    let mut content = None;
+    let mut age_rating = None;
+
+    let mut i = 0;
+    while i + 2 <= descriptors.len() {
+        let tag = descriptors[i];
+        let body = match descriptors.get(i + 2..i + 2 + descriptors[i + 1] as usize) {
+            Some(body) => body,
+            None => break,
+        };
+        i += 2 + body.len();
+
+        match tag {
+            // The language, the title and a short text, often the episode.
+            SHORT_EVENT_DESCRIPTOR if title.is_none() => {
+                let name_end = 4 + *body.get(3)? as usize;
+                title = Some(dvb_text(body.get(4..name_end)?));
+                let text_length = body.get(name_end).map(|l| *l as usize).unwrap_or(0);
+                if let Some(text) = body.get(name_end + 1..name_end + 1 + text_length) {
+                    texts.push(dvb_text(text));
+                }
+            }
+            // The number of the descriptor, the language, items and a part of the description.
+            EXTENDED_EVENT_DESCRIPTOR => {
+                let items_end = 5 + body.get(4).map(|l| *l as usize).unwrap_or(0);
+                let text_length = body.get(items_end).map(|l| *l as usize).unwrap_or(0);
+                if let Some(text) = body.get(items_end + 1..items_end + 1 + text_length) {
+                    extended_text.push_str(&dvb_text(text));
+                }
+            }
+            CONTENT_DESCRIPTOR if content.is_none() => content = body.first().cloned(),
+            // The country and the rating, the minimum age minus 3.
+            PARENTAL_RATING_DESCRIPTOR if age_rating.is_none() => {
+                age_rating = body
+                    .get(3)
+                    .filter(|rating| (0x01..=0x0f).contains(*rating))
+                    .map(|rating| u32::from(*rating) + 3);
+            }
+            _ => {}
+        }
+    }
+
+    texts.push(extended_text);
+    let description: Vec<String> = texts
+        .into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    Some(Event {
+        service_id,
+        start,
+        duration,
+        title: title.filter(|t| !t.is_empty())?,
+        description: Some(description.join("\n\n")).filter(|d| !d.is_empty()),
+        content,
+        age_rating,
+    })
+}
+
+/// The unix timestamp of a start time, the modified julian date and the time in UTC as binary coded decimals.
+fn start_time(bytes: &[u8]) -> Option<i64> {
+    let mjd = u16::from_be_bytes([bytes[0], bytes[1]]);
+    // The start of events without a start time is all ones.
+    if mjd == 0xffff {
+        return None;
+    }
+    Some((i64::from(mjd) - MJD_UNIX_EPOCH) * 24 * 60 * 60 + duration(&bytes[2..5])?)
+}
+
+/// The seconds of a duration or a time as binary coded decimals of the hours, minutes and seconds.
+fn duration(bytes: &[u8]) -> Option<i64> {
+    let bcd = |byte: u8| {
+        let (high, low) = (byte >> 4, byte & 0x0f);
+        if high > 9 || low > 9 {
+            None
+        } else {
+            Some(i64::from(high * 10 + low))
+        }
+    };
+    Some(bcd(bytes[0])? * 3600 + bcd(bytes[1])? * 60 + bcd(bytes[2])?)
+}
+
+/// Decode a text of a descriptor. Its first byte selects the character table if it is below 0x20, otherwise it is
+/// ISO/IEC 6937. The tables of ISO/IEC 8859 are read as Latin-1, which is right for their letters used in Germany.
+fn dvb_text(bytes: &[u8]) -> String {
+    let mut chars: Vec<char> = vec![];
+    match bytes.first() {
+        Some(0x15) => chars.extend(String::from_utf8_lossy(&bytes[1..]).chars()),
+        Some(0x10) => chars.extend(bytes.iter().skip(3).map(|b| char::from(*b))),
+        Some(&table) if table < 0x20 => chars.extend(bytes.iter().skip(1).map(|b| char::from(*b))),
+        _ => {
+            // The diacritical marks precede their letter.
+            let mut diacritic = None;
+            for &byte in bytes {
+                if let Some((_byte, mark)) = DIACRITICS.iter().find(|(b, _mark)| *b == byte) {
+                    diacritic = Some(*mark);
+                    continue;
+                }
+                chars.push(if byte == 0xfb { 'ß' } else { char::from(byte) });
+                chars.extend(diacritic.take());
+            }
+        }
+    }
+
+    chars
+        .into_iter()
+        .filter_map(|c| match c {
+            // The line break of the control codes, also in the private use area for UTF-8.
+            '\u{8a}' | '\u{e08a}' => Some('\n'),
+            '\u{80}'..='\u{9f}' | '\u{e080}'..='\u{e09f}' => None,
+            c => Some(c),
+        })
+        .nfc()
+        .collect()
+}
+
+/// The genres of the content nibbles of DVB events (ETSI EN 300 468, content descriptor), in German like the genres of
+/// TV Spielfilm. The first nibble is the level 1 content, the second one the level 2 content.
+const GENRES: &[(u8, &str)] = &[
+    (0x10, "Spielfilm"),
+    (0x11, "Krimi"),
+    (0x12, "Abenteuer"),
+    (0x13, "Science-Fiction"),
+    (0x14, "Komödie"),
+    (0x15, "Soap"),
+    (0x16, "Liebe"),
+    (0x17, "Drama"),
+    (0x18, "Erotik"),
+    (0x20, "Nachrichten"),
+    (0x21, "Nachrichten"),
+    (0x22, "Magazin"),
+    (0x23, "Dokumentation"),
+    (0x24, "Diskussion"),
+    (0x30, "Show"),
+    (0x31, "Quiz"),
+    (0x32, "Varieté"),
+    (0x33, "Talkshow"),
+    (0x40, "Sport"),
+    (0x50, "Kinder"),
+    (0x60, "Musik"),
+    (0x70, "Kultur"),
+    (0x80, "Gesellschaft"),
+    (0x90, "Wissen"),
+    (0xa0, "Freizeit"),
+];
+
+/// The level 1 content nibbles mapped to the divisions of TV Spielfilm, so filters work with all providers.
+const DIVISIONS: &[(u8, &str)] = &[
+    (0x1, "Spielfilm"),
+    (0x2, "Report"),
+    (0x3, "Show"),
+    (0x4, "Sport"),
+    (0x8, "Report"),
+    (0x9, "Report"),
+];
+
+/// Get the genre and the division of TV Spielfilm of the content of a DVB event, e.g. `Krimi` and `Spielfilm` for
+/// `0x11`. Unknown level 2 contents get the genre of their level 1 content, soaps are a series.
+pub(super) fn genre_and_division(content: u8) -> (Option<&'static str>, Option<&'static str>) {
+    let genre_of = |content: u8| {
+        GENRES
+            .iter()
+            .find(|(nibbles, _genre)| *nibbles == content)
+            .map(|(_nibbles, genre)| *genre)
+    };
+    let genre = genre_of(content).or_else(|| genre_of(content & 0xf0));
+
+    let division = if content == 0x15 {
+        Some("Serie")
+    } else {
+        DIVISIONS
+            .iter()
+            .find(|(level_1, _division)| *level_1 == content >> 4)
+            .map(|(_level_1, division)| *division)
+    };
+
+    (genre, division)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A section of the EIT of the service with the events.
+    fn section(service_id: u16, events: &[u8]) -> Vec<u8> {
+        let length = 11 + events.len() + 4;
+        let mut section = vec![0x4e, 0xf0 | (length >> 8) as u8, length as u8];
+        section.extend_from_slice(&service_id.to_be_bytes());
+        section.extend_from_slice(&[0xc1, 0, 0, 0, 1, 0, 1, 0, 0x4e]);
+        section.extend_from_slice(events);
+        section.extend_from_slice(&[0, 0, 0, 0]);
+        section
+    }
+
+    /// A event on 2021-05-01 at 18:15 UTC for 90 minutes with the descriptors.
+    fn event(descriptors: &[u8]) -> Vec<u8> {
+        let mut event = vec![0, 1, 0xe7, 0xc7, 0x18, 0x15, 0x00, 0x01, 0x30, 0x00];
+        event.push(0x80 | (descriptors.len() >> 8) as u8);
+        event.push(descriptors.len() as u8);
+        event.extend_from_slice(descriptors);
+        event
+    }
+
+    /// The descriptors of Tatort, a crime movie for ages from 12.
+    fn descriptors() -> Vec<u8> {
+        let mut descriptors = vec![SHORT_EVENT_DESCRIPTOR, 22];
+        descriptors.extend_from_slice(b"deu\x06Tatort\x0bGef\xc8ahrlich");
+        descriptors.extend_from_slice(&[EXTENDED_EVENT_DESCRIPTOR, 15, 0x00]);
+        descriptors.extend_from_slice(b"deu\x00\x09\x15Ein Fall");
+        descriptors.extend_from_slice(&[CONTENT_DESCRIPTOR, 2, 0x11, 0x00]);
+        descriptors.extend_from_slice(&[PARENTAL_RATING_DESCRIPTOR, 4]);
+        descriptors.extend_from_slice(b"DEU\x09");
+        descriptors
+    }
+
+    #[test]
+    fn test_parse_section() {
+        let events = parse_dump(&section(28106, &event(&descriptors())));
+        assert_eq!(events.len(), 1);
+        let tatort = &events[0];
+        assert_eq!(tatort.service_id, 28106);
+        assert_eq!(tatort.start, 1619892900);
+        assert_eq!(tatort.duration, 90 * 60);
+        assert_eq!(tatort.title, "Tatort");
+        assert_eq!(
+            tatort.description,
+            Some("Gefährlich\n\nEin Fall".to_string())
+        );
+        assert_eq!(tatort.content, Some(0x11));
+        assert_eq!(tatort.age_rating, Some(12));
+
+        // Events without a title and sections of other tables are left out.
+        assert!(parse_dump(&section(28106, &event(&[]))).is_empty());
+        let mut other_table = section(28106, &event(&descriptors()));
+        other_table[0] = 0x42;
+        assert!(parse_dump(&other_table).is_empty());
+    }
+
+    #[test]
+    fn test_transport_stream() {
+        let section = section(28106, &event(&descriptors()));
+        // The section starts in the first packet of the EIT and ends in the second one, between them is a packet of
+        // another PID.
+        let mut dump = vec![SYNC_BYTE, 0x40, 0x12, 0x10, 0x00];
+        dump.extend_from_slice(&section[..50]);
+        dump.resize(PACKET_SIZE, 0xff);
+        dump.extend_from_slice(&[SYNC_BYTE, 0x40, 0x00, 0x10]);
+        dump.resize(2 * PACKET_SIZE, 0xff);
+        dump.extend_from_slice(&[SYNC_BYTE, 0x00, 0x12, 0x11]);
+        dump.extend_from_slice(&section[50..]);
+        dump.resize(3 * PACKET_SIZE, 0xff);
+
+        let events = parse_dump(&dump);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title, "Tatort");
+    }
+
+    #[test]
+    fn test_dvb_text() {
+        assert_eq!(dvb_text(b"M\xc8unchen \xfb"), "München ß");
+        assert_eq!(dvb_text(b"\x15K\xc3\xb6ln"), "Köln");
+        assert_eq!(dvb_text(b"\x05K\xf6ln"), "Köln");
+        assert_eq!(
+            dvb_text(b"Erste Zeile\x8aZweite\x86 Zeile\x87"),
+            "Erste Zeile\nZweite Zeile"
+        );
+    }
+
+    #[test]
+    fn test_genre_and_division() {
+        assert_eq!(genre_and_division(0x11), (Some("Krimi"), Some("Spielfilm")));
+        assert_eq!(genre_and_division(0x15), (Some("Soap"), Some("Serie")));
+        assert_eq!(
+            genre_and_division(0x23),
+            (Some("Dokumentation"), Some("Report"))
+        );
+        assert_eq!(genre_and_division(0x4b), (Some("Sport"), Some("Sport")));
+        assert_eq!(genre_and_division(0x60), (Some("Musik"), None));
+        assert_eq!(genre_and_division(0xf0), (None, None));
+    }
+}
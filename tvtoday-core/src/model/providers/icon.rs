@@ -0,0 +1,196 @@
+use crate::model::metrics::{self, ReloadStep};
+use crate::model::{cache, paths, Program};
+use crate::Error;
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Instant, SystemTime};
+
+use image::RgbaImage;
+use webp::Decoder;
+
+/// The maximum width and height of the channel icons.
+const ICON_SIZE: u32 = 44;
+
+/// How many seconds a cached channel icon is used before it is downloaded again.
+const ICONS_MAX_AGE: u64 = 7 * 24 * 60 * 60;
+
+/// The channel icons of a provider reading the URLs of the logos from its website.
+#[derive(Clone)]
+pub(super) struct Icons {
+    /// Maps the name of each channel to the URL of its logo.
+    urls: HashMap<String, String>,
+    /// Maps the URL of each logo already loaded to the decoded icon.
+    loaded: HashMap<String, RgbaImage>,
+}
+
+impl Icons {
+    /// Create the icons without any logos.
+    pub(super) fn new() -> Self {
+        Icons {
+            urls: HashMap::new(),
+            loaded: HashMap::new(),
+        }
+    }
+
+    /// Remember the URL of the logo of the channel.
+    pub(super) fn add_url(&mut self, channel: &str, url: &str) {
+        self.urls.insert(channel.to_string(), url.to_string());
+    }
+
+    /// Get the program with the icons of the channels set. Icons that can not be loaded are left out, so the names of
+    /// their channels are shown instead. Icons not fitting into the limit of the icon cache are left out too.
+    pub(super) async fn set_icons(&mut self, program: &Program) -> Program {
+        let mut urls: Vec<String> = program
+            .iter()
+            .filter_map(|(channel, _movie)| self.urls.get(&channel.get_name()).cloned())
+            .collect();
+        urls.sort();
+        urls.dedup();
+
+        let mut size: usize = self.loaded.values().map(|i| i.as_raw().len()).sum();
+        for url in urls {
+            if let Entry::Vacant(entry) = self.loaded.entry(url) {
+                if let Some(icon) = get_icon(entry.key()).await {
+                    if size + icon.as_raw().len() <= cache::get_icon_limit() {
+                        size += icon.as_raw().len();
+                        entry.insert(icon);
+                    }
+                }
+            }
+        }
+        let _ = cache::prune_disk_cache();
+
+        program
+            .iter()
+            .map(|(channel, movie)| {
+                let mut channel = channel.clone();
+                let icon = self
+                    .urls
+                    .get(&channel.get_name())
+                    .and_then(|url| self.loaded.get(url));
+                channel.set_icon(icon.cloned());
+
+                (channel, movie.clone())
+            })
+            .collect()
+    }
+}
+
+/// Get the icon at the URL. It is cached on disk and only downloaded again once it expired. Without a connection the
+/// cached icon is used even if it expired.
+async fn get_icon(url: &str) -> Option<RgbaImage> {
+    let path = paths::user_cache_file(&icon_cache_name(url));
+
+    let fresh = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .map(|modified| is_fresh(modified, SystemTime::now()))
+        .unwrap_or(false);
+    if !fresh {
+        if let Ok(bytes) = download(url).await {
+            let _ = fs::write(&path, bytes);
+        }
+    }
+
+    let bytes = fs::read(&path).ok()?;
+    // Decoding is done in a blocking task, as it would block the runtime.
+    tokio::task::spawn_blocking(move || {
+        metrics::time(ReloadStep::DecodeIcons, || decode_icon(&bytes).ok())
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Download the content at the URL.
+async fn download(url: &str) -> Result<Vec<u8>, Error> {
+    let waiting = Instant::now();
+    let response = reqwest::get(url).await?.error_for_status()?;
+    let bytes = response.bytes().await?.to_vec();
+    metrics::record(ReloadStep::Network, waiting.elapsed());
+    Ok(bytes)
+}
+
+/// Decode a channel icon and scale it down to fit `ICON_SIZE`. Besides the formats supported by `image`, webp is
+/// supported.
+fn decode_icon(bytes: &[u8]) -> Result<RgbaImage, Error> {
+    image::load_from_memory(bytes)
+        .ok()
+        .or_else(|| Decoder::new(bytes).decode().map(|image| image.as_image()))
+        .map(|image| image.thumbnail(ICON_SIZE, ICON_SIZE).into_rgba8())
+        .ok_or(Error::DecodingImage)
+}
+
+/// The name of the file a channel icon is cached in, derived from its URL.
+fn icon_cache_name(url: &str) -> String {
+    let name: String = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("icon-{}", name)
+}
+
+/// Weather a cached file last modified at the given time can be used without downloading it again.
+fn is_fresh(modified: SystemTime, now: SystemTime) -> bool {
+    now.duration_since(modified)
+        .map(|age| age.as_secs() < ICONS_MAX_AGE)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The start of a webp image, cut off after the header.
+    const TRUNCATED_WEBP: &[u8] = b"RIFF\x24\x00\x00\x00WEBPVP8 \x18\x00\x00\x00\x30\x01";
+
+    #[test]
+    fn test_decode_truncated_icon() {
+        assert!(matches!(
+            decode_icon(TRUNCATED_WEBP),
+            Err(Error::DecodingImage)
+        ));
+        assert!(matches!(decode_icon(&[]), Err(Error::DecodingImage)));
+    }
+
+    #[test]
+    fn test_icon_cache_name() {
+        assert_eq!(
+            icon_cache_name("https://a2.tvspielfilm.de/images/tv/sender/mini/zdf.png"),
+            "icon-a2.tvspielfilm.de_images_tv_sender_mini_zdf.png"
+        );
+        assert_eq!(
+            icon_cache_name("http://example.org/logo?id=1&size=44"),
+            "icon-example.org_logo_id_1_size_44"
+        );
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let modified = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+
+        assert!(is_fresh(modified, modified));
+        assert!(is_fresh(
+            modified,
+            modified + std::time::Duration::from_secs(ICONS_MAX_AGE - 1)
+        ));
+        assert!(!is_fresh(
+            modified,
+            modified + std::time::Duration::from_secs(ICONS_MAX_AGE)
+        ));
+        // A file modified in the future (e.g. after changing the clock) is not trusted.
+        assert!(!is_fresh(
+            modified,
+            modified - std::time::Duration::from_secs(1)
+        ));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::model::{Channel, Movie, MovieBuilder, Program, Provider, TimeSlot};
+use crate::Error;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use chrono::Local;
+
+/// The program of the mock provider with the channel, title, genre and start of each broadcast.
+const PROGRAM: &[(&str, &str, &str, (u32, u32))] = &[
+    ("Das Erste", "Tatort", "Krimi", (20, 15)),
+    ("Das Erste", "Tagesthemen", "Nachrichten", (21, 45)),
+    ("ZDF", "Der Bergdoktor", "Drama", (20, 15)),
+    ("ZDF", "heute journal", "Nachrichten", (21, 45)),
+    ("ARTE", "Die Reifeprüfung", "Komödie", (20, 15)),
+    ("ARTE", "Metropolis", "Science-Fiction", (22, 0)),
+    ("3sat", "Die Nacht der lebenden Toten", "Horror", (22, 25)),
+];
+
+/// The environment variable with the latency of each request in milliseconds, either fixed like `500` or uniformly
+/// distributed in a range like `100-3000`.
+const LATENCY_VARIABLE: &str = "TVTODAY_MOCK_LATENCY";
+/// The environment variable with the probability of each request to fail, from `0` to `1`.
+const FAILURE_RATE_VARIABLE: &str = "TVTODAY_MOCK_FAILURE_RATE";
+/// The environment variable with the seed of the random latencies and failures, to reproduce a run.
+const SEED_VARIABLE: &str = "TVTODAY_MOCK_SEED";
+
+/// A provider with a fixed program, e.g. to test the user interface or take screenshots without network access.
+///
+/// It can simulate a slow and flaky network, configured with `TVTODAY_MOCK_LATENCY`, `TVTODAY_MOCK_FAILURE_RATE` and
+/// `TVTODAY_MOCK_SEED`. The program is then loaded one channel after another, each with its own latency and chance to
+/// fail with a networking error.
+pub struct Mock {
+    time_slot: TimeSlot,
+    days_ahead: u32,
+    min_latency: Duration,
+    max_latency: Duration,
+    failure_rate: f64,
+    /// The state of the pseudo random numbers, shared by requests taking `&self`.
+    random: AtomicU64,
+}
+
+impl Mock {
+    /// Create a mock provider with each request taking between `min_latency` and `max_latency` and failing with the
+    /// probability `failure_rate`. The same seed gives the same latencies and failures.
+    pub fn with_network(
+        min_latency: Duration,
+        max_latency: Duration,
+        failure_rate: f64,
+        seed: u64,
+    ) -> Self {
+        Mock {
+            time_slot: TimeSlot::Evening,
+            days_ahead: 0,
+            min_latency,
+            max_latency: max_latency.max(min_latency),
+            failure_rate: failure_rate.clamp(0.0, 1.0),
+            // Xorshift never leaves the state 0.
+            random: AtomicU64::new(seed.max(1)),
+        }
+    }
+
+    /// Get the next pseudo random number from 0 to 1.
+    fn next_random(&self) -> f64 {
+        let mut x = self.random.load(Ordering::Relaxed);
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.random.store(x, Ordering::Relaxed);
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Wait for the simulated latency of a request, failing like the network was lost.
+    async fn request(&self) -> Result<(), Error> {
+        let spread = self.max_latency - self.min_latency;
+        let latency = self.min_latency + spread.mul_f64(self.next_random());
+        if latency > Duration::from_millis(0) {
+            tokio::time::sleep(latency).await;
+        }
+
+        if self.failure_rate > 0.0 && self.next_random() < self.failure_rate {
+            Err(Error::Networking)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Get the fixed program of the day.
+    fn program(&self) -> Program {
+        let date = Local::now().date_naive() + chrono::Duration::days(self.days_ahead as i64);
+
+        PROGRAM
+            .iter()
+            .map(|(channel, title, genre, (hour, minute))| {
+                let mut builder = MovieBuilder::new(title);
+                builder
+                    .with_genre(genre)
+                    .with_start(date.and_hms_opt(*hour, *minute, 0).unwrap());
+                (Channel::new(channel), builder.build())
+            })
+            .collect()
+    }
+}
+
+/// Parse a latency like `500` or `100-3000` in milliseconds.
+fn parse_latency(latency: &str) -> Option<(Duration, Duration)> {
+    let mut bounds = latency.splitn(2, '-').map(|b| b.trim().parse::<u64>());
+    let min = bounds.next()?.ok()?;
+    let max = match bounds.next() {
+        Some(max) => max.ok()?,
+        None => min,
+    };
+    Some((Duration::from_millis(min), Duration::from_millis(max)))
+}
+
+#[async_trait]
+impl Provider for Mock {
+    /// Create the mock provider, simulating the network configured by the environment. Without configuration, it
+    /// answers immediately and never fails.
+    fn new() -> Self {
+        let (min_latency, max_latency) = std::env::var(LATENCY_VARIABLE)
+            .ok()
+            .and_then(|l| parse_latency(&l))
+            .unwrap_or_default();
+        let failure_rate = std::env::var(FAILURE_RATE_VARIABLE)
+            .ok()
+            .and_then(|r| r.trim().parse().ok())
+            .unwrap_or(0.0);
+        let seed = std::env::var(SEED_VARIABLE)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_nanos() as u64)
+                    .unwrap_or(1)
+            });
+
+        Mock::with_network(min_latency, max_latency, failure_rate, seed)
+    }
+
+    fn clone(&self) -> Self {
+        Mock {
+            time_slot: self.time_slot,
+            days_ahead: self.days_ahead,
+            min_latency: self.min_latency,
+            max_latency: self.max_latency,
+            failure_rate: self.failure_rate,
+            random: AtomicU64::new(self.random.load(Ordering::Relaxed)),
+        }
+    }
+
+    fn set_time_slot(&mut self, time_slot: TimeSlot) {
+        self.time_slot = time_slot;
+    }
+
+    fn set_days_ahead(&mut self, days: u32) {
+        self.days_ahead = days;
+    }
+
+    async fn get_program(&mut self) -> Result<Program, Error> {
+        self.get_program_progressively(&|_| {}).await
+    }
+
+    /// Get the program one channel after another, each part being a request to the simulated network.
+    async fn get_program_progressively(
+        &mut self,
+        on_part: &(dyn Fn(Program) + Send + Sync),
+    ) -> Result<Program, Error> {
+        let program = self.program();
+
+        let mut channels: Vec<String> = vec![];
+        for (channel, _movie) in program.iter() {
+            if !channels.contains(&channel.get_name()) {
+                channels.push(channel.get_name());
+            }
+        }
+
+        for name in channels {
+            self.request().await?;
+            on_part(program.channel(&name).into_iter().cloned().collect());
+        }
+
+        Ok(program)
+    }
+
+    /// Get the description of the movie. It is returned unchanged if the simulated request failed.
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+        self.request().await?;
+
+        let mut movie = movie.clone();
+        movie.set_description(Some(format!(
+            "A broadcast of the mock provider called {}.",
+            movie.get_title()
+        )));
+        Ok(movie)
+    }
+
+    fn get_url(&self, _movie: &Movie) -> Option<String> {
+        None
+    }
+
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+        self.request().await?;
+        Ok(program.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use tokio::runtime::Runtime;
+
+    #[test]
+    fn test_parse_latency() {
+        assert_eq!(
+            parse_latency("500"),
+            Some((Duration::from_millis(500), Duration::from_millis(500)))
+        );
+        assert_eq!(
+            parse_latency("100-3000"),
+            Some((Duration::from_millis(100), Duration::from_millis(3000)))
+        );
+        assert_eq!(parse_latency("slow"), None);
+        assert_eq!(parse_latency("100-"), None);
+    }
+
+    #[test]
+    fn test_failures() {
+        let rt = Runtime::new().unwrap();
+        let no_latency = Duration::from_millis(0);
+
+        let mut reliable = Mock::with_network(no_latency, no_latency, 0.0, 42);
+        assert_eq!(
+            rt.block_on(reliable.get_program()).unwrap().iter().count(),
+            PROGRAM.len()
+        );
+
+        let mut broken = Mock::with_network(no_latency, no_latency, 1.0, 42);
+        assert!(matches!(
+            rt.block_on(broken.get_program()),
+            Err(Error::Networking)
+        ));
+
+        // The same seed fails the same requests.
+        let results = |seed| {
+            let mut flaky = Mock::with_network(no_latency, no_latency, 0.5, seed);
+            (0..20)
+                .map(|_| rt.block_on(flaky.get_icons(&Program::new())).is_ok())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(results(7), results(7));
+        assert!(results(7).contains(&true));
+        assert!(results(7).contains(&false));
+    }
+
+    #[test]
+    fn test_progressive_parts() {
+        let rt = Runtime::new().unwrap();
+        let latency = Duration::from_millis(1);
+        let mut mock = Mock::with_network(latency, latency, 0.0, 1);
+
+        let parts = std::sync::Mutex::new(vec![]);
+        let program = rt
+            .block_on(mock.get_program_progressively(&|part| parts.lock().unwrap().push(part)))
+            .unwrap();
+
+        let parts = parts.into_inner().unwrap();
+        assert_eq!(parts.len(), 4);
+        assert_eq!(
+            parts.iter().map(|p| p.iter().count()).sum::<usize>(),
+            program.iter().count()
+        );
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(feature = "dvb")]
+mod dvb;
+#[cfg(any(feature = "tvheadend", feature = "dvb"))]
+mod eit;
+#[cfg(any(
+    feature = "tv-spielfilm",
+    feature = "tv-today",
+    feature = "zattoo",
+    feature = "tvheadend",
+    feature = "mythtv"
+))]
+mod icon;
+#[cfg(feature = "mock")]
+mod mock;
+#[cfg(feature = "mythtv")]
+mod mythtv;
+mod saved;
+#[cfg(any(
+    feature = "zattoo",
+    feature = "tvheadend",
+    feature = "mythtv",
+    feature = "dvb"
+))]
+mod slot;
+#[cfg(feature = "tv-movie")]
+mod tv_movie;
+#[cfg(feature = "tv-spielfilm")]
+mod tv_spielfilm;
+#[cfg(feature = "tv-today")]
+mod tv_today;
+#[cfg(feature = "tvheadend")]
+mod tvheadend;
+#[cfg(feature = "zattoo")]
+mod zattoo;
+
+#[cfg(feature = "dvb")]
+pub use dvb::Dvb;
+#[cfg(feature = "mock")]
+pub use mock::Mock;
+#[cfg(feature = "mythtv")]
+pub use mythtv::MythTv;
+pub use saved::Saved;
+#[cfg(feature = "tv-movie")]
+pub use tv_movie::TvMovie;
+#[cfg(feature = "tv-spielfilm")]
+pub use tv_spielfilm::TvSpielfilm;
+#[cfg(feature = "tv-today")]
+pub use tv_today::TvToday;
+#[cfg(feature = "tvheadend")]
+pub use tvheadend::Tvheadend;
+#[cfg(feature = "zattoo")]
+pub use zattoo::Zattoo;
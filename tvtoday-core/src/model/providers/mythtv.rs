@@ -0,0 +1,349 @@
+use crate::model::metrics::{self, ReloadStep};
+use crate::model::providers::icon::Icons;
+use crate::model::providers::slot::{local_time, slot_timestamp};
+use crate::model::{Channel, Movie, MovieBuilder, ParseFailure, Program, Provider, TimeSlot};
+use crate::Error;
+
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::DateTime;
+use reqwest::header::ACCEPT;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+
+/// The address of the backend if none is set, MythTV running on this computer with the default port of its services.
+const DEFAULT_SERVER: &str = "http://localhost:6544";
+
+/// The name of the provider in reports about parse failures.
+const PROVIDER_NAME: &str = "mythtv";
+
+/// The part of the answer that could not be read when getting the guide.
+const PART_GUIDE: &str = "ProgramGuide.Channels";
+
+/// How many seconds of the guide are requested, starting at the time slot.
+const GUIDE_DURATION: i64 = 60;
+
+/// The category types of MythTV mapped to the divisions of TV Spielfilm, so filters work with all providers.
+const DIVISIONS: &[(&str, &str)] = &[
+    ("movie", "Spielfilm"),
+    ("series", "Serie"),
+    ("sports", "Sport"),
+];
+
+/// The answer of the guide service.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GuideAnswer {
+    program_guide: Guide,
+}
+
+/// The guide of the channels of the backend.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Guide {
+    channels: Vec<GuideChannel>,
+}
+
+/// A channel of the backend with its broadcasts. All values are strings in the answers of the services.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GuideChannel {
+    chan_id: String,
+    channel_name: Option<String>,
+    call_sign: Option<String>,
+    /// The path of the icon of the channel on the backend.
+    #[serde(rename = "IconURL")]
+    icon_url: Option<String>,
+    #[serde(default)]
+    programs: Vec<GuideProgram>,
+}
+
+/// A broadcast in the guide.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GuideProgram {
+    title: String,
+    /// The start in UTC like `2021-05-01T18:15:00Z`.
+    start_time: String,
+    /// The end in UTC.
+    end_time: String,
+    category: Option<String>,
+    /// The type of the broadcast, e.g. `movie` or `series`.
+    cat_type: Option<String>,
+    description: Option<String>,
+    /// The date of the first broadcast like `1927-01-10`.
+    airdate: Option<String>,
+}
+
+/// The program of the channels of a MythTV backend, read from the guide of its services API. The address of the backend
+/// is set with `set_server`, the login and password with `set_credentials` if it needs them.
+pub struct MythTv {
+    client: Client,
+    /// The address of the services of the backend, e.g. `http://localhost:6544`.
+    server: String,
+    /// The login of the user at the backend.
+    login: Option<String>,
+    /// The password of the user at the backend.
+    password: Option<String>,
+    /// The icons of the channels from the backend.
+    icons: Icons,
+    /// The time slot of the program.
+    time_slot: TimeSlot,
+    /// The day of the program as the number of days from today.
+    days_ahead: u32,
+    /// The part of the answer that could not be read the last time parsing failed.
+    parse_failure: Option<ParseFailure>,
+}
+
+#[async_trait]
+impl Provider for MythTv {
+    fn new() -> Self {
+        MythTv {
+            client: Client::new(),
+            server: DEFAULT_SERVER.to_string(),
+            login: None,
+            password: None,
+            icons: Icons::new(),
+            time_slot: TimeSlot::Evening,
+            days_ahead: 0,
+            parse_failure: None,
+        }
+    }
+
+    fn clone(&self) -> Self {
+        MythTv {
+            client: self.client.clone(),
+            server: self.server.clone(),
+            login: self.login.clone(),
+            password: self.password.clone(),
+            icons: self.icons.clone(),
+            time_slot: self.time_slot,
+            days_ahead: self.days_ahead,
+            parse_failure: self.parse_failure.clone(),
+        }
+    }
+
+    /// Get the broadcasts running at the time slot on each channel of the backend.
+    async fn get_program(&mut self) -> Result<Program, Error> {
+        let time = slot_timestamp(self.time_slot, self.days_ahead);
+        let start = utc_time(time);
+        let end = utc_time(time + GUIDE_DURATION);
+
+        let waiting = Instant::now();
+        let mut request = self
+            .client
+            .get(format!("{}/Guide/GetProgramGuide", self.server))
+            .header(ACCEPT, "application/json")
+            .query(&[
+                ("StartTime", &start[..]),
+                ("EndTime", &end[..]),
+                ("Details", "true"),
+            ]);
+        if let Some(login) = &self.login {
+            request = request.basic_auth(login, self.password.as_ref());
+        }
+        let response = request.send().await?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(Error::Login);
+        }
+        let json = response.error_for_status()?.text().await?;
+        metrics::record(ReloadStep::Network, waiting.elapsed());
+
+        metrics::time(ReloadStep::Parse, || self.parse_program(&json, time))
+    }
+
+    /// The guide already contains all information about the movie.
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+        Ok(movie.clone())
+    }
+
+    /// The services have no pages about single broadcasts.
+    fn get_url(&self, _movie: &Movie) -> Option<String> {
+        None
+    }
+
+    fn get_parse_failure(&self) -> Option<ParseFailure> {
+        self.parse_failure.clone()
+    }
+
+    fn set_credentials(&mut self, login: &str, password: &str) {
+        self.login = Some(login.to_string());
+        self.password = Some(password.to_string());
+    }
+
+    fn set_server(&mut self, url: &str) {
+        self.server = url.trim_end_matches('/').to_string();
+    }
+
+    fn set_time_slot(&mut self, time_slot: TimeSlot) {
+        self.time_slot = time_slot;
+    }
+
+    fn set_days_ahead(&mut self, days: u32) {
+        self.days_ahead = days;
+    }
+
+    /// Get the icons of the channels from the backend.
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+        Ok(self.icons.set_icons(program).await)
+    }
+}
+
+impl MythTv {
+    /// Parse the program from the guide, the broadcasts running at the unix timestamp. Also remembers the URLs of the
+    /// icons of the channels.
+    fn parse_program(&mut self, json: &str, time: i64) -> Result<Program, Error> {
+        self.parse_failure = None;
+        let answer: GuideAnswer = serde_json::from_str(json).map_err(|_| {
+            self.parse_failure = Some(ParseFailure::new(PROVIDER_NAME, PART_GUIDE));
+            Error::ParsingWebsite
+        })?;
+
+        let mut program = Program::new();
+        for channel in answer.program_guide.channels {
+            let non_empty = |name: &&String| !name.trim().is_empty();
+            let name = match channel
+                .channel_name
+                .as_ref()
+                .filter(non_empty)
+                .or_else(|| channel.call_sign.as_ref().filter(non_empty))
+            {
+                Some(name) => name.trim().to_string(),
+                None => format!("Channel {}", channel.chan_id),
+            };
+
+            let running = channel.programs.iter().find(|broadcast| {
+                match (
+                    timestamp(&broadcast.start_time),
+                    timestamp(&broadcast.end_time),
+                ) {
+                    (Some(start), Some(end)) => start <= time && time < end,
+                    _ => false,
+                }
+            });
+            if let Some(broadcast) = running {
+                if let Some(icon_url) = channel.icon_url.as_ref().filter(|u| !u.is_empty()) {
+                    self.icons
+                        .add_url(&name, &format!("{}{}", self.server, icon_url));
+                }
+                program.add(Channel::new(&name), movie_of(broadcast));
+            }
+        }
+
+        Ok(program)
+    }
+}
+
+/// The movie of the broadcast in the guide.
+fn movie_of(broadcast: &GuideProgram) -> Movie {
+    let mut movie_builder = MovieBuilder::new(&broadcast.title);
+    if let Some(start) = timestamp(&broadcast.start_time) {
+        movie_builder.with_start(local_time(start));
+    }
+    if let Some(category) = broadcast.category.as_ref().filter(|c| !c.is_empty()) {
+        movie_builder.with_genre(category);
+    }
+    let division = broadcast.cat_type.as_ref().and_then(|cat_type| {
+        DIVISIONS
+            .iter()
+            .find(|(mythtv, _tv_spielfilm)| mythtv.eq_ignore_ascii_case(cat_type))
+            .map(|(_mythtv, tv_spielfilm)| tv_spielfilm)
+    });
+    if let Some(division) = division {
+        movie_builder.with_division(division);
+        // The first broadcast of a movie is usually its release.
+        if *division == "Spielfilm" {
+            let year = broadcast
+                .airdate
+                .as_ref()
+                .and_then(|airdate| airdate.get(..4))
+                .and_then(|year| year.parse().ok());
+            if let Some(year) = year {
+                movie_builder.with_year(year);
+            }
+        }
+    }
+
+    let mut movie = movie_builder.build();
+    movie.set_description(
+        broadcast
+            .description
+            .clone()
+            .filter(|d| !d.trim().is_empty()),
+    );
+    movie
+}
+
+/// The unix timestamp of a time in UTC like `2021-05-01T18:15:00Z`.
+fn timestamp(time: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|time| time.timestamp())
+}
+
+/// The time in UTC of the unix timestamp, as expected by the services.
+fn utc_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_program() {
+        let json = r#"{"ProgramGuide": {"StartTime": "2021-05-01T18:15:00Z", "Channels": [
+            {"ChanId": "1001", "ChanNum": "1", "CallSign": "ARD", "ChannelName": "Das Erste HD",
+             "IconURL": "/Guide/GetChannelIcon?ChanId=1001", "Programs": [
+                {"Title": "Tagesschau", "StartTime": "2021-05-01T18:00:00Z", "EndTime": "2021-05-01T18:15:00Z"},
+                {"Title": "Tatort", "StartTime": "2021-05-01T18:15:00Z", "EndTime": "2021-05-01T19:45:00Z",
+                 "Category": "Krimi", "CatType": "series", "Description": "Ein Fall."}
+            ]},
+            {"ChanId": "1002", "CallSign": "arte", "ChannelName": "", "Programs": [
+                {"Title": "Metropolis", "StartTime": "2021-05-01T18:00:00Z", "EndTime": "2021-05-01T20:30:00Z",
+                 "CatType": "movie", "Airdate": "1927-01-10"}
+            ]},
+            {"ChanId": "1003", "ChannelName": "3sat", "Programs": []}
+        ]}}"#;
+        let time = timestamp("2021-05-01T18:15:00Z").unwrap();
+
+        let mut provider = MythTv::new();
+        let program = provider
+            .parse_program(json, time)
+            .expect("Could not parse the guide");
+
+        let movies: Vec<(String, Movie)> = program
+            .iter()
+            .map(|(channel, movie)| (channel.get_name(), movie.clone()))
+            .collect();
+        assert_eq!(movies.len(), 2);
+        let (channel, tatort) = &movies[0];
+        assert_eq!(channel, "Das Erste HD");
+        assert_eq!(tatort.get_title(), "Tatort");
+        assert_eq!(tatort.get_genre(), Some("Krimi".to_string()));
+        assert_eq!(tatort.get_division(), Some("Serie".to_string()));
+        assert_eq!(tatort.get_description(), Some("Ein Fall.".to_string()));
+        assert_eq!(tatort.get_start(), Some(local_time(time)));
+        let (channel, metropolis) = &movies[1];
+        assert_eq!(channel, "arte");
+        assert_eq!(metropolis.get_division(), Some("Spielfilm".to_string()));
+        assert_eq!(metropolis.get_year(), Some(1927));
+
+        assert!(provider.parse_program("{}", time).is_err());
+        assert_eq!(
+            provider.get_parse_failure(),
+            Some(ParseFailure::new(PROVIDER_NAME, PART_GUIDE))
+        );
+    }
+
+    #[test]
+    fn test_utc_time() {
+        let time = timestamp("2021-05-01T18:15:00Z").unwrap();
+        assert_eq!(utc_time(time), "2021-05-01T18:15:00Z");
+        assert_eq!(timestamp("yesterday"), None);
+    }
+}
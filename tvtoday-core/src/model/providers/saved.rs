@@ -0,0 +1,50 @@
+use crate::model::{Movie, Program, Provider, TimeSlot};
+use crate::Error;
+
+use async_trait::async_trait;
+
+/// A provider showing a program saved before, e.g. to review the listings of yesterday or to look at a program that
+/// could not be parsed without a network connection.
+///
+/// The program is the same for every time slot and day and no more information is available than was saved.
+pub struct Saved {
+    program: Program,
+}
+
+impl Saved {
+    /// Create a provider always giving the program.
+    pub fn with_program(program: Program) -> Self {
+        Saved { program }
+    }
+}
+
+#[async_trait]
+impl Provider for Saved {
+    fn new() -> Self {
+        Saved::with_program(Program::new())
+    }
+
+    fn clone(&self) -> Self {
+        Saved::with_program(self.program.clone())
+    }
+
+    fn set_time_slot(&mut self, _time_slot: TimeSlot) {}
+
+    fn set_days_ahead(&mut self, _days: u32) {}
+
+    async fn get_program(&mut self) -> Result<Program, Error> {
+        Ok(self.program.clone())
+    }
+
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+        Ok(movie.clone())
+    }
+
+    fn get_url(&self, _movie: &Movie) -> Option<String> {
+        None
+    }
+
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+        Ok(program.clone())
+    }
+}
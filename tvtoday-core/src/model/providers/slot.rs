@@ -0,0 +1,44 @@
+use crate::model::TimeSlot;
+
+use chrono::{DateTime, Duration, Local, NaiveDateTime, NaiveTime, TimeZone};
+
+/// The unix timestamp of the start of the time slot on the day the given number of days from today, for providers
+/// reading a guide of broadcasts with their start and end instead of pages for the time slots.
+pub(super) fn slot_timestamp(time_slot: TimeSlot, days_ahead: u32) -> i64 {
+    let date = Local::now().date_naive() + Duration::days(days_ahead as i64);
+    let time = match time_slot {
+        TimeSlot::Now => Local::now().time(),
+        TimeSlot::Evening => NaiveTime::from_hms_opt(20, 15, 0).unwrap(),
+        TimeSlot::Late => NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+    };
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|time| time.timestamp())
+        .unwrap_or_else(|| Local::now().timestamp())
+}
+
+/// The local time of the unix timestamp. Timestamps out of range give the start of the epoch.
+pub(super) fn local_time(timestamp: i64) -> NaiveDateTime {
+    DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .with_timezone(&Local)
+        .naive_local()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slot_timestamp() {
+        let evening = local_time(slot_timestamp(TimeSlot::Evening, 1));
+        assert_eq!(
+            evening,
+            (Local::now().date_naive() + Duration::days(1))
+                .and_hms_opt(20, 15, 0)
+                .unwrap()
+        );
+        assert!(slot_timestamp(TimeSlot::Late, 0) > slot_timestamp(TimeSlot::Evening, 0));
+    }
+}
@@ -0,0 +1,385 @@
+use crate::model::metrics::{self, ReloadStep};
+use crate::model::{Channel, Movie, MovieBuilder, ParseFailure, Program, Provider, TimeSlot};
+use crate::Error;
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::{Duration, Local, NaiveDateTime, NaiveTime};
+use scraper::{Html, Selector};
+
+/// The website the links of the listing are relative to.
+const BASE_URL: &str = "https://www.tvmovie.de";
+
+/// The URL of the program without the page of the time slot.
+const URL: &str = "https://www.tvmovie.de/tv/";
+
+/// The name of the provider in reports about parse failures.
+const PROVIDER_NAME: &str = "tvmovie.de";
+
+/// The selector of the rows of the listing, one for each broadcast.
+const SELECTOR_ROWS: &str = ".broadcast-list .broadcast";
+
+/// The selector of the channel name, every row of the listing must have one.
+const SELECTOR_CHANNEL_NAME: &str = ".broadcast__channel";
+
+/// The selector of the movie title, every row of the listing must have one.
+const SELECTOR_MOVIE_TITLE: &str = ".broadcast__title a";
+
+/// Movies starting before this hour are broadcasted after midnight.
+const DAY_START_HOUR: u32 = 5;
+
+/// The divisions of TV Movie mapped to the divisions of TV Spielfilm, so filters work with both providers.
+const DIVISIONS: &[(&str, &str)] = &[
+    ("Spielfilm", "Spielfilm"),
+    ("Film", "Spielfilm"),
+    ("Serie", "Serie"),
+    ("Dokumentation", "Report"),
+    ("Reportage", "Report"),
+    ("Magazin", "Report"),
+    ("Nachrichten", "Report"),
+    ("Show", "Show"),
+    ("Sport", "Sport"),
+];
+
+pub struct TvMovie {
+    /// Maps each movie to a URL with more information (e.g. description).
+    more_information_urls: HashMap<Movie, String>,
+    /// The time slot of the program.
+    time_slot: TimeSlot,
+    /// The day of the program as the number of days from today.
+    days_ahead: u32,
+    /// The part of the website that could not be read the last time parsing failed.
+    parse_failure: Option<ParseFailure>,
+}
+
+#[async_trait]
+impl Provider for TvMovie {
+    fn new() -> Self {
+        TvMovie {
+            more_information_urls: HashMap::new(),
+            time_slot: TimeSlot::Evening,
+            days_ahead: 0,
+            parse_failure: None,
+        }
+    }
+
+    fn clone(&self) -> Self {
+        TvMovie {
+            more_information_urls: self.more_information_urls.clone(),
+            time_slot: self.time_slot,
+            days_ahead: self.days_ahead,
+            parse_failure: self.parse_failure.clone(),
+        }
+    }
+
+    async fn get_program(&mut self) -> Result<Program, Error> {
+        let waiting = Instant::now();
+        let html = reqwest::get(&self.url())
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        metrics::record(ReloadStep::Network, waiting.elapsed());
+
+        metrics::time(ReloadStep::Parse, || self.parse_program(&html))
+    }
+
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+        let url = match self.more_information_urls.get(movie) {
+            Some(url) => url,
+            None => return Ok(movie.clone()),
+        };
+        let html = reqwest::get(url).await?.text().await?;
+
+        let document = Html::parse_document(&html);
+        let selector_description = Selector::parse(".broadcast-detail__description p")
+            .expect("failed to parse selector for movie description");
+        let selector_poster = Selector::parse("meta[property=\"og:image\"]")
+            .expect("failed to parse selector for movie poster");
+
+        let description: String = document
+            .select(&selector_description)
+            .map(|e| e.text().collect::<String>().trim().to_string() + "\n\n")
+            .collect();
+        let poster_url = document
+            .select(&selector_poster)
+            .next()
+            .and_then(|e| e.value().attr("content"))
+            .map(|u| u.to_string());
+
+        let mut movie_clone = movie.clone();
+        if !description.is_empty() {
+            movie_clone.set_description(Some(description));
+        }
+        movie_clone.set_poster_url(poster_url.or_else(|| movie.get_poster_url()));
+        Ok(movie_clone)
+    }
+
+    fn get_url(&self, movie: &Movie) -> Option<String> {
+        self.more_information_urls.get(movie).cloned()
+    }
+
+    fn get_parse_failure(&self) -> Option<ParseFailure> {
+        self.parse_failure.clone()
+    }
+
+    fn set_time_slot(&mut self, time_slot: TimeSlot) {
+        self.time_slot = time_slot;
+    }
+
+    fn set_days_ahead(&mut self, days: u32) {
+        self.days_ahead = days;
+    }
+
+    /// The overview has no usable channel logos, so the names of the channels are shown instead.
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+        Ok(program.clone())
+    }
+}
+
+impl TvMovie {
+    /// Parse the program from the HTML of the website. Also remembers the URLs of more information.
+    fn parse_program(&mut self, html: &str) -> Result<Program, Error> {
+        let document = Html::parse_document(html);
+        self.parse_failure = None;
+
+        // The selectors to get the movie and channel data.
+        let selector_rows =
+            Selector::parse(SELECTOR_ROWS).expect("failed to parse selector for rows");
+        let selector_channel_name = Selector::parse(SELECTOR_CHANNEL_NAME)
+            .expect("failed to parse selector for channel name");
+        let selector_movie_title = Selector::parse(SELECTOR_MOVIE_TITLE)
+            .expect("failed to parse selector for movie title");
+        let selector_movie_category = Selector::parse(".broadcast__genre")
+            .expect("failed to parse selector for movie category");
+        let selector_movie_info =
+            Selector::parse(".broadcast__info").expect("failed to parse selector for movie info");
+        let selector_movie_start =
+            Selector::parse(".broadcast__time").expect("failed to parse selector for movie start");
+
+        let mut program = Program::new();
+        for row in document.select(&selector_rows) {
+            // The channel name.
+            let channel_name = match row.select(&selector_channel_name).next() {
+                Some(element) => element.text().collect::<String>().trim().to_string(),
+                None => {
+                    self.parse_failure =
+                        Some(ParseFailure::new(PROVIDER_NAME, SELECTOR_CHANNEL_NAME));
+                    return Err(Error::ParsingWebsite);
+                }
+            };
+
+            // The title of the movie and the link to more information.
+            let title_element = match row.select(&selector_movie_title).next() {
+                Some(element) => element,
+                None => {
+                    self.parse_failure =
+                        Some(ParseFailure::new(PROVIDER_NAME, SELECTOR_MOVIE_TITLE));
+                    return Err(Error::ParsingWebsite);
+                }
+            };
+            let title = title_element.text().collect::<String>().trim().to_string();
+
+            let channel = Channel::new(&channel_name);
+            let mut movie_builder = MovieBuilder::new(&title);
+
+            // The category of TV Movie is split into the genre and the division.
+            if let Some(category) = row.select(&selector_movie_category).next() {
+                let (genre, division) =
+                    genre_and_division(category.text().collect::<String>().trim());
+                if let Some(genre) = genre {
+                    movie_builder.with_genre(&genre);
+                }
+                if let Some(division) = division {
+                    movie_builder.with_division(&division);
+                }
+            }
+
+            // The year is the last part of the info, after the country.
+            if let Some(info) = row.select(&selector_movie_info).next() {
+                if let Some(year) = year_from_info(&info.text().collect::<String>()) {
+                    movie_builder.with_year(year);
+                }
+            }
+
+            // Get the start time of the movie.
+            if let Some(start) = row.select(&selector_movie_start).next() {
+                let start = start.text().collect::<String>();
+                if let Ok(time) = NaiveTime::parse_from_str(start.trim(), "%H:%M") {
+                    movie_builder.with_start(start_from_time(self.days_ahead, time));
+                }
+            }
+
+            let movie = movie_builder.build();
+            if let Some(href) = title_element.value().attr("href") {
+                let url = if href.starts_with("http") {
+                    href.to_string()
+                } else {
+                    format!("{}{}", BASE_URL, href)
+                };
+                self.more_information_urls.insert(movie.clone(), url);
+            }
+
+            program.add(channel, movie);
+        }
+
+        Ok(program)
+    }
+
+    /// The URL of the program in the time slot of the day.
+    fn url(&self) -> String {
+        let page = time_slot_page(self.time_slot);
+        match self.days_ahead {
+            0 => format!("{}{}", URL, page),
+            days => format!(
+                "{}{}?date={}",
+                URL,
+                page,
+                (Local::now().date_naive() + Duration::days(days as i64)).format("%Y-%m-%d")
+            ),
+        }
+    }
+}
+
+/// Split the category of TV Movie into the genre and the division of TV Spielfilm. The category is either a division
+/// and a genre separated by a comma like `Spielfilm, Thriller`, a compound like `Krimiserie` or `Actionfilm`, or a
+/// single word like `Dokumentation` or `Western`.
+fn genre_and_division(category: &str) -> (Option<String>, Option<String>) {
+    let division_of = |name: &str| {
+        DIVISIONS
+            .iter()
+            .find(|(tv_movie, _tv_spielfilm)| tv_movie.eq_ignore_ascii_case(name))
+            .map(|(_tv_movie, tv_spielfilm)| tv_spielfilm.to_string())
+    };
+    let non_empty = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+
+    let mut parts = category.splitn(2, ',');
+    if let (Some(division), Some(genre)) = (parts.next(), parts.next()) {
+        return match division_of(division.trim()) {
+            Some(division) => (non_empty(genre), Some(division)),
+            None => (non_empty(category), None),
+        };
+    }
+
+    if let Some(division) = division_of(category) {
+        // Only the division is known, e.g. for a documentary, which is also the best genre.
+        return (non_empty(category), Some(division));
+    }
+
+    let lowercase = category.to_lowercase();
+    for (suffix, division) in [("serie", "Serie"), ("film", "Spielfilm")].iter() {
+        if lowercase.ends_with(suffix) {
+            let genre = category[..category.len() - suffix.len()].trim_end_matches('-');
+            return (non_empty(genre), Some(division.to_string()));
+        }
+    }
+
+    (non_empty(category), None)
+}
+
+/// Get the year from the info of a broadcast like `USA 1994` or `D, A 2019`.
+fn year_from_info(info: &str) -> Option<u32> {
+    info.split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(|part| part.parse::<u32>().ok())
+        .rfind(|year| (1900..2100).contains(year))
+}
+
+/// The page of the program in the time slot.
+fn time_slot_page(time_slot: TimeSlot) -> &'static str {
+    match time_slot {
+        TimeSlot::Now => "jetzt-im-tv",
+        TimeSlot::Evening => "heute-2015",
+        TimeSlot::Late => "heute-2200",
+    }
+}
+
+/// Get the start time of a movie broadcasted at the given time on the day the given number of days from today.
+fn start_from_time(days_ahead: u32, time: NaiveTime) -> NaiveDateTime {
+    let mut date = Local::now().date_naive() + Duration::days(days_ahead as i64);
+    if time < NaiveTime::from_hms_opt(DAY_START_HOUR, 0, 0).unwrap() {
+        date += Duration::days(1);
+    }
+    date.and_time(time)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A listing with a row of the given content.
+    fn listing(row: &str) -> String {
+        format!(
+            "<html><body><ul class=\"broadcast-list\"><li class=\"broadcast\">{}</li></ul></body></html>",
+            row
+        )
+    }
+
+    #[test]
+    fn test_genre_and_division() {
+        let some = |s: &str| Some(s.to_string());
+
+        assert_eq!(
+            genre_and_division("Spielfilm, Thriller"),
+            (some("Thriller"), some("Spielfilm"))
+        );
+        assert_eq!(
+            genre_and_division("Krimiserie"),
+            (some("Krimi"), some("Serie"))
+        );
+        assert_eq!(
+            genre_and_division("Science-Fiction-Film"),
+            (some("Science-Fiction"), some("Spielfilm"))
+        );
+        assert_eq!(genre_and_division("Serie"), (some("Serie"), some("Serie")));
+        assert_eq!(
+            genre_and_division("Dokumentation"),
+            (some("Dokumentation"), some("Report"))
+        );
+        assert_eq!(genre_and_division("Western"), (some("Western"), None));
+        assert_eq!(
+            genre_and_division("Drama, Liebe"),
+            (some("Drama, Liebe"), None)
+        );
+        assert_eq!(genre_and_division(""), (None, None));
+    }
+
+    #[test]
+    fn test_year_from_info() {
+        assert_eq!(year_from_info("USA 1994"), Some(1994));
+        assert_eq!(year_from_info("D, A 2019"), Some(2019));
+        assert_eq!(year_from_info("D 90 Min."), None);
+        assert_eq!(year_from_info(""), None);
+    }
+
+    #[test]
+    fn test_parse_program() {
+        let channel = r#"<span class="broadcast__channel">ZDF</span>"#;
+        let title = r#"<span class="broadcast__title"><a href="/tv/tatort-123">Tatort</a></span>"#;
+        let details = r#"<span class="broadcast__time">20:15</span>
+            <span class="broadcast__genre">Krimiserie</span>
+            <span class="broadcast__info">D 2021</span>"#;
+
+        let mut provider = TvMovie::new();
+        let program = provider
+            .parse_program(&listing(&format!("{}{}{}", channel, title, details)))
+            .expect("Could not parse the listing");
+        let (parsed_channel, movie) = program.iter().next().expect("No broadcast was parsed");
+        assert_eq!(parsed_channel.get_name(), "ZDF");
+        assert_eq!(movie.get_title(), "Tatort");
+        assert_eq!(movie.get_genre(), Some("Krimi".to_string()));
+        assert_eq!(movie.get_division(), Some("Serie".to_string()));
+        assert_eq!(movie.get_year(), Some(2021));
+        assert_eq!(
+            provider.get_url(movie),
+            Some("https://www.tvmovie.de/tv/tatort-123".to_string())
+        );
+
+        assert!(provider.parse_program(&listing(channel)).is_err());
+        assert_eq!(
+            provider.get_parse_failure(),
+            Some(ParseFailure::new(PROVIDER_NAME, SELECTOR_MOVIE_TITLE))
+        );
+    }
+}
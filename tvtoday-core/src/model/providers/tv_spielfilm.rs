@@ -0,0 +1,425 @@
+use crate::model::metrics::{self, ReloadStep};
+use crate::model::providers::icon::Icons;
+use crate::model::{Channel, Movie, MovieBuilder, ParseFailure, Program, Provider, TimeSlot};
+use crate::Error;
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::{Duration, Local, NaiveDateTime, NaiveTime};
+use scraper::{Html, Selector};
+
+/// The URL of the program without the page of the time slot.
+const URL: &str = "https://www.tvspielfilm.de/tv-programm/sendungen/";
+
+/// The name of the provider in reports about parse failures.
+const PROVIDER_NAME: &str = "tvspielfilm.de";
+
+/// The selector of the channel name, every row of the listing must have one.
+const SELECTOR_CHANNEL_NAME: &str = ".programm-col1 a";
+
+/// The selector of the movie title, every row of the listing must have one.
+const SELECTOR_MOVIE_TITLE: &str = ".col-3 span a strong";
+
+/// The number of bytes of the website received before the rows received so far are parsed again.
+const PARSE_STEP: usize = 64 * 1024;
+
+/// Movies starting before this hour are broadcasted after midnight.
+const DAY_START_HOUR: u32 = 5;
+
+pub struct TvSpielfilm {
+    /// Maps each movie to a URL with more information (e.g. description).
+    more_information_urls: HashMap<Movie, String>,
+    /// The icons of the channels from the logos in the listing.
+    icons: Icons,
+    /// The time slot of the program.
+    time_slot: TimeSlot,
+    /// The day of the program as the number of days from today.
+    days_ahead: u32,
+    /// The part of the website that could not be read the last time parsing failed.
+    parse_failure: Option<ParseFailure>,
+}
+
+#[async_trait]
+impl Provider for TvSpielfilm {
+    fn new() -> Self {
+        TvSpielfilm {
+            more_information_urls: HashMap::new(),
+            icons: Icons::new(),
+            time_slot: TimeSlot::Evening,
+            days_ahead: 0,
+            parse_failure: None,
+        }
+    }
+
+    fn clone(&self) -> Self {
+        TvSpielfilm {
+            more_information_urls: self.more_information_urls.clone(),
+            icons: self.icons.clone(),
+            time_slot: self.time_slot,
+            days_ahead: self.days_ahead,
+            parse_failure: self.parse_failure.clone(),
+        }
+    }
+
+    async fn get_program(&mut self) -> Result<Program, Error> {
+        self.get_program_progressively(&|_| {}).await
+    }
+
+    async fn get_program_progressively(
+        &mut self,
+        on_part: &(dyn Fn(Program) + Send + Sync),
+    ) -> Result<Program, Error> {
+        // Get the contents of the website. The icons are loaded afterwards in `get_icons`.
+        let waiting = Instant::now();
+        let mut response = reqwest::get(&self.url()).await?;
+        metrics::record(ReloadStep::Network, waiting.elapsed());
+
+        // The rows received so far are parsed from time to time. The last row parsed may be incomplete, it is only
+        // passed on with the next part.
+        let mut html = vec![];
+        let mut next_parse = PARSE_STEP;
+        let mut passed = 0;
+        loop {
+            let waiting = Instant::now();
+            let chunk = response.chunk().await?;
+            metrics::record(ReloadStep::Network, waiting.elapsed());
+            let chunk = match chunk {
+                Some(chunk) => chunk,
+                None => break,
+            };
+
+            html.extend_from_slice(&chunk);
+            if html.len() < next_parse {
+                continue;
+            }
+            next_parse = html.len() + PARSE_STEP;
+
+            let parsed = metrics::time(ReloadStep::Parse, || {
+                self.parse_program(&String::from_utf8_lossy(&html))
+            });
+            if let Ok(program) = parsed {
+                let complete = program.iter().count().saturating_sub(1);
+                if complete > passed {
+                    on_part(
+                        program
+                            .iter()
+                            .take(complete)
+                            .skip(passed)
+                            .cloned()
+                            .collect(),
+                    );
+                    passed = complete;
+                }
+            }
+        }
+
+        let program = metrics::time(ReloadStep::Parse, || {
+            self.parse_program(&String::from_utf8_lossy(&html))
+        })?;
+        let rest: Program = program.iter().skip(passed).cloned().collect();
+        if rest.iter().next().is_some() {
+            on_part(rest);
+        }
+
+        Ok(program)
+    }
+
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+        if let Some(more_information_url) = self.more_information_urls.get(movie) {
+            // Get the contents of the website.
+            let html = reqwest::get(more_information_url).await?.text().await?;
+
+            let document = Html::parse_document(&html);
+
+            let selector_description =
+                Selector::parse("#content div div article section.broadcast-detail__description p")
+                    .expect("failed to parse selector for movie description");
+
+            // Get the description.
+            let description = document
+                .select(&selector_description)
+                .map(|e| e.inner_html() + "\n\n")
+                .collect();
+
+            let selector_dt = Selector::parse("dl dt").expect("failed to parse selector for dt");
+            let selector_dd = Selector::parse("dl dd").expect("failed to parse selector for dd");
+            let selector_cast = Selector::parse("section.cast dl.cast dd")
+                .expect("failed to parse selector for movie cast");
+            let selector_poster = Selector::parse("meta[property=\"og:image\"]")
+                .expect("failed to parse selector for movie poster");
+
+            // The terms and descriptions of the description lists.
+            let details: Vec<(String, String)> = document
+                .select(&selector_dt)
+                .zip(document.select(&selector_dd))
+                .map(|(dt, dd)| {
+                    (
+                        dt.text().collect::<String>().trim().to_string(),
+                        dd.text().collect::<String>().trim().to_string(),
+                    )
+                })
+                .collect();
+
+            // Get the age rating (FSK) from the description list.
+            let age_rating = details
+                .iter()
+                .find(|(dt, _dd)| dt.starts_with("FSK"))
+                .and_then(|(_dt, dd)| {
+                    dd.chars()
+                        .filter(|c| c.is_ascii_digit())
+                        .collect::<String>()
+                        .parse::<u32>()
+                        .ok()
+                });
+
+            // Get the director from the description list.
+            let director = details
+                .iter()
+                .find(|(dt, _dd)| dt.starts_with("Regie"))
+                .map(|(_dt, dd)| dd.clone());
+
+            // Get the actors.
+            let cast: Vec<String> = document
+                .select(&selector_cast)
+                .map(|e| e.text().collect::<String>().trim().to_string())
+                .filter(|a| !a.is_empty())
+                .collect();
+
+            // Get the URL of the poster.
+            let poster_url = document
+                .select(&selector_poster)
+                .next()
+                .and_then(|e| e.value().attr("content"))
+                .map(|u| u.to_string());
+
+            // Create a cloned movie ant manipulate it.
+            let mut movie_clone = movie.clone();
+
+            movie_clone.set_description(Some(description));
+            movie_clone.set_age_rating(age_rating.or_else(|| movie.get_age_rating()));
+            movie_clone.set_director(director.or_else(|| movie.get_director()));
+            movie_clone.set_poster_url(poster_url.or_else(|| movie.get_poster_url()));
+            if !cast.is_empty() {
+                movie_clone.set_cast(cast);
+            }
+
+            Ok(movie_clone)
+        } else {
+            Ok(movie.clone())
+        }
+    }
+
+    fn get_url(&self, movie: &Movie) -> Option<String> {
+        self.more_information_urls.get(movie).cloned()
+    }
+
+    fn get_parse_failure(&self) -> Option<ParseFailure> {
+        self.parse_failure.clone()
+    }
+
+    fn set_time_slot(&mut self, time_slot: TimeSlot) {
+        self.time_slot = time_slot;
+    }
+
+    fn set_days_ahead(&mut self, days: u32) {
+        self.days_ahead = days;
+    }
+
+    /// Get the icons of the channels from the logos in the listing.
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+        Ok(self.icons.set_icons(program).await)
+    }
+}
+
+impl TvSpielfilm {
+    /// Parse the program from the HTML of the website. Also remembers the URLs of the logos and of more information.
+    fn parse_program(&mut self, html: &str) -> Result<Program, Error> {
+        let document = Html::parse_document(html);
+        self.parse_failure = None;
+
+        // The selectors to get the movie and channel data.
+        let selector_list_rows = Selector::parse("body #wrapper #main .content-area #content .tvlistings .content-holder .tab-content .info-table tbody .hover").expect("failed to parse selector for list row");
+        let selector_channel_name = Selector::parse(SELECTOR_CHANNEL_NAME)
+            .expect("failed to parse selector for channel name");
+        let selector_channel_logo = Selector::parse(".programm-col1 a img")
+            .expect("failed to parse selector for channel logo");
+        let selector_movie_title = Selector::parse(SELECTOR_MOVIE_TITLE)
+            .expect("failed to parse selector for movie title");
+        let selector_movie_genre =
+            Selector::parse(".col-4 span").expect("failed to parse selector for movie genre");
+        let selector_movie_division =
+            Selector::parse(".col-5 span").expect("failed to parse selector for movie division");
+        let selector_movie_year =
+            Selector::parse(".col-3 span a").expect("failed to parse selector for movie year");
+        let selector_movie_information = selector_movie_year.clone();
+        let selector_movie_start =
+            Selector::parse(".col-2 strong").expect("failed to parse selector for movie start");
+
+        // Create the program.
+        let mut program = Program::new();
+        for row in document.select(&selector_list_rows) {
+            // The channel name.
+            let channel_str_opt = row.select(&selector_channel_name).next();
+            if channel_str_opt.is_none() {
+                self.parse_failure = Some(ParseFailure::new(PROVIDER_NAME, SELECTOR_CHANNEL_NAME));
+                return Err(Error::ParsingWebsite);
+            }
+            let mut channel_str = channel_str_opt.unwrap().value().attr("title").unwrap();
+
+            // Remove trailing " Program".
+            if channel_str.ends_with(" Programm") {
+                channel_str = &channel_str[0..channel_str.len() - 9];
+            }
+
+            // The URL of the logo of the channel. Lazy loaded images have it in `data-src`.
+            let logo_url = row
+                .select(&selector_channel_logo)
+                .next()
+                .and_then(|e| e.value().attr("data-src").or(e.value().attr("src")))
+                .filter(|u| u.starts_with("http"));
+            if let Some(logo_url) = logo_url {
+                self.icons.add_url(channel_str, logo_url);
+            }
+
+            // The title of the movie.
+            let title_str_opt = row.select(&selector_movie_title).next();
+            if title_str_opt.is_none() {
+                self.parse_failure = Some(ParseFailure::new(PROVIDER_NAME, SELECTOR_MOVIE_TITLE));
+                return Err(Error::ParsingWebsite);
+            }
+            let title_str = title_str_opt.unwrap().inner_html();
+
+            // Create the channel and the movie.
+            let channel = Channel::new(channel_str);
+            let mut movie_builder = MovieBuilder::new(&title_str);
+
+            // Get the genre of the movie.
+            let genre_str_opt = row.select(&selector_movie_genre).next();
+            if let Some(genre_str) = genre_str_opt {
+                movie_builder.with_genre(genre_str.inner_html().trim());
+            }
+
+            // Get the division of the movie.
+            let division_str_opt = row.select(&selector_movie_division).next();
+            if let Some(division_str) = division_str_opt {
+                movie_builder.with_division(
+                    division_str
+                        .inner_html()
+                        .trim()
+                        .split(" ")
+                        .collect::<Vec<&str>>()
+                        .first()
+                        .unwrap(),
+                );
+            }
+
+            // Get the start time of the movie.
+            let start_str_opt = row.select(&selector_movie_start).next();
+            if let Some(start_str) = start_str_opt {
+                if let Ok(time) = NaiveTime::parse_from_str(start_str.inner_html().trim(), "%H:%M")
+                {
+                    movie_builder.with_start(start_from_time(self.days_ahead, time));
+                }
+            }
+
+            // Get the year of the movie.
+            let year_str_opt = row.select(&selector_movie_year).next();
+            if let Some(year_str) = year_str_opt {
+                if let Ok(year) = year_str
+                    .value()
+                    .attr("title")
+                    .unwrap_or("")
+                    .split(" ")
+                    .last()
+                    .unwrap()
+                    .parse()
+                {
+                    movie_builder.with_year(year);
+                }
+            }
+
+            let movie = movie_builder.build();
+            // The more information page.
+            let information_str_opt = row.select(&selector_movie_information).next();
+            if let Some(information_str) = information_str_opt {
+                if let Some(href) = information_str.value().attr("href") {
+                    self.more_information_urls
+                        .insert(movie.clone(), href.to_string());
+                }
+            }
+
+            program.add(channel, movie);
+        }
+
+        Ok(program)
+    }
+
+    /// The URL of the program in the time slot of the day.
+    fn url(&self) -> String {
+        let page = time_slot_page(self.time_slot);
+        match self.days_ahead {
+            0 => format!("{}{}", URL, page),
+            days => format!(
+                "{}{}?date={}",
+                URL,
+                page,
+                (Local::now().date_naive() + Duration::days(days as i64)).format("%Y-%m-%d")
+            ),
+        }
+    }
+}
+
+/// The page of the program in the time slot.
+fn time_slot_page(time_slot: TimeSlot) -> &'static str {
+    match time_slot {
+        TimeSlot::Now => "jetzt.html",
+        TimeSlot::Evening => "abends.html",
+        TimeSlot::Late => "spaet.html",
+    }
+}
+
+/// Get the start time of a movie broadcasted at the given time on the day the given number of days from today.
+fn start_from_time(days_ahead: u32, time: NaiveTime) -> NaiveDateTime {
+    let mut date = Local::now().date_naive() + Duration::days(days_ahead as i64);
+    if time < NaiveTime::from_hms_opt(DAY_START_HOUR, 0, 0).unwrap() {
+        date += Duration::days(1);
+    }
+    date.and_time(time)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A listing with a row of the given content.
+    fn listing(row: &str) -> String {
+        format!(
+            "<html><body><div id=\"wrapper\"><div id=\"main\"><div class=\"content-area\"><div id=\"content\">\
+             <div class=\"tvlistings\"><div class=\"content-holder\"><div class=\"tab-content\">\
+             <table class=\"info-table\"><tbody><tr class=\"hover\">{}</tr></tbody></table>\
+             </div></div></div></div></div></div></div></body></html>",
+            row
+        )
+    }
+
+    #[test]
+    fn test_parse_failure() {
+        let channel = r#"<td class="programm-col1"><a title="ZDF Programm">ZDF</a></td>"#;
+        let title = r#"<td class="col-3"><span><a><strong>Tatort</strong></a></span></td>"#;
+
+        let mut provider = TvSpielfilm::new();
+        let program = provider
+            .parse_program(&listing(&format!("{}{}", channel, title)))
+            .expect("Could not parse the listing");
+        assert_eq!(program.iter().count(), 1);
+        assert_eq!(provider.get_parse_failure(), None);
+
+        assert!(provider.parse_program(&listing(channel)).is_err());
+        assert_eq!(
+            provider.get_parse_failure(),
+            Some(ParseFailure::new(PROVIDER_NAME, SELECTOR_MOVIE_TITLE))
+        );
+    }
+}
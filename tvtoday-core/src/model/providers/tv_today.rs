@@ -0,0 +1,392 @@
+use crate::model::metrics::{self, ReloadStep};
+use crate::model::providers::icon::Icons;
+use crate::model::{Channel, Movie, MovieBuilder, ParseFailure, Program, Provider, TimeSlot};
+use crate::Error;
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::{Duration, Local, NaiveDateTime, NaiveTime};
+use scraper::{Html, Selector};
+
+/// The website the links of the listing are relative to.
+const BASE_URL: &str = "https://www.tvtoday.de";
+
+/// The URL of the program without the page of the time slot.
+const URL: &str = "https://www.tvtoday.de/programm/standard/";
+
+/// The name of the provider in reports about parse failures.
+const PROVIDER_NAME: &str = "tvtoday.de";
+
+/// The selector of the rows of the listing, one for each broadcast.
+const SELECTOR_ROWS: &str = ".program-listing .program-item";
+
+/// The selector of the channel logo, every row of the listing must have one. Its alternative text is the channel name.
+const SELECTOR_CHANNEL_LOGO: &str = ".program-item__channel img";
+
+/// The selector of the movie title, every row of the listing must have one.
+const SELECTOR_MOVIE_TITLE: &str = ".program-item__title a";
+
+/// Movies starting before this hour are broadcasted after midnight.
+const DAY_START_HOUR: u32 = 5;
+
+pub struct TvToday {
+    /// Maps each movie to the URL of its detail page.
+    more_information_urls: HashMap<Movie, String>,
+    /// The icons of the channels from the logos in the listing.
+    icons: Icons,
+    /// The time slot of the program.
+    time_slot: TimeSlot,
+    /// The day of the program as the number of days from today.
+    days_ahead: u32,
+    /// The part of the website that could not be read the last time parsing failed.
+    parse_failure: Option<ParseFailure>,
+}
+
+#[async_trait]
+impl Provider for TvToday {
+    fn new() -> Self {
+        TvToday {
+            more_information_urls: HashMap::new(),
+            icons: Icons::new(),
+            time_slot: TimeSlot::Evening,
+            days_ahead: 0,
+            parse_failure: None,
+        }
+    }
+
+    fn clone(&self) -> Self {
+        TvToday {
+            more_information_urls: self.more_information_urls.clone(),
+            icons: self.icons.clone(),
+            time_slot: self.time_slot,
+            days_ahead: self.days_ahead,
+            parse_failure: self.parse_failure.clone(),
+        }
+    }
+
+    async fn get_program(&mut self) -> Result<Program, Error> {
+        // Get the contents of the website. The icons are loaded afterwards in `get_icons`.
+        let waiting = Instant::now();
+        let html = reqwest::get(&self.url())
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        metrics::record(ReloadStep::Network, waiting.elapsed());
+
+        metrics::time(ReloadStep::Parse, || self.parse_program(&html))
+    }
+
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+        let url = match self.more_information_urls.get(movie) {
+            Some(url) => url,
+            None => return Ok(movie.clone()),
+        };
+        let html = reqwest::get(url).await?.text().await?;
+
+        let mut movie_clone = movie.clone();
+        parse_details(&html, &mut movie_clone);
+        Ok(movie_clone)
+    }
+
+    fn get_url(&self, movie: &Movie) -> Option<String> {
+        self.more_information_urls.get(movie).cloned()
+    }
+
+    fn get_parse_failure(&self) -> Option<ParseFailure> {
+        self.parse_failure.clone()
+    }
+
+    fn set_time_slot(&mut self, time_slot: TimeSlot) {
+        self.time_slot = time_slot;
+    }
+
+    fn set_days_ahead(&mut self, days: u32) {
+        self.days_ahead = days;
+    }
+
+    /// Get the icons of the channels from the logos in the listing.
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+        Ok(self.icons.set_icons(program).await)
+    }
+}
+
+impl TvToday {
+    /// Parse the program from the HTML of the website. Also remembers the URLs of the logos and of the detail pages.
+    fn parse_program(&mut self, html: &str) -> Result<Program, Error> {
+        let document = Html::parse_document(html);
+        self.parse_failure = None;
+
+        // The selectors to get the movie and channel data.
+        let selector_rows =
+            Selector::parse(SELECTOR_ROWS).expect("failed to parse selector for rows");
+        let selector_channel_logo = Selector::parse(SELECTOR_CHANNEL_LOGO)
+            .expect("failed to parse selector for channel logo");
+        let selector_movie_title = Selector::parse(SELECTOR_MOVIE_TITLE)
+            .expect("failed to parse selector for movie title");
+        let selector_movie_genre = Selector::parse(".program-item__genre")
+            .expect("failed to parse selector for movie genre");
+        let selector_movie_division = Selector::parse(".program-item__category")
+            .expect("failed to parse selector for movie division");
+        let selector_movie_year = Selector::parse(".program-item__year")
+            .expect("failed to parse selector for movie year");
+        let selector_movie_start = Selector::parse(".program-item__time")
+            .expect("failed to parse selector for movie start");
+
+        let mut program = Program::new();
+        for row in document.select(&selector_rows) {
+            // The channel is only shown by its logo.
+            let logo = match row.select(&selector_channel_logo).next() {
+                Some(logo) => logo.value(),
+                None => {
+                    self.parse_failure =
+                        Some(ParseFailure::new(PROVIDER_NAME, SELECTOR_CHANNEL_LOGO));
+                    return Err(Error::ParsingWebsite);
+                }
+            };
+            let channel_name = logo.attr("alt").unwrap_or_default().trim();
+
+            // The URL of the logo. Lazy loaded images have it in `data-src`.
+            let logo_url = logo
+                .attr("data-src")
+                .or(logo.attr("src"))
+                .filter(|u| u.starts_with("http"));
+            if let Some(logo_url) = logo_url {
+                self.icons.add_url(channel_name, logo_url);
+            }
+
+            // The title of the movie and the link to its detail page.
+            let title_element = match row.select(&selector_movie_title).next() {
+                Some(element) => element,
+                None => {
+                    self.parse_failure =
+                        Some(ParseFailure::new(PROVIDER_NAME, SELECTOR_MOVIE_TITLE));
+                    return Err(Error::ParsingWebsite);
+                }
+            };
+            let title = title_element.text().collect::<String>().trim().to_string();
+
+            let channel = Channel::new(channel_name);
+            let mut movie_builder = MovieBuilder::new(&title);
+
+            if let Some(genre) = row.select(&selector_movie_genre).next() {
+                movie_builder.with_genre(genre.text().collect::<String>().trim());
+            }
+            if let Some(division) = row.select(&selector_movie_division).next() {
+                movie_builder.with_division(division.text().collect::<String>().trim());
+            }
+            if let Some(year) = row.select(&selector_movie_year).next() {
+                if let Ok(year) = year.text().collect::<String>().trim().parse() {
+                    movie_builder.with_year(year);
+                }
+            }
+
+            // Get the start time of the movie.
+            if let Some(start) = row.select(&selector_movie_start).next() {
+                let start = start.text().collect::<String>();
+                if let Ok(time) = NaiveTime::parse_from_str(start.trim(), "%H:%M") {
+                    movie_builder.with_start(start_from_time(self.days_ahead, time));
+                }
+            }
+
+            let movie = movie_builder.build();
+            if let Some(href) = title_element.value().attr("href") {
+                let url = if href.starts_with("http") {
+                    href.to_string()
+                } else {
+                    format!("{}{}", BASE_URL, href)
+                };
+                self.more_information_urls.insert(movie.clone(), url);
+            }
+
+            program.add(channel, movie);
+        }
+
+        Ok(program)
+    }
+
+    /// The URL of the program in the time slot of the day.
+    fn url(&self) -> String {
+        let page = time_slot_page(self.time_slot);
+        match self.days_ahead {
+            0 => format!("{}{}", URL, page),
+            days => format!(
+                "{}{}&date={}",
+                URL,
+                page,
+                (Local::now().date_naive() + Duration::days(days as i64)).format("%Y-%m-%d")
+            ),
+        }
+    }
+}
+
+/// Set the description, age rating, director, cast and poster from the detail page of the movie. Details missing on
+/// the page are kept.
+fn parse_details(html: &str, movie: &mut Movie) {
+    let document = Html::parse_document(html);
+
+    let selector_description = Selector::parse(".program-detail__description p")
+        .expect("failed to parse selector for movie description");
+    let selector_dt =
+        Selector::parse(".program-detail__facts dt").expect("failed to parse selector for dt");
+    let selector_dd =
+        Selector::parse(".program-detail__facts dd").expect("failed to parse selector for dd");
+    let selector_cast = Selector::parse(".program-detail__cast li")
+        .expect("failed to parse selector for movie cast");
+    let selector_poster = Selector::parse("meta[property=\"og:image\"]")
+        .expect("failed to parse selector for movie poster");
+
+    let description: String = document
+        .select(&selector_description)
+        .map(|e| e.text().collect::<String>().trim().to_string() + "\n\n")
+        .collect();
+    if !description.is_empty() {
+        movie.set_description(Some(description));
+    }
+
+    // The terms and descriptions of the facts.
+    let facts: Vec<(String, String)> = document
+        .select(&selector_dt)
+        .zip(document.select(&selector_dd))
+        .map(|(dt, dd)| {
+            (
+                dt.text().collect::<String>().trim().to_string(),
+                dd.text().collect::<String>().trim().to_string(),
+            )
+        })
+        .collect();
+
+    let age_rating = facts
+        .iter()
+        .find(|(dt, _dd)| dt.starts_with("FSK"))
+        .and_then(|(_dt, dd)| {
+            dd.chars()
+                .filter(|c| c.is_ascii_digit())
+                .collect::<String>()
+                .parse::<u32>()
+                .ok()
+        });
+    if age_rating.is_some() {
+        movie.set_age_rating(age_rating);
+    }
+
+    let director = facts
+        .iter()
+        .find(|(dt, _dd)| dt.starts_with("Regie"))
+        .map(|(_dt, dd)| dd.clone());
+    if director.is_some() {
+        movie.set_director(director);
+    }
+
+    let cast: Vec<String> = document
+        .select(&selector_cast)
+        .map(|e| e.text().collect::<String>().trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect();
+    if !cast.is_empty() {
+        movie.set_cast(cast);
+    }
+
+    let poster_url = document
+        .select(&selector_poster)
+        .next()
+        .and_then(|e| e.value().attr("content"))
+        .map(|u| u.to_string());
+    if poster_url.is_some() {
+        movie.set_poster_url(poster_url);
+    }
+}
+
+/// The page of the program in the time slot.
+fn time_slot_page(time_slot: TimeSlot) -> &'static str {
+    match time_slot {
+        TimeSlot::Now => "?slot=now",
+        TimeSlot::Evening => "?slot=primetime",
+        TimeSlot::Late => "?slot=late",
+    }
+}
+
+/// Get the start time of a movie broadcasted at the given time on the day the given number of days from today.
+fn start_from_time(days_ahead: u32, time: NaiveTime) -> NaiveDateTime {
+    let mut date = Local::now().date_naive() + Duration::days(days_ahead as i64);
+    if time < NaiveTime::from_hms_opt(DAY_START_HOUR, 0, 0).unwrap() {
+        date += Duration::days(1);
+    }
+    date.and_time(time)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A listing with a row of the given content.
+    fn listing(row: &str) -> String {
+        format!(
+            "<html><body><div class=\"program-listing\"><div class=\"program-item\">{}</div></div></body></html>",
+            row
+        )
+    }
+
+    #[test]
+    fn test_parse_program() {
+        let channel = r#"<div class="program-item__channel">
+            <img alt="ZDF" data-src="https://www.tvtoday.de/logos/zdf.png"></div>"#;
+        let title =
+            r#"<div class="program-item__title"><a href="/programm/tatort-1">Tatort</a></div>"#;
+        let details = r#"<span class="program-item__time">20:15</span>
+            <span class="program-item__genre">Krimi</span>
+            <span class="program-item__category">Serie</span>
+            <span class="program-item__year">2021</span>"#;
+
+        let mut provider = TvToday::new();
+        let program = provider
+            .parse_program(&listing(&format!("{}{}{}", channel, title, details)))
+            .expect("Could not parse the listing");
+        let (parsed_channel, movie) = program.iter().next().expect("No broadcast was parsed");
+        assert_eq!(parsed_channel.get_name(), "ZDF");
+        assert_eq!(movie.get_title(), "Tatort");
+        assert_eq!(movie.get_genre(), Some("Krimi".to_string()));
+        assert_eq!(movie.get_division(), Some("Serie".to_string()));
+        assert_eq!(movie.get_year(), Some(2021));
+        assert_eq!(
+            provider.get_url(movie),
+            Some("https://www.tvtoday.de/programm/tatort-1".to_string())
+        );
+
+        assert!(provider.parse_program(&listing(title)).is_err());
+        assert_eq!(
+            provider.get_parse_failure(),
+            Some(ParseFailure::new(PROVIDER_NAME, SELECTOR_CHANNEL_LOGO))
+        );
+    }
+
+    #[test]
+    fn test_parse_details() {
+        let html = r#"<html><head><meta property="og:image" content="https://www.tvtoday.de/poster.jpg"></head>
+            <body><div class="program-detail__description"><p>Ein Fall.</p></div>
+            <dl class="program-detail__facts"><dt>Regie</dt><dd>Max Muster</dd><dt>FSK</dt><dd>ab 12</dd></dl>
+            <ul class="program-detail__cast"><li>Anna</li><li> </li><li>Ben</li></ul></body></html>"#;
+
+        let mut movie = MovieBuilder::new("Tatort");
+        movie.with_age_rating(16);
+        let mut movie = movie.build();
+        parse_details(html, &mut movie);
+        assert_eq!(movie.get_description(), Some("Ein Fall.\n\n".to_string()));
+        assert_eq!(movie.get_director(), Some("Max Muster".to_string()));
+        assert_eq!(movie.get_age_rating(), Some(12));
+        assert_eq!(
+            movie.get_cast(),
+            vec!["Anna".to_string(), "Ben".to_string()]
+        );
+        assert_eq!(
+            movie.get_poster_url(),
+            Some("https://www.tvtoday.de/poster.jpg".to_string())
+        );
+
+        // Details missing on the page are kept.
+        parse_details("<html></html>", &mut movie);
+        assert_eq!(movie.get_age_rating(), Some(12));
+    }
+}
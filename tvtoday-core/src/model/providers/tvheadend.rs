@@ -0,0 +1,300 @@
+use crate::model::metrics::{self, ReloadStep};
+use crate::model::providers::eit;
+use crate::model::providers::icon::Icons;
+use crate::model::providers::slot::{local_time, slot_timestamp};
+use crate::model::{Channel, Movie, MovieBuilder, ParseFailure, Program, Provider, TimeSlot};
+use crate::Error;
+
+use std::time::Instant;
+
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+
+/// The address of the server if none is set, tvheadend running on this computer with its default port.
+const DEFAULT_SERVER: &str = "http://localhost:9981";
+
+/// The name of the provider in reports about parse failures.
+const PROVIDER_NAME: &str = "tvheadend";
+
+/// The part of the answer that could not be read when getting the guide.
+const PART_GRID: &str = "entries";
+
+/// The maximum number of broadcasts requested, one for each channel.
+const GRID_LIMIT: &str = "1000";
+
+/// The broadcasts of the electronic program guide.
+#[derive(Deserialize)]
+struct Grid {
+    entries: Vec<Event>,
+}
+
+/// A broadcast of the electronic program guide.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Event {
+    channel_name: String,
+    /// The path of the logo of the channel on the server, or its URL.
+    channel_icon: Option<String>,
+    title: String,
+    /// The start as a unix timestamp.
+    start: i64,
+    summary: Option<String>,
+    description: Option<String>,
+    /// The content nibbles of DVB.
+    #[serde(default)]
+    genre: Vec<u8>,
+    age_rating: Option<u32>,
+    #[serde(rename = "copyright_year")]
+    copyright_year: Option<u32>,
+    image: Option<String>,
+}
+
+/// The program received by the DVB tuners of a tvheadend server, read from its electronic program guide. The address of
+/// the server is set with `set_server`, the login and password with `set_credentials` if it needs them.
+pub struct Tvheadend {
+    client: Client,
+    /// The address of the server, e.g. `http://localhost:9981`.
+    server: String,
+    /// The login of the user at the server.
+    login: Option<String>,
+    /// The password of the user at the server.
+    password: Option<String>,
+    /// The icons of the channels from the logos on the server.
+    icons: Icons,
+    /// The time slot of the program.
+    time_slot: TimeSlot,
+    /// The day of the program as the number of days from today.
+    days_ahead: u32,
+    /// The part of the answer that could not be read the last time parsing failed.
+    parse_failure: Option<ParseFailure>,
+}
+
+#[async_trait]
+impl Provider for Tvheadend {
+    fn new() -> Self {
+        Tvheadend {
+            client: Client::new(),
+            server: DEFAULT_SERVER.to_string(),
+            login: None,
+            password: None,
+            icons: Icons::new(),
+            time_slot: TimeSlot::Evening,
+            days_ahead: 0,
+            parse_failure: None,
+        }
+    }
+
+    fn clone(&self) -> Self {
+        Tvheadend {
+            client: self.client.clone(),
+            server: self.server.clone(),
+            login: self.login.clone(),
+            password: self.password.clone(),
+            icons: self.icons.clone(),
+            time_slot: self.time_slot,
+            days_ahead: self.days_ahead,
+            parse_failure: self.parse_failure.clone(),
+        }
+    }
+
+    /// Get the broadcasts running at the time slot on each channel, in the order of the channel numbers.
+    async fn get_program(&mut self) -> Result<Program, Error> {
+        let time = slot_timestamp(self.time_slot, self.days_ahead);
+        let filter = json!([
+            {"field": "start", "type": "numeric", "comparison": "lt", "value": time + 1},
+            {"field": "stop", "type": "numeric", "comparison": "gt", "value": time},
+        ])
+        .to_string();
+
+        let waiting = Instant::now();
+        let mut request = self
+            .client
+            .get(format!("{}/api/epg/events/grid", self.server))
+            .query(&[
+                ("limit", GRID_LIMIT),
+                ("sort", "channelNumber"),
+                ("dir", "ASC"),
+                ("filter", &filter[..]),
+            ]);
+        if let Some(login) = &self.login {
+            request = request.basic_auth(login, self.password.as_ref());
+        }
+        let response = request.send().await?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(Error::Login);
+        }
+        let json = response.error_for_status()?.text().await?;
+        metrics::record(ReloadStep::Network, waiting.elapsed());
+
+        metrics::time(ReloadStep::Parse, || self.parse_program(&json))
+    }
+
+    /// The guide already contains all information about the movie.
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+        Ok(movie.clone())
+    }
+
+    /// The server has no pages about single broadcasts.
+    fn get_url(&self, _movie: &Movie) -> Option<String> {
+        None
+    }
+
+    fn get_parse_failure(&self) -> Option<ParseFailure> {
+        self.parse_failure.clone()
+    }
+
+    fn set_credentials(&mut self, login: &str, password: &str) {
+        self.login = Some(login.to_string());
+        self.password = Some(password.to_string());
+    }
+
+    fn set_server(&mut self, url: &str) {
+        self.server = url.trim_end_matches('/').to_string();
+    }
+
+    fn set_time_slot(&mut self, time_slot: TimeSlot) {
+        self.time_slot = time_slot;
+    }
+
+    fn set_days_ahead(&mut self, days: u32) {
+        self.days_ahead = days;
+    }
+
+    /// Get the icons of the channels from the logos on the server.
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+        Ok(self.icons.set_icons(program).await)
+    }
+}
+
+impl Tvheadend {
+    /// Parse the program from the guide. Also remembers the URLs of the logos of the channels.
+    fn parse_program(&mut self, json: &str) -> Result<Program, Error> {
+        self.parse_failure = None;
+        let grid: Grid = serde_json::from_str(json).map_err(|_| {
+            self.parse_failure = Some(ParseFailure::new(PROVIDER_NAME, PART_GRID));
+            Error::ParsingWebsite
+        })?;
+
+        let mut program = Program::new();
+        for event in grid.entries {
+            if let Some(url) = event
+                .channel_icon
+                .as_ref()
+                .and_then(|icon| icon_url(&self.server, icon))
+            {
+                self.icons.add_url(&event.channel_name, &url);
+            }
+            program.add(Channel::new(&event.channel_name), movie_of(&event));
+        }
+
+        Ok(program)
+    }
+}
+
+/// The movie of the broadcast in the guide.
+fn movie_of(event: &Event) -> Movie {
+    let mut movie_builder = MovieBuilder::new(&event.title);
+    movie_builder.with_start(local_time(event.start));
+    if let Some(&content) = event.genre.first() {
+        let (genre, division) = eit::genre_and_division(content);
+        if let Some(genre) = genre {
+            movie_builder.with_genre(genre);
+        }
+        if let Some(division) = division {
+            movie_builder.with_division(division);
+        }
+    }
+    // Unknown years and age ratings are 0.
+    if let Some(year) = event.copyright_year.filter(|year| *year > 0) {
+        movie_builder.with_year(year);
+    }
+    if let Some(age_rating) = event.age_rating.filter(|age| *age > 0) {
+        movie_builder.with_age_rating(age_rating);
+    }
+    if let Some(image) = &event.image {
+        movie_builder.with_poster_url(image);
+    }
+
+    let mut movie = movie_builder.build();
+    let description = event
+        .description
+        .as_ref()
+        .or(event.summary.as_ref())
+        .filter(|d| !d.trim().is_empty());
+    movie.set_description(description.cloned());
+    movie
+}
+
+/// The URL of the logo of a channel, which is either a path in the image cache of the server or a URL. Picons on the
+/// disk of the server can not be loaded.
+fn icon_url(server: &str, icon: &str) -> Option<String> {
+    if icon.starts_with("http://") || icon.starts_with("https://") {
+        Some(icon.to_string())
+    } else if icon.starts_with("imagecache/") {
+        Some(format!("{}/{}", server, icon))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_program() {
+        let json = r#"{"totalCount": 2, "entries": [
+            {"eventId": 1, "channelName": "Das Erste HD", "channelIcon": "imagecache/12", "title": "Tatort",
+             "start": 1000, "stop": 6000, "summary": "Ein Fall.", "genre": [17], "ageRating": 12},
+            {"eventId": 2, "channelName": "arte HD", "channelIcon": "picon://1_0_19.png", "title": "Metropolis",
+             "start": 900, "stop": 8000, "description": "Ein Stummfilm.", "genre": [19], "copyright_year": 1927}
+        ]}"#;
+
+        let mut provider = Tvheadend::new();
+        provider.set_server("http://tv.local:9981/");
+        let program = provider
+            .parse_program(json)
+            .expect("Could not parse the guide");
+
+        let movies: Vec<(String, Movie)> = program
+            .iter()
+            .map(|(channel, movie)| (channel.get_name(), movie.clone()))
+            .collect();
+        assert_eq!(movies.len(), 2);
+        let (channel, tatort) = &movies[0];
+        assert_eq!(channel, "Das Erste HD");
+        assert_eq!(tatort.get_title(), "Tatort");
+        assert_eq!(tatort.get_genre(), Some("Krimi".to_string()));
+        assert_eq!(tatort.get_division(), Some("Spielfilm".to_string()));
+        assert_eq!(tatort.get_age_rating(), Some(12));
+        assert_eq!(tatort.get_description(), Some("Ein Fall.".to_string()));
+        let (_channel, metropolis) = &movies[1];
+        assert_eq!(metropolis.get_year(), Some(1927));
+        assert_eq!(
+            metropolis.get_description(),
+            Some("Ein Stummfilm.".to_string())
+        );
+
+        assert!(provider.parse_program("<html></html>").is_err());
+        assert_eq!(
+            provider.get_parse_failure(),
+            Some(ParseFailure::new(PROVIDER_NAME, PART_GRID))
+        );
+    }
+
+    #[test]
+    fn test_icon_url() {
+        let server = "http://localhost:9981";
+        assert_eq!(
+            icon_url(server, "imagecache/12"),
+            Some("http://localhost:9981/imagecache/12".to_string())
+        );
+        assert_eq!(
+            icon_url(server, "https://example.org/ard.png"),
+            Some("https://example.org/ard.png".to_string())
+        );
+        assert_eq!(icon_url(server, "picon://1_0_19.png"), None);
+    }
+}
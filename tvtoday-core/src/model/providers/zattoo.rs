@@ -0,0 +1,564 @@
+use crate::model::metrics::{self, ReloadStep};
+use crate::model::providers::icon::Icons;
+use crate::model::providers::slot::{local_time, slot_timestamp};
+use crate::model::{
+    paths, Channel, Movie, MovieBuilder, ParseFailure, Program, Provider, TimeSlot,
+};
+use crate::Error;
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// The website of Zattoo, which also serves its API.
+const BASE_URL: &str = "https://zattoo.com";
+
+/// The host of the channel logos, the line-up only contains their paths.
+const LOGO_URL: &str = "https://images.zattic.com";
+
+/// The name of the provider in reports about parse failures.
+const PROVIDER_NAME: &str = "zattoo.com";
+
+/// The version of the web app sent when opening a session.
+const APP_VERSION: &str = "3.2120.1";
+
+/// How many seconds of the guide are requested, starting at the time slot.
+const GUIDE_DURATION: i64 = 60 * 60;
+
+/// The part of the answer that could not be read when logging in.
+const PART_SESSION: &str = "session.power_guide_hash";
+
+/// The part of the answer that could not be read when getting the channel line-up.
+const PART_CHANNELS: &str = "channel_groups";
+
+/// The part of the answer that could not be read when getting the guide.
+const PART_GUIDE: &str = "channels.programs";
+
+/// The categories of Zattoo mapped to the divisions of TV Spielfilm, so filters work with both providers.
+const DIVISIONS: &[(&str, &str)] = &[
+    ("Spielfilm", "Spielfilm"),
+    ("Film", "Spielfilm"),
+    ("Serie", "Serie"),
+    ("Dokumentation", "Report"),
+    ("Reportage", "Report"),
+    ("Magazin", "Report"),
+    ("Nachrichten", "Report"),
+    ("Show", "Show"),
+    ("Unterhaltung", "Show"),
+    ("Sport", "Sport"),
+];
+
+/// The token needed to open a session.
+#[derive(Deserialize)]
+struct Token {
+    session_token: String,
+}
+
+/// The answer to logging in.
+#[derive(Deserialize)]
+struct Login {
+    session: Session,
+}
+
+/// The session of the account.
+#[derive(Deserialize)]
+struct Session {
+    /// Identifies the channel line-up of the account in the guide.
+    power_guide_hash: String,
+}
+
+/// The channel line-up of the account.
+#[derive(Deserialize)]
+struct LineUp {
+    channel_groups: Vec<LineUpGroup>,
+}
+
+/// A group of channels in the line-up, e.g. the public channels.
+#[derive(Deserialize)]
+struct LineUpGroup {
+    channels: Vec<LineUpChannel>,
+}
+
+/// A channel in the line-up.
+#[derive(Deserialize)]
+struct LineUpChannel {
+    cid: String,
+    title: String,
+    #[serde(default)]
+    qualities: Vec<Quality>,
+}
+
+/// A quality the channel is streamed in, with its logo.
+#[derive(Deserialize)]
+struct Quality {
+    logo_black_84: Option<String>,
+}
+
+/// The guide of the channels in the line-up.
+#[derive(Deserialize)]
+struct Guide {
+    channels: Vec<GuideChannel>,
+}
+
+/// The broadcasts of a channel in the guide.
+#[derive(Deserialize)]
+struct GuideChannel {
+    cid: String,
+    #[serde(default)]
+    programs: Vec<Broadcast>,
+}
+
+/// A broadcast in the guide.
+#[derive(Deserialize)]
+struct Broadcast {
+    id: u64,
+    /// The title.
+    t: String,
+    /// The start as a unix timestamp.
+    s: i64,
+    /// The end as a unix timestamp.
+    e: i64,
+    /// The genres.
+    #[serde(default)]
+    g: Vec<String>,
+    /// The categories.
+    #[serde(default)]
+    c: Vec<String>,
+    /// The URL of a image.
+    i_url: Option<String>,
+}
+
+/// The details of broadcasts.
+#[derive(Deserialize)]
+struct Details {
+    programs: Vec<Detail>,
+}
+
+/// The details of a broadcast.
+#[derive(Deserialize)]
+struct Detail {
+    /// The description.
+    d: Option<String>,
+    year: Option<u32>,
+    /// The credits.
+    cr: Option<Credits>,
+}
+
+/// The people involved in a broadcast.
+#[derive(Deserialize)]
+struct Credits {
+    #[serde(default)]
+    director: Vec<String>,
+    #[serde(default)]
+    actor: Vec<String>,
+}
+
+/// The program of the channels subscribed at Zattoo, read from its guide. An account is needed, its login and password
+/// are set with `set_credentials`.
+pub struct Zattoo {
+    /// The client keeping the cookie of the session.
+    client: Client,
+    /// The login of the account.
+    login: Option<String>,
+    /// The password of the account.
+    password: Option<String>,
+    /// Identifies the channel line-up of the account, known after logging in.
+    power_guide_hash: Option<String>,
+    /// The ids and names of the channels in the order of the line-up.
+    channels: Vec<(String, String)>,
+    /// Maps each movie to the id of its channel and its own id, to get more information.
+    program_ids: HashMap<Movie, (String, u64)>,
+    /// The icons of the channels from the logos in the line-up.
+    icons: Icons,
+    /// The time slot of the program.
+    time_slot: TimeSlot,
+    /// The day of the program as the number of days from today.
+    days_ahead: u32,
+    /// The part of the answer that could not be read the last time parsing failed.
+    parse_failure: Option<ParseFailure>,
+}
+
+#[async_trait]
+impl Provider for Zattoo {
+    fn new() -> Self {
+        Zattoo {
+            client: Client::builder()
+                .cookie_store(true)
+                .build()
+                .expect("failed to create the client"),
+            login: None,
+            password: None,
+            power_guide_hash: None,
+            channels: vec![],
+            program_ids: HashMap::new(),
+            icons: Icons::new(),
+            time_slot: TimeSlot::Evening,
+            days_ahead: 0,
+            parse_failure: None,
+        }
+    }
+
+    fn clone(&self) -> Self {
+        Zattoo {
+            client: self.client.clone(),
+            login: self.login.clone(),
+            password: self.password.clone(),
+            power_guide_hash: self.power_guide_hash.clone(),
+            channels: self.channels.clone(),
+            program_ids: self.program_ids.clone(),
+            icons: self.icons.clone(),
+            time_slot: self.time_slot,
+            days_ahead: self.days_ahead,
+            parse_failure: self.parse_failure.clone(),
+        }
+    }
+
+    /// Get the program, logging in first if there is no session yet or it expired.
+    async fn get_program(&mut self) -> Result<Program, Error> {
+        self.parse_failure = None;
+        if self.power_guide_hash.is_none() {
+            self.log_in().await?;
+        }
+
+        let time = slot_timestamp(self.time_slot, self.days_ahead);
+        let json = match self.get_guide(time).await {
+            Err(Error::Login) => {
+                self.log_in().await?;
+                self.get_guide(time).await?
+            }
+            result => result?,
+        };
+
+        metrics::time(ReloadStep::Parse, || self.parse_program(&json, time))
+    }
+
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+        let (power_guide_hash, (_cid, id)) =
+            match (&self.power_guide_hash, self.program_ids.get(movie)) {
+                (Some(power_guide_hash), Some(ids)) => (power_guide_hash, ids),
+                _ => return Ok(movie.clone()),
+            };
+        let url = format!(
+            "{}/zapi/v2/cached/program/power_details/{}?program_ids={}",
+            BASE_URL, power_guide_hash, id
+        );
+        let json = self.client.get(&url).send().await?.text().await?;
+
+        let mut movie_clone = movie.clone();
+        parse_details(&json, &mut movie_clone);
+        Ok(movie_clone)
+    }
+
+    fn get_url(&self, movie: &Movie) -> Option<String> {
+        self.program_ids
+            .get(movie)
+            .map(|(cid, id)| format!("{}/program/{}/{}", BASE_URL, cid, id))
+    }
+
+    fn get_parse_failure(&self) -> Option<ParseFailure> {
+        self.parse_failure.clone()
+    }
+
+    /// Set the login and password of the account. The next program is loaded in a new session.
+    fn set_credentials(&mut self, login: &str, password: &str) {
+        self.login = Some(login.to_string());
+        self.password = Some(password.to_string());
+        self.power_guide_hash = None;
+    }
+
+    fn set_time_slot(&mut self, time_slot: TimeSlot) {
+        self.time_slot = time_slot;
+    }
+
+    fn set_days_ahead(&mut self, days: u32) {
+        self.days_ahead = days;
+    }
+
+    /// Get the icons of the channels from the logos in the line-up.
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+        Ok(self.icons.set_icons(program).await)
+    }
+}
+
+impl Zattoo {
+    /// Open a session and log in to the account, then get the channel line-up of the account.
+    async fn log_in(&mut self) -> Result<(), Error> {
+        let (login, password) = match (&self.login, &self.password) {
+            (Some(login), Some(password)) => (login.clone(), password.clone()),
+            _ => return Err(Error::Login),
+        };
+
+        let waiting = Instant::now();
+        let json = self
+            .client
+            .get(format!("{}/token.json", BASE_URL))
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let token: Token = self.parse(&json, PART_SESSION)?;
+
+        // Opening the session sets its cookie.
+        self.client
+            .post(format!("{}/zapi/v3/session/hello", BASE_URL))
+            .form(&[
+                ("uuid", &paths::device_id()[..]),
+                ("lang", "de"),
+                ("app_version", APP_VERSION),
+                ("format", "json"),
+                ("client_app_token", &token.session_token[..]),
+            ])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let response = self
+            .client
+            .post(format!("{}/zapi/v2/account/login", BASE_URL))
+            .form(&[
+                ("login", &login[..]),
+                ("password", &password[..]),
+                ("remember", "true"),
+            ])
+            .send()
+            .await?;
+        if response.status().is_client_error() {
+            return Err(Error::Login);
+        }
+        let json = response.error_for_status()?.text().await?;
+        let session: Login = self.parse(&json, PART_SESSION)?;
+
+        let json = self
+            .client
+            .get(format!(
+                "{}/zapi/v2/cached/channels/{}?details=False",
+                BASE_URL, session.session.power_guide_hash
+            ))
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        metrics::record(ReloadStep::Network, waiting.elapsed());
+
+        self.parse_line_up(&json)?;
+        self.power_guide_hash = Some(session.session.power_guide_hash);
+        Ok(())
+    }
+
+    /// Get the guide starting at the unix timestamp. Fails with `Error::Login` if the session expired.
+    async fn get_guide(&self, time: i64) -> Result<String, Error> {
+        let power_guide_hash = self.power_guide_hash.as_ref().ok_or(Error::Login)?;
+
+        let waiting = Instant::now();
+        let response = self
+            .client
+            .get(format!(
+                "{}/zapi/v2/cached/program/power_guide/{}?start={}&end={}",
+                BASE_URL,
+                power_guide_hash,
+                time,
+                time + GUIDE_DURATION
+            ))
+            .send()
+            .await?;
+        if response.status() == StatusCode::UNAUTHORIZED
+            || response.status() == StatusCode::FORBIDDEN
+        {
+            return Err(Error::Login);
+        }
+        let json = response.error_for_status()?.text().await?;
+        metrics::record(ReloadStep::Network, waiting.elapsed());
+
+        Ok(json)
+    }
+
+    /// Parse the channel line-up of the account. Also remembers the URLs of the logos.
+    fn parse_line_up(&mut self, json: &str) -> Result<(), Error> {
+        let line_up: LineUp = self.parse(json, PART_CHANNELS)?;
+
+        self.channels.clear();
+        for channel in line_up
+            .channel_groups
+            .into_iter()
+            .flat_map(|group| group.channels)
+        {
+            let logo = channel
+                .qualities
+                .iter()
+                .find_map(|quality| quality.logo_black_84.as_ref());
+            if let Some(logo) = logo {
+                self.icons
+                    .add_url(&channel.title, &format!("{}{}", LOGO_URL, logo));
+            }
+            self.channels.push((channel.cid, channel.title));
+        }
+
+        Ok(())
+    }
+
+    /// Parse the program from the guide, the broadcasts running at the unix timestamp on the channels of the line-up.
+    /// Also remembers the ids of the movies.
+    fn parse_program(&mut self, json: &str, time: i64) -> Result<Program, Error> {
+        let guide: Guide = self.parse(json, PART_GUIDE)?;
+
+        let mut program = Program::new();
+        for (cid, name) in &self.channels {
+            let broadcast = guide
+                .channels
+                .iter()
+                .filter(|channel| &channel.cid == cid)
+                .flat_map(|channel| channel.programs.iter())
+                .find(|broadcast| broadcast.s <= time && time < broadcast.e);
+
+            if let Some(broadcast) = broadcast {
+                let movie = movie_of(broadcast);
+                self.program_ids
+                    .insert(movie.clone(), (cid.clone(), broadcast.id));
+                program.add(Channel::new(name), movie);
+            }
+        }
+
+        Ok(program)
+    }
+
+    /// Parse a answer of the API, remembering the part that could not be read.
+    fn parse<T: DeserializeOwned>(&mut self, json: &str, part: &str) -> Result<T, Error> {
+        serde_json::from_str(json).map_err(|_| {
+            self.parse_failure = Some(ParseFailure::new(PROVIDER_NAME, part));
+            Error::ParsingWebsite
+        })
+    }
+}
+
+/// The movie of the broadcast in the guide.
+fn movie_of(broadcast: &Broadcast) -> Movie {
+    let mut movie_builder = MovieBuilder::new(&broadcast.t);
+    movie_builder.with_start(local_time(broadcast.s));
+    if let Some(genre) = broadcast.g.first() {
+        movie_builder.with_genre(genre);
+    }
+    let division = broadcast.c.iter().find_map(|category| {
+        DIVISIONS
+            .iter()
+            .find(|(zattoo, _tv_spielfilm)| zattoo.eq_ignore_ascii_case(category))
+            .map(|(_zattoo, tv_spielfilm)| tv_spielfilm)
+    });
+    if let Some(division) = division {
+        movie_builder.with_division(division);
+    }
+    if let Some(url) = &broadcast.i_url {
+        movie_builder.with_poster_url(url);
+    }
+    movie_builder.build()
+}
+
+/// Parse the details of the movie, keeping the information of the guide that is missing in them.
+fn parse_details(json: &str, movie: &mut Movie) {
+    let detail = match serde_json::from_str::<Details>(json) {
+        Ok(details) => match details.programs.into_iter().next() {
+            Some(detail) => detail,
+            None => return,
+        },
+        Err(_) => return,
+    };
+
+    if let Some(description) = detail.d.filter(|d| !d.trim().is_empty()) {
+        movie.set_description(Some(description));
+    }
+    if let Some(year) = detail.year {
+        movie.set_year(Some(year));
+    }
+    if let Some(credits) = detail.cr {
+        if let Some(director) = credits.director.into_iter().next() {
+            movie.set_director(Some(director));
+        }
+        if !credits.actor.is_empty() {
+            movie.set_cast(credits.actor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LINE_UP: &str = r#"{"channel_groups": [
+        {"channels": [{"cid": "zdf", "title": "ZDF", "qualities": [{"logo_black_84": "/logos/zdf.png"}]}]},
+        {"channels": [{"cid": "arte", "title": "ARTE"}, {"cid": "3sat", "title": "3sat"}]}
+    ]}"#;
+
+    #[test]
+    fn test_parse_program() {
+        let guide = r#"{"channels": [
+            {"cid": "3sat", "programs": [{"id": 3, "t": "Kulturzeit", "s": 900, "e": 1000}]},
+            {"cid": "arte", "programs": [
+                {"id": 1, "t": "Arte Journal", "s": 0, "e": 1000},
+                {"id": 2, "t": "Der Himmel über Berlin", "s": 1000, "e": 8000, "g": ["Drama"], "c": ["Spielfilm"]}
+            ]},
+            {"cid": "zdf", "programs": [{"id": 4, "t": "Tatort", "s": 500, "e": 6000, "c": ["Serie"]}]},
+            {"cid": "rtl", "programs": [{"id": 5, "t": "Nicht abonniert", "s": 0, "e": 6000}]}
+        ]}"#;
+
+        let mut provider = Zattoo::new();
+        provider
+            .parse_line_up(LINE_UP)
+            .expect("Could not parse the line-up");
+        let program = provider
+            .parse_program(guide, 1000)
+            .expect("Could not parse the guide");
+
+        let broadcasts: Vec<(String, String)> = program
+            .iter()
+            .map(|(channel, movie)| (channel.get_name(), movie.get_title()))
+            .collect();
+        assert_eq!(
+            broadcasts,
+            vec![
+                ("ZDF".to_string(), "Tatort".to_string()),
+                ("ARTE".to_string(), "Der Himmel über Berlin".to_string()),
+            ]
+        );
+
+        let (_channel, movie) = program.iter().nth(1).unwrap();
+        assert_eq!(movie.get_genre(), Some("Drama".to_string()));
+        assert_eq!(movie.get_division(), Some("Spielfilm".to_string()));
+        assert_eq!(
+            provider.get_url(movie),
+            Some("https://zattoo.com/program/arte/2".to_string())
+        );
+
+        assert!(provider.parse_program("{}", 1000).is_err());
+        assert_eq!(
+            provider.get_parse_failure(),
+            Some(ParseFailure::new(PROVIDER_NAME, PART_GUIDE))
+        );
+    }
+
+    #[test]
+    fn test_parse_details() {
+        let json = r#"{"programs": [{"d": "Ein Fall.", "year": 2021, "cr": {"director": ["Max Muster"], "actor": ["Anna", "Ben"]}}]}"#;
+
+        let mut movie = MovieBuilder::new("Tatort");
+        movie.with_year(2020);
+        let mut movie = movie.build();
+        parse_details(json, &mut movie);
+        assert_eq!(movie.get_description(), Some("Ein Fall.".to_string()));
+        assert_eq!(movie.get_year(), Some(2021));
+        assert_eq!(movie.get_director(), Some("Max Muster".to_string()));
+        assert_eq!(
+            movie.get_cast(),
+            vec!["Anna".to_string(), "Ben".to_string()]
+        );
+
+        // Details missing in the answer are kept.
+        parse_details(r#"{"programs": [{}]}"#, &mut movie);
+        parse_details("not json", &mut movie);
+        assert_eq!(movie.get_year(), Some(2021));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::model::Movie;
+use crate::Error;
+
+use serde_json::Value;
+
+/// The URL of the OMDb API.
+const OMDB_URL: &str = "https://www.omdbapi.com/";
+
+/// The scores of a movie on external rating sites.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Ratings {
+    imdb: Option<String>,
+    rotten_tomatoes: Option<String>,
+    metacritic: Option<String>,
+}
+
+impl Ratings {
+    /// Get the optional IMDb score, e.g. "7.8/10".
+    pub fn get_imdb(&self) -> Option<String> {
+        self.imdb.clone()
+    }
+
+    /// Get the optional Rotten Tomatoes score, e.g. "87%".
+    pub fn get_rotten_tomatoes(&self) -> Option<String> {
+        self.rotten_tomatoes.clone()
+    }
+
+    /// Get the optional Metacritic score, e.g. "64/100".
+    pub fn get_metacritic(&self) -> Option<String> {
+        self.metacritic.clone()
+    }
+
+    /// Whether no score is known.
+    pub fn is_empty(&self) -> bool {
+        self.imdb.is_none() && self.rotten_tomatoes.is_none() && self.metacritic.is_none()
+    }
+
+    /// Parse the ratings from the response of the OMDb API. A response for a unknown movie has no scores.
+    pub fn from_omdb(json: &str) -> Result<Self, Error> {
+        let value: Value = serde_json::from_str(json).map_err(|_| Error::ParsingWebsite)?;
+
+        let mut ratings = Ratings::default();
+
+        if let Some(scores) = value["Ratings"].as_array() {
+            for score in scores {
+                let score_value = score["Value"].as_str().map(|s| s.to_string());
+                match score["Source"].as_str() {
+                    Some("Internet Movie Database") => ratings.imdb = score_value,
+                    Some("Rotten Tomatoes") => ratings.rotten_tomatoes = score_value,
+                    Some("Metacritic") => ratings.metacritic = score_value,
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(ratings)
+    }
+}
+
+/// Look up the ratings of the movie by its title and year using the OMDb API with the given API key.
+pub async fn get_ratings(api_key: &str, movie: &Movie) -> Result<Ratings, Error> {
+    let mut query = vec![
+        ("apikey", api_key.to_string()),
+        ("t", movie.get_title()),
+        ("type", "movie".to_string()),
+    ];
+    if let Some(year) = movie.get_year() {
+        query.push(("y", year.to_string()));
+    }
+
+    let json = reqwest::Client::new()
+        .get(OMDB_URL)
+        .query(&query)
+        .send()
+        .await?
+        .text()
+        .await?;
+
+    Ratings::from_omdb(&json)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_omdb() {
+        let json = r#"{
+            "Title": "Hello",
+            "Ratings": [
+                {"Source": "Internet Movie Database", "Value": "7.8/10"},
+                {"Source": "Rotten Tomatoes", "Value": "87%"}
+            ],
+            "Response": "True"
+        }"#;
+
+        let ratings = Ratings::from_omdb(json).unwrap();
+        assert_eq!(ratings.get_imdb(), Some("7.8/10".to_string()));
+        assert_eq!(ratings.get_rotten_tomatoes(), Some("87%".to_string()));
+        assert_eq!(ratings.get_metacritic(), None);
+
+        let not_found = r#"{"Response": "False", "Error": "Movie not found!"}"#;
+        assert!(Ratings::from_omdb(not_found).unwrap().is_empty());
+
+        assert!(Ratings::from_omdb("not json").is_err());
+    }
+}
@@ -0,0 +1,321 @@
+use crate::model::{Channel, Movie, Program};
+
+use std::convert::TryFrom;
+
+use chrono::{Duration, NaiveDateTime};
+
+/// The format the start time is stored in.
+const START_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// A reminder to notify the user some minutes before a movie starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reminder {
+    channel: String,
+    title: String,
+    start: NaiveDateTime,
+    minutes_before: u32,
+}
+
+/// A change of a reminded broadcast found in a newly loaded program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReminderChange {
+    /// The broadcast was moved. Contains the updated reminder and the previous start time.
+    Moved(Reminder, NaiveDateTime),
+    /// The broadcast is no longer in the program.
+    Cancelled(Reminder),
+}
+
+/// All reminders of the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reminders {
+    reminders: Vec<Reminder>,
+}
+
+impl Reminder {
+    /// Create a reminder for the movie on the channel. Returns `None` if the movie has no start time.
+    pub fn new(channel: &Channel, movie: &Movie, minutes_before: u32) -> Option<Self> {
+        movie.get_start().map(|start| Reminder {
+            channel: channel.get_name(),
+            title: movie.get_title(),
+            start,
+            minutes_before,
+        })
+    }
+
+    /// Get the name of the channel.
+    pub fn get_channel(&self) -> String {
+        self.channel.clone()
+    }
+
+    /// Get the title of the movie.
+    pub fn get_title(&self) -> String {
+        self.title.clone()
+    }
+
+    /// Get the start time of the movie.
+    pub fn get_start(&self) -> NaiveDateTime {
+        self.start
+    }
+
+    /// Get how many minutes before the start the user will be notified.
+    pub fn get_minutes_before(&self) -> u32 {
+        self.minutes_before
+    }
+
+    /// Get the time the user should be notified.
+    pub fn get_due(&self) -> NaiveDateTime {
+        self.start - Duration::minutes(self.minutes_before as i64)
+    }
+
+    /// Weather the reminder is for the given movie on the given channel.
+    pub fn matches(&self, channel: &Channel, movie: &Movie) -> bool {
+        self.channel == channel.get_name()
+            && self.title == movie.get_title()
+            && Some(self.start) == movie.get_start()
+    }
+}
+
+impl From<Reminder> for [String; 4] {
+    /// Convert from a `Reminder` to `[String; 4]`.
+    fn from(item: Reminder) -> [String; 4] {
+        [
+            item.channel,
+            item.title,
+            item.start.format(START_FORMAT).to_string(),
+            item.minutes_before.to_string(),
+        ]
+    }
+}
+
+impl TryFrom<[String; 4]> for Reminder {
+    type Error = ();
+
+    /// Try to convert from `[String; 4]` to a `Reminder`.
+    fn try_from(item: [String; 4]) -> Result<Reminder, ()> {
+        let start = NaiveDateTime::parse_from_str(&item[2], START_FORMAT).map_err(|_| ())?;
+        let minutes_before = item[3].parse().map_err(|_| ())?;
+
+        Ok(Reminder {
+            channel: item[0].clone(),
+            title: item[1].clone(),
+            start,
+            minutes_before,
+        })
+    }
+}
+
+impl Reminders {
+    /// Create a new, empty list of reminders.
+    pub fn new() -> Self {
+        Reminders { reminders: vec![] }
+    }
+
+    /// Add a reminder. A existing reminder for the same movie is replaced.
+    pub fn add(&mut self, reminder: Reminder) {
+        self.reminders.retain(|r| {
+            r.channel != reminder.channel || r.title != reminder.title || r.start != reminder.start
+        });
+        self.reminders.push(reminder);
+    }
+
+    /// Remove the reminder for the given movie on the given channel.
+    pub fn remove(&mut self, channel: &Channel, movie: &Movie) {
+        self.reminders.retain(|r| !r.matches(channel, movie));
+    }
+
+    /// Get the reminder for the given movie on the given channel.
+    pub fn get(&self, channel: &Channel, movie: &Movie) -> Option<&Reminder> {
+        self.reminders.iter().find(|r| r.matches(channel, movie))
+    }
+
+    /// Turn the reminders into a iterator.
+    pub fn iter(&self) -> std::slice::Iter<'_, Reminder> {
+        self.reminders.iter()
+    }
+
+    /// Get the earliest time one of the reminders is due.
+    pub fn next_due(&self) -> Option<NaiveDateTime> {
+        self.reminders.iter().map(|r| r.get_due()).min()
+    }
+
+    /// Remove all reminders that are due at the given time. The ones for movies that did not start yet are returned.
+    pub fn take_due(&mut self, now: NaiveDateTime) -> Vec<Reminder> {
+        let (due, remaining): (Vec<Reminder>, Vec<Reminder>) =
+            self.reminders.drain(..).partition(|r| r.get_due() <= now);
+        self.reminders = remaining;
+
+        due.into_iter().filter(|r| r.start > now).collect()
+    }
+
+    /// Update the reminders to the given program. Broadcasts are identified by channel and title.
+    /// Reminders of moved broadcasts get the new start time, the ones of cancelled broadcasts are removed.
+    /// Only reminders in the time span of the program on their channel are checked, as the others cannot be known.
+    pub fn update_from(&mut self, program: &Program) -> Vec<ReminderChange> {
+        let mut changes = vec![];
+
+        for reminder in self.reminders.iter_mut() {
+            let entries = program.channel(&reminder.channel);
+            let starts: Vec<NaiveDateTime> =
+                entries.iter().filter_map(|(_c, m)| m.get_start()).collect();
+
+            let in_time_span = starts.iter().any(|s| s <= &reminder.start)
+                && starts.iter().any(|s| s >= &reminder.start);
+            if !in_time_span {
+                continue;
+            }
+
+            let new_start = entries
+                .iter()
+                .filter(|(_c, m)| m.get_title() == reminder.title)
+                .filter_map(|(_c, m)| m.get_start())
+                .min_by_key(|s| (*s - reminder.start).num_seconds().abs());
+
+            match new_start {
+                Some(start) if start == reminder.start => {}
+                Some(start) => {
+                    let old_start = reminder.start;
+                    reminder.start = start;
+                    changes.push(ReminderChange::Moved(reminder.clone(), old_start));
+                }
+                None => changes.push(ReminderChange::Cancelled(reminder.clone())),
+            }
+        }
+
+        self.reminders
+            .retain(|r| !changes.contains(&ReminderChange::Cancelled(r.clone())));
+
+        changes
+    }
+}
+
+impl Default for Reminders {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::MovieBuilder;
+
+    use chrono::NaiveDate;
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2021, 4, 20)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn movie(title: &str, start: NaiveDateTime) -> Movie {
+        let mut builder = MovieBuilder::new(title);
+        builder.with_start(start);
+        builder.build()
+    }
+
+    #[test]
+    fn test_take_due() {
+        let channel = Channel::new("ZDF");
+        let mut reminders = Reminders::new();
+        reminders.add(Reminder::new(&channel, &movie("Early", at(20, 15)), 10).unwrap());
+        reminders.add(Reminder::new(&channel, &movie("Late", at(22, 0)), 10).unwrap());
+        reminders.add(Reminder::new(&channel, &movie("Missed", at(19, 0)), 10).unwrap());
+
+        let due = reminders.take_due(at(20, 10));
+
+        assert_eq!(
+            due.iter().map(|r| r.get_title()).collect::<Vec<String>>(),
+            vec!["Early"]
+        );
+        assert_eq!(
+            reminders
+                .iter()
+                .map(|r| r.get_title())
+                .collect::<Vec<String>>(),
+            vec!["Late"]
+        );
+    }
+
+    #[test]
+    fn test_next_due() {
+        let channel = Channel::new("ZDF");
+        let mut reminders = Reminders::new();
+        assert_eq!(reminders.next_due(), None);
+
+        reminders.add(Reminder::new(&channel, &movie("Late", at(22, 0)), 10).unwrap());
+        reminders.add(Reminder::new(&channel, &movie("Early", at(20, 15)), 5).unwrap());
+
+        assert_eq!(reminders.next_due(), Some(at(20, 10)));
+    }
+
+    #[test]
+    fn test_update_from() {
+        let channel = Channel::new("ZDF");
+        let mut reminders = Reminders::new();
+        reminders.add(Reminder::new(&channel, &movie("Same", at(20, 15)), 10).unwrap());
+        reminders.add(Reminder::new(&channel, &movie("Moved", at(21, 0)), 10).unwrap());
+        reminders.add(Reminder::new(&channel, &movie("Cancelled", at(22, 0)), 10).unwrap());
+        reminders.add(Reminder::new(&channel, &movie("Tomorrow", at(23, 30)), 10).unwrap());
+        reminders.add(Reminder::new(&Channel::new("ARD"), &movie("Other", at(21, 0)), 10).unwrap());
+
+        let program: Program = vec![
+            (channel.clone(), movie("Same", at(20, 15))),
+            (channel.clone(), movie("Moved", at(21, 30))),
+            (channel.clone(), movie("Late", at(23, 0))),
+        ]
+        .into_iter()
+        .collect();
+
+        let changes = reminders.update_from(&program);
+
+        assert_eq!(
+            changes,
+            vec![
+                ReminderChange::Moved(
+                    Reminder::new(&channel, &movie("Moved", at(21, 30)), 10).unwrap(),
+                    at(21, 0)
+                ),
+                ReminderChange::Cancelled(
+                    Reminder::new(&channel, &movie("Cancelled", at(22, 0)), 10).unwrap()
+                ),
+            ]
+        );
+        assert_eq!(
+            reminders
+                .iter()
+                .map(|r| r.get_title())
+                .collect::<Vec<String>>(),
+            vec!["Same", "Moved", "Tomorrow", "Other"]
+        );
+    }
+
+    #[test]
+    fn test_str_array() {
+        let reminder = Reminder::new(&Channel::new("ZDF"), &movie("Hello", at(20, 15)), 5).unwrap();
+
+        assert_eq!(
+            <[String; 4]>::from(reminder.clone()),
+            [
+                "ZDF".to_string(),
+                "Hello".to_string(),
+                "2021-04-20 20:15".to_string(),
+                "5".to_string()
+            ]
+        );
+        assert_eq!(
+            Reminder::try_from(<[String; 4]>::from(reminder.clone())),
+            Ok(reminder)
+        );
+        assert_eq!(
+            Reminder::try_from([
+                "ZDF".to_string(),
+                "Hello".to_string(),
+                "tonight".to_string(),
+                "5".to_string()
+            ]),
+            Err(())
+        );
+    }
+}
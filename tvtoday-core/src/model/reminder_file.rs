@@ -0,0 +1,52 @@
+use crate::model::{encryption, Reminder, Reminders};
+use crate::Error;
+
+use std::convert::TryFrom;
+use std::path::Path;
+
+use csv::{ReaderBuilder, Writer};
+
+impl Reminders {
+    /// Write the reminders to a file at the given path.
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let mut writer = Writer::from_writer(vec![]);
+
+        for reminder in self.iter() {
+            writer.write_record(<[String; 4]>::from(reminder.clone()))?;
+        }
+
+        let content = writer.into_inner().map_err(|_| Error::ParsingFile)?;
+
+        encryption::write_file(path, &content)
+    }
+
+    /// Read the reminders from a file at the given path.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = encryption::read_file(path)?;
+
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(&content[..]);
+
+        let mut reminders = Reminders::new();
+
+        for record_res in reader.records() {
+            let record = record_res?;
+
+            if record.len() != 4 {
+                return Err(Error::ParsingFile);
+            }
+
+            let record_arr = [
+                record.get(0).unwrap().to_string(),
+                record.get(1).unwrap().to_string(),
+                record.get(2).unwrap().to_string(),
+                record.get(3).unwrap().to_string(),
+            ];
+
+            reminders.add(Reminder::try_from(record_arr).map_err(|_| Error::ParsingFile)?);
+        }
+
+        Ok(reminders)
+    }
+}
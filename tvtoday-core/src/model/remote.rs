@@ -0,0 +1,469 @@
+//! A remote control for a phone in the same network: a web server shows the program as listed in the application and
+//! sends the commands to open the details of a movie or to pin it back to the application. The URL of the server
+//! contains a random token, so only devices that scanned its QR code can use it.
+use crate::model::{Movie, Program};
+use crate::Error;
+
+use std::convert::Infallible;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, UdpSocket};
+use std::sync::{Arc, Mutex};
+
+use hyper::header::{CONTENT_TYPE, LOCATION};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use image::{Rgba, RgbaImage};
+use qrcode::QrCode;
+use ring::constant_time;
+use ring::rand::{SecureRandom, SystemRandom};
+use url::form_urlencoded;
+
+/// The number of random bytes of the token in the URL.
+const TOKEN_LEN: usize = 16;
+
+/// The minimal width and height of the QR code in pixels, so it can be scanned from the screen.
+const QR_CODE_SIZE: u32 = 240;
+
+/// A address outside of the local network, used to find the address of the computer in the local network. Nothing is
+/// sent to it.
+const OUTSIDE_ADDRESS: Ipv4Addr = Ipv4Addr::new(192, 0, 2, 1);
+
+/// A command sent from the remote control to the application.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RemoteCommand {
+    /// Open the details of the movie.
+    Open(Movie),
+    /// Pin the movie to the top of the list, or unpin it if it is pinned.
+    TogglePin(Movie),
+}
+
+/// The program shown by the remote control in the order of the list, with the pinned movies. Cloning the view shares
+/// it, so the application can update it while the server shows it.
+#[derive(Clone, Default)]
+pub struct RemoteView {
+    shown: Arc<Mutex<(Program, Vec<Movie>)>>,
+}
+
+impl RemoteView {
+    /// Create a new, empty view.
+    pub fn new() -> Self {
+        RemoteView::default()
+    }
+
+    /// Show the program, e.g. after the list changed.
+    pub fn set(&self, program: Program, pinned: Vec<Movie>) {
+        *self.shown.lock().unwrap() = (program, pinned);
+    }
+
+    /// Get a copy of the shown program and the pinned movies.
+    fn get(&self) -> (Program, Vec<Movie>) {
+        self.shown.lock().unwrap().clone()
+    }
+}
+
+/// The web server of the remote control, listening on all interfaces.
+pub struct RemoteServer {
+    listener: TcpListener,
+    token: String,
+    view: RemoteView,
+}
+
+impl RemoteServer {
+    /// Listen on a free port for requests to the remote control showing the view.
+    pub fn bind(view: RemoteView) -> Result<Self, Error> {
+        let listener =
+            TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0)).map_err(|_| Error::RemoteControl)?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|_| Error::RemoteControl)?;
+
+        let mut token = [0; TOKEN_LEN];
+        SystemRandom::new()
+            .fill(&mut token)
+            .map_err(|_| Error::RemoteControl)?;
+
+        Ok(RemoteServer {
+            listener,
+            token: token.iter().map(|b| format!("{:02x}", b)).collect(),
+            view,
+        })
+    }
+
+    /// Get the URL to open the remote control at from another device in the local network.
+    pub fn get_url(&self) -> Result<String, Error> {
+        let port = self
+            .listener
+            .local_addr()
+            .map_err(|_| Error::RemoteControl)?
+            .port();
+        let address = lan_address().ok_or(Error::RemoteControl)?;
+
+        Ok(format!(
+            "http://{}/?token={}",
+            SocketAddr::new(address, port),
+            self.token
+        ))
+    }
+
+    /// Answer the requests to the remote control until the future is dropped, calling `on_command` with each command
+    /// sent from it.
+    pub async fn serve<F>(self, on_command: F) -> Result<(), Error>
+    where
+        F: Fn(RemoteCommand) + Send + Sync + 'static,
+    {
+        let token = Arc::new(self.token);
+        let view = self.view;
+        let on_command = Arc::new(on_command);
+
+        let make_service = make_service_fn(move |_connection| {
+            let (token, view, on_command) = (token.clone(), view.clone(), on_command.clone());
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let (token, view, on_command) =
+                        (token.clone(), view.clone(), on_command.clone());
+                    async move {
+                        let reply = reply(request, &token, &view).await;
+                        if let Some(command) = reply.command.clone() {
+                            on_command(command);
+                        }
+                        Ok::<_, Infallible>(reply.into_response())
+                    }
+                }))
+            }
+        });
+
+        Server::from_tcp(self.listener)
+            .map_err(|_| Error::RemoteControl)?
+            .serve(make_service)
+            .await
+            .map_err(|_| Error::RemoteControl)
+    }
+}
+
+/// Render the text, e.g. the URL of the remote control, as a QR code to scan it with a phone.
+pub fn qr_code(text: &str) -> Result<RgbaImage, Error> {
+    let code = QrCode::new(text).map_err(|_| Error::RenderingImage)?;
+    Ok(code
+        .render::<Rgba<u8>>()
+        .min_dimensions(QR_CODE_SIZE, QR_CODE_SIZE)
+        .build())
+}
+
+/// The address of the computer in the local network, `None` if it is not connected to a network.
+fn lan_address() -> Option<IpAddr> {
+    // Connecting a UDP socket only chooses the route, the address of the interface it is bound to afterwards is the one
+    // other devices reach the computer at.
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((OUTSIDE_ADDRESS, 80)).ok()?;
+    Some(socket.local_addr().ok()?.ip()).filter(|ip| !ip.is_loopback() && !ip.is_unspecified())
+}
+
+/// The reply of the remote control to a request, with the command sent by it.
+#[derive(Debug, PartialEq)]
+struct Reply {
+    status: StatusCode,
+    /// Where to go after a command, the page of the program again.
+    location: Option<String>,
+    html: String,
+    command: Option<RemoteCommand>,
+}
+
+impl Reply {
+    fn into_response(self) -> Response<Body> {
+        let mut builder = Response::builder()
+            .status(self.status)
+            .header(CONTENT_TYPE, "text/html; charset=utf-8");
+        if let Some(location) = self.location {
+            builder = builder.header(LOCATION, location);
+        }
+        builder
+            .body(Body::from(self.html))
+            .unwrap_or_else(|_| Response::new(Body::empty()))
+    }
+}
+
+/// Read the request and reply to it with the current view.
+async fn reply(request: Request<Body>, token: &str, view: &RemoteView) -> Reply {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().unwrap_or_default().to_string();
+    let body = hyper::body::to_bytes(request.into_body())
+        .await
+        .unwrap_or_default();
+
+    let (program, pinned) = view.get();
+    reply_to(
+        &method,
+        &path,
+        query.as_bytes(),
+        &body,
+        token,
+        &program,
+        &pinned,
+    )
+}
+
+/// Reply to the request with the given method, path, query and form data. Requests without the token are forbidden,
+/// e.g. from a URL of a earlier start of the application.
+fn reply_to(
+    method: &Method,
+    path: &str,
+    query: &[u8],
+    body: &[u8],
+    token: &str,
+    program: &Program,
+    pinned: &[Movie],
+) -> Reply {
+    let parameters: Vec<(String, String)> = form_urlencoded::parse(query)
+        .chain(form_urlencoded::parse(body))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    let parameter = |name: &str| {
+        parameters
+            .iter()
+            .find(|(k, _v)| k == name)
+            .map(|(_k, v)| v.as_str())
+    };
+
+    let authorized = parameter("token")
+        .map(|t| constant_time::verify_slices_are_equal(t.as_bytes(), token.as_bytes()).is_ok())
+        .unwrap_or(false);
+    if !authorized {
+        return Reply {
+            status: StatusCode::FORBIDDEN,
+            location: None,
+            html: page(
+                "<p>This link is outdated. Scan the QR code of the remote control in TV Today again.</p>\n",
+            ),
+            command: None,
+        };
+    }
+
+    match (method, path) {
+        (&Method::GET, "/") => Reply {
+            status: StatusCode::OK,
+            location: None,
+            html: program_page(program, pinned, token),
+            command: None,
+        },
+        (&Method::POST, "/open") | (&Method::POST, "/pin") => {
+            // The movie is sent by its position in the page, the title makes sure the list did not change meanwhile.
+            let movie = parameter("movie")
+                .and_then(|i| i.parse::<usize>().ok())
+                .and_then(|i| program.iter().nth(i))
+                .map(|(_c, m)| m)
+                .filter(|m| Some(m.get_title().as_str()) == parameter("title"))
+                .cloned();
+            let command = movie.map(|m| {
+                if path == "/open" {
+                    RemoteCommand::Open(m)
+                } else {
+                    RemoteCommand::TogglePin(m)
+                }
+            });
+
+            Reply {
+                status: StatusCode::SEE_OTHER,
+                location: Some(format!("/?token={}", token)),
+                html: String::new(),
+                command,
+            }
+        }
+        _ => Reply {
+            status: StatusCode::NOT_FOUND,
+            location: None,
+            html: page("<p>Nothing found.</p>\n"),
+            command: None,
+        },
+    }
+}
+
+/// The page listing the program with buttons to open the details of a movie and to pin it.
+fn program_page(program: &Program, pinned: &[Movie], token: &str) -> String {
+    if program.is_empty() {
+        return page("<p>Nothing found.</p>\n");
+    }
+
+    let mut content = String::from("<ul>\n");
+    for (index, (channel, movie)) in program.iter().enumerate() {
+        let form = |action: &str, label: &str| {
+            format!(
+                "<form method=\"post\" action=\"{}\">\
+                 <input type=\"hidden\" name=\"token\" value=\"{}\">\
+                 <input type=\"hidden\" name=\"movie\" value=\"{}\">\
+                 <input type=\"hidden\" name=\"title\" value=\"{}\">\
+                 <button>{}</button></form>",
+                action,
+                token,
+                index,
+                escape(&movie.get_title()),
+                label
+            )
+        };
+
+        content.push_str(&format!(
+            "<li>{}{} ({}) {} {}</li>\n",
+            movie
+                .get_start()
+                .map(|s| format!("<b>{}</b> ", s.format("%H:%M")))
+                .unwrap_or_default(),
+            escape(&movie.get_title()),
+            escape(&channel.get_name()),
+            form("/open", "Details"),
+            form(
+                "/pin",
+                if pinned.contains(movie) {
+                    "Unpin"
+                } else {
+                    "Pin"
+                }
+            )
+        ));
+    }
+    content.push_str("</ul>\n");
+
+    page(&content)
+}
+
+/// A page of the remote control with the given content.
+fn page(content: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>TV Today</title>\n</head>\n<body>\n<h1>TV Today</h1>\n{}</body>\n</html>\n",
+        content
+    )
+}
+
+/// Escape the text to be used as content or value of a attribute of a HTML element.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::{Channel, MovieBuilder};
+
+    use chrono::NaiveDate;
+
+    fn program() -> Program {
+        let mut builder = MovieBuilder::new("Tom & Jerry");
+        builder.with_start(
+            NaiveDate::from_ymd_opt(2021, 4, 20)
+                .unwrap()
+                .and_hms_opt(20, 15, 0)
+                .unwrap(),
+        );
+        vec![
+            (Channel::new("Das Erste"), builder.build()),
+            (Channel::new("ZDF"), Movie::new("Unknown")),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_reply_forbidden() {
+        let program = program();
+        for query in &["", "token=wrong"] {
+            let reply = reply_to(
+                &Method::GET,
+                "/",
+                query.as_bytes(),
+                b"",
+                "secret",
+                &program,
+                &[],
+            );
+            assert_eq!(reply.status, StatusCode::FORBIDDEN);
+            assert!(!reply.html.contains("Jerry"));
+        }
+    }
+
+    #[test]
+    fn test_reply_page() {
+        let program = program();
+        let pinned = vec![Movie::new("Unknown")];
+        let reply = reply_to(
+            &Method::GET,
+            "/",
+            b"token=secret",
+            b"",
+            "secret",
+            &program,
+            &pinned,
+        );
+
+        assert_eq!(reply.status, StatusCode::OK);
+        assert_eq!(reply.command, None);
+        assert!(reply.html.contains(
+            "<li><b>20:15</b> Tom &amp; Jerry (Das Erste) <form method=\"post\" action=\"/open\">"
+        ));
+        assert!(reply.html.contains(
+            "<input type=\"hidden\" name=\"title\" value=\"Tom &amp; Jerry\"><button>Pin</button>"
+        ));
+        assert!(reply.html.contains(
+            "<input type=\"hidden\" name=\"title\" value=\"Unknown\"><button>Unpin</button>"
+        ));
+    }
+
+    #[test]
+    fn test_reply_command() {
+        let program = program();
+        let reply = reply_to(
+            &Method::POST,
+            "/pin",
+            b"",
+            b"token=secret&movie=1&title=Unknown",
+            "secret",
+            &program,
+            &[],
+        );
+        assert_eq!(reply.status, StatusCode::SEE_OTHER);
+        assert_eq!(reply.location, Some("/?token=secret".to_string()));
+        assert_eq!(
+            reply.command,
+            Some(RemoteCommand::TogglePin(Movie::new("Unknown")))
+        );
+
+        let reply = reply_to(
+            &Method::POST,
+            "/open",
+            b"",
+            b"token=secret&movie=0&title=Tom+%26+Jerry",
+            "secret",
+            &program,
+            &[],
+        );
+        assert_eq!(
+            reply.command,
+            Some(RemoteCommand::Open(
+                program.iter().next().unwrap().1.clone()
+            ))
+        );
+
+        // The list changed since the page was loaded.
+        let reply = reply_to(
+            &Method::POST,
+            "/open",
+            b"",
+            b"token=secret&movie=0&title=Unknown",
+            "secret",
+            &program,
+            &[],
+        );
+        assert_eq!(reply.status, StatusCode::SEE_OTHER);
+        assert_eq!(reply.command, None);
+    }
+
+    #[test]
+    fn test_qr_code() {
+        let image = qr_code("http://192.168.1.2:40000/?token=0123456789abcdef").unwrap();
+        assert_eq!(image.width(), image.height());
+        assert!(image.width() >= QR_CODE_SIZE);
+    }
+}
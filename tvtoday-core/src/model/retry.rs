@@ -0,0 +1,219 @@
+use crate::model::{Movie, ParseFailure, Program, Provider, TimeSlot};
+use crate::Error;
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+/// The default number of attempts for each request.
+const DEFAULT_ATTEMPTS: u32 = 3;
+/// The default delay before the first retry. It doubles with each further retry.
+const DEFAULT_INITIAL_DELAY: Duration = Duration::from_millis(500);
+
+/// A provider retrying failed requests of the wrapped provider with a exponential backoff.
+pub struct Retrying<P: Provider> {
+    provider: P,
+    attempts: u32,
+    initial_delay: Duration,
+}
+
+impl<P: Provider> Retrying<P> {
+    /// Wrap the provider, trying each request at most `attempts` times.
+    /// The first retry is done after `initial_delay`, each further retry waits twice as long as the one before.
+    pub fn with_config(provider: P, attempts: u32, initial_delay: Duration) -> Self {
+        Retrying {
+            provider,
+            attempts: attempts.max(1),
+            initial_delay,
+        }
+    }
+
+    /// The delay before the retry after the given failed attempt, starting at 0.
+    fn delay(&self, attempt: u32) -> Duration {
+        self.initial_delay * 2u32.pow(attempt)
+    }
+}
+
+#[async_trait]
+impl<P: Provider + Sync> Provider for Retrying<P> {
+    fn new() -> Self {
+        Retrying::with_config(P::new(), DEFAULT_ATTEMPTS, DEFAULT_INITIAL_DELAY)
+    }
+
+    fn clone(&self) -> Self {
+        Retrying {
+            provider: self.provider.clone(),
+            attempts: self.attempts,
+            initial_delay: self.initial_delay,
+        }
+    }
+
+    /// Get the program, retrying only on networking errors.
+    async fn get_program(&mut self) -> Result<Program, Error> {
+        self.get_program_progressively(&|_| {}).await
+    }
+
+    /// Get the program progressively, retrying only on networking errors.
+    async fn get_program_progressively(
+        &mut self,
+        on_part: &(dyn Fn(Program) + Send + Sync),
+    ) -> Result<Program, Error> {
+        let mut attempt = 0;
+        loop {
+            match self.provider.get_program_progressively(on_part).await {
+                Err(Error::Networking) if attempt + 1 < self.attempts => {
+                    tokio::time::sleep(self.delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Get more information about the movie, retrying only on networking errors.
+    async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+        let mut attempt = 0;
+        loop {
+            match self.provider.get_more_information(movie).await {
+                Err(Error::Networking) if attempt + 1 < self.attempts => {
+                    tokio::time::sleep(self.delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn get_url(&self, movie: &Movie) -> Option<String> {
+        self.provider.get_url(movie)
+    }
+
+    fn get_parse_failure(&self) -> Option<ParseFailure> {
+        self.provider.get_parse_failure()
+    }
+
+    fn set_credentials(&mut self, login: &str, password: &str) {
+        self.provider.set_credentials(login, password)
+    }
+
+    fn set_server(&mut self, url: &str) {
+        self.provider.set_server(url)
+    }
+
+    fn set_time_slot(&mut self, time_slot: TimeSlot) {
+        self.provider.set_time_slot(time_slot)
+    }
+
+    fn set_days_ahead(&mut self, days: u32) {
+        self.provider.set_days_ahead(days)
+    }
+
+    async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+        self.provider.get_icons(program).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use tokio::runtime::Runtime;
+
+    /// A provider failing the given number of times before succeeding.
+    struct Flaky {
+        failures: AtomicU32,
+    }
+
+    impl Flaky {
+        fn failing(failures: u32) -> Self {
+            Flaky {
+                failures: AtomicU32::new(failures),
+            }
+        }
+
+        /// Whether the request fails, counting down the remaining failures.
+        fn fail(&self) -> bool {
+            self.failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |f| f.checked_sub(1))
+                .is_ok()
+        }
+    }
+
+    #[async_trait]
+    impl Provider for Flaky {
+        fn new() -> Self {
+            Flaky::failing(0)
+        }
+
+        fn clone(&self) -> Self {
+            Flaky::failing(self.failures.load(Ordering::SeqCst))
+        }
+
+        async fn get_program(&mut self) -> Result<Program, Error> {
+            if self.fail() {
+                Err(Error::Networking)
+            } else {
+                Ok(Program::new())
+            }
+        }
+
+        async fn get_more_information(&self, movie: &Movie) -> Result<Movie, Error> {
+            if self.fail() {
+                Err(Error::Networking)
+            } else {
+                Ok(movie.clone())
+            }
+        }
+
+        fn get_url(&self, _movie: &Movie) -> Option<String> {
+            None
+        }
+
+        fn set_time_slot(&mut self, _time_slot: TimeSlot) {}
+
+        fn set_days_ahead(&mut self, _days: u32) {}
+
+        async fn get_icons(&mut self, program: &Program) -> Result<Program, Error> {
+            Ok(program.clone())
+        }
+    }
+
+    #[test]
+    fn test_retry_get_program() {
+        let rt = Runtime::new().unwrap();
+
+        let mut retrying = Retrying::with_config(Flaky::failing(2), 3, Duration::from_millis(0));
+        assert!(rt.block_on(retrying.get_program()).is_ok());
+
+        let mut retrying = Retrying::with_config(Flaky::failing(3), 3, Duration::from_millis(0));
+        assert!(rt.block_on(retrying.get_program()).is_err());
+    }
+
+    #[test]
+    fn test_retry_get_more_information() {
+        let rt = Runtime::new().unwrap();
+        let movie = Movie::new("Hello");
+
+        let retrying = Retrying::with_config(Flaky::failing(2), 3, Duration::from_millis(0));
+        assert_eq!(
+            rt.block_on(retrying.get_more_information(&movie)).ok(),
+            Some(movie.clone())
+        );
+
+        // A unchanged movie is no failure, so it is returned without waiting for a retry.
+        let retrying = Retrying::with_config(Flaky::failing(0), 3, Duration::from_secs(60));
+        let result = rt.block_on(async {
+            tokio::time::timeout(
+                Duration::from_secs(1),
+                retrying.get_more_information(&movie),
+            )
+            .await
+        });
+        assert_eq!(result.ok().and_then(|r| r.ok()), Some(movie.clone()));
+
+        let retrying = Retrying::with_config(Flaky::failing(3), 3, Duration::from_millis(0));
+        assert!(rt.block_on(retrying.get_more_information(&movie)).is_err());
+    }
+}
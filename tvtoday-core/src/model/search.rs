@@ -0,0 +1,74 @@
+use unicode_normalization::char::{decompose_compatible, is_combining_mark};
+use unicode_normalization::UnicodeNormalization;
+
+/// Fold the text to compare it independent of its spelling, e.g. `"Märchen – „Schneeweißchen“"` and
+/// `"maerchen - \"schneeweisschen\""` are folded to the same text.
+///
+/// Letters are lowercased, the german umlauts and `ß` are transliterated, other accents are removed and the typographic
+/// quotes and dashes are replaced by their ASCII counterparts.
+pub fn fold(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+
+    for c in text.nfc().flat_map(char::to_lowercase) {
+        match c {
+            'ä' => folded.push_str("ae"),
+            'ö' => folded.push_str("oe"),
+            'ü' => folded.push_str("ue"),
+            'ß' => folded.push_str("ss"),
+            '‘' | '’' | '‚' | '‛' | '‹' | '›' | '`' | '´' | '′' => folded.push('\''),
+            '“' | '”' | '„' | '‟' | '«' | '»' | '″' => folded.push('"'),
+            '‐' | '‑' | '‒' | '–' | '—' | '―' | '−' => folded.push('-'),
+            _ => decompose_compatible(c, |d| {
+                if !is_combining_mark(d) {
+                    folded.extend(d.to_lowercase());
+                }
+            }),
+        }
+    }
+
+    folded
+}
+
+/// Weather both texts are the same after folding them.
+pub fn same_text(a: &str, b: &str) -> bool {
+    fold(a) == fold(b)
+}
+
+/// Weather the text contains the query of a search, which was already folded.
+pub fn matches_search(text: &str, folded_query: &str) -> bool {
+    fold(text).contains(folded_query)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(fold("Märchen"), "maerchen");
+        assert_eq!(fold("MAERCHEN"), "maerchen");
+        assert_eq!(fold("Straße"), "strasse");
+        assert_eq!(fold("Amélie"), "amelie");
+        // The umlaut is also transliterated if it is decomposed.
+        assert_eq!(fold("Mu\u{308}nchen"), "muenchen");
+        assert_eq!(fold("Tatort – „Borowski“"), "tatort - \"borowski\"");
+        assert_eq!(fold("Ocean’s Eleven"), "ocean's eleven");
+        assert_eq!(fold("ﬁve"), "five");
+    }
+
+    #[test]
+    fn test_same_text() {
+        assert!(same_text("Die Fälscher", "die faelscher"));
+        assert!(same_text("Großstadtrevier", "GROSSSTADTREVIER"));
+        assert!(same_text("Spider-Man", "Spider‐Man"));
+        assert!(!same_text("Tatort", "Polizeiruf 110"));
+    }
+
+    #[test]
+    fn test_matches_search() {
+        assert!(matches_search("Die Fälscher", &fold("fäl")));
+        assert!(matches_search("Die Fälscher", &fold("FAEL")));
+        assert!(matches_search("Ocean's Eleven", &fold("ocean’s")));
+        assert!(!matches_search("Die Fälscher", &fold("fal")));
+    }
+}
@@ -0,0 +1,617 @@
+use crate::model::{StartedBroadcasts, TimeSlot};
+
+use std::convert::TryFrom;
+use std::path::PathBuf;
+
+/// The settings of the application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    compact: bool,
+    dark: bool,
+    reduced_motion: bool,
+    two_line_titles: bool,
+    double_click: bool,
+    inhibit_suspend: bool,
+    omdb_api_key: Option<String>,
+    translation_url: Option<String>,
+    provider: String,
+    subscribed_channels: Vec<String>,
+    time_slot: TimeSlot,
+    refresh_interval: u32,
+    auto_refresh: bool,
+    started_broadcasts: StartedBroadcasts,
+    started_minutes: u32,
+    transition_duration: u32,
+    icon_cache_limit: u32,
+    description_cache_limit: u32,
+    disk_cache_limit: u32,
+    min_personal_rating: u32,
+    data_dir: Option<PathBuf>,
+    widget_path: Option<PathBuf>,
+    health_report_url: Option<String>,
+    provider_login: Option<String>,
+    provider_password: Option<String>,
+    provider_server: Option<String>,
+}
+
+impl Settings {
+    /// Create the default settings.
+    pub fn new() -> Self {
+        Settings {
+            compact: false,
+            dark: false,
+            reduced_motion: false,
+            two_line_titles: false,
+            double_click: false,
+            inhibit_suspend: false,
+            omdb_api_key: None,
+            translation_url: None,
+            provider: "tvspielfilm".to_string(),
+            subscribed_channels: vec![],
+            time_slot: TimeSlot::Evening,
+            refresh_interval: 30,
+            auto_refresh: false,
+            started_broadcasts: StartedBroadcasts::Show,
+            started_minutes: 10,
+            transition_duration: 200,
+            icon_cache_limit: 8,
+            description_cache_limit: 2,
+            disk_cache_limit: 50,
+            min_personal_rating: 0,
+            data_dir: None,
+            widget_path: None,
+            health_report_url: None,
+            provider_login: None,
+            provider_password: None,
+            provider_server: None,
+        }
+    }
+
+    /// Get whether the compact text-only mode for e-ink and low-power displays is used.
+    pub fn get_compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Set whether the compact text-only mode for e-ink and low-power displays is used.
+    pub fn set_compact(&mut self, compact: bool) {
+        self.compact = compact
+    }
+
+    /// Get whether the dark theme variant is requested.
+    pub fn get_dark(&self) -> bool {
+        self.dark
+    }
+
+    /// Set whether the dark theme variant is requested.
+    pub fn set_dark(&mut self, dark: bool) {
+        self.dark = dark
+    }
+
+    /// Get whether all animations are disabled, e.g. for weak hardware.
+    pub fn get_reduced_motion(&self) -> bool {
+        self.reduced_motion
+    }
+
+    /// Set whether all animations are disabled, e.g. for weak hardware.
+    pub fn set_reduced_motion(&mut self, reduced_motion: bool) {
+        self.reduced_motion = reduced_motion
+    }
+
+    /// Get whether long titles wrap to a second line before being ellipsized.
+    pub fn get_two_line_titles(&self) -> bool {
+        self.two_line_titles
+    }
+
+    /// Set whether long titles wrap to a second line before being ellipsized.
+    pub fn set_two_line_titles(&mut self, two_line_titles: bool) {
+        self.two_line_titles = two_line_titles
+    }
+
+    /// Get whether movies are opened with a double click and selected with a single click, as on the desktop.
+    /// Otherwise a single click or tap opens them, as on phones.
+    pub fn get_double_click(&self) -> bool {
+        self.double_click
+    }
+
+    /// Set whether movies are opened with a double click and selected with a single click, as on the desktop.
+    /// Otherwise a single click or tap opens them, as on phones.
+    pub fn set_double_click(&mut self, double_click: bool) {
+        self.double_click = double_click
+    }
+
+    /// Get whether suspend is inhibited shortly before a reminder is due.
+    pub fn get_inhibit_suspend(&self) -> bool {
+        self.inhibit_suspend
+    }
+
+    /// Set whether suspend is inhibited shortly before a reminder is due.
+    pub fn set_inhibit_suspend(&mut self, inhibit_suspend: bool) {
+        self.inhibit_suspend = inhibit_suspend
+    }
+
+    /// Get the optional API key of OMDb, used to look up external ratings.
+    pub fn get_omdb_api_key(&self) -> Option<String> {
+        self.omdb_api_key.clone()
+    }
+
+    /// Set the optional API key of OMDb, used to look up external ratings.
+    pub fn set_omdb_api_key(&mut self, omdb_api_key: Option<String>) {
+        self.omdb_api_key = omdb_api_key
+    }
+
+    /// Get the optional URL of the LibreTranslate server, used to translate descriptions.
+    pub fn get_translation_url(&self) -> Option<String> {
+        self.translation_url.clone()
+    }
+
+    /// Set the optional URL of the LibreTranslate server, used to translate descriptions.
+    pub fn set_translation_url(&mut self, translation_url: Option<String>) {
+        self.translation_url = translation_url
+    }
+
+    /// Get the id of the provider of the program.
+    pub fn get_provider(&self) -> String {
+        self.provider.clone()
+    }
+
+    /// Set the id of the provider of the program.
+    pub fn set_provider(&mut self, provider: &str) {
+        self.provider = provider.to_string()
+    }
+
+    /// Get the names of the channels shown at all. All channels are shown if it is empty.
+    pub fn get_subscribed_channels(&self) -> Vec<String> {
+        self.subscribed_channels.clone()
+    }
+
+    /// Set the names of the channels shown at all. All channels are shown if it is empty.
+    pub fn set_subscribed_channels(&mut self, subscribed_channels: Vec<String>) {
+        self.subscribed_channels = subscribed_channels
+    }
+
+    /// Get the time slot of the program shown by default.
+    pub fn get_time_slot(&self) -> TimeSlot {
+        self.time_slot
+    }
+
+    /// Set the time slot of the program shown by default.
+    pub fn set_time_slot(&mut self, time_slot: TimeSlot) {
+        self.time_slot = time_slot
+    }
+
+    /// Get the minutes after which the program is refreshed when the window is activated, or in the background if
+    /// enabled.
+    pub fn get_refresh_interval(&self) -> u32 {
+        self.refresh_interval
+    }
+
+    /// Set the minutes after which the program is refreshed when the window is activated, or in the background if
+    /// enabled.
+    pub fn set_refresh_interval(&mut self, refresh_interval: u32) {
+        self.refresh_interval = refresh_interval
+    }
+
+    /// Get whether the program is refreshed in the background after the refresh interval.
+    pub fn get_auto_refresh(&self) -> bool {
+        self.auto_refresh
+    }
+
+    /// Set whether the program is refreshed in the background after the refresh interval.
+    pub fn set_auto_refresh(&mut self, auto_refresh: bool) {
+        self.auto_refresh = auto_refresh
+    }
+
+    /// Get how movies that started more than the started minutes ago are shown.
+    pub fn get_started_broadcasts(&self) -> StartedBroadcasts {
+        self.started_broadcasts
+    }
+
+    /// Set how movies that started more than the started minutes ago are shown.
+    pub fn set_started_broadcasts(&mut self, started_broadcasts: StartedBroadcasts) {
+        self.started_broadcasts = started_broadcasts
+    }
+
+    /// Get the minutes after the start of a movie after which it is dimmed or hidden as started.
+    pub fn get_started_minutes(&self) -> u32 {
+        self.started_minutes
+    }
+
+    /// Set the minutes after the start of a movie after which it is dimmed or hidden as started.
+    pub fn set_started_minutes(&mut self, started_minutes: u32) {
+        self.started_minutes = started_minutes
+    }
+
+    /// Get the duration of the page transitions in milliseconds. No animations are shown if it is zero.
+    pub fn get_transition_duration(&self) -> u32 {
+        self.transition_duration
+    }
+
+    /// Set the duration of the page transitions in milliseconds. No animations are shown if it is zero.
+    pub fn set_transition_duration(&mut self, transition_duration: u32) {
+        self.transition_duration = transition_duration
+    }
+
+    /// Get the maximum size of the decoded channel icons in memory in MiB.
+    pub fn get_icon_cache_limit(&self) -> u32 {
+        self.icon_cache_limit
+    }
+
+    /// Set the maximum size of the decoded channel icons in memory in MiB.
+    pub fn set_icon_cache_limit(&mut self, icon_cache_limit: u32) {
+        self.icon_cache_limit = icon_cache_limit
+    }
+
+    /// Get the maximum size of the descriptions cached in memory in MiB.
+    pub fn get_description_cache_limit(&self) -> u32 {
+        self.description_cache_limit
+    }
+
+    /// Set the maximum size of the descriptions cached in memory in MiB.
+    pub fn set_description_cache_limit(&mut self, description_cache_limit: u32) {
+        self.description_cache_limit = description_cache_limit
+    }
+
+    /// Get the maximum size of the cache directory, e.g. the downloaded channel icons, in MiB.
+    pub fn get_disk_cache_limit(&self) -> u32 {
+        self.disk_cache_limit
+    }
+
+    /// Set the maximum size of the cache directory, e.g. the downloaded channel icons, in MiB.
+    pub fn set_disk_cache_limit(&mut self, disk_cache_limit: u32) {
+        self.disk_cache_limit = disk_cache_limit
+    }
+
+    /// Get the rating from 1 to 10 the user must have given a movie on IMDb for it to be shown. Movies the user did not
+    /// rate are always shown, all movies are shown if it is zero.
+    pub fn get_min_personal_rating(&self) -> u32 {
+        self.min_personal_rating
+    }
+
+    /// Set the rating from 1 to 10 the user must have given a movie on IMDb for it to be shown. Movies the user did not
+    /// rate are always shown, all movies are shown if it is zero.
+    pub fn set_min_personal_rating(&mut self, min_personal_rating: u32) {
+        self.min_personal_rating = min_personal_rating
+    }
+
+    /// Get the directory overriding the default user data directory.
+    pub fn get_data_dir(&self) -> Option<PathBuf> {
+        self.data_dir.clone()
+    }
+
+    /// Set the directory overriding the default user data directory.
+    pub fn set_data_dir(&mut self, data_dir: Option<PathBuf>) {
+        self.data_dir = data_dir
+    }
+
+    /// Get the optional path tonight's picks are written to on each reload for desktop widgets.
+    pub fn get_widget_path(&self) -> Option<PathBuf> {
+        self.widget_path.clone()
+    }
+
+    /// Set the optional path tonight's picks are written to on each reload for desktop widgets.
+    pub fn set_widget_path(&mut self, widget_path: Option<PathBuf>) {
+        self.widget_path = widget_path
+    }
+
+    /// Get the optional URL reports are sent to when the website of the provider can not be read. Reports are never
+    /// sent if this is not set.
+    pub fn get_health_report_url(&self) -> Option<String> {
+        self.health_report_url.clone()
+    }
+
+    /// Set the optional URL reports are sent to when the website of the provider can not be read.
+    pub fn set_health_report_url(&mut self, health_report_url: Option<String>) {
+        self.health_report_url = health_report_url
+    }
+
+    /// Get the optional login of the account at the provider, for providers that need one like Zattoo.
+    pub fn get_provider_login(&self) -> Option<String> {
+        self.provider_login.clone()
+    }
+
+    /// Set the optional login of the account at the provider.
+    pub fn set_provider_login(&mut self, provider_login: Option<String>) {
+        self.provider_login = provider_login
+    }
+
+    /// Get the optional password of the account at the provider.
+    pub fn get_provider_password(&self) -> Option<String> {
+        self.provider_password.clone()
+    }
+
+    /// Set the optional password of the account at the provider.
+    pub fn set_provider_password(&mut self, provider_password: Option<String>) {
+        self.provider_password = provider_password
+    }
+
+    /// Get the optional address of the server of the provider, for providers running on a server of the user like
+    /// tvheadend.
+    pub fn get_provider_server(&self) -> Option<String> {
+        self.provider_server.clone()
+    }
+
+    /// Set the optional address of the server of the provider.
+    pub fn set_provider_server(&mut self, provider_server: Option<String>) {
+        self.provider_server = provider_server
+    }
+
+    /// Get the secrets of the settings as keys and values: the password of the account at the provider and the API key
+    /// of OMDb. Unset secrets are not included. They are stored apart from the other settings.
+    pub fn get_credentials(&self) -> Vec<[String; 2]> {
+        let mut result = vec![];
+        if let Some(provider_password) = &self.provider_password {
+            result.push(["provider_password".to_string(), provider_password.clone()]);
+        }
+        if let Some(omdb_api_key) = &self.omdb_api_key {
+            result.push(["omdb_api_key".to_string(), omdb_api_key.clone()]);
+        }
+        result
+    }
+
+    /// Set the secrets of the settings from keys and values as returned by `get_credentials`. Secrets that are not
+    /// included are unset, unknown keys are ignored.
+    pub fn set_credentials(&mut self, credentials: Vec<[String; 2]>) {
+        self.provider_password = None;
+        self.omdb_api_key = None;
+        for i in credentials {
+            match &i[0][..] {
+                "provider_password" => self.provider_password = Some(i[1].clone()),
+                "omdb_api_key" => self.omdb_api_key = Some(i[1].clone()),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Settings> for Vec<[String; 2]> {
+    /// Convert from `Settings` to `Vec<[String; 2]>` of keys and values. Unset optional settings are not stored, each
+    /// subscribed channel is stored in its own row. The credentials are stored in their own file, the provider and the
+    /// time slot are stored in GSettings.
+    fn from(item: Settings) -> Vec<[String; 2]> {
+        let mut result = vec![
+            ["compact".to_string(), item.compact.to_string()],
+            ["dark".to_string(), item.dark.to_string()],
+            [
+                "reduced_motion".to_string(),
+                item.reduced_motion.to_string(),
+            ],
+            [
+                "two_line_titles".to_string(),
+                item.two_line_titles.to_string(),
+            ],
+            ["double_click".to_string(), item.double_click.to_string()],
+            [
+                "inhibit_suspend".to_string(),
+                item.inhibit_suspend.to_string(),
+            ],
+            [
+                "refresh_interval".to_string(),
+                item.refresh_interval.to_string(),
+            ],
+            ["auto_refresh".to_string(), item.auto_refresh.to_string()],
+            [
+                "started_broadcasts".to_string(),
+                item.started_broadcasts.to_string(),
+            ],
+            [
+                "started_minutes".to_string(),
+                item.started_minutes.to_string(),
+            ],
+            [
+                "transition_duration".to_string(),
+                item.transition_duration.to_string(),
+            ],
+            [
+                "icon_cache_limit".to_string(),
+                item.icon_cache_limit.to_string(),
+            ],
+            [
+                "description_cache_limit".to_string(),
+                item.description_cache_limit.to_string(),
+            ],
+            [
+                "disk_cache_limit".to_string(),
+                item.disk_cache_limit.to_string(),
+            ],
+            [
+                "min_personal_rating".to_string(),
+                item.min_personal_rating.to_string(),
+            ],
+        ];
+        for channel in item.subscribed_channels {
+            result.push(["subscribed_channel".to_string(), channel]);
+        }
+        if let Some(translation_url) = item.translation_url {
+            result.push(["translation_url".to_string(), translation_url]);
+        }
+        if let Some(data_dir) = item.data_dir {
+            result.push([
+                "data_dir".to_string(),
+                data_dir.to_string_lossy().to_string(),
+            ]);
+        }
+        if let Some(widget_path) = item.widget_path {
+            result.push([
+                "widget_path".to_string(),
+                widget_path.to_string_lossy().to_string(),
+            ]);
+        }
+        if let Some(health_report_url) = item.health_report_url {
+            result.push(["health_report_url".to_string(), health_report_url]);
+        }
+        if let Some(provider_login) = item.provider_login {
+            result.push(["provider_login".to_string(), provider_login]);
+        }
+        if let Some(provider_server) = item.provider_server {
+            result.push(["provider_server".to_string(), provider_server]);
+        }
+        result
+    }
+}
+
+impl TryFrom<Vec<[String; 2]>> for Settings {
+    type Error = ();
+
+    /// Try to convert from `Vec<[String; 2]>` of keys and values to `Settings`. Unknown keys are ignored, missing keys
+    /// keep their default value.
+    fn try_from(item: Vec<[String; 2]>) -> Result<Settings, ()> {
+        let mut settings = Settings::new();
+
+        for i in item {
+            match &i[0][..] {
+                "compact" => settings.compact = i[1].parse().map_err(|_| ())?,
+                "dark" => settings.dark = i[1].parse().map_err(|_| ())?,
+                "reduced_motion" => settings.reduced_motion = i[1].parse().map_err(|_| ())?,
+                "two_line_titles" => settings.two_line_titles = i[1].parse().map_err(|_| ())?,
+                "double_click" => settings.double_click = i[1].parse().map_err(|_| ())?,
+                "inhibit_suspend" => settings.inhibit_suspend = i[1].parse().map_err(|_| ())?,
+                "translation_url" => settings.translation_url = Some(i[1].clone()),
+                // Earlier versions stored the provider and the time slot with the other settings.
+                "provider" => settings.provider = i[1].clone(),
+                "time_slot" => settings.time_slot = TimeSlot::try_from(&i[1][..])?,
+                "subscribed_channel" => settings.subscribed_channels.push(i[1].clone()),
+                "refresh_interval" => settings.refresh_interval = i[1].parse().map_err(|_| ())?,
+                "auto_refresh" => settings.auto_refresh = i[1].parse().map_err(|_| ())?,
+                "started_broadcasts" => {
+                    settings.started_broadcasts = StartedBroadcasts::try_from(&i[1][..])?
+                }
+                "started_minutes" => settings.started_minutes = i[1].parse().map_err(|_| ())?,
+                "transition_duration" => {
+                    settings.transition_duration = i[1].parse().map_err(|_| ())?
+                }
+                "icon_cache_limit" => settings.icon_cache_limit = i[1].parse().map_err(|_| ())?,
+                "description_cache_limit" => {
+                    settings.description_cache_limit = i[1].parse().map_err(|_| ())?
+                }
+                "disk_cache_limit" => settings.disk_cache_limit = i[1].parse().map_err(|_| ())?,
+                "min_personal_rating" => {
+                    settings.min_personal_rating = i[1].parse().map_err(|_| ())?
+                }
+                "data_dir" => settings.data_dir = Some(PathBuf::from(&i[1])),
+                "widget_path" => settings.widget_path = Some(PathBuf::from(&i[1])),
+                "health_report_url" => settings.health_report_url = Some(i[1].clone()),
+                "provider_login" => settings.provider_login = Some(i[1].clone()),
+                // Earlier versions stored the credentials with the other settings.
+                "provider_password" => settings.provider_password = Some(i[1].clone()),
+                "omdb_api_key" => settings.omdb_api_key = Some(i[1].clone()),
+                "provider_server" => settings.provider_server = Some(i[1].clone()),
+                _ => {}
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_str_array() {
+        let mut settings = Settings::new();
+        settings.set_compact(true);
+        settings.set_dark(true);
+        settings.set_inhibit_suspend(true);
+        settings.set_double_click(true);
+        settings.set_omdb_api_key(Some("key".to_string()));
+        settings.set_translation_url(Some("https://translate.example.org".to_string()));
+        settings.set_subscribed_channels(vec!["ZDF".to_string(), "ARTE".to_string()]);
+        settings.set_data_dir(Some(PathBuf::from("/tmp/tvtoday")));
+        settings.set_widget_path(Some(PathBuf::from("/tmp/tonight.json")));
+        settings.set_health_report_url(Some("https://health.example.org".to_string()));
+        settings.set_provider_login(Some("user@example.org".to_string()));
+        settings.set_provider_password(Some("secret".to_string()));
+        settings.set_provider_server(Some("http://localhost:9981".to_string()));
+        settings.set_auto_refresh(true);
+        settings.set_started_broadcasts(StartedBroadcasts::Dim);
+        settings.set_disk_cache_limit(1);
+        settings.set_min_personal_rating(5);
+
+        let content = <Vec<[String; 2]>>::from(settings.clone());
+        assert_eq!(
+            content,
+            vec![
+                ["compact".to_string(), "true".to_string()],
+                ["dark".to_string(), "true".to_string()],
+                ["reduced_motion".to_string(), "false".to_string()],
+                ["two_line_titles".to_string(), "false".to_string()],
+                ["double_click".to_string(), "true".to_string()],
+                ["inhibit_suspend".to_string(), "true".to_string()],
+                ["refresh_interval".to_string(), "30".to_string()],
+                ["auto_refresh".to_string(), "true".to_string()],
+                ["started_broadcasts".to_string(), "dim".to_string()],
+                ["started_minutes".to_string(), "10".to_string()],
+                ["transition_duration".to_string(), "200".to_string()],
+                ["icon_cache_limit".to_string(), "8".to_string()],
+                ["description_cache_limit".to_string(), "2".to_string()],
+                ["disk_cache_limit".to_string(), "1".to_string()],
+                ["min_personal_rating".to_string(), "5".to_string()],
+                ["subscribed_channel".to_string(), "ZDF".to_string()],
+                ["subscribed_channel".to_string(), "ARTE".to_string()],
+                [
+                    "translation_url".to_string(),
+                    "https://translate.example.org".to_string()
+                ],
+                ["data_dir".to_string(), "/tmp/tvtoday".to_string()],
+                ["widget_path".to_string(), "/tmp/tonight.json".to_string()],
+                [
+                    "health_report_url".to_string(),
+                    "https://health.example.org".to_string()
+                ],
+                ["provider_login".to_string(), "user@example.org".to_string()],
+                [
+                    "provider_server".to_string(),
+                    "http://localhost:9981".to_string()
+                ]
+            ]
+        );
+
+        // The credentials are stored apart from the other settings.
+        let credentials = settings.get_credentials();
+        assert_eq!(
+            credentials,
+            vec![
+                ["provider_password".to_string(), "secret".to_string()],
+                ["omdb_api_key".to_string(), "key".to_string()]
+            ]
+        );
+        let mut read = Settings::try_from(content).unwrap();
+        assert_eq!(read.get_provider_password(), None);
+        assert_eq!(read.get_omdb_api_key(), None);
+        read.set_credentials(credentials);
+        assert_eq!(read, settings);
+
+        assert_eq!(Settings::try_from(vec![]), Ok(Settings::new()));
+        assert_eq!(
+            Settings::try_from(vec![
+                ["provider_password".to_string(), "secret".to_string()],
+                ["omdb_api_key".to_string(), "key".to_string()]
+            ])
+            .map(|s| s.get_credentials().len()),
+            Ok(2)
+        );
+        assert_eq!(
+            Settings::try_from(vec![
+                ["provider".to_string(), "zattoo".to_string()],
+                ["time_slot".to_string(), "late".to_string()]
+            ])
+            .map(|s| (s.get_provider(), s.get_time_slot())),
+            Ok(("zattoo".to_string(), TimeSlot::Late))
+        );
+        assert_eq!(
+            Settings::try_from(vec![["compact".to_string(), "maybe".to_string()]]),
+            Err(())
+        );
+        assert_eq!(
+            Settings::try_from(vec![["time_slot".to_string(), "tomorrow".to_string()]]),
+            Err(())
+        );
+    }
+}
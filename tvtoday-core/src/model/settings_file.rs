@@ -0,0 +1,106 @@
+use crate::model::{encryption, Settings};
+use crate::Error;
+
+use std::convert::TryInto;
+use std::fs::{self, OpenOptions};
+use std::path::Path;
+
+use csv::{ReaderBuilder, Writer};
+
+impl Settings {
+    /// Write the settings to a file at the given path.
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+
+        let mut writer = Writer::from_writer(file);
+
+        let content = <Vec<[String; 2]>>::from(self.clone());
+
+        for c in content {
+            writer.write_record(&c)?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Write the credentials of the settings, the password of the account at the provider and the API key of OMDb, to a
+    /// file at the given path, removing it if there are none. They are kept out of the other settings, so they are only
+    /// readable by the user and encrypted with the user data.
+    pub fn write_credentials_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let content = self.get_credentials();
+        if content.is_empty() {
+            if path.as_ref().exists() {
+                fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+
+        let mut writer = Writer::from_writer(vec![]);
+        for c in content {
+            writer.write_record(&c)?;
+        }
+        let content = writer.into_inner().map_err(|_| Error::ParsingFile)?;
+
+        encryption::write_private_file(path, &content)
+    }
+
+    /// Read the credentials of the settings from a file at the given path. The user data must be unlocked if it is
+    /// encrypted.
+    pub fn read_credentials_from_path<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        let content = encryption::read_file(path)?;
+
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(&content[..]);
+
+        let mut credentials: Vec<[String; 2]> = vec![];
+
+        for record_res in reader.records() {
+            let record = record_res?;
+
+            if record.len() != 2 {
+                return Err(Error::ParsingFile);
+            }
+
+            credentials.push([
+                record.get(0).unwrap().to_string(),
+                record.get(1).unwrap().to_string(),
+            ]);
+        }
+
+        self.set_credentials(credentials);
+        Ok(())
+    }
+
+    /// Read the settings from a file at the given path.
+    pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = OpenOptions::new().read(true).open(path)?;
+
+        let mut reader = ReaderBuilder::new().has_headers(false).from_reader(file);
+
+        let mut content: Vec<[String; 2]> = vec![];
+
+        for record_res in reader.records() {
+            let record = record_res?;
+
+            if record.len() != 2 {
+                return Err(Error::ParsingFile);
+            }
+
+            let record_arr = [
+                record.get(0).unwrap().to_string(),
+                record.get(1).unwrap().to_string(),
+            ];
+
+            content.push(record_arr);
+        }
+
+        content.try_into().map_err(|_| Error::ParsingFile)
+    }
+}
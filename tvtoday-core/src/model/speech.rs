@@ -0,0 +1,13 @@
+use crate::Error;
+
+use std::process::Command;
+
+/// Read out the text using speech-dispatcher. This does not wait for the text to be spoken.
+pub fn speak(text: &str) -> Result<(), Error> {
+    Command::new("spd-say")
+        .arg("--")
+        .arg(text)
+        .spawn()
+        .map(|_| ())
+        .map_err(|_| Error::Speech)
+}
@@ -0,0 +1,71 @@
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+/// How broadcasts that already started some time ago are shown, e.g. when opening the application mid-evening.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StartedBroadcasts {
+    /// Started broadcasts are shown like the others.
+    Show,
+    /// Started broadcasts are shown dimmed.
+    Dim,
+    /// Started broadcasts are not shown.
+    Hide,
+}
+
+impl StartedBroadcasts {
+    /// All ways to show started broadcasts.
+    pub const ALL: [StartedBroadcasts; 3] = [
+        StartedBroadcasts::Show,
+        StartedBroadcasts::Dim,
+        StartedBroadcasts::Hide,
+    ];
+
+    /// The label shown to the user.
+    pub fn label(&self) -> &'static str {
+        match self {
+            StartedBroadcasts::Show => "Show started movies",
+            StartedBroadcasts::Dim => "Dim started movies",
+            StartedBroadcasts::Hide => "Hide started movies",
+        }
+    }
+}
+
+impl Display for StartedBroadcasts {
+    /// The identifier, used for storing it.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            StartedBroadcasts::Show => write!(f, "show"),
+            StartedBroadcasts::Dim => write!(f, "dim"),
+            StartedBroadcasts::Hide => write!(f, "hide"),
+        }
+    }
+}
+
+impl TryFrom<&str> for StartedBroadcasts {
+    type Error = ();
+
+    /// Try to convert from the identifier to the `StartedBroadcasts`.
+    fn try_from(item: &str) -> Result<StartedBroadcasts, ()> {
+        StartedBroadcasts::ALL
+            .iter()
+            .find(|s| s.to_string() == item)
+            .cloned()
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_str() {
+        for started in StartedBroadcasts::ALL.iter() {
+            assert_eq!(
+                StartedBroadcasts::try_from(&started.to_string()[..]),
+                Ok(*started)
+            );
+        }
+        assert_eq!(StartedBroadcasts::try_from("blur"), Err(()));
+    }
+}
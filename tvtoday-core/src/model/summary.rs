@@ -0,0 +1,128 @@
+use crate::model::{Movie, Program};
+
+use chrono::NaiveTime;
+
+/// Get tonight's picks of the program. These are the pinned movies or, if none are pinned, the movies starting at prime
+/// time (20:15).
+pub fn tonights_picks(program: &Program, pinned: &[Movie]) -> Program {
+    let picks: Program = program
+        .iter()
+        .filter(|(_c, m)| pinned.contains(m))
+        .cloned()
+        .collect();
+
+    if picks.iter().next().is_some() {
+        return picks;
+    }
+
+    let prime_time = NaiveTime::from_hms_opt(20, 15, 0).unwrap();
+    program
+        .iter()
+        .filter(|(_c, m)| m.get_start().map(|s| s.time()) == Some(prime_time))
+        .cloned()
+        .collect()
+}
+
+/// Summarize the broadcasts in a short text like "Tonight at 20:15: Tatort on Das Erste.", suitable to be read out or
+/// shown in a notification.
+pub fn summarize(picks: &Program) -> String {
+    let mut sorted: Vec<_> = picks.iter().collect();
+    sorted.sort_by_key(|(_c, m)| (m.get_start().is_none(), m.get_start()));
+
+    let mut sentences: Vec<String> = vec![];
+    let mut current: Option<(Option<String>, Vec<String>)> = None;
+
+    for (channel, movie) in sorted {
+        let time = movie.get_start().map(|s| s.format("%H:%M").to_string());
+        let broadcast = format!("{} on {}", movie.get_title(), channel.get_name());
+
+        match current.as_mut() {
+            Some((current_time, broadcasts)) if current_time == &time => broadcasts.push(broadcast),
+            _ => {
+                if let Some(c) = current.take() {
+                    sentences.push(sentence(c, sentences.is_empty()));
+                }
+                current = Some((time, vec![broadcast]));
+            }
+        }
+    }
+
+    if let Some(c) = current.take() {
+        sentences.push(sentence(c, sentences.is_empty()));
+    }
+
+    if sentences.is_empty() {
+        "Nothing picked for tonight.".to_string()
+    } else {
+        sentences.join(" ")
+    }
+}
+
+/// A sentence about the broadcasts at the same time.
+fn sentence((time, broadcasts): (Option<String>, Vec<String>), first: bool) -> String {
+    let prefix = match (time, first) {
+        (Some(time), true) => format!("Tonight at {}", time),
+        (Some(time), false) => format!("At {}", time),
+        (None, true) => "Tonight".to_string(),
+        (None, false) => "Also".to_string(),
+    };
+
+    format!("{}: {}.", prefix, broadcasts.join(" and "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::{Channel, MovieBuilder};
+
+    use chrono::NaiveDate;
+
+    fn movie(title: &str, hour: u32, minute: u32) -> Movie {
+        let mut builder = MovieBuilder::new(title);
+        builder.with_start(
+            NaiveDate::from_ymd_opt(2021, 4, 20)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap(),
+        );
+        builder.build()
+    }
+
+    #[test]
+    fn test_summarize() {
+        let program: Program = vec![
+            (Channel::new("RTL"), movie("Late", 22, 0)),
+            (Channel::new("Das Erste"), movie("Tatort", 20, 15)),
+            (Channel::new("ZDF"), movie("Terra X", 20, 15)),
+            (Channel::new("ARTE"), Movie::new("Unknown")),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            summarize(&program),
+            "Tonight at 20:15: Tatort on Das Erste and Terra X on ZDF. At 22:00: Late on RTL. Also: Unknown on ARTE."
+        );
+        assert_eq!(summarize(&Program::new()), "Nothing picked for tonight.");
+    }
+
+    #[test]
+    fn test_tonights_picks() {
+        let program: Program = vec![
+            (Channel::new("RTL"), movie("Late", 22, 0)),
+            (Channel::new("ZDF"), movie("Terra X", 20, 15)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            summarize(&tonights_picks(&program, &[])),
+            "Tonight at 20:15: Terra X on ZDF."
+        );
+        assert_eq!(
+            summarize(&tonights_picks(&program, &[movie("Late", 22, 0)])),
+            "Tonight at 22:00: Late on RTL."
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+/// A change of a synced value, made on a device at a time in milliseconds since the epoch. A value of `None` removes the
+/// key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    time: i64,
+    device: String,
+    key: String,
+    value: Option<String>,
+}
+
+/// The state of synced key-value pairs, built from the changes of all devices. The latest change of each key wins, so
+/// the changes can be applied in any order and more than once, e.g. from conflicting copies created by a sync tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncState {
+    latest: BTreeMap<String, Change>,
+}
+
+impl Change {
+    /// Create a change of the key to the value, made on the device at the time.
+    pub fn new(time: i64, device: &str, key: &str, value: Option<String>) -> Self {
+        Change {
+            time,
+            device: device.to_string(),
+            key: key.to_string(),
+            value,
+        }
+    }
+}
+
+impl From<Change> for [String; 5] {
+    /// Convert from a `Change` to `[String; 5]`.
+    fn from(item: Change) -> [String; 5] {
+        [
+            item.time.to_string(),
+            item.device,
+            item.key,
+            item.value.is_some().to_string(),
+            item.value.unwrap_or_default(),
+        ]
+    }
+}
+
+impl TryFrom<[String; 5]> for Change {
+    type Error = ();
+
+    /// Try to convert from `[String; 5]` to a `Change`.
+    fn try_from(item: [String; 5]) -> Result<Change, ()> {
+        let present: bool = item[3].parse().map_err(|_| ())?;
+
+        Ok(Change {
+            time: item[0].parse().map_err(|_| ())?,
+            device: item[1].clone(),
+            key: item[2].clone(),
+            value: if present { Some(item[4].clone()) } else { None },
+        })
+    }
+}
+
+impl Default for SyncState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyncState {
+    /// Create a new, empty state.
+    pub fn new() -> Self {
+        SyncState {
+            latest: BTreeMap::new(),
+        }
+    }
+
+    /// Apply the change if it is newer than the last change of its key. Changes at the same time are ordered by their
+    /// device and value, so every device gets the same state.
+    pub fn apply(&mut self, change: Change) {
+        let newer = match self.latest.get(&change.key) {
+            Some(latest) => {
+                (change.time, &change.device, &change.value)
+                    > (latest.time, &latest.device, &latest.value)
+            }
+            None => true,
+        };
+
+        if newer {
+            self.latest.insert(change.key.clone(), change);
+        }
+    }
+
+    /// Get the current values by their keys.
+    pub fn values(&self) -> BTreeMap<String, String> {
+        self.latest
+            .iter()
+            .filter_map(|(key, change)| change.value.clone().map(|v| (key.clone(), v)))
+            .collect()
+    }
+
+    /// Get the changes made on the device at the time to get from this state to the given values.
+    pub fn changes_to(
+        &self,
+        values: &BTreeMap<String, String>,
+        time: i64,
+        device: &str,
+    ) -> Vec<Change> {
+        let current = self.values();
+
+        let removed = current
+            .keys()
+            .filter(|key| !values.contains_key(*key))
+            .map(|key| Change::new(time, device, key, None));
+        let set = values
+            .iter()
+            .filter(|(key, value)| current.get(*key) != Some(value))
+            .map(|(key, value)| Change::new(time, device, key, Some(value.clone())));
+
+        removed.chain(set).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let changes = [
+            Change::new(1, "phone", "mode", Some("allow".to_string())),
+            Change::new(2, "laptop", "mode", Some("block".to_string())),
+            Change::new(1, "phone", "title/Hello", Some(String::new())),
+            Change::new(3, "laptop", "title/Hello", None),
+            Change::new(2, "phone", "title/Bye", Some(String::new())),
+        ];
+
+        let mut forward = SyncState::new();
+        for change in changes.iter().cloned() {
+            forward.apply(change);
+        }
+        // The order and applying changes again, e.g. from a conflicting copy, do not matter.
+        let mut backward = SyncState::new();
+        for change in changes.iter().rev().chain(changes.iter()).cloned() {
+            backward.apply(change);
+        }
+
+        assert_eq!(forward, backward);
+        assert_eq!(
+            forward
+                .values()
+                .into_iter()
+                .collect::<Vec<(String, String)>>(),
+            vec![
+                ("mode".to_string(), "block".to_string()),
+                ("title/Bye".to_string(), String::new())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_changes_to() {
+        let mut state = SyncState::new();
+        state.apply(Change::new(1, "phone", "mode", Some("allow".to_string())));
+        state.apply(Change::new(1, "phone", "title/Hello", Some(String::new())));
+
+        let mut values = BTreeMap::new();
+        values.insert("mode".to_string(), "allow".to_string());
+        values.insert("title/Bye".to_string(), String::new());
+
+        let changes = state.changes_to(&values, 5, "laptop");
+        assert_eq!(
+            changes,
+            vec![
+                Change::new(5, "laptop", "title/Hello", None),
+                Change::new(5, "laptop", "title/Bye", Some(String::new())),
+            ]
+        );
+
+        for change in changes {
+            state.apply(change);
+        }
+        assert_eq!(state.values(), values);
+    }
+
+    #[test]
+    fn test_str_array() {
+        let change = Change::new(5, "laptop", "title/Hello", None);
+
+        assert_eq!(
+            <[String; 5]>::from(change.clone()),
+            [
+                "5".to_string(),
+                "laptop".to_string(),
+                "title/Hello".to_string(),
+                "false".to_string(),
+                String::new()
+            ]
+        );
+        assert_eq!(
+            Change::try_from(<[String; 5]>::from(change.clone())),
+            Ok(change)
+        );
+    }
+}
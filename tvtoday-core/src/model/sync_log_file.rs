@@ -0,0 +1,124 @@
+use crate::model::{encoding, encryption, Change, SyncState};
+use crate::Error;
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use csv::{ReaderBuilder, Writer};
+
+/// The change log of this device for values synced between devices, e.g. by syncing the data directory with Nextcloud or
+/// Syncthing. Each device only appends to its own log and reads the logs of all devices, so syncing never creates
+/// conflicts. Conflicting copies created anyway are read like the other logs.
+pub struct SyncLog {
+    /// The directory containing the logs of all devices.
+    dir: PathBuf,
+    /// The name of the logs, e.g. "filters".
+    name: String,
+    device: String,
+    /// The state the values were last read or written in.
+    state: SyncState,
+}
+
+impl SyncLog {
+    /// Create the log with the given name of the device in the directory, without reading the logs.
+    pub fn new<P: AsRef<Path>>(dir: P, name: &str, device: &str) -> Self {
+        SyncLog {
+            dir: dir.as_ref().to_path_buf(),
+            name: name.to_string(),
+            device: device.to_string(),
+            state: SyncState::new(),
+        }
+    }
+
+    /// Read the logs with the given name of all devices in the directory. These are the files named
+    /// `{name}.{device}.log`. Records and encrypted logs that can not be read, e.g. a line only partially written, are
+    /// skipped.
+    pub fn read_from_dir<P: AsRef<Path>>(dir: P, name: &str, device: &str) -> Result<Self, Error> {
+        let mut log = SyncLog::new(dir, name, device);
+        let prefix = format!("{}.", name);
+
+        for entry in fs::read_dir(&log.dir)? {
+            let path = entry?.path();
+            let is_log = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.starts_with(&prefix) && n.ends_with(".log"))
+                .unwrap_or(false);
+            if !is_log {
+                continue;
+            }
+
+            // E.g. a log only partially synced can not be decrypted.
+            let content = match encryption::read_file(path) {
+                Ok(content) => encoding::normalize(&content),
+                Err(_) => continue,
+            };
+            let mut reader = ReaderBuilder::new()
+                .has_headers(false)
+                .flexible(true)
+                .from_reader(content.as_bytes());
+
+            for record in reader.records().filter_map(|r| r.ok()) {
+                if record.len() != 5 {
+                    continue;
+                }
+
+                let record_arr = [
+                    record.get(0).unwrap().to_string(),
+                    record.get(1).unwrap().to_string(),
+                    record.get(2).unwrap().to_string(),
+                    record.get(3).unwrap().to_string(),
+                    record.get(4).unwrap().to_string(),
+                ];
+
+                if let Ok(change) = Change::try_from(record_arr) {
+                    log.state.apply(change);
+                }
+            }
+        }
+
+        Ok(log)
+    }
+
+    /// Get the current values by their keys.
+    pub fn values(&self) -> BTreeMap<String, String> {
+        self.state.values()
+    }
+
+    /// Append the changes from the values read or written last to the given values to the log of this device. Changes
+    /// made on other devices in the meantime are kept if they changed other keys.
+    pub fn write(&mut self, values: &BTreeMap<String, String>) -> Result<(), Error> {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        let changes = self.state.changes_to(values, time, &self.device);
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let mut path = self.dir.clone();
+        path.push(format!("{}.{}.log", self.name, self.device));
+
+        // Encrypted logs can not be appended to, the log is written as a whole.
+        let content = if path.exists() {
+            encryption::read_file(&path)?
+        } else {
+            vec![]
+        };
+
+        let mut writer = Writer::from_writer(content);
+
+        for change in changes {
+            writer.write_record(<[String; 5]>::from(change.clone()))?;
+            self.state.apply(change);
+        }
+
+        let content = writer.into_inner().map_err(|_| Error::ParsingFile)?;
+
+        encryption::write_file(path, &content)
+    }
+}
@@ -0,0 +1,67 @@
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+/// A time slot of the program of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeSlot {
+    /// The movies running right now.
+    Now,
+    /// The prime time, starting at 20:15.
+    Evening,
+    /// The late program, starting at 22:00.
+    Late,
+}
+
+impl TimeSlot {
+    /// All time slots, in the order they are broadcasted.
+    pub const ALL: [TimeSlot; 3] = [TimeSlot::Now, TimeSlot::Evening, TimeSlot::Late];
+
+    /// The label of the time slot shown to the user.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeSlot::Now => "Now",
+            TimeSlot::Evening => "Evening (20:15)",
+            TimeSlot::Late => "Late (22:00)",
+        }
+    }
+}
+
+impl Display for TimeSlot {
+    /// The identifier of the time slot, used for storing it.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            TimeSlot::Now => write!(f, "now"),
+            TimeSlot::Evening => write!(f, "evening"),
+            TimeSlot::Late => write!(f, "late"),
+        }
+    }
+}
+
+impl TryFrom<&str> for TimeSlot {
+    type Error = ();
+
+    /// Try to convert from the identifier of the time slot to the `TimeSlot`.
+    fn try_from(item: &str) -> Result<TimeSlot, ()> {
+        TimeSlot::ALL
+            .iter()
+            .find(|t| t.to_string() == item)
+            .cloned()
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_str() {
+        for time_slot in TimeSlot::ALL.iter() {
+            assert_eq!(
+                TimeSlot::try_from(&time_slot.to_string()[..]),
+                Ok(*time_slot)
+            );
+        }
+        assert_eq!(TimeSlot::try_from("tomorrow"), Err(()));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::model::search::fold;
+
+use std::collections::HashMap;
+
+/// Normalize the title to match the same movie from different sources, e.g. `"Matrix: Reloaded"` and
+/// `"matrix reloaded"`. The title is folded and everything but letters and digits separates words.
+pub fn normalize_title(title: &str) -> String {
+    fold(title)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Values looked up by the normalized title and the year of a movie.
+///
+/// The year of a broadcast may differ by one from the year of another source, e.g. the production and the release
+/// year, so neighbouring years match if no value has the exact year. Movies without a year only match if the title is
+/// unique.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TitleIndex<V> {
+    values: HashMap<String, Vec<(Option<u32>, V)>>,
+}
+
+impl<V> Default for TitleIndex<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> TitleIndex<V> {
+    /// Create a new, empty index.
+    pub fn new() -> Self {
+        TitleIndex {
+            values: HashMap::new(),
+        }
+    }
+
+    /// Insert the value of the title and year.
+    pub fn insert(&mut self, title: &str, year: Option<u32>, value: V) {
+        self.values
+            .entry(normalize_title(title))
+            .or_default()
+            .push((year, value));
+    }
+
+    /// Get the value matching the title and year.
+    pub fn get(&self, title: &str, year: Option<u32>) -> Option<&V> {
+        let candidates = self.values.get(&normalize_title(title))?;
+
+        match year {
+            Some(year) => candidates
+                .iter()
+                .find(|(y, _v)| *y == Some(year))
+                .or_else(|| {
+                    candidates.iter().find(|(y, _v)| {
+                        y.map(|y| (y as i64 - year as i64).abs() <= 1)
+                            .unwrap_or(false)
+                    })
+                })
+                .map(|(_y, v)| v),
+            None if candidates.len() == 1 => Some(&candidates[0].1),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize_title() {
+        assert_eq!(normalize_title("Matrix: Reloaded"), "matrix reloaded");
+        assert_eq!(normalize_title("  Die Fälscher!  "), "die faelscher");
+        assert_eq!(normalize_title("Die Faelscher"), "die faelscher");
+        assert_eq!(normalize_title("AC/DC"), "ac dc");
+    }
+
+    #[test]
+    fn test_get() {
+        let mut index = TitleIndex::new();
+        index.insert("Solaris", Some(1972), 8);
+        index.insert("Solaris", Some(2002), 6);
+        index.insert("Metropolis", Some(1927), 9);
+
+        assert_eq!(index.get("solaris", Some(1972)), Some(&8));
+        assert_eq!(index.get("Solaris", Some(2003)), Some(&6));
+        assert_eq!(index.get("Solaris", Some(1990)), None);
+        // Without a year, only unique titles match.
+        assert_eq!(index.get("Solaris", None), None);
+        assert_eq!(index.get("METROPOLIS", None), Some(&9));
+        assert_eq!(index.get("Tatort", None), None);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::Error;
+
+use reqwest::header::CONTENT_TYPE;
+use serde_json::{json, Value};
+
+/// The language of the scraped descriptions.
+const SOURCE_LANGUAGE: &str = "de";
+/// The language the descriptions are translated to.
+const TARGET_LANGUAGE: &str = "en";
+
+/// Parse the translated text from the response of a LibreTranslate server.
+pub fn parse_translation(json: &str) -> Result<String, Error> {
+    let value: Value = serde_json::from_str(json).map_err(|_| Error::ParsingWebsite)?;
+
+    value["translatedText"]
+        .as_str()
+        .map(|t| t.to_string())
+        .ok_or(Error::ParsingWebsite)
+}
+
+/// Translate the german text using the LibreTranslate server at the given URL.
+pub async fn translate(url: &str, text: &str) -> Result<String, Error> {
+    let body = json!({
+        "q": text,
+        "source": SOURCE_LANGUAGE,
+        "target": TARGET_LANGUAGE,
+        "format": "text",
+    });
+
+    let json = reqwest::Client::new()
+        .post(format!("{}/translate", url.trim_end_matches('/')))
+        .header(CONTENT_TYPE, "application/json")
+        .body(body.to_string())
+        .send()
+        .await?
+        .text()
+        .await?;
+
+    parse_translation(&json)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_translation() {
+        assert_eq!(
+            parse_translation(r#"{"translatedText": "A detective investigates."}"#).ok(),
+            Some("A detective investigates.".to_string())
+        );
+        assert!(parse_translation(r#"{"error": "Invalid request"}"#).is_err());
+        assert!(parse_translation("<html>").is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use crate::model::{ChannelOrder, Program, Reminders};
+
+use chrono::NaiveDateTime;
+use serde_json::{json, Value};
+
+/// The version of the JSON written for desktop widgets. It is only increased on incompatible changes, new fields may be
+/// added without changing it.
+pub const WIDGET_VERSION: u32 = 1;
+
+/// Create the JSON of tonight's picks for desktop widgets like Waybar or GNOME Shell extensions.
+///
+/// The schema is kept stable:
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "updated": "2021-04-20T19:03",
+///   "summary": "Tonight at 20:15: Tatort on Das Erste.",
+///   "picks": [
+///     { "channel": "Das Erste", "title": "Tatort", "start": "2021-04-20T20:15", "genre": "Krimi" }
+///   ]
+/// }
+/// ```
+///
+/// `start` and `genre` are `null` if unknown. The picks are ordered by their start time.
+pub fn widget_json(picks: &Program, updated: NaiveDateTime) -> Value {
+    json!({
+        "version": WIDGET_VERSION,
+        "updated": updated.format("%Y-%m-%dT%H:%M").to_string(),
+        "summary": crate::model::summarize(picks),
+        "picks": sorted(picks)
+            .iter()
+            .map(|(channel, movie)| {
+                json!({
+                    "channel": channel.get_name(),
+                    "title": movie.get_title(),
+                    "start": movie.get_start().map(|s| s.format("%Y-%m-%dT%H:%M").to_string()),
+                    "genre": movie.get_genre(),
+                })
+            })
+            .collect::<Vec<Value>>(),
+    })
+}
+
+/// Create the plain text of tonight's picks for desktop widgets like Conky, one line like `20:15 Tatort (Das Erste)`
+/// per pick ordered by their start time. The time is left out if unknown.
+pub fn widget_text(picks: &Program) -> String {
+    let lines: Vec<String> = sorted(picks)
+        .iter()
+        .map(|(channel, movie)| {
+            let broadcast = format!("{} ({})", movie.get_title(), channel.get_name());
+            match movie.get_start() {
+                Some(start) => format!("{} {}", start.format("%H:%M"), broadcast),
+                None => broadcast,
+            }
+        })
+        .collect();
+
+    if lines.is_empty() {
+        "Nothing picked for tonight.\n".to_string()
+    } else {
+        lines.join("\n") + "\n"
+    }
+}
+
+/// Create the JSON object of the next broadcast with a reminder or on a favorite channel for status bars like Waybar or
+/// polybar, e.g. `{"text": "20:15 Tatort", "tooltip": "Tatort on Das Erste at 20:15", "class": "reminder"}`.
+///
+/// Broadcasts starting before `now` are left out. `class` is `reminder` or `favorite`, or `none` with an empty text if
+/// nothing is next. A reminder is preferred to a favorite channel starting at the same time.
+pub fn waybar_json(
+    program: &Program,
+    reminders: &Reminders,
+    channel_order: &ChannelOrder,
+    now: NaiveDateTime,
+) -> Value {
+    let reminded = reminders
+        .iter()
+        .map(|r| (r.get_start(), "reminder", r.get_title(), r.get_channel()));
+    let favorites = program
+        .iter()
+        .filter(|(c, _m)| channel_order.is_favorite(&c.get_name()))
+        .filter_map(|(c, m)| {
+            m.get_start()
+                .map(|start| (start, "favorite", m.get_title(), c.get_name()))
+        });
+
+    let next = reminded
+        .chain(favorites)
+        .filter(|(start, _class, _title, _channel)| start >= &now)
+        .min_by_key(|(start, class, _title, _channel)| (*start, *class != "reminder"));
+
+    match next {
+        Some((start, class, title, channel)) => {
+            let time = start.format("%H:%M");
+            json!({
+                "text": format!("{} {}", time, title),
+                "tooltip": format!("{} on {} at {}", title, channel, time),
+                "class": class,
+            })
+        }
+        None => json!({
+            "text": "",
+            "tooltip": "Nothing on the watchlist or the favorite channels",
+            "class": "none",
+        }),
+    }
+}
+
+/// Get the picks ordered by their start time, the ones without start time last.
+fn sorted(picks: &Program) -> Program {
+    let mut sorted: Vec<_> = picks.iter().cloned().collect();
+    sorted.sort_by_key(|(_c, m)| (m.get_start().is_none(), m.get_start()));
+    sorted.into_iter().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::{Channel, MovieBuilder};
+
+    use chrono::NaiveDate;
+
+    fn picks() -> Program {
+        let mut tatort = MovieBuilder::new("Tatort");
+        tatort
+            .with_start(
+                NaiveDate::from_ymd_opt(2021, 4, 20)
+                    .unwrap()
+                    .and_hms_opt(20, 15, 0)
+                    .unwrap(),
+            )
+            .with_genre("Krimi");
+        let mut late = MovieBuilder::new("Late Night");
+        late.with_start(
+            NaiveDate::from_ymd_opt(2021, 4, 20)
+                .unwrap()
+                .and_hms_opt(22, 0, 0)
+                .unwrap(),
+        );
+
+        vec![
+            (Channel::new("ZDF"), late.build()),
+            (Channel::new("Das Erste"), tatort.build()),
+            (Channel::new("ARTE"), MovieBuilder::new("Doku").build()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_widget_text() {
+        assert_eq!(
+            widget_text(&picks()),
+            "20:15 Tatort (Das Erste)\n22:00 Late Night (ZDF)\nDoku (ARTE)\n"
+        );
+        assert_eq!(
+            widget_text(&Program::new()),
+            "Nothing picked for tonight.\n"
+        );
+    }
+
+    #[test]
+    fn test_waybar_json() {
+        let at = |hour, minute| {
+            NaiveDate::from_ymd_opt(2021, 4, 20)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap()
+        };
+        let program = picks();
+        let mut channel_order = ChannelOrder::new();
+        channel_order.set_favorite("ZDF", true);
+        let mut reminders = Reminders::new();
+
+        assert_eq!(
+            waybar_json(&program, &reminders, &channel_order, at(19, 0)),
+            json!({
+                "text": "22:00 Late Night",
+                "tooltip": "Late Night on ZDF at 22:00",
+                "class": "favorite",
+            })
+        );
+
+        reminders.add(crate::model::Reminder::new(&program[1].0, &program[1].1, 5).unwrap());
+        assert_eq!(
+            waybar_json(&program, &reminders, &channel_order, at(19, 0))["class"],
+            "reminder"
+        );
+        assert_eq!(
+            waybar_json(&program, &reminders, &channel_order, at(20, 30))["class"],
+            "favorite"
+        );
+        assert_eq!(
+            waybar_json(&program, &reminders, &channel_order, at(23, 0))["class"],
+            "none"
+        );
+    }
+
+    #[test]
+    fn test_widget_json() {
+        let updated = NaiveDate::from_ymd_opt(2021, 4, 20)
+            .unwrap()
+            .and_hms_opt(19, 3, 0)
+            .unwrap();
+        let json = widget_json(&picks(), updated);
+
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["updated"], "2021-04-20T19:03");
+        assert_eq!(
+            json["picks"][0],
+            json!({
+                "channel": "Das Erste",
+                "title": "Tatort",
+                "start": "2021-04-20T20:15",
+                "genre": "Krimi",
+            })
+        );
+        assert_eq!(json["picks"][2]["start"], Value::Null);
+        assert!(json["summary"]
+            .as_str()
+            .unwrap()
+            .starts_with("Tonight at 20:15"));
+    }
+}
@@ -0,0 +1,29 @@
+use crate::model::{widget_json, widget_text, Program};
+use crate::Error;
+
+use std::fs;
+use std::path::Path;
+
+use chrono::NaiveDateTime;
+
+/// Write tonight's picks for desktop widgets to a file at the given path, as JSON if it ends with `.json` and as plain
+/// text otherwise. The file is replaced at once, so widgets never read a partly written file.
+pub fn write_widget_to_path<P: AsRef<Path>>(
+    picks: &Program,
+    updated: NaiveDateTime,
+    path: P,
+) -> Result<(), Error> {
+    let path = path.as_ref();
+    let content = if path.extension().map(|e| e == "json").unwrap_or(false) {
+        serde_json::to_string_pretty(&widget_json(picks, updated))
+            .map_err(|_| Error::ParsingFile)?
+    } else {
+        widget_text(picks)
+    };
+
+    let temporary = path.with_extension("tmp");
+    fs::write(&temporary, content)?;
+    fs::rename(&temporary, path)?;
+
+    Ok(())
+}